@@ -28,4 +28,29 @@ db.clients.createIndex({ nume: 1 });
 db.clients.createIndex({ "lista_bilete.cod": 1 });
 
 print("Created indexes on clients collection");
+
+db.idempotency_keys.createIndex({ scope: 1, key: 1 }, { unique: true });
+db.idempotency_keys.createIndex({ created_at: 1 }, { expireAfterSeconds: 86400 });
+
+print("Created indexes on idempotency_keys collection");
+
+db.purchase_sagas.createIndex({ state: 1, updated_at: 1 });
+
+print("Created indexes on purchase_sagas collection");
+
+db.ticket_transfers.createIndex(
+  { ticket_cod: 1 },
+  {
+    unique: true,
+    partialFilterExpression: { status: { $in: ["PENDING", "ACCEPTING"] } },
+  },
+);
+db.ticket_transfers.createIndex({ from_client_id: 1, created_at: -1 });
+db.ticket_transfers.createIndex({ to_client_id: 1, created_at: -1 });
+
+print("Created indexes on ticket_transfers collection");
+
+db.calendar_feeds.createIndex({ token: 1 }, { unique: true });
+
+print("Created indexes on calendar_feeds collection");
 print("Database setup complete");
@@ -1,3 +1,17 @@
+DROP TABLE IF EXISTS CHECK_INS CASCADE;
+
+DROP TABLE IF EXISTS EVENT_SCANNERS CASCADE;
+
+DROP TABLE IF EXISTS PROMO_REDEMPTIONS CASCADE;
+
+DROP TABLE IF EXISTS EVENT_SEATS CASCADE;
+
+DROP TABLE IF EXISTS REFUND_REQUESTS CASCADE;
+
+DROP TABLE IF EXISTS IDEMPOTENCY_KEYS CASCADE;
+
+DROP TABLE IF EXISTS OUTBOX CASCADE;
+
 DROP TABLE IF EXISTS EVENIMENTE CASCADE;
 
 DROP TABLE IF EXISTS PACHETE CASCADE;
@@ -6,8 +20,89 @@ DROP TABLE IF EXISTS JOIN_PE CASCADE;
 
 DROP TABLE IF EXISTS BILETE CASCADE;
 
+DROP TABLE IF EXISTS PRICE_TIERS CASCADE;
+
+DROP TABLE IF EXISTS SEAT_HOLDS CASCADE;
+
+DROP TABLE IF EXISTS WAITLIST CASCADE;
+
+DROP TABLE IF EXISTS PROMO_CODES CASCADE;
+
+DROP TABLE IF EXISTS ROOM_SEATS CASCADE;
+
+DROP TABLE IF EXISTS VENUE_ROOMS CASCADE;
+
+DROP TABLE IF EXISTS VENUES CASCADE;
+
 CREATE EXTENSION IF NOT EXISTS unaccent;
 
+CREATE EXTENSION IF NOT EXISTS btree_gist;
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE OR REPLACE FUNCTION immutable_unaccent (TEXT) RETURNS TEXT AS $$
+    SELECT public.unaccent('public.unaccent', $1)
+$$ LANGUAGE sql IMMUTABLE PARALLEL SAFE STRICT;
+
+-- Haversine distance in kilometres; NULL when either point has no coordinates.
+CREATE OR REPLACE FUNCTION great_circle_km (
+    lat1 DOUBLE PRECISION,
+    lng1 DOUBLE PRECISION,
+    lat2 DOUBLE PRECISION,
+    lng2 DOUBLE PRECISION
+) RETURNS DOUBLE PRECISION AS $$
+    SELECT 2 * 6371.0088 * asin(least(1.0, sqrt(
+        power(sin(radians(lat2 - lat1) / 2), 2)
+        + cos(radians(lat1)) * cos(radians(lat2)) * power(sin(radians(lng2 - lng1) / 2), 2)
+    )))
+$$ LANGUAGE sql IMMUTABLE PARALLEL SAFE STRICT;
+
+DROP TEXT SEARCH CONFIGURATION IF EXISTS search_simple;
+
+CREATE TEXT SEARCH CONFIGURATION search_simple (COPY = simple);
+
+ALTER TEXT SEARCH CONFIGURATION search_simple
+ALTER MAPPING FOR hword,
+hword_part,
+word
+WITH
+    unaccent,
+    simple;
+
+CREATE TABLE
+    VENUES (
+        id SERIAL PRIMARY KEY,
+        id_owner INTEGER NOT NULL,
+        name VARCHAR(100) UNIQUE NOT NULL,
+        address VARCHAR(255) NOT NULL,
+        city VARCHAR(100) NOT NULL,
+        country CHAR(2) NOT NULL,
+        latitude DOUBLE PRECISION NULL CHECK (latitude BETWEEN -90 AND 90),
+        longitude DOUBLE PRECISION NULL CHECK (longitude BETWEEN -180 AND 180),
+        created_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+        CONSTRAINT chk_venue_coordinates CHECK ((latitude IS NULL) = (longitude IS NULL))
+    );
+
+CREATE TABLE
+    VENUE_ROOMS (
+        id SERIAL PRIMARY KEY,
+        venue_id INTEGER NOT NULL REFERENCES VENUES (id) ON DELETE CASCADE,
+        name VARCHAR(100) NOT NULL,
+        capacity INTEGER NOT NULL CHECK (capacity > 0),
+        CONSTRAINT uq_room_venue_name UNIQUE (venue_id, name)
+    );
+
+CREATE TABLE
+    ROOM_SEATS (
+        id SERIAL PRIMARY KEY,
+        room_id INTEGER NOT NULL REFERENCES VENUE_ROOMS (id) ON DELETE CASCADE,
+        section VARCHAR(50) NOT NULL,
+        row_label VARCHAR(10) NOT NULL,
+        seat_number INTEGER NOT NULL CHECK (seat_number > 0),
+        accessible BOOLEAN NOT NULL DEFAULT FALSE,
+        CONSTRAINT uq_room_seat UNIQUE (room_id, section, row_label, seat_number)
+    );
+
 CREATE TABLE
     EVENIMENTE (
         ID SERIAL PRIMARY KEY,
@@ -15,9 +110,73 @@ CREATE TABLE
         nume VARCHAR(255) UNIQUE NOT NULL,
         locatie VARCHAR(255) NULL,
         descriere TEXT NULL,
-        numarLocuri INTEGER NULL
+        numarLocuri INTEGER NULL,
+        starts_at TIMESTAMPTZ NULL,
+        ends_at TIMESTAMPTZ NULL,
+        timezone VARCHAR(64) NULL,
+        status VARCHAR(20) DEFAULT 'DRAFT' NOT NULL,
+        room_id INTEGER NULL REFERENCES VENUE_ROOMS (id) ON DELETE RESTRICT,
+        category VARCHAR(30) NULL,
+        tags TEXT[] DEFAULT '{}' NOT NULL,
+        latitude DOUBLE PRECISION NULL CHECK (latitude BETWEEN -90 AND 90),
+        longitude DOUBLE PRECISION NULL CHECK (longitude BETWEEN -180 AND 180),
+        search_vector TSVECTOR GENERATED ALWAYS AS (
+            setweight(to_tsvector('search_simple', COALESCE(nume, '')), 'A')
+            || setweight(to_tsvector('search_simple', COALESCE(locatie, '')), 'B')
+            || setweight(to_tsvector('search_simple', COALESCE(descriere, '')), 'C')
+        ) STORED,
+        CONSTRAINT chk_eveniment_status CHECK (
+            status IN ('DRAFT', 'PUBLISHED', 'SALES_CLOSED', 'CANCELLED')
+        ),
+        CONSTRAINT chk_eveniment_category CHECK (
+            category IN (
+                'PROGRAMMING_LANGUAGES',
+                'WEB',
+                'MOBILE',
+                'CLOUD_DEVOPS',
+                'DATA_AI',
+                'SECURITY',
+                'OTHER'
+            )
+        ),
+        CONSTRAINT chk_eveniment_coordinates CHECK ((latitude IS NULL) = (longitude IS NULL)),
+        CONSTRAINT chk_eveniment_interval CHECK (
+            starts_at IS NULL
+            OR ends_at IS NULL
+            OR ends_at > starts_at
+        ),
+        CONSTRAINT chk_eveniment_room_schedule CHECK (
+            room_id IS NULL
+            OR (
+                starts_at IS NOT NULL
+                AND ends_at IS NOT NULL
+            )
+        ),
+        CONSTRAINT excl_eveniment_room_booking EXCLUDE USING gist (
+            room_id WITH =,
+            tstzrange(starts_at, ends_at) WITH &&
+        ) WHERE (
+            room_id IS NOT NULL
+            AND status <> 'CANCELLED'
+        )
     );
 
+CREATE INDEX idx_evenimente_starts_at ON EVENIMENTE (starts_at);
+
+CREATE INDEX idx_evenimente_status ON EVENIMENTE (status);
+
+CREATE INDEX idx_evenimente_room ON EVENIMENTE (room_id);
+
+CREATE INDEX idx_evenimente_category ON EVENIMENTE (category);
+
+CREATE INDEX idx_evenimente_tags ON EVENIMENTE USING gin (tags);
+
+CREATE INDEX idx_evenimente_latitude ON EVENIMENTE (latitude);
+
+CREATE INDEX idx_evenimente_search ON EVENIMENTE USING gin (search_vector);
+
+CREATE INDEX idx_evenimente_nume_trgm ON EVENIMENTE USING gin (immutable_unaccent (nume) gin_trgm_ops);
+
 CREATE TABLE
     PACHETE (
         ID SERIAL PRIMARY KEY,
@@ -25,9 +184,44 @@ CREATE TABLE
         nume VARCHAR(255) UNIQUE NOT NULL,
         locatie VARCHAR(255) NULL,
         descriere TEXT NULL,
-        numarLocuri INTEGER NULL
+        numarLocuri INTEGER NULL,
+        status VARCHAR(20) DEFAULT 'DRAFT' NOT NULL,
+        category VARCHAR(30) NULL,
+        tags TEXT[] DEFAULT '{}' NOT NULL,
+        latitude DOUBLE PRECISION NULL CHECK (latitude BETWEEN -90 AND 90),
+        longitude DOUBLE PRECISION NULL CHECK (longitude BETWEEN -180 AND 180),
+        search_vector TSVECTOR GENERATED ALWAYS AS (
+            setweight(to_tsvector('search_simple', COALESCE(nume, '')), 'A')
+            || setweight(to_tsvector('search_simple', COALESCE(locatie, '')), 'B')
+            || setweight(to_tsvector('search_simple', COALESCE(descriere, '')), 'C')
+        ) STORED,
+        CONSTRAINT chk_pachet_status CHECK (
+            status IN ('DRAFT', 'PUBLISHED', 'SALES_CLOSED', 'CANCELLED')
+        ),
+        CONSTRAINT chk_pachet_coordinates CHECK ((latitude IS NULL) = (longitude IS NULL)),
+        CONSTRAINT chk_pachet_category CHECK (
+            category IN (
+                'PROGRAMMING_LANGUAGES',
+                'WEB',
+                'MOBILE',
+                'CLOUD_DEVOPS',
+                'DATA_AI',
+                'SECURITY',
+                'OTHER'
+            )
+        )
     );
 
+CREATE INDEX idx_pachete_category ON PACHETE (category);
+
+CREATE INDEX idx_pachete_tags ON PACHETE USING gin (tags);
+
+CREATE INDEX idx_pachete_latitude ON PACHETE (latitude);
+
+CREATE INDEX idx_pachete_search ON PACHETE USING gin (search_vector);
+
+CREATE INDEX idx_pachete_nume_trgm ON PACHETE USING gin (immutable_unaccent (nume) gin_trgm_ops);
+
 CREATE TABLE
     JOIN_PE (
         PachetID INTEGER REFERENCES PACHETE (ID) ON DELETE CASCADE,
@@ -35,11 +229,159 @@ CREATE TABLE
         PRIMARY KEY (PachetID, EvenimentID)
     );
 
+CREATE TABLE
+    PRICE_TIERS (
+        id SERIAL PRIMARY KEY,
+        event_id INTEGER REFERENCES EVENIMENTE (ID) ON DELETE CASCADE,
+        packet_id INTEGER REFERENCES PACHETE (ID) ON DELETE CASCADE,
+        name VARCHAR(50) NOT NULL,
+        currency CHAR(3) NOT NULL,
+        price_minor BIGINT NOT NULL CHECK (price_minor >= 0),
+        capacity INTEGER NULL CHECK (capacity > 0),
+        sold INTEGER NOT NULL DEFAULT 0 CHECK (sold >= 0),
+        sales_start TIMESTAMPTZ NULL,
+        sales_end TIMESTAMPTZ NULL,
+        CONSTRAINT chk_tier_exclusiv CHECK (
+            (
+                packet_id IS NOT NULL
+                AND event_id IS NULL
+            )
+            OR (
+                packet_id IS NULL
+                AND event_id IS NOT NULL
+            )
+        ),
+        CONSTRAINT chk_tier_capacity CHECK (
+            capacity IS NULL
+            OR sold <= capacity
+        ),
+        CONSTRAINT chk_tier_window CHECK (
+            sales_start IS NULL
+            OR sales_end IS NULL
+            OR sales_end > sales_start
+        ),
+        CONSTRAINT uq_tier_event_name UNIQUE (event_id, name),
+        CONSTRAINT uq_tier_packet_name UNIQUE (packet_id, name)
+    );
+
+CREATE TABLE
+    PROMO_CODES (
+        id SERIAL PRIMARY KEY,
+        event_id INTEGER REFERENCES EVENIMENTE (ID) ON DELETE CASCADE,
+        packet_id INTEGER REFERENCES PACHETE (ID) ON DELETE CASCADE,
+        code VARCHAR(50) NOT NULL,
+        discount_type VARCHAR(10) NOT NULL,
+        discount_value BIGINT NOT NULL CHECK (discount_value > 0),
+        currency CHAR(3) NULL,
+        max_uses INTEGER NULL CHECK (max_uses > 0),
+        per_user_limit INTEGER NULL CHECK (per_user_limit > 0),
+        uses INTEGER NOT NULL DEFAULT 0 CHECK (uses >= 0),
+        expires_at TIMESTAMPTZ NULL,
+        active BOOLEAN NOT NULL DEFAULT TRUE,
+        created_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+        CONSTRAINT chk_promo_exclusiv CHECK (
+            (
+                packet_id IS NOT NULL
+                AND event_id IS NULL
+            )
+            OR (
+                packet_id IS NULL
+                AND event_id IS NOT NULL
+            )
+        ),
+        CONSTRAINT chk_promo_discount CHECK (
+            (
+                discount_type = 'PERCENT'
+                AND discount_value <= 100
+                AND currency IS NULL
+            )
+            OR (
+                discount_type = 'FIXED'
+                AND currency IS NOT NULL
+            )
+        ),
+        CONSTRAINT chk_promo_uses CHECK (
+            max_uses IS NULL
+            OR uses <= max_uses
+        ),
+        CONSTRAINT uq_promo_event_code UNIQUE (event_id, code),
+        CONSTRAINT uq_promo_packet_code UNIQUE (packet_id, code)
+    );
+
+CREATE TABLE
+    SEAT_HOLDS (
+        id VARCHAR(50) PRIMARY KEY,
+        event_id INTEGER REFERENCES EVENIMENTE (ID) ON DELETE CASCADE,
+        packet_id INTEGER REFERENCES PACHETE (ID) ON DELETE CASCADE,
+        holder_id INTEGER NOT NULL,
+        quantity INTEGER NOT NULL CHECK (quantity > 0),
+        created_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+        expires_at TIMESTAMPTZ NOT NULL,
+        CONSTRAINT chk_hold_exclusiv CHECK (
+            (
+                packet_id IS NOT NULL
+                AND event_id IS NULL
+            )
+            OR (
+                packet_id IS NULL
+                AND event_id IS NOT NULL
+            )
+        )
+    );
+
+CREATE INDEX idx_seat_holds_expires_at ON SEAT_HOLDS (expires_at);
+
+CREATE TABLE
+    WAITLIST (
+        id SERIAL PRIMARY KEY,
+        event_id INTEGER REFERENCES EVENIMENTE (ID) ON DELETE CASCADE,
+        packet_id INTEGER REFERENCES PACHETE (ID) ON DELETE CASCADE,
+        user_id INTEGER NOT NULL,
+        email VARCHAR(255) NOT NULL,
+        status VARCHAR(20) DEFAULT 'WAITING' NOT NULL,
+        hold_id VARCHAR(50) NULL,
+        offer_expires_at TIMESTAMPTZ NULL,
+        created_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+        CONSTRAINT chk_waitlist_status CHECK (
+            status IN ('WAITING', 'OFFERED', 'ACCEPTED', 'EXPIRED')
+        ),
+        CONSTRAINT chk_waitlist_exclusiv CHECK (
+            (
+                packet_id IS NOT NULL
+                AND event_id IS NULL
+            )
+            OR (
+                packet_id IS NULL
+                AND event_id IS NOT NULL
+            )
+        )
+    );
+
+CREATE UNIQUE INDEX uq_waitlist_event_user ON WAITLIST (event_id, user_id)
+WHERE
+    status IN ('WAITING', 'OFFERED');
+
+CREATE UNIQUE INDEX uq_waitlist_packet_user ON WAITLIST (packet_id, user_id)
+WHERE
+    status IN ('WAITING', 'OFFERED');
+
+CREATE INDEX idx_waitlist_hold_id ON WAITLIST (hold_id);
+
 CREATE TABLE
     BILETE (
         COD VARCHAR(50) PRIMARY KEY,
         PachetID INTEGER REFERENCES PACHETE (ID) ON DELETE SET NULL,
         EvenimentID INTEGER REFERENCES EVENIMENTE (ID) ON DELETE SET NULL,
+        tier_id INTEGER REFERENCES PRICE_TIERS (id) ON DELETE SET NULL,
+        price_minor BIGINT NULL,
+        currency CHAR(3) NULL,
+        promo_code_id INTEGER REFERENCES PROMO_CODES (id) ON DELETE SET NULL,
+        discount_minor BIGINT NULL,
+        checked_in_at TIMESTAMPTZ NULL,
+        checked_in_gate VARCHAR(50) NULL,
+        seat_id INTEGER REFERENCES ROOM_SEATS (id) ON DELETE SET NULL,
+        seat_label VARCHAR(120) NULL,
+        created_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
         CONSTRAINT chk_bilet_exclusiv CHECK (
             (
                 PachetID IS NOT NULL
@@ -52,6 +394,53 @@ CREATE TABLE
         )
     );
 
+CREATE UNIQUE INDEX uq_bilete_event_seat ON BILETE (EvenimentID, seat_id)
+WHERE
+    seat_id IS NOT NULL;
+
+CREATE INDEX idx_bilete_created_at ON BILETE (created_at);
+
+CREATE TABLE
+    EVENT_SEATS (
+        event_id INTEGER REFERENCES EVENIMENTE (ID) ON DELETE CASCADE,
+        seat_id INTEGER REFERENCES ROOM_SEATS (id) ON DELETE RESTRICT,
+        ticket_cod VARCHAR(50) NULL REFERENCES BILETE (COD) ON DELETE SET NULL,
+        PRIMARY KEY (event_id, seat_id)
+    );
+
+CREATE UNIQUE INDEX uq_event_seats_ticket ON EVENT_SEATS (ticket_cod);
+
+CREATE TABLE
+    PROMO_REDEMPTIONS (
+        ticket_cod VARCHAR(50) PRIMARY KEY REFERENCES BILETE (COD) ON DELETE CASCADE,
+        promo_code_id INTEGER NOT NULL REFERENCES PROMO_CODES (id) ON DELETE CASCADE,
+        buyer_id INTEGER NOT NULL,
+        discount_minor BIGINT NOT NULL,
+        redeemed_at TIMESTAMPTZ DEFAULT NOW() NOT NULL
+    );
+
+CREATE INDEX idx_promo_redemptions_code_buyer ON PROMO_REDEMPTIONS (promo_code_id, buyer_id);
+
+CREATE TABLE
+    EVENT_SCANNERS (
+        event_id INTEGER REFERENCES EVENIMENTE (ID) ON DELETE CASCADE,
+        user_id INTEGER NOT NULL,
+        created_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+        PRIMARY KEY (event_id, user_id)
+    );
+
+CREATE TABLE
+    CHECK_INS (
+        ticket_cod VARCHAR(50) REFERENCES BILETE (COD),
+        event_id INTEGER REFERENCES EVENIMENTE (ID) ON DELETE CASCADE,
+        gate VARCHAR(50) NOT NULL,
+        scanned_by INTEGER NOT NULL,
+        checked_in_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+        PRIMARY KEY (ticket_cod, event_id)
+    );
+
+CREATE INDEX idx_check_ins_event_id ON CHECK_INS (event_id);
+
 CREATE TABLE
     REFUND_REQUESTS (
         id SERIAL PRIMARY KEY,
@@ -65,5 +454,38 @@ CREATE TABLE
         reason TEXT,
         rejection_message TEXT,
         created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-        resolved_at TIMESTAMP
-    );
\ No newline at end of file
+        resolved_at TIMESTAMP,
+        tier_id INTEGER REFERENCES PRICE_TIERS (id) ON DELETE SET NULL,
+        price_minor BIGINT NULL,
+        currency CHAR(3) NULL,
+        discount_minor BIGINT NULL,
+        purchased_at TIMESTAMPTZ NULL
+    );
+CREATE TABLE
+    IDEMPOTENCY_KEYS (
+        scope VARCHAR(100) NOT NULL,
+        key VARCHAR(255) NOT NULL,
+        fingerprint CHAR(64) NOT NULL,
+        status_code INTEGER NULL,
+        response_body TEXT NULL,
+        created_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+        PRIMARY KEY (scope, key)
+    );
+
+CREATE INDEX idx_idempotency_keys_created_at ON IDEMPOTENCY_KEYS (created_at);
+
+CREATE TABLE
+    OUTBOX (
+        id BIGSERIAL PRIMARY KEY,
+        routing_key VARCHAR(100) NOT NULL,
+        payload TEXT NOT NULL,
+        attempts INTEGER DEFAULT 0 NOT NULL,
+        last_error TEXT NULL,
+        created_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+        next_attempt_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+        sent_at TIMESTAMPTZ NULL
+    );
+
+CREATE INDEX idx_outbox_pending ON OUTBOX (next_attempt_at)
+WHERE
+    sent_at IS NULL;
@@ -261,4 +261,119 @@ VALUES
     (12, 18),
     (13, 5),
     (13, 16),
-    (13, 13);
\ No newline at end of file
+    (13, 13);
+UPDATE EVENIMENTE AS e
+SET
+    starts_at = v.starts_at,
+    ends_at = v.ends_at,
+    timezone = v.timezone
+FROM (
+        VALUES
+            (1, TIMESTAMPTZ '2027-11-12 14:00:00+00', TIMESTAMPTZ '2027-11-14 22:00:00+00', 'America/New_York'),
+            (2, TIMESTAMPTZ '2027-06-08 07:00:00+00', TIMESTAMPTZ '2027-06-11 16:00:00+00', 'Europe/Berlin'),
+            (3, TIMESTAMPTZ '2027-06-22 08:00:00+00', TIMESTAMPTZ '2027-06-24 17:00:00+00', 'Europe/London'),
+            (4, TIMESTAMPTZ '2027-05-17 08:00:00+00', TIMESTAMPTZ '2027-05-21 16:00:00+00', 'Europe/London'),
+            (5, TIMESTAMPTZ '2027-11-09 16:00:00+00', TIMESTAMPTZ '2027-11-11 01:00:00+00', 'America/Los_Angeles'),
+            (6, TIMESTAMPTZ '2027-09-02 07:00:00+00', TIMESTAMPTZ '2027-09-03 16:00:00+00', 'Europe/Warsaw'),
+            (7, TIMESTAMPTZ '2027-03-18 08:00:00+00', TIMESTAMPTZ '2027-03-20 17:00:00+00', 'Europe/Rome'),
+            (8, TIMESTAMPTZ '2027-06-10 07:00:00+00', TIMESTAMPTZ '2027-06-11 18:00:00+00', 'Europe/Amsterdam'),
+            (9, TIMESTAMPTZ '2027-04-14 07:00:00+00', TIMESTAMPTZ '2027-04-15 16:00:00+00', 'Europe/Prague'),
+            (10, TIMESTAMPTZ '2027-03-22 08:00:00+00', TIMESTAMPTZ '2027-03-25 17:00:00+00', 'Europe/Paris'),
+            (11, TIMESTAMPTZ '2027-10-04 07:00:00+00', TIMESTAMPTZ '2027-10-06 16:00:00+00', 'Europe/Berlin'),
+            (12, TIMESTAMPTZ '2027-05-03 08:00:00+00', TIMESTAMPTZ '2027-05-05 17:00:00+00', 'Europe/London'),
+            (13, TIMESTAMPTZ '2027-11-18 14:00:00+00', TIMESTAMPTZ '2027-11-19 22:00:00+00', 'America/New_York'),
+            (14, TIMESTAMPTZ '2025-09-02 16:00:00+00', TIMESTAMPTZ '2025-09-05 01:00:00+00', 'America/Los_Angeles'),
+            (15, TIMESTAMPTZ '2027-06-02 07:00:00+00', TIMESTAMPTZ '2027-06-04 16:00:00+00', 'Europe/Zurich'),
+            (16, TIMESTAMPTZ '2027-10-21 07:00:00+00', TIMESTAMPTZ '2027-10-22 16:00:00+00', 'Europe/Madrid'),
+            (17, TIMESTAMPTZ '2027-02-11 08:00:00+00', TIMESTAMPTZ '2027-02-12 17:00:00+00', 'Europe/Amsterdam'),
+            (18, TIMESTAMPTZ '2027-04-08 00:00:00+00', TIMESTAMPTZ '2027-04-09 09:00:00+00', 'Asia/Tokyo')
+    ) AS v (id, starts_at, ends_at, timezone)
+WHERE
+    e.ID = v.id;
+
+UPDATE EVENIMENTE AS e
+SET
+    category = v.category,
+    tags = v.tags::TEXT[]
+FROM (
+        VALUES
+            (1, 'PROGRAMMING_LANGUAGES', '{clojure,functional}'),
+            (2, 'PROGRAMMING_LANGUAGES', '{c++,performance}'),
+            (3, 'CLOUD_DEVOPS', '{devops,ci-cd,sre}'),
+            (4, 'WEB', '{python,django}'),
+            (5, 'PROGRAMMING_LANGUAGES', '{c#,.net,azure}'),
+            (6, 'PROGRAMMING_LANGUAGES', '{elixir,functional}'),
+            (7, 'PROGRAMMING_LANGUAGES', '{go,microservices}'),
+            (8, 'WEB', '{javascript}'),
+            (9, 'MOBILE', '{kotlin,android}'),
+            (10, 'CLOUD_DEVOPS', '{kubernetes,cloud-native}'),
+            (11, 'DATA_AI', '{machine-learning,python}'),
+            (12, 'DATA_AI', '{python,data-science}'),
+            (13, 'WEB', '{javascript,react}'),
+            (14, 'PROGRAMMING_LANGUAGES', '{rust,systems}'),
+            (15, 'PROGRAMMING_LANGUAGES', '{scala,functional}'),
+            (16, 'MOBILE', '{swift,ios}'),
+            (17, 'WEB', '{javascript,vue}'),
+            (18, 'WEB', '{webassembly,rust}')
+    ) AS v (id, category, tags)
+WHERE
+    e.ID = v.id;
+
+UPDATE PACHETE AS p
+SET
+    category = v.category,
+    tags = v.tags::TEXT[]
+FROM (
+        VALUES
+            (1, 'DATA_AI', '{machine-learning,webassembly}'),
+            (2, 'PROGRAMMING_LANGUAGES', '{go,elixir,python,microservices}'),
+            (3, 'CLOUD_DEVOPS', '{kubernetes,webassembly,devops}'),
+            (4, 'DATA_AI', '{python,data-science,machine-learning}'),
+            (5, 'CLOUD_DEVOPS', '{devops,kubernetes}'),
+            (6, 'WEB', '{javascript,react,vue}'),
+            (7, 'PROGRAMMING_LANGUAGES', '{functional,elixir,scala,clojure}'),
+            (8, 'PROGRAMMING_LANGUAGES', '{c#,.net,azure}'),
+            (9, 'MOBILE', '{kotlin,swift}'),
+            (10, 'WEB', '{javascript,react,vue}'),
+            (11, 'PROGRAMMING_LANGUAGES', '{python,django}'),
+            (12, 'WEB', '{javascript,react}'),
+            (13, 'PROGRAMMING_LANGUAGES', '{rust,c++,systems}')
+    ) AS v (id, category, tags)
+WHERE
+    p.ID = v.id;
+
+UPDATE EVENIMENTE AS e
+SET
+    latitude = v.latitude,
+    longitude = v.longitude
+FROM (
+        VALUES
+            (1, 35.994, -78.8986),
+            (2, 52.52, 13.405),
+            (3, 51.5074, -0.1278),
+            (4, 55.9533, -3.1883),
+            (5, 47.6062, -122.3321),
+            (6, 50.0647, 19.945),
+            (7, 43.7696, 11.2558),
+            (8, 52.3676, 4.9041),
+            (9, 50.0755, 14.4378),
+            (10, 48.8566, 2.3522),
+            (11, 48.1351, 11.582),
+            (12, 51.5074, -0.1278),
+            (13, 40.7128, -74.006),
+            (14, 37.7749, -122.4194),
+            (15, 46.5197, 6.6323),
+            (16, 41.3874, 2.1686),
+            (17, 52.3676, 4.9041),
+            (18, 35.6762, 139.6503)
+    ) AS v (id, latitude, longitude)
+WHERE
+    e.ID = v.id;
+
+UPDATE EVENIMENTE
+SET
+    status = 'PUBLISHED';
+
+UPDATE PACHETE
+SET
+    status = 'PUBLISHED';
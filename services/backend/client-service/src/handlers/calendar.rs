@@ -0,0 +1,139 @@
+use crate::AppState;
+use crate::handlers::refund::get_user_email;
+use crate::middleware::UserClaims;
+use crate::models::calendar::CalendarFeedLink;
+use crate::services::event_service;
+use crate::utils::error::{ClientApiError, map_authorization_error, map_event_service_error};
+use axum::http::{StatusCode, header};
+use axum::response::IntoResponse;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    routing::get,
+};
+use common::authorization::Authorization;
+use std::sync::Arc;
+
+const CALENDAR_CONTENT_TYPE: &str = "text/calendar; charset=utf-8";
+
+pub fn calendar_router() -> Router<Arc<AppState>> {
+    Router::new().route(
+        "/clients/{id}/calendar",
+        get(get_calendar_feed).delete(revoke_calendar_feed),
+    )
+}
+
+/// Calendar apps cannot send a bearer token, so the feed is reachable without
+/// authentication; the token in the path is the only credential.
+pub fn public_calendar_router() -> Router<Arc<AppState>> {
+    Router::new().route("/calendar/{token}", get(get_calendar))
+}
+
+async fn authorize_client(
+    state: &AppState,
+    user_claims: &UserClaims,
+    client_id: &str,
+) -> Result<(), ClientApiError> {
+    let client = state.client_repo.get_client(client_id).await?;
+    let user_email = get_user_email(state, user_claims.user_id).await;
+    Authorization::can_access_resource(user_claims, &client, user_email.as_deref())
+        .map_err(map_authorization_error)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/client-manager/clients/{id}/calendar",
+    params(
+        ("id" = String, Path, description = "Client ID (MongoDB ObjectId)")
+    ),
+    responses(
+        (status = 200, description = "Subscription URL of the client's ticket calendar", body = CalendarFeedLink),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Can only access own calendar"),
+        (status = 404, description = "Client not found")
+    ),
+    tag = "Calendar",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_calendar_feed(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<String>,
+) -> Result<Json<CalendarFeedLink>, ClientApiError> {
+    authorize_client(&state, &user_claims, &id).await?;
+
+    let feed = state.calendar_feed_repo.get_or_create(&id).await?;
+
+    Ok(Json(CalendarFeedLink {
+        url: format!("{}/calendar/{}.ics", state.base_url, feed.token),
+        created_at: feed.created_at,
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/client-manager/clients/{id}/calendar",
+    params(
+        ("id" = String, Path, description = "Client ID (MongoDB ObjectId)")
+    ),
+    responses(
+        (status = 204, description = "Feed URL revoked; the next request for it issues a new one"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Can only access own calendar"),
+        (status = 404, description = "Client not found")
+    ),
+    tag = "Calendar",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn revoke_calendar_feed(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ClientApiError> {
+    authorize_client(&state, &user_claims, &id).await?;
+
+    state.calendar_feed_repo.delete(&id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/client-manager/calendar/{token}",
+    params(
+        ("token" = String, Path, description = "Feed token, optionally followed by `.ics`")
+    ),
+    responses(
+        (status = 200, description = "iCalendar feed with every event the client holds a ticket for", content_type = "text/calendar", body = String),
+        (status = 404, description = "Unknown or revoked feed")
+    ),
+    tag = "Calendar"
+)]
+pub async fn get_calendar(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, ClientApiError> {
+    let token = token.strip_suffix(".ics").unwrap_or(&token);
+    let feed = state.calendar_feed_repo.find_by_token(token).await?;
+    let client = state.client_repo.get_client(&feed.client_id).await?;
+
+    let codes = client
+        .lista_bilete
+        .into_iter()
+        .map(|ticket| ticket.cod)
+        .collect();
+
+    let body = event_service::get_tickets_calendar(
+        &state.event_manager_client,
+        codes,
+        &state.service_token,
+    )
+    .await
+    .map_err(map_event_service_error)?;
+
+    Ok(([(header::CONTENT_TYPE, CALENDAR_CONTENT_TYPE)], body))
+}
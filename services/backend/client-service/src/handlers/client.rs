@@ -2,18 +2,20 @@ use std::sync::Arc;
 
 use axum::extract::rejection::JsonRejection;
 use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
-use axum::routing::{delete, get};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{delete, get, post};
 use axum::{Extension, Json, Router};
 use validator::Validate;
 
 use crate::AppState;
 use crate::middleware::{Authorization, UserClaims};
 use crate::models::client::{
-    AddTicket, Client, ClientQuery, CreateClient, TicketBuyerInfo, TicketRef, UpdateClient,
+    AddTicket, Client, ClientQuery, CreateClient, TicketBuyer, TicketBuyerInfo,
+    TicketBuyersRequest, TicketRef, UpdateClient,
 };
 use crate::services::event_service;
 use crate::utils::error::{ClientApiError, map_authorization_error, map_event_service_error};
+use crate::utils::idempotency::{idempotency_key, request_fingerprint, run_idempotent};
 use crate::utils::links::{
     Response, build_filtered_client, build_simple_client, build_ticket_buyer_info, build_ticket_ref,
 };
@@ -54,97 +56,6 @@ async fn get_user_email(state: &AppState, user_id: i32) -> Option<String> {
     }
 }
 
-async fn create_ticket_via_event_service(
-    state: &AppState,
-    payload: &AddTicket,
-) -> Result<event_service::TicketDetails, ClientApiError> {
-    match (payload.id_event, payload.id_pachet) {
-        (Some(event_id), None) => event_service::create_ticket_for_event(
-            &state.event_manager_client,
-            event_id,
-            &state.service_token,
-        )
-        .await
-        .map_err(map_event_service_error),
-        (None, Some(packet_id)) => event_service::create_ticket_for_packet(
-            &state.event_manager_client,
-            packet_id,
-            &state.service_token,
-        )
-        .await
-        .map_err(map_event_service_error),
-        _ => Err(ClientApiError::BadRequest(
-            "Must specify either evenimentid or pachetid".to_string(),
-        )),
-    }
-}
-
-fn build_ticket_ref_from_details(ticket_details: &event_service::TicketDetails) -> TicketRef {
-    TicketRef {
-        cod: ticket_details.ticket.cod.clone(),
-        nume_eveniment: ticket_details
-            .event
-            .as_ref()
-            .map(|e| e.nume.clone())
-            .or_else(|| ticket_details.packet.as_ref().map(|p| p.nume.clone())),
-        locatie: ticket_details
-            .event
-            .as_ref()
-            .map(|e| e.locatie.clone())
-            .or_else(|| ticket_details.packet.as_ref().map(|p| p.locatie.clone())),
-        descriere: ticket_details
-            .event
-            .as_ref()
-            .map(|e| e.descriere.clone())
-            .or_else(|| ticket_details.packet.as_ref().map(|p| p.descriere.clone())),
-        refund_status: None,
-    }
-}
-
-async fn add_ticket_with_rollback(
-    state: &AppState,
-    client_id: &str,
-    ticket_ref: TicketRef,
-    ticket_code: &str,
-) -> Result<Client, ClientApiError> {
-    match state
-        .client_repo
-        .add_ticket_ref_to_client(client_id, ticket_ref)
-        .await
-    {
-        Ok(client) => Ok(client),
-        Err(mongo_error) => {
-            tracing::error!(
-                "Failed to add ticket {} to client {}. Rolling back ticket creation: {:?}",
-                ticket_code,
-                client_id,
-                mongo_error
-            );
-
-            if let Err(delete_error) = event_service::delete_ticket(
-                &state.event_manager_client,
-                ticket_code,
-                &state.service_token,
-            )
-            .await
-            {
-                tracing::error!(
-                    "CRITICAL: Failed to rollback ticket {} from event-service: {:?}. Manual cleanup required!",
-                    ticket_code,
-                    delete_error
-                );
-            } else {
-                tracing::info!(
-                    "Successfully rolled back ticket {} from event-service",
-                    ticket_code
-                );
-            }
-
-            Err(ClientApiError::Client(mongo_error))
-        }
-    }
-}
-
 #[utoipa::path(
     post,
     path = "/api/client-manager/clients",
@@ -179,6 +90,7 @@ pub fn client_manager_router() -> Router<Arc<AppState>> {
         .route("/clients", get(list_clients).post(create_client))
         .route("/clients/me", get(get_my_client).delete(delete_my_account))
         .route("/clients/data/{ticket_code}", get(get_client_by_ticket))
+        .route("/clients/data/batch", post(get_clients_by_tickets))
         .route(
             "/clients/{id}",
             get(get_client)
@@ -202,10 +114,14 @@ pub fn client_manager_router() -> Router<Arc<AppState>> {
     params(
         ("email" = Option<String>, Query, description = "Filter by client email"),
         ("prenume" = Option<String>, Query, description = "Filter by first name"),
-        ("nume" = Option<String>, Query, description = "Filter by last name")
+        ("nume" = Option<String>, Query, description = "Filter by last name"),
+        ("items_per_page" = Option<i64>, Query, description = "Clients per page (1-100, default 10)"),
+        ("cursor" = Option<String>, Query, description = "Opaque position from a `next`/`prev` link or the `X-Next-Cursor` header"),
+        ("include_total" = Option<bool>, Query, description = "Return the number of matching clients in `X-Total-Count`")
     ),
     responses(
         (status = 200, description = "List clients (optionally filtered)", body = Vec<Client>),
+        (status = 400, description = "Invalid cursor"),
         (status = 401, description = "Unauthorized - Missing or invalid token"),
         (status = 403, description = "Forbidden - Admin role required"),
         (status = 500, description = "Internal server error")
@@ -219,23 +135,31 @@ pub async fn list_clients(
     State(state): State<Arc<AppState>>,
     Extension(claims): Extension<UserClaims>,
     Query(query): Query<ClientQuery>,
-) -> Result<Json<Vec<Response<Client>>>, ClientApiError> {
+) -> Result<(HeaderMap, Json<Vec<Response<Client>>>), ClientApiError> {
     Authorization::can_list_all(&claims).map_err(map_authorization_error)?;
+    query.validate()?;
 
     let clients = state.client_repo.list_clients(query.clone()).await?;
+    let headers = clients.headers();
 
-    let has_filters = query.email.is_some() || query.prenume.is_some() || query.nume.is_some();
+    let has_filters = query.email.is_some()
+        || query.prenume.is_some()
+        || query.nume.is_some()
+        || query.items_per_page.is_some()
+        || query.cursor.is_some()
+        || clients.has_more_pages();
 
     let responses: Vec<Response<Client>> = if has_filters {
         build_filtered_client(clients, &query, &state.base_url)
     } else {
         clients
+            .items
             .into_iter()
             .map(|client| build_simple_client(client, &state.base_url))
             .collect()
     };
 
-    Ok(Json(responses))
+    Ok((headers, Json(responses)))
 }
 
 #[utoipa::path(
@@ -416,15 +340,16 @@ pub async fn get_client_tickets(
     post,
     path = "/api/client-manager/clients/{id}/tickets",
     params(
-        ("id" = String, Path, description = "Client ID (MongoDB ObjectId)")
+        ("id" = String, Path, description = "Client ID (MongoDB ObjectId)"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key replay the first response instead of buying again")
     ),
     request_body = AddTicket,
     responses(
-        (status = 201, description = "Ticket created and added to client. Event/packet details auto-populated from event service. Seat count decremented.", body = Client),
+        (status = 201, description = "Ticket(s) created and added to client. With `quantity`, all tickets are bought in one transaction and added together or not at all. Event/packet details auto-populated from event service. Seat count decremented.", body = Client),
         (status = 400, description = "Invalid event/packet ID"),
         (status = 401, description = "Unauthorized - Missing or invalid token"),
         (status = 404, description = "Client not found or event/packet not found"),
-        (status = 409, description = "No seats available"),
+        (status = 409, description = "No seats available, or a request with the same Idempotency-Key is still running"),
         (status = 422, description = "Validation error"),
         (status = 500, description = "External service error")
     ),
@@ -437,8 +362,9 @@ pub async fn add_ticket_to_client(
     State(state): State<Arc<AppState>>,
     Extension(user_claims): Extension<UserClaims>,
     Path(id): Path<String>,
+    headers: HeaderMap,
     payload: Result<Json<AddTicket>, JsonRejection>,
-) -> Result<(StatusCode, Json<Response<Client>>), ClientApiError> {
+) -> Result<axum::response::Response, ClientApiError> {
     let client = state.client_repo.get_client(&id).await?;
     let user_email = get_user_email(&state, user_claims.user_id).await;
     Authorization::can_modify_resource(&user_claims, &client, user_email.as_deref())
@@ -447,16 +373,28 @@ pub async fn add_ticket_to_client(
     let Json(payload) = payload?;
     payload.validate()?;
 
-    let ticket_details = create_ticket_via_event_service(&state, &payload).await?;
-    let ticket_code = ticket_details.ticket.cod.clone();
-    let ticket_ref = build_ticket_ref_from_details(&ticket_details);
-
-    let client = add_ticket_with_rollback(&state, &id, ticket_ref, &ticket_code).await?;
-
-    Ok((
-        StatusCode::CREATED,
-        Json(build_simple_client(client, &state.base_url)),
-    ))
+    let key = idempotency_key(&headers)?;
+    let scope = format!("add_ticket:{}:{}", user_claims.user_id, id);
+    let fingerprint = request_fingerprint(&payload);
+
+    run_idempotent(
+        &state.idempotency_repo,
+        &scope,
+        key.as_deref(),
+        &fingerprint,
+        async {
+            let client = state
+                .purchase_saga_coordinator
+                .purchase(&id, user_claims.user_id, &payload)
+                .await?;
+
+            Ok((
+                StatusCode::CREATED,
+                Json(build_simple_client(client, &state.base_url)),
+            ))
+        },
+    )
+    .await
 }
 
 #[utoipa::path(
@@ -532,11 +470,10 @@ pub async fn get_my_client(
 
     let query = ClientQuery {
         email: Some(user_email.clone()),
-        prenume: None,
-        nume: None,
+        ..Default::default()
     };
 
-    let clients = state.client_repo.list_clients(query).await?;
+    let clients = state.client_repo.list_clients(query).await?.items;
     tracing::info!("Found {} clients for email {}", clients.len(), user_email);
 
     let client = clients.into_iter().next().ok_or_else(|| {
@@ -573,11 +510,10 @@ pub async fn delete_my_account(
 
     let query = ClientQuery {
         email: Some(user_email.clone()),
-        prenume: None,
-        nume: None,
+        ..Default::default()
     };
 
-    let clients = state.client_repo.list_clients(query).await?;
+    let clients = state.client_repo.list_clients(query).await?.items;
     let client = clients
         .into_iter()
         .next()
@@ -655,6 +591,61 @@ pub async fn get_client_by_ticket(
     )))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/client-manager/clients/data/batch",
+    request_body = TicketBuyersRequest,
+    responses(
+        (status = 200, description = "Buyer of every ticket that was found; unknown codes are left out, and so is the email of buyers whose info is private", body = [TicketBuyer]),
+        (status = 401, description = "Unauthorized - Missing or invalid token"),
+        (status = 403, description = "Forbidden - clients-service role required"),
+        (status = 422, description = "Validation failed")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "clients"
+)]
+pub async fn get_clients_by_tickets(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    payload: Result<Json<TicketBuyersRequest>, JsonRejection>,
+) -> Result<Json<Vec<TicketBuyer>>, ClientApiError> {
+    if !user_claims.is_clients_service() && !user_claims.is_admin() {
+        return Err(ClientApiError::Forbidden(
+            "Only internal services can look up buyers in bulk".to_string(),
+        ));
+    }
+
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    let clients = state
+        .client_repo
+        .find_clients_by_ticket_codes(&payload.codes)
+        .await?;
+
+    let requested: std::collections::HashSet<&str> =
+        payload.codes.iter().map(String::as_str).collect();
+    let mut buyers = Vec::new();
+    for client in clients {
+        let codes: Vec<String> = client
+            .lista_bilete
+            .iter()
+            .filter(|ticket| requested.contains(ticket.cod.as_str()))
+            .map(|ticket| ticket.cod.clone())
+            .collect();
+        let buyer = TicketBuyerInfo::from(client);
+        buyers.extend(
+            codes
+                .into_iter()
+                .map(|ticket_code| TicketBuyer::new(ticket_code, &buyer)),
+        );
+    }
+
+    Ok(Json(buyers))
+}
+
 pub fn internal_router() -> Router<Arc<AppState>> {
     Router::new().route(
         "/clients/delete-by-email",
@@ -1,6 +1,11 @@
 pub mod auth;
+pub mod calendar;
 pub mod client;
+pub mod reconciliation;
 pub mod refund;
+pub mod ticket_pass;
+pub mod ticket_transfer;
+pub mod waitlist;
 
 use std::sync::Arc;
 
@@ -15,6 +20,15 @@ pub fn api_router() -> Router<Arc<AppState>> {
     Router::new()
         .merge(client::client_manager_router())
         .merge(refund::refund_router())
+        .merge(reconciliation::reconciliation_router())
+        .merge(ticket_pass::ticket_pass_router())
+        .merge(ticket_transfer::ticket_transfer_router())
+        .merge(waitlist::waitlist_router())
+        .merge(calendar::calendar_router())
+}
+
+pub fn public_router() -> Router<Arc<AppState>> {
+    Router::new().merge(calendar::public_calendar_router())
 }
 
 pub fn internal_router() -> Router<Arc<AppState>> {
@@ -0,0 +1,55 @@
+use crate::AppState;
+use crate::middleware::UserClaims;
+use crate::models::reconciliation::{DriftReport, ReconciliationQuery};
+use crate::utils::error::ClientApiError;
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    routing::post,
+};
+use std::sync::Arc;
+use tracing::info;
+
+pub fn reconciliation_router() -> Router<Arc<AppState>> {
+    Router::new().route("/reconciliation", post(run_reconciliation))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/client-manager/reconciliation",
+    params(
+        ("repair" = Option<bool>, Query, description = "Also fix phantom references and stale refund statuses")
+    ),
+    responses(
+        (status = 200, description = "Drift between event-service tickets and client ticket lists", body = DriftReport),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only admins can run reconciliation"),
+        (status = 500, description = "External service error")
+    ),
+    tag = "Reconciliation",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn run_reconciliation(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Query(query): Query<ReconciliationQuery>,
+) -> Result<Json<DriftReport>, ClientApiError> {
+    if !user_claims.is_admin() {
+        return Err(ClientApiError::Forbidden(
+            "Only admins can run reconciliation".to_string(),
+        ));
+    }
+
+    let report = state.ticket_reconciler.reconcile(query.repair).await?;
+
+    info!(
+        "Admin {} ran reconciliation (repair: {}), {} drift entries",
+        user_claims.user_id,
+        query.repair,
+        report.drift_count()
+    );
+
+    Ok(Json(report))
+}
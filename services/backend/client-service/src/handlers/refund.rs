@@ -1,14 +1,16 @@
 use crate::AppState;
 use crate::middleware::UserClaims;
+use crate::models::client::Client;
 use crate::models::refund::CreateRefundRequest;
 use crate::services::event_manager;
 use crate::utils::error::ClientApiError;
+use crate::utils::idempotency::{idempotency_key, request_fingerprint, run_idempotent};
 use axum::extract::rejection::JsonRejection;
 use axum::response::IntoResponse;
 use axum::{
     Extension, Json, Router,
     extract::{Path, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     routing::post,
 };
 use common::authorization::Authorization;
@@ -32,7 +34,8 @@ pub fn refund_router() -> Router<Arc<AppState>> {
     post,
     path = "/api/client-manager/clients/{id}/refunds",
     params(
-        ("id" = String, Path, description = "Client ID")
+        ("id" = String, Path, description = "Client ID"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key replay the first response instead of submitting again")
     ),
     request_body = CreateRefundRequest,
     responses(
@@ -40,7 +43,8 @@ pub fn refund_router() -> Router<Arc<AppState>> {
         (status = 400, description = "Invalid request"),
         (status = 401, description = "Missing or invalid authentication token"),
         (status = 403, description = "Forbidden - Can only request refunds on own tickets"),
-        (status = 404, description = "Ticket not found in client's tickets")
+        (status = 404, description = "Ticket not found in client's tickets"),
+        (status = 409, description = "Refund already requested, or a request with the same Idempotency-Key is still running")
     ),
     tag = "Refunds",
     security(
@@ -51,6 +55,7 @@ pub async fn request_refund(
     State(state): State<Arc<AppState>>,
     Extension(user_claims): Extension<UserClaims>,
     Path(id): Path<String>,
+    headers: HeaderMap,
     payload: Result<Json<CreateRefundRequest>, JsonRejection>,
 ) -> Result<impl IntoResponse, ClientApiError> {
     let client = state.client_repo.get_client(&id).await?;
@@ -62,6 +67,27 @@ pub async fn request_refund(
     let Json(payload) = payload?;
     payload.validate()?;
 
+    let key = idempotency_key(&headers)?;
+    let scope = format!("refund:{}:{}", user_claims.user_id, id);
+    let fingerprint = request_fingerprint(&payload);
+
+    run_idempotent(
+        &state.idempotency_repo,
+        &scope,
+        key.as_deref(),
+        &fingerprint,
+        async { submit_refund(&state, &user_claims, &id, &client, &payload).await },
+    )
+    .await
+}
+
+async fn submit_refund(
+    state: &AppState,
+    user_claims: &UserClaims,
+    id: &str,
+    client: &Client,
+    payload: &CreateRefundRequest,
+) -> Result<(StatusCode, Json<RefundResponse>), ClientApiError> {
     let ticket = client
         .lista_bilete
         .iter()
@@ -102,6 +128,12 @@ pub async fn request_refund(
         ClientApiError::InternalError("Failed to get ticket details".to_string())
     })?;
 
+    if ticket_details.ticket.checked_in_at.is_some() {
+        return Err(ClientApiError::Conflict(
+            "This ticket has already been used at the event and cannot be refunded".to_string(),
+        ));
+    }
+
     let event_id = ticket_details.ticket.evenimentid;
     let packet_id = ticket_details.ticket.pachetid;
 
@@ -124,6 +156,7 @@ pub async fn request_refund(
         packet_id,
         event_owner_id,
         reason: payload.reason.clone(),
+        auto_approve: false,
     };
 
     let json = serde_json::to_vec(&message).map_err(|e| {
@@ -142,7 +175,7 @@ pub async fn request_refund(
 
     if let Err(e) = state
         .client_repo
-        .update_ticket_refund_status(&id, &payload.ticket_cod, Some("PENDING"))
+        .update_ticket_refund_status(id, &payload.ticket_cod, Some("PENDING"))
         .await
     {
         error!("Failed to update ticket refund status: {:?}", e);
@@ -163,7 +196,7 @@ pub async fn request_refund(
     ))
 }
 
-async fn get_user_email(state: &AppState, user_id: i32) -> Option<String> {
+pub(crate) async fn get_user_email(state: &AppState, user_id: i32) -> Option<String> {
     use crate::handlers::client::auth::GetUserEmailRequest;
     use crate::handlers::client::auth::auth_service_client::AuthServiceClient;
 
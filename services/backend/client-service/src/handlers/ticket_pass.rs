@@ -0,0 +1,209 @@
+use crate::AppState;
+use crate::handlers::refund::get_user_email;
+use crate::middleware::UserClaims;
+use crate::models::ticket_pass::{
+    QrFormat, QrQuery, SignedTicketPass, TicketSigningKey, VerifyTicketPass,
+};
+use crate::services::event_service;
+use crate::utils::error::{ClientApiError, map_authorization_error, map_event_service_error};
+use crate::utils::qr;
+use axum::extract::rejection::JsonRejection;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, Query, State},
+    routing::{get, post},
+};
+use common::authorization::Authorization;
+use common::ticket_pass::{TicketPass, TicketPassError};
+use std::sync::Arc;
+use validator::Validate;
+
+pub fn ticket_pass_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/clients/{id}/tickets/{cod}/pass", get(get_ticket_pass))
+        .route("/clients/{id}/tickets/{cod}/qr", get(get_ticket_qr))
+        .route("/tickets/signing-key", get(get_signing_key))
+        .route("/tickets/verify", post(verify_ticket_pass))
+}
+
+fn map_ticket_pass_error(error: TicketPassError) -> ClientApiError {
+    match error {
+        TicketPassError::InvalidSignature => {
+            ClientApiError::BadRequest("Ticket signature is not valid".to_string())
+        }
+        TicketPassError::Malformed(msg) => {
+            ClientApiError::BadRequest(format!("Malformed ticket pass: {}", msg))
+        }
+        TicketPassError::InvalidKey(msg) => ClientApiError::InternalError(msg),
+    }
+}
+
+async fn sign_ticket(
+    state: &AppState,
+    user_claims: &UserClaims,
+    client_id: &str,
+    cod: &str,
+) -> Result<SignedTicketPass, ClientApiError> {
+    let client = state.client_repo.get_client(client_id).await?;
+    let user_email = get_user_email(state, user_claims.user_id).await;
+    Authorization::can_access_resource(user_claims, &client, user_email.as_deref())
+        .map_err(map_authorization_error)?;
+
+    if !client.lista_bilete.iter().any(|t| t.cod == cod) {
+        return Err(ClientApiError::NotFound(format!(
+            "Ticket {} does not belong to client {}",
+            cod, client_id
+        )));
+    }
+
+    let details =
+        event_service::get_ticket_details(&state.event_manager_client, cod, &state.service_token)
+            .await
+            .map_err(map_event_service_error)?;
+
+    let pass = TicketPass::new(
+        cod.to_string(),
+        details.ticket.evenimentid,
+        details.ticket.pachetid,
+        client_id.to_string(),
+    );
+
+    Ok(SignedTicketPass {
+        token: state.ticket_signer.sign(&pass),
+        pass,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/client-manager/clients/{id}/tickets/{cod}/pass",
+    params(
+        ("id" = String, Path, description = "Client ID (MongoDB ObjectId)"),
+        ("cod" = String, Path, description = "Ticket code")
+    ),
+    responses(
+        (status = 200, description = "Signed ticket pass", body = SignedTicketPass),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Can only access own tickets"),
+        (status = 404, description = "Client or ticket not found")
+    ),
+    tag = "Ticket passes",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_ticket_pass(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path((id, cod)): Path<(String, String)>,
+) -> Result<Json<SignedTicketPass>, ClientApiError> {
+    let signed = sign_ticket(&state, &user_claims, &id, &cod).await?;
+    Ok(Json(signed))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/client-manager/clients/{id}/tickets/{cod}/qr",
+    params(
+        ("id" = String, Path, description = "Client ID (MongoDB ObjectId)"),
+        ("cod" = String, Path, description = "Ticket code"),
+        ("format" = Option<QrFormat>, Query, description = "Image format, `svg` (default) or `png`")
+    ),
+    responses(
+        (status = 200, description = "QR code encoding the signed ticket pass", content((String = "image/svg+xml"), (Vec<u8> = "image/png"))),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Can only access own tickets"),
+        (status = 404, description = "Client or ticket not found")
+    ),
+    tag = "Ticket passes",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_ticket_qr(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path((id, cod)): Path<(String, String)>,
+    Query(query): Query<QrQuery>,
+) -> Result<impl IntoResponse, ClientApiError> {
+    let signed = sign_ticket(&state, &user_claims, &id, &cod).await?;
+
+    let (content_type, body) = match query.format {
+        QrFormat::Svg => (
+            "image/svg+xml",
+            qr::render_svg(&signed.token)
+                .map_err(ClientApiError::InternalError)?
+                .into_bytes(),
+        ),
+        QrFormat::Png => (
+            "image/png",
+            qr::render_png(&signed.token).map_err(ClientApiError::InternalError)?,
+        ),
+    };
+
+    Ok(([(header::CONTENT_TYPE, content_type)], body))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/client-manager/tickets/signing-key",
+    responses(
+        (status = 200, description = "Public key for verifying ticket passes offline", body = TicketSigningKey),
+        (status = 401, description = "Missing or invalid authentication token")
+    ),
+    tag = "Ticket passes",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_signing_key(State(state): State<Arc<AppState>>) -> Json<TicketSigningKey> {
+    Json(TicketSigningKey {
+        algorithm: "Ed25519".to_string(),
+        public_key: state.ticket_signer.public_key(),
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/client-manager/tickets/verify",
+    request_body = VerifyTicketPass,
+    responses(
+        (status = 200, description = "Signature is valid and the holder still owns the ticket", body = TicketPass),
+        (status = 400, description = "Malformed pass or invalid signature"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 409, description = "Pass is authentic but the ticket was refunded or changed hands"),
+        (status = 422, description = "Validation error")
+    ),
+    tag = "Ticket passes",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn verify_ticket_pass(
+    State(state): State<Arc<AppState>>,
+    payload: Result<Json<VerifyTicketPass>, JsonRejection>,
+) -> Result<Json<TicketPass>, ClientApiError> {
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    let pass = state
+        .ticket_signer
+        .verifier()
+        .verify(&payload.token)
+        .map_err(map_ticket_pass_error)?;
+
+    let holder = state
+        .client_repo
+        .find_client_by_ticket_code(&pass.cod)
+        .await?;
+    if holder.map(|client| client.id.to_hex()).as_deref() != Some(pass.holder.as_str()) {
+        return Err(ClientApiError::Conflict(format!(
+            "Ticket {} is no longer held by {}",
+            pass.cod, pass.holder
+        )));
+    }
+
+    Ok(Json(pass))
+}
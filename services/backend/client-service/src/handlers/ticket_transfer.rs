@@ -0,0 +1,506 @@
+use crate::AppState;
+use crate::handlers::refund::get_user_email;
+use crate::middleware::UserClaims;
+use crate::models::client::{Client, TicketRef};
+use crate::models::ticket_transfer::{
+    ClientTransfers, StartTransfer, TicketTransfer, TicketTransferRecord, TransferStatus,
+};
+use crate::repositories::ticket_transfer_repo::now_rfc3339;
+use crate::services::event_service;
+use crate::utils::error::{ClientApiError, map_authorization_error, map_event_service_error};
+use axum::extract::rejection::JsonRejection;
+use axum::response::IntoResponse;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+};
+use common::authorization::Authorization;
+use common::rabbitmq::messages::{
+    ROUTING_KEY_TICKET_TRANSFER, TicketTransferNotice, TicketTransferStatus,
+};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+use validator::Validate;
+
+pub fn ticket_transfer_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/clients/{id}/tickets/{cod}/transfers",
+            post(start_transfer),
+        )
+        .route("/clients/{id}/transfers", get(list_transfers))
+        .route(
+            "/clients/{id}/transfers/{transfer_id}/accept",
+            post(accept_transfer),
+        )
+        .route(
+            "/clients/{id}/transfers/{transfer_id}/decline",
+            post(decline_transfer),
+        )
+        .route(
+            "/clients/{id}/transfers/{transfer_id}",
+            delete(cancel_transfer),
+        )
+}
+
+async fn authorized_client(
+    state: &AppState,
+    user_claims: &UserClaims,
+    id: &str,
+) -> Result<Client, ClientApiError> {
+    let client = state.client_repo.get_client(id).await?;
+    let user_email = get_user_email(state, user_claims.user_id).await;
+    Authorization::can_modify_resource(user_claims, &client, user_email.as_deref())
+        .map_err(map_authorization_error)?;
+    Ok(client)
+}
+
+fn ensure_transferable(ticket: &TicketRef) -> Result<(), ClientApiError> {
+    match ticket.refund_status.as_deref() {
+        Some("PENDING") => Err(ClientApiError::Conflict(
+            "A refund request is pending for this ticket".to_string(),
+        )),
+        Some("APPROVED") => Err(ClientApiError::Conflict(
+            "This ticket has already been refunded".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+async fn notify(state: &AppState, transfer: &TicketTransfer, status: TicketTransferStatus) {
+    let event_name = state
+        .client_repo
+        .find_client_by_ticket_code(&transfer.ticket_cod)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|client| {
+            client
+                .lista_bilete
+                .into_iter()
+                .find(|t| t.cod == transfer.ticket_cod)
+        })
+        .and_then(|t| t.nume_eveniment);
+
+    let message = TicketTransferNotice {
+        transfer_id: transfer.id.clone(),
+        ticket_cod: transfer.ticket_cod.clone(),
+        event_name,
+        from_email: transfer.from_email.clone(),
+        to_email: transfer.to_email.clone(),
+        status,
+    };
+
+    match serde_json::to_vec(&message) {
+        Ok(json) => {
+            if let Err(e) = state
+                .rabbitmq
+                .publish(ROUTING_KEY_TICKET_TRANSFER, &json)
+                .await
+            {
+                error!("Failed to publish ticket transfer notice: {:?}", e);
+            }
+        }
+        Err(e) => error!("Failed to serialize ticket transfer notice: {:?}", e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/client-manager/clients/{id}/tickets/{cod}/transfers",
+    params(
+        ("id" = String, Path, description = "Client ID of the current holder"),
+        ("cod" = String, Path, description = "Ticket code")
+    ),
+    request_body = StartTransfer,
+    responses(
+        (status = 201, description = "Transfer started; the recipient is notified and has to accept it", body = TicketTransfer),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Can only transfer own tickets"),
+        (status = 404, description = "Ticket not found, or no client is registered with that email"),
+        (status = 409, description = "Ticket already used, refunded or has a transfer in progress")
+    ),
+    tag = "Ticket transfers",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn start_transfer(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path((id, cod)): Path<(String, String)>,
+    payload: Result<Json<StartTransfer>, JsonRejection>,
+) -> Result<impl IntoResponse, ClientApiError> {
+    let client = authorized_client(&state, &user_claims, &id).await?;
+
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    let ticket = client
+        .lista_bilete
+        .iter()
+        .find(|t| t.cod == cod)
+        .ok_or_else(|| {
+            ClientApiError::NotFound(format!(
+                "Ticket with code '{}' not found in this client's tickets",
+                cod
+            ))
+        })?;
+    ensure_transferable(ticket)?;
+
+    if payload.to_email.eq_ignore_ascii_case(&client.email) {
+        return Err(ClientApiError::BadRequest(
+            "Cannot transfer a ticket to yourself".to_string(),
+        ));
+    }
+
+    let recipient = state
+        .client_repo
+        .find_client_by_email(&payload.to_email)
+        .await?
+        .ok_or_else(|| {
+            ClientApiError::NotFound(format!(
+                "No client is registered with email {}",
+                payload.to_email
+            ))
+        })?;
+
+    let details =
+        event_service::get_ticket_details(&state.event_manager_client, &cod, &state.service_token)
+            .await
+            .map_err(map_event_service_error)?;
+    if details.ticket.checked_in_at.is_some() {
+        return Err(ClientApiError::Conflict(
+            "This ticket has already been used at the event".to_string(),
+        ));
+    }
+
+    let transfer = TicketTransfer {
+        id: Uuid::now_v7().to_string(),
+        ticket_cod: cod.clone(),
+        from_client_id: id.clone(),
+        from_email: client.email.clone(),
+        to_client_id: recipient.id.to_hex(),
+        to_email: recipient.email,
+        status: TransferStatus::Pending,
+        created_at: now_rfc3339(),
+        resolved_at: None,
+    };
+
+    if !state.ticket_transfer_repo.create(&transfer).await? {
+        return Err(ClientApiError::Conflict(format!(
+            "Ticket {} already has a transfer in progress",
+            cod
+        )));
+    }
+
+    info!(
+        "Client {} started transfer {} of ticket {} to {}",
+        id, transfer.id, cod, transfer.to_email
+    );
+    notify(&state, &transfer, TicketTransferStatus::Requested).await;
+
+    Ok((StatusCode::CREATED, Json(transfer)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/client-manager/clients/{id}/transfers",
+    params(
+        ("id" = String, Path, description = "Client ID")
+    ),
+    responses(
+        (status = 200, description = "Transfers received and sent by the client, newest first", body = ClientTransfers),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Can only list own transfers"),
+        (status = 404, description = "Client not found")
+    ),
+    tag = "Ticket transfers",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn list_transfers(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<String>,
+) -> Result<Json<ClientTransfers>, ClientApiError> {
+    authorized_client(&state, &user_claims, &id).await?;
+
+    let (incoming, outgoing) = state
+        .ticket_transfer_repo
+        .list_for_client(&id)
+        .await?
+        .into_iter()
+        .partition(|t| t.to_client_id == id);
+
+    Ok(Json(ClientTransfers { incoming, outgoing }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/client-manager/clients/{id}/transfers/{transfer_id}/accept",
+    params(
+        ("id" = String, Path, description = "Client ID of the recipient"),
+        ("transfer_id" = String, Path, description = "Transfer ID")
+    ),
+    responses(
+        (status = 200, description = "Ticket moved to the recipient", body = Client),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Can only accept transfers addressed to own account"),
+        (status = 404, description = "Transfer not found for this client"),
+        (status = 409, description = "Transfer already resolved, or the ticket can no longer be transferred")
+    ),
+    tag = "Ticket transfers",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn accept_transfer(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path((id, transfer_id)): Path<(String, String)>,
+) -> Result<Json<Client>, ClientApiError> {
+    authorized_client(&state, &user_claims, &id).await?;
+
+    let transfer = state.ticket_transfer_repo.get(&transfer_id).await?;
+    if transfer.to_client_id != id {
+        return Err(ClientApiError::NotFound(format!(
+            "Transfer {} not found for this client",
+            transfer_id
+        )));
+    }
+
+    match transfer.status {
+        TransferStatus::Pending => {
+            let claimed = state
+                .ticket_transfer_repo
+                .advance(
+                    &transfer_id,
+                    TransferStatus::Pending,
+                    TransferStatus::Accepting,
+                )
+                .await?;
+            if !claimed {
+                return Err(ClientApiError::Conflict(format!(
+                    "Transfer {} was resolved in the meantime",
+                    transfer_id
+                )));
+            }
+        }
+        // A previous accept stopped half way; the steps below pick it up.
+        TransferStatus::Accepting => {}
+        status => {
+            return Err(ClientApiError::Conflict(format!(
+                "Transfer {} is already {}",
+                transfer_id,
+                status.as_str()
+            )));
+        }
+    }
+
+    let sender = state
+        .client_repo
+        .get_client(&transfer.from_client_id)
+        .await?;
+    let held = sender
+        .lista_bilete
+        .into_iter()
+        .find(|t| t.cod == transfer.ticket_cod);
+
+    match held {
+        Some(mut ticket) => {
+            if let Err(e) = ensure_transferable(&ticket) {
+                state
+                    .ticket_transfer_repo
+                    .advance(
+                        &transfer_id,
+                        TransferStatus::Accepting,
+                        TransferStatus::Cancelled,
+                    )
+                    .await?;
+                notify(&state, &transfer, TicketTransferStatus::Cancelled).await;
+                return Err(e);
+            }
+
+            ticket.transfer_history.push(TicketTransferRecord {
+                transfer_id: transfer.id.clone(),
+                from_email: transfer.from_email.clone(),
+                to_email: transfer.to_email.clone(),
+                transferred_at: now_rfc3339(),
+            });
+
+            // Give before take: a crash in between leaves the ticket on both
+            // lists, never on neither, and the next accept removes the copy.
+            state.client_repo.give_ticket_ref(&id, &ticket).await?;
+            state
+                .client_repo
+                .remove_ticket_refs_from_client(
+                    &transfer.from_client_id,
+                    std::slice::from_ref(&transfer.ticket_cod),
+                )
+                .await?;
+        }
+        None => {
+            let recipient = state.client_repo.get_client(&id).await?;
+            if !recipient
+                .lista_bilete
+                .iter()
+                .any(|t| t.cod == transfer.ticket_cod)
+            {
+                warn!(
+                    "Ticket {} left client {} before transfer {} was accepted",
+                    transfer.ticket_cod, transfer.from_client_id, transfer_id
+                );
+                state
+                    .ticket_transfer_repo
+                    .advance(
+                        &transfer_id,
+                        TransferStatus::Accepting,
+                        TransferStatus::Cancelled,
+                    )
+                    .await?;
+                notify(&state, &transfer, TicketTransferStatus::Cancelled).await;
+                return Err(ClientApiError::Conflict(
+                    "The ticket is no longer held by the sender".to_string(),
+                ));
+            }
+        }
+    }
+
+    state
+        .ticket_transfer_repo
+        .advance(
+            &transfer_id,
+            TransferStatus::Accepting,
+            TransferStatus::Accepted,
+        )
+        .await?;
+
+    info!(
+        "Ticket {} transferred from client {} to client {}",
+        transfer.ticket_cod, transfer.from_client_id, id
+    );
+    notify(&state, &transfer, TicketTransferStatus::Accepted).await;
+
+    let recipient = state.client_repo.get_client(&id).await?;
+    Ok(Json(recipient))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/client-manager/clients/{id}/transfers/{transfer_id}/decline",
+    params(
+        ("id" = String, Path, description = "Client ID of the recipient"),
+        ("transfer_id" = String, Path, description = "Transfer ID")
+    ),
+    responses(
+        (status = 200, description = "Transfer declined; the ticket stays with the sender", body = TicketTransfer),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Can only decline transfers addressed to own account"),
+        (status = 404, description = "Transfer not found for this client"),
+        (status = 409, description = "Transfer is no longer pending")
+    ),
+    tag = "Ticket transfers",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn decline_transfer(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path((id, transfer_id)): Path<(String, String)>,
+) -> Result<Json<TicketTransfer>, ClientApiError> {
+    authorized_client(&state, &user_claims, &id).await?;
+
+    let transfer = state.ticket_transfer_repo.get(&transfer_id).await?;
+    if transfer.to_client_id != id {
+        return Err(ClientApiError::NotFound(format!(
+            "Transfer {} not found for this client",
+            transfer_id
+        )));
+    }
+
+    resolve_pending(
+        &state,
+        transfer,
+        TransferStatus::Declined,
+        TicketTransferStatus::Declined,
+    )
+    .await
+    .map(Json)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/client-manager/clients/{id}/transfers/{transfer_id}",
+    params(
+        ("id" = String, Path, description = "Client ID of the sender"),
+        ("transfer_id" = String, Path, description = "Transfer ID")
+    ),
+    responses(
+        (status = 204, description = "Transfer cancelled"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Can only cancel own transfers"),
+        (status = 404, description = "Transfer not found for this client"),
+        (status = 409, description = "Transfer is no longer pending")
+    ),
+    tag = "Ticket transfers",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn cancel_transfer(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path((id, transfer_id)): Path<(String, String)>,
+) -> Result<StatusCode, ClientApiError> {
+    authorized_client(&state, &user_claims, &id).await?;
+
+    let transfer = state.ticket_transfer_repo.get(&transfer_id).await?;
+    if transfer.from_client_id != id {
+        return Err(ClientApiError::NotFound(format!(
+            "Transfer {} not found for this client",
+            transfer_id
+        )));
+    }
+
+    resolve_pending(
+        &state,
+        transfer,
+        TransferStatus::Cancelled,
+        TicketTransferStatus::Cancelled,
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn resolve_pending(
+    state: &AppState,
+    mut transfer: TicketTransfer,
+    to: TransferStatus,
+    notice: TicketTransferStatus,
+) -> Result<TicketTransfer, ClientApiError> {
+    let moved = state
+        .ticket_transfer_repo
+        .advance(&transfer.id, TransferStatus::Pending, to)
+        .await?;
+    if !moved {
+        return Err(ClientApiError::Conflict(format!(
+            "Transfer {} is no longer pending",
+            transfer.id
+        )));
+    }
+
+    info!("Transfer {} {}", transfer.id, to.as_str());
+    notify(state, &transfer, notice).await;
+
+    transfer.status = to;
+    transfer.resolved_at = Some(now_rfc3339());
+    Ok(transfer)
+}
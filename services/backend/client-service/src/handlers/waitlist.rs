@@ -0,0 +1,126 @@
+use crate::AppState;
+use crate::handlers::refund::get_user_email;
+use crate::middleware::UserClaims;
+use crate::models::waitlist::JoinWaitlist;
+use crate::services::event_service::{self, WaitlistEntryInfo};
+use crate::utils::error::{ClientApiError, map_authorization_error, map_event_service_error};
+use axum::extract::rejection::JsonRejection;
+use axum::response::IntoResponse;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, post},
+};
+use common::authorization::Authorization;
+use std::sync::Arc;
+use tracing::info;
+use validator::Validate;
+
+pub fn waitlist_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/clients/{id}/waitlist", post(join_waitlist))
+        .route("/clients/{id}/waitlist/{entry_id}", delete(leave_waitlist))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/client-manager/clients/{id}/waitlist",
+    params(
+        ("id" = String, Path, description = "Client ID")
+    ),
+    request_body = JoinWaitlist,
+    responses(
+        (status = 201, description = "Joined the waitlist; seat offers arrive over WebSocket and email", body = WaitlistEntryInfo),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Can only join waitlists for own account"),
+        (status = 404, description = "Client, event or packet not found"),
+        (status = 409, description = "Already waiting, or the event or packet is not on sale")
+    ),
+    tag = "Waitlist",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn join_waitlist(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<String>,
+    payload: Result<Json<JoinWaitlist>, JsonRejection>,
+) -> Result<impl IntoResponse, ClientApiError> {
+    let client = state.client_repo.get_client(&id).await?;
+
+    let user_email = get_user_email(&state, user_claims.user_id).await;
+    Authorization::can_modify_resource(&user_claims, &client, user_email.as_deref())
+        .map_err(map_authorization_error)?;
+
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    let entry = event_service::join_waitlist(
+        &state.event_manager_client,
+        payload.id_event,
+        payload.id_pachet,
+        user_claims.user_id,
+        &client.email,
+        &state.service_token,
+    )
+    .await
+    .map_err(map_event_service_error)?;
+
+    info!("Client {} joined waitlist entry {}", id, entry.id);
+
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/client-manager/clients/{id}/waitlist/{entry_id}",
+    params(
+        ("id" = String, Path, description = "Client ID"),
+        ("entry_id" = i32, Path, description = "Waitlist entry ID")
+    ),
+    responses(
+        (status = 204, description = "Left the waitlist"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Can only leave own waitlists"),
+        (status = 404, description = "Waitlist entry not found for this client")
+    ),
+    tag = "Waitlist",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn leave_waitlist(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path((id, entry_id)): Path<(String, i32)>,
+) -> Result<impl IntoResponse, ClientApiError> {
+    let client = state.client_repo.get_client(&id).await?;
+
+    let user_email = get_user_email(&state, user_claims.user_id).await;
+    Authorization::can_modify_resource(&user_claims, &client, user_email.as_deref())
+        .map_err(map_authorization_error)?;
+
+    let entry = event_service::get_waitlist_entry(
+        &state.event_manager_client,
+        entry_id,
+        &state.service_token,
+    )
+    .await
+    .map_err(map_event_service_error)?;
+
+    if entry.email != client.email {
+        return Err(ClientApiError::NotFound(format!(
+            "Waitlist entry {} not found for this client",
+            entry_id
+        )));
+    }
+
+    event_service::leave_waitlist(&state.event_manager_client, entry_id, &state.service_token)
+        .await
+        .map_err(map_event_service_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
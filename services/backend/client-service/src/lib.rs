@@ -1,6 +1,11 @@
+use crate::repositories::calendar_feed_repo::CalendarFeedRepo;
 use crate::repositories::client_repo::ClientRepo;
+use crate::repositories::idempotency_repo::IdempotencyRepo;
+use crate::repositories::ticket_transfer_repo::TicketTransferRepo;
+use crate::services::{PurchaseSagaCoordinator, TicketReconciler};
 use crate::services::event_manager::EventManagerClient;
 use common::rabbitmq::RabbitMQ;
+use common::ticket_pass::TicketSigner;
 use std::sync::Arc;
 pub mod handlers;
 pub mod middleware;
@@ -11,6 +16,12 @@ pub mod utils;
 
 pub struct AppState {
     pub client_repo: Arc<ClientRepo>,
+    pub idempotency_repo: Arc<IdempotencyRepo>,
+    pub ticket_transfer_repo: Arc<TicketTransferRepo>,
+    pub calendar_feed_repo: Arc<CalendarFeedRepo>,
+    pub purchase_saga_coordinator: Arc<PurchaseSagaCoordinator>,
+    pub ticket_reconciler: Arc<TicketReconciler>,
+    pub ticket_signer: Arc<TicketSigner>,
     pub base_url: String,
     pub event_service_url: String,
     pub auth_service_url: String,
@@ -1,14 +1,24 @@
 use anyhow::Result;
+use axum::http::HeaderName;
 use axum::middleware;
 use axum::{Router, extract::State, routing::get};
 use axum_prometheus::PrometheusMetricLayer;
 use client_service::handlers::auth::auth::AuthRequest;
 use client_service::handlers::auth::auth::auth_service_client::AuthServiceClient;
 use client_service::middleware::auth::auth_middleware;
-use client_service::services::RefundConsumer;
+use client_service::services::{
+    EventCancelledConsumer, PurchaseSagaCoordinator, PurchaseSagaRecoveryWorker, RefundConsumer,
+    TicketReconciler, TicketReconcilerWorker,
+};
 use client_service::services::event_manager::EventManagerClient;
+use client_service::repositories::calendar_feed_repo::CalendarFeedRepo;
+use client_service::repositories::idempotency_repo::IdempotencyRepo;
+use client_service::repositories::purchase_saga_repo::PurchaseSagaRepo;
+use client_service::repositories::ticket_transfer_repo::TicketTransferRepo;
 use client_service::{AppState, handlers, repositories::client_repo::ClientRepo};
+use common::pagination::{NEXT_CURSOR_HEADER, TOTAL_COUNT_HEADER};
 use common::rabbitmq::RabbitMQ;
+use common::ticket_pass::TicketSigner;
 use mongodb::{Client, options::ClientOptions};
 use std::sync::Arc;
 use tower_http::cors::{Any, CorsLayer};
@@ -81,9 +91,48 @@ async fn main() -> Result<()> {
     let service_token = auth_response.token_value;
     info!("{:<12} - Service authentication successful", "AUTH");
 
+    let ticket_signing_key =
+        std::env::var("TICKET_SIGNING_KEY").expect("TICKET_SIGNING_KEY must be set");
+    let ticket_signer = Arc::new(
+        TicketSigner::from_seed(&ticket_signing_key).expect("TICKET_SIGNING_KEY is not a valid key"),
+    );
+
     let event_manager_client = Arc::new(EventManagerClient::new(event_service_url.clone()));
 
-    let client_repo = Arc::new(ClientRepo::new(database));
+    let client_repo = Arc::new(ClientRepo::new(database.clone()));
+    let idempotency_repo = Arc::new(IdempotencyRepo::new(database.clone()));
+    let ticket_transfer_repo = Arc::new(TicketTransferRepo::new(database.clone()));
+    let calendar_feed_repo = Arc::new(CalendarFeedRepo::new(database.clone()));
+    let purchase_saga_repo = Arc::new(PurchaseSagaRepo::new(database));
+
+    let purchase_saga_coordinator = Arc::new(PurchaseSagaCoordinator::new(
+        Arc::clone(&purchase_saga_repo),
+        Arc::clone(&client_repo),
+        Arc::clone(&event_manager_client),
+        service_token.clone(),
+    ));
+
+    let saga_recovery_worker =
+        PurchaseSagaRecoveryWorker::new(Arc::clone(&purchase_saga_coordinator));
+    tokio::spawn(async move {
+        saga_recovery_worker.start().await;
+    });
+
+    let ticket_reconciler = Arc::new(TicketReconciler::new(
+        Arc::clone(&client_repo),
+        purchase_saga_repo,
+        Arc::clone(&event_manager_client),
+        service_token.clone(),
+    ));
+
+    let reconcile_repair = std::env::var("RECONCILIATION_REPAIR")
+        .map(|value| value == "true")
+        .unwrap_or(false);
+    let reconciler_worker =
+        TicketReconcilerWorker::new(Arc::clone(&ticket_reconciler), reconcile_repair);
+    tokio::spawn(async move {
+        reconciler_worker.start().await;
+    });
 
     let refund_consumer = RefundConsumer::new(Arc::clone(&rabbitmq), Arc::clone(&client_repo));
     let refund_consumer_task = tokio::spawn(async move {
@@ -92,8 +141,27 @@ async fn main() -> Result<()> {
         }
     });
 
+    let event_cancelled_consumer = EventCancelledConsumer::new(
+        Arc::clone(&rabbitmq),
+        Arc::clone(&client_repo),
+        Arc::clone(&event_manager_client),
+        service_token.clone(),
+        auth_service_url.clone(),
+    );
+    let event_cancelled_consumer_task = tokio::spawn(async move {
+        if let Err(e) = event_cancelled_consumer.start().await {
+            error!("Event cancellation consumer error: {:?}", e);
+        }
+    });
+
     let app_state = Arc::new(AppState {
         client_repo,
+        idempotency_repo,
+        ticket_transfer_repo,
+        calendar_feed_repo,
+        purchase_saga_coordinator,
+        ticket_reconciler,
+        ticket_signer,
         base_url: "http://localhost:8002/api/client-manager".to_string(),
         event_service_url,
         auth_service_url,
@@ -106,7 +174,11 @@ async fn main() -> Result<()> {
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
-        .allow_headers(Any);
+        .allow_headers(Any)
+        .expose_headers([
+            HeaderName::from_static(TOTAL_COUNT_HEADER),
+            HeaderName::from_static(NEXT_CURSOR_HEADER),
+        ]);
 
     let (prometheus_layer, metric_handle) = PrometheusMetricLayer::pair();
 
@@ -121,6 +193,7 @@ async fn main() -> Result<()> {
                 auth_middleware,
             )),
         )
+        .nest("/api/client-manager", handlers::public_router())
         .nest(
             "/api/client-manager",
             handlers::api_router().layer(middleware::from_fn_with_state(
@@ -151,6 +224,9 @@ async fn main() -> Result<()> {
         _ = refund_consumer_task => {
             error!("Refund consumer ended unexpectedly");
         }
+        _ = event_cancelled_consumer_task => {
+            error!("Event cancellation consumer ended unexpectedly");
+        }
     }
 
     Ok(())
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Subscribable calendar of a client, keyed by the client id. Whoever knows the
+/// token can read the feed, so it is only shown to the client and admins.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarFeed {
+    #[serde(rename = "_id")]
+    pub client_id: String,
+    pub token: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CalendarFeedLink {
+    /// Address to subscribe to from a calendar app; needs no `Authorization` header.
+    pub url: String,
+    pub created_at: String,
+}
@@ -1,5 +1,7 @@
 use bson::oid::ObjectId;
 use common::authorization::{ResourceOwnership, UserClaims};
+
+use crate::models::ticket_transfer::TicketTransferRecord;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
@@ -49,6 +51,8 @@ pub struct TicketRef {
     pub descriere: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub refund_status: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transfer_history: Vec<TicketTransferRecord>,
 }
 
 #[derive(Debug, Deserialize, Validate, ToSchema)]
@@ -102,7 +106,7 @@ pub struct UpdateClient {
     pub social_media: Option<SocialMedia>,
 }
 
-#[derive(Debug, Deserialize, ToSchema, Clone)]
+#[derive(Debug, Default, Deserialize, ToSchema, Clone, Validate)]
 pub struct ClientQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
@@ -110,9 +114,22 @@ pub struct ClientQuery {
     pub prenume: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nume: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(range(
+        min = 1,
+        max = 100,
+        message = "Items per page must be between 1 and 100"
+    ))]
+    pub items_per_page: Option<i64>,
+    /// Opaque position taken from a `next`/`prev` link.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 512, message = "Cursor must be less than 512 characters"))]
+    pub cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_total: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 #[serde(deny_unknown_fields)]
 #[validate(schema(function = "validate_add_ticket"))]
 pub struct AddTicket {
@@ -120,6 +137,22 @@ pub struct AddTicket {
     pub id_event: Option<i32>,
     #[serde(rename = "pachetid")]
     pub id_pachet: Option<i32>,
+    #[validate(range(min = 0, message = "Tier ID cannot be negative"))]
+    pub tier_id: Option<i32>,
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Hold ID must be between 1 and 50 characters"
+    ))]
+    pub hold_id: Option<String>,
+    #[validate(range(min = 1, max = 10, message = "Quantity must be between 1 and 10"))]
+    pub quantity: Option<i32>,
+    #[validate(length(
+        min = 3,
+        max = 50,
+        message = "Promo code must be between 3 and 50 characters"
+    ))]
+    pub promo_code: Option<String>,
 }
 
 fn validate_add_ticket(ticket: &AddTicket) -> Result<(), validator::ValidationError> {
@@ -153,7 +186,7 @@ fn validate_add_ticket(ticket: &AddTicket) -> Result<(), validator::ValidationEr
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TicketBuyerInfo {
     pub email: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -175,6 +208,43 @@ impl From<Client> for TicketBuyerInfo {
     }
 }
 
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TicketBuyersRequest {
+    #[validate(length(
+        min = 1,
+        max = 1000,
+        message = "Between 1 and 1000 ticket codes can be looked up at once"
+    ))]
+    pub codes: Vec<String>,
+}
+
+/// Buyer of a ticket in a batch lookup; the email, like the names, is only
+/// sent for clients who made their info public.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TicketBuyer {
+    pub ticket_code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prenume: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nume: Option<String>,
+    pub public_info: bool,
+}
+
+impl TicketBuyer {
+    pub fn new(ticket_code: String, buyer: &TicketBuyerInfo) -> Self {
+        Self {
+            ticket_code,
+            email: buyer.public_info.then(|| buyer.email.clone()),
+            prenume: buyer.prenume.clone(),
+            nume: buyer.nume.clone(),
+            public_info: buyer.public_info,
+        }
+    }
+}
+
 impl ResourceOwnership for Client {
     fn is_owned_by(&self, claims: &UserClaims, user_email: Option<&str>) -> bool {
         if let Some(email) = user_email {
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IdempotencyRecord {
+    pub scope: String,
+    pub key: String,
+    /// Absent on keys stored before fingerprints were recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+    pub created_at: bson::DateTime,
+}
+
+/// Outcome of trying to claim an idempotency key before running a request.
+#[derive(Debug)]
+pub enum IdempotencyClaim {
+    Claimed,
+    InProgress,
+    /// The key was first used with a different request.
+    Mismatch,
+    Completed {
+        status: u16,
+        body: serde_json::Value,
+    },
+}
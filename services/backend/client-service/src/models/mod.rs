@@ -1,3 +1,10 @@
 pub mod auth;
+pub mod calendar;
 pub mod client;
+pub mod idempotency;
+pub mod purchase_saga;
+pub mod reconciliation;
 pub mod refund;
+pub mod ticket_pass;
+pub mod ticket_transfer;
+pub mod waitlist;
@@ -0,0 +1,58 @@
+use bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use crate::models::client::TicketRef;
+
+/// Steps of a ticket purchase. A saga only ever ends in `Completed` (every
+/// ticket is in the client's `lista_bilete`) or `Compensated` (every ticket
+/// was released in event-service).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SagaState {
+    Started,
+    Reserved,
+    Completed,
+    Compensating,
+    Compensated,
+}
+
+impl SagaState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SagaState::Started => "STARTED",
+            SagaState::Reserved => "RESERVED",
+            SagaState::Completed => "COMPLETED",
+            SagaState::Compensating => "COMPENSATING",
+            SagaState::Compensated => "COMPENSATED",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurchaseSaga {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub client_id: String,
+    pub ticket_codes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_event: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_pachet: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tier_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hold_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub promo_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buyer_id: Option<i32>,
+    pub state: SagaState,
+    #[serde(default)]
+    pub ticket_refs: Vec<TicketRef>,
+    #[serde(default)]
+    pub attempts: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub created_at: bson::DateTime,
+    pub updated_at: bson::DateTime,
+}
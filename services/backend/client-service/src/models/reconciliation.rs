@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReconciliationQuery {
+    #[serde(default)]
+    pub repair: bool,
+}
+
+/// Differences found between event-service's `BILETE` table and the
+/// `lista_bilete` references kept on clients.
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct DriftReport {
+    pub generated_at: String,
+    pub tickets_checked: usize,
+    pub references_checked: usize,
+    /// Tickets in `BILETE` that no client references.
+    pub orphaned_tickets: Vec<OrphanedTicket>,
+    /// References to tickets that no longer exist in `BILETE`.
+    pub phantom_references: Vec<PhantomReference>,
+    /// Tickets referenced more than once, by one or several clients.
+    pub duplicate_references: Vec<DuplicateReference>,
+    /// References whose `refund_status` disagrees with the latest refund request.
+    pub stale_refund_statuses: Vec<StaleRefundStatus>,
+}
+
+impl DriftReport {
+    pub fn drift_count(&self) -> usize {
+        self.orphaned_tickets.len()
+            + self.phantom_references.len()
+            + self.duplicate_references.len()
+            + self.stale_refund_statuses.len()
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrphanedTicket {
+    pub ticket_cod: String,
+    pub evenimentid: Option<i32>,
+    pub pachetid: Option<i32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PhantomReference {
+    pub client_id: String,
+    pub ticket_cod: String,
+    pub repaired: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DuplicateReference {
+    pub ticket_cod: String,
+    pub client_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StaleRefundStatus {
+    pub client_id: String,
+    pub ticket_cod: String,
+    pub client_status: Option<String>,
+    pub event_service_status: Option<String>,
+    pub repaired: bool,
+}
@@ -0,0 +1,41 @@
+use common::ticket_pass::TicketPass;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SignedTicketPass {
+    /// Signed pass, as encoded in the QR code.
+    pub token: String,
+    pub pass: TicketPass,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct VerifyTicketPass {
+    #[validate(length(
+        min = 1,
+        max = 2048,
+        message = "Token must be between 1 and 2048 characters"
+    ))]
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TicketSigningKey {
+    pub algorithm: String,
+    pub public_key: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum QrFormat {
+    #[default]
+    Svg,
+    Png,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QrQuery {
+    #[serde(default)]
+    pub format: QrFormat,
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Lifecycle of a transfer. `Accepting` marks a transfer whose ticket is being
+/// moved; accepting it again finishes the move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransferStatus {
+    Pending,
+    Accepting,
+    Accepted,
+    Declined,
+    Cancelled,
+}
+
+impl TransferStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferStatus::Pending => "PENDING",
+            TransferStatus::Accepting => "ACCEPTING",
+            TransferStatus::Accepted => "ACCEPTED",
+            TransferStatus::Declined => "DECLINED",
+            TransferStatus::Cancelled => "CANCELLED",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TicketTransfer {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub ticket_cod: String,
+    pub from_client_id: String,
+    pub from_email: String,
+    pub to_client_id: String,
+    pub to_email: String,
+    pub status: TransferStatus,
+    pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<String>,
+}
+
+/// Entry in a ticket's `transfer_history`, appended every time the ticket
+/// changes hands.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TicketTransferRecord {
+    pub transfer_id: String,
+    pub from_email: String,
+    pub to_email: String,
+    pub transferred_at: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StartTransfer {
+    #[validate(email(message = "Invalid email format"))]
+    pub to_email: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ClientTransfers {
+    pub incoming: Vec<TicketTransfer>,
+    pub outgoing: Vec<TicketTransfer>,
+}
@@ -0,0 +1,34 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[validate(schema(function = "validate_join_waitlist"))]
+pub struct JoinWaitlist {
+    #[serde(rename = "evenimentid")]
+    pub id_event: Option<i32>,
+    #[serde(rename = "pachetid")]
+    pub id_pachet: Option<i32>,
+}
+
+fn validate_join_waitlist(payload: &JoinWaitlist) -> Result<(), ValidationError> {
+    match (payload.id_event, payload.id_pachet) {
+        (Some(_), Some(_)) => {
+            let mut err = ValidationError::new("exclusive_ids");
+            err.message = Some("A waitlist is for EITHER an event OR a packet, not both.".into());
+            Err(err)
+        }
+        (None, None) => {
+            let mut err = ValidationError::new("exclusive_ids");
+            err.message = Some("Must specify either evenimentid or pachetid.".into());
+            Err(err)
+        }
+        (Some(id), None) | (None, Some(id)) if id < 0 => {
+            let mut err = ValidationError::new("negative_id");
+            err.message = Some("ID cannot be negative".into());
+            Err(err)
+        }
+        _ => Ok(()),
+    }
+}
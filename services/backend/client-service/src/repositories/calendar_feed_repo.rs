@@ -0,0 +1,70 @@
+use bson::doc;
+use mongodb::{Collection, Database};
+use uuid::Uuid;
+
+use crate::models::calendar::CalendarFeed;
+use crate::repositories::ticket_transfer_repo::now_rfc3339;
+use crate::utils::error::ClientRepoError;
+
+pub struct CalendarFeedRepo {
+    collection: Collection<CalendarFeed>,
+}
+
+impl CalendarFeedRepo {
+    pub fn new(db: Database) -> Self {
+        Self {
+            collection: db.collection("calendar_feeds"),
+        }
+    }
+
+    /// Returns the feed of a client, issuing a token on first use. Concurrent
+    /// callers all get the token that was stored first.
+    pub async fn get_or_create(&self, client_id: &str) -> Result<CalendarFeed, ClientRepoError> {
+        self.collection
+            .update_one(
+                doc! { "_id": client_id },
+                doc! { "$setOnInsert": {
+                    "token": new_token(),
+                    "created_at": now_rfc3339(),
+                } },
+            )
+            .upsert(true)
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        self.collection
+            .find_one(doc! { "_id": client_id })
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| {
+                ClientRepoError::NotFound(format!(
+                    "Calendar feed of client {} not found",
+                    client_id
+                ))
+            })
+    }
+
+    pub async fn find_by_token(&self, token: &str) -> Result<CalendarFeed, ClientRepoError> {
+        self.collection
+            .find_one(doc! { "token": token })
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| ClientRepoError::NotFound("Calendar feed not found".to_string()))
+    }
+
+    /// Revokes the token; the next `get_or_create` issues a new one.
+    pub async fn delete(&self, client_id: &str) -> Result<bool, ClientRepoError> {
+        let result = self
+            .collection
+            .delete_one(doc! { "_id": client_id })
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        Ok(result.deleted_count == 1)
+    }
+}
+
+/// 244 random bits, hex encoded.
+fn new_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
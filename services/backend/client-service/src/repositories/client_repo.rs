@@ -1,11 +1,15 @@
 use bson::oid::ObjectId;
 use bson::{Document, doc};
+use common::pagination::{Cursor, Page};
 use futures::stream::TryStreamExt;
+use mongodb::options::FindOptions;
 use mongodb::{Collection, Database};
 
 use crate::models::client::{Client, ClientQuery, CreateClient, TicketRef, UpdateClient};
 use crate::utils::error::ClientRepoError;
 
+const DEFAULT_ITEMS_PER_PAGE: i64 = 10;
+
 pub struct ClientRepo {
     collection: Collection<Client>,
 }
@@ -25,7 +29,8 @@ impl ClientRepo {
         Ok(())
     }
 
-    pub async fn list_clients(&self, query: ClientQuery) -> Result<Vec<Client>, ClientRepoError> {
+    /// One page of clients in `_id` (creation) order, keyed on the hex id.
+    pub async fn list_clients(&self, query: ClientQuery) -> Result<Page<Client>, ClientRepoError> {
         let mut filter = Document::new();
 
         if let Some(email) = query.email {
@@ -40,9 +45,47 @@ impl ClientRepo {
             filter.insert("nume", doc! { "$regex": nume, "$options": "i" });
         }
 
+        let total = if query.include_total == Some(true) {
+            let total = self
+                .collection
+                .count_documents(filter.clone())
+                .await
+                .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+            Some(total as i64)
+        } else {
+            None
+        };
+
+        let position = match query.cursor.as_deref() {
+            Some(cursor) => {
+                let cursor = Cursor::<String>::decode(cursor)
+                    .and_then(|cursor| {
+                        Some((ObjectId::parse_str(&cursor.key).ok()?, cursor.before))
+                    })
+                    .ok_or_else(|| {
+                        ClientRepoError::InvalidCursor(format!("Invalid cursor: {}", cursor))
+                    })?;
+                Some(cursor)
+            }
+            None => None,
+        };
+        let backward = position.is_some_and(|(_, before)| before);
+
+        if let Some((id, before)) = position {
+            let operator = if before { "$lt" } else { "$gt" };
+            filter.insert("_id", doc! { operator: id });
+        }
+
+        let items_per_page = query.items_per_page.unwrap_or(DEFAULT_ITEMS_PER_PAGE);
         let cursor = self
             .collection
             .find(filter)
+            .with_options(
+                FindOptions::builder()
+                    .sort(doc! { "_id": if backward { -1 } else { 1 } })
+                    .limit(items_per_page + 1)
+                    .build(),
+            )
             .await
             .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
 
@@ -51,7 +94,14 @@ impl ClientRepo {
             .await
             .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
 
-        Ok(clients)
+        Ok(Page::from_rows(
+            clients,
+            items_per_page as usize,
+            backward,
+            position.is_some(),
+            |client| client.id.to_hex(),
+        )
+        .with_total(total))
     }
 
     pub async fn get_client(&self, id: &str) -> Result<Client, ClientRepoError> {
@@ -181,11 +231,35 @@ impl ClientRepo {
         Ok(result.deleted_count > 0)
     }
 
+    pub async fn find_client_by_email(
+        &self,
+        email: &str,
+    ) -> Result<Option<Client>, ClientRepoError> {
+        self.collection
+            .find_one(doc! { "email": email })
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))
+    }
+
     pub async fn get_client_tickets(&self, id: &str) -> Result<Vec<TicketRef>, ClientRepoError> {
         let client = self.get_client(id).await?;
         Ok(client.lista_bilete)
     }
 
+    /// Every client that holds at least one ticket reference.
+    pub async fn list_ticket_holders(&self) -> Result<Vec<Client>, ClientRepoError> {
+        let cursor = self
+            .collection
+            .find(doc! { "lista_bilete.0": { "$exists": true } })
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        cursor
+            .try_collect()
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))
+    }
+
     pub async fn add_ticket_ref_to_client(
         &self,
         id: &str,
@@ -216,6 +290,37 @@ impl ClientRepo {
         self.get_client(id).await
     }
 
+    pub async fn add_ticket_refs_to_client(
+        &self,
+        id: &str,
+        ticket_refs: Vec<TicketRef>,
+    ) -> Result<Client, ClientRepoError> {
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| ClientRepoError::InvalidObjectId(format!("Invalid ID: {}", id)))?;
+
+        let client = self.get_client(id).await?;
+
+        let new_refs: Vec<_> = ticket_refs
+            .iter()
+            .filter(|ticket_ref| !client.lista_bilete.iter().any(|t| t.cod == ticket_ref.cod))
+            .map(|ticket_ref| bson::to_bson(ticket_ref).unwrap())
+            .collect();
+
+        if new_refs.is_empty() {
+            return Ok(client);
+        }
+
+        self.collection
+            .update_one(
+                doc! { "_id": object_id },
+                doc! { "$push": { "lista_bilete": { "$each": new_refs } } },
+            )
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        self.get_client(id).await
+    }
+
     pub async fn remove_ticket_from_client(
         &self,
         id: &str,
@@ -237,6 +342,49 @@ impl ClientRepo {
         self.get_client(id).await
     }
 
+    pub async fn remove_ticket_refs_from_client(
+        &self,
+        id: &str,
+        ticket_codes: &[String],
+    ) -> Result<(), ClientRepoError> {
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| ClientRepoError::InvalidObjectId(format!("Invalid ID: {}", id)))?;
+
+        self.collection
+            .update_one(
+                doc! { "_id": object_id },
+                doc! { "$pull": { "lista_bilete": { "cod": { "$in": ticket_codes } } } },
+            )
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Hands `ticket_ref` to a client unless it already holds that ticket, so
+    /// a transfer that is accepted again does not duplicate it.
+    pub async fn give_ticket_ref(
+        &self,
+        id: &str,
+        ticket_ref: &TicketRef,
+    ) -> Result<(), ClientRepoError> {
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| ClientRepoError::InvalidObjectId(format!("Invalid ID: {}", id)))?;
+
+        let bson_ref =
+            bson::to_bson(ticket_ref).map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        self.collection
+            .update_one(
+                doc! { "_id": object_id, "lista_bilete.cod": { "$ne": &ticket_ref.cod } },
+                doc! { "$push": { "lista_bilete": bson_ref } },
+            )
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
     pub async fn find_client_by_ticket_code(
         &self,
         ticket_code: &str,
@@ -250,6 +398,23 @@ impl ClientRepo {
         Ok(client)
     }
 
+    /// Clients holding any of `codes`, each loaded once.
+    pub async fn find_clients_by_ticket_codes(
+        &self,
+        codes: &[String],
+    ) -> Result<Vec<Client>, ClientRepoError> {
+        let cursor = self
+            .collection
+            .find(doc! { "lista_bilete.cod": { "$in": codes } })
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        cursor
+            .try_collect()
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))
+    }
+
     pub async fn update_ticket_refund_status(
         &self,
         client_id: &str,
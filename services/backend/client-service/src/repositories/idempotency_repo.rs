@@ -0,0 +1,129 @@
+use bson::doc;
+use common::idempotency::IDEMPOTENCY_CLAIM_TIMEOUT_SECS;
+use mongodb::error::{ErrorKind, WriteFailure};
+use mongodb::{Collection, Database};
+
+use crate::models::idempotency::{IdempotencyClaim, IdempotencyRecord};
+use crate::utils::error::ClientRepoError;
+
+const DUPLICATE_KEY_CODE: i32 = 11000;
+
+pub struct IdempotencyRepo {
+    collection: Collection<IdempotencyRecord>,
+}
+
+impl IdempotencyRepo {
+    pub fn new(db: Database) -> Self {
+        Self {
+            collection: db.collection("idempotency_keys"),
+        }
+    }
+
+    /// Reserves `key` within `scope` for the request with `fingerprint`. If the
+    /// key was used before, returns the stored response, reports that the
+    /// first request has not finished yet or that it was a different request.
+    /// A claim left unfinished for too long is taken over.
+    pub async fn claim(
+        &self,
+        scope: &str,
+        key: &str,
+        fingerprint: &str,
+    ) -> Result<IdempotencyClaim, ClientRepoError> {
+        let record = IdempotencyRecord {
+            scope: scope.to_string(),
+            key: key.to_string(),
+            fingerprint: Some(fingerprint.to_string()),
+            status: None,
+            body: None,
+            created_at: bson::DateTime::now(),
+        };
+
+        match self.collection.insert_one(&record).await {
+            Ok(_) => return Ok(IdempotencyClaim::Claimed),
+            Err(e) if is_duplicate_key(&e) => {}
+            Err(e) => return Err(ClientRepoError::DatabaseError(e.to_string())),
+        }
+
+        let stale_before = bson::DateTime::from_millis(
+            bson::DateTime::now().timestamp_millis() - IDEMPOTENCY_CLAIM_TIMEOUT_SECS * 1000,
+        );
+        let reclaimed = self
+            .collection
+            .update_one(
+                doc! {
+                    "scope": scope,
+                    "key": key,
+                    "status": { "$exists": false },
+                    "created_at": { "$lt": stale_before },
+                },
+                doc! { "$set": { "fingerprint": fingerprint, "created_at": bson::DateTime::now() } },
+            )
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        if reclaimed.modified_count == 1 {
+            return Ok(IdempotencyClaim::Claimed);
+        }
+
+        let existing = self
+            .collection
+            .find_one(doc! { "scope": scope, "key": key })
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        match existing {
+            Some(IdempotencyRecord {
+                fingerprint: Some(stored),
+                ..
+            }) if stored != fingerprint => Ok(IdempotencyClaim::Mismatch),
+            Some(IdempotencyRecord {
+                status: Some(status),
+                body: Some(body),
+                ..
+            }) => Ok(IdempotencyClaim::Completed {
+                status: status as u16,
+                body,
+            }),
+            _ => Ok(IdempotencyClaim::InProgress),
+        }
+    }
+
+    pub async fn complete(
+        &self,
+        scope: &str,
+        key: &str,
+        status: u16,
+        body: &serde_json::Value,
+    ) -> Result<(), ClientRepoError> {
+        let body =
+            bson::to_bson(body).map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        self.collection
+            .update_one(
+                doc! { "scope": scope, "key": key },
+                doc! { "$set": { "status": status as i32, "body": body } },
+            )
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Frees a key whose request failed so that a retry can run it again.
+    pub async fn release(&self, scope: &str, key: &str) -> Result<(), ClientRepoError> {
+        self.collection
+            .delete_one(doc! { "scope": scope, "key": key, "status": { "$exists": false } })
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+pub(crate) fn is_duplicate_key(error: &mongodb::error::Error) -> bool {
+    matches!(
+        error.kind.as_ref(),
+        ErrorKind::Write(WriteFailure::WriteError(write_error))
+            if write_error.code == DUPLICATE_KEY_CODE
+    )
+}
@@ -1 +1,5 @@
+pub mod calendar_feed_repo;
 pub mod client_repo;
+pub mod idempotency_repo;
+pub mod purchase_saga_repo;
+pub mod ticket_transfer_repo;
@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use bson::doc;
+use bson::oid::ObjectId;
+use futures::stream::TryStreamExt;
+use mongodb::{Collection, Database};
+
+use crate::models::client::TicketRef;
+use crate::models::purchase_saga::{PurchaseSaga, SagaState};
+use crate::utils::error::ClientRepoError;
+
+pub struct PurchaseSagaRepo {
+    collection: Collection<PurchaseSaga>,
+}
+
+impl PurchaseSagaRepo {
+    pub fn new(db: Database) -> Self {
+        Self {
+            collection: db.collection("purchase_sagas"),
+        }
+    }
+
+    pub async fn create(&self, saga: &PurchaseSaga) -> Result<(), ClientRepoError> {
+        self.collection
+            .insert_one(saga)
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Moves a saga from `from` to `to`. Returns `false` when the saga was no
+    /// longer in `from`, i.e. someone else already moved it on.
+    pub async fn advance(
+        &self,
+        id: ObjectId,
+        from: SagaState,
+        to: SagaState,
+    ) -> Result<bool, ClientRepoError> {
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": id, "state": from.as_str() },
+                doc! { "$set": { "state": to.as_str(), "updated_at": bson::DateTime::now() } },
+            )
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        Ok(result.modified_count == 1)
+    }
+
+    pub async fn mark_reserved(
+        &self,
+        id: ObjectId,
+        ticket_refs: &[TicketRef],
+    ) -> Result<bool, ClientRepoError> {
+        let refs = bson::to_bson(ticket_refs)
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": id, "state": SagaState::Started.as_str() },
+                doc! { "$set": {
+                    "state": SagaState::Reserved.as_str(),
+                    "ticket_refs": refs,
+                    "updated_at": bson::DateTime::now(),
+                } },
+            )
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        Ok(result.modified_count == 1)
+    }
+
+    pub async fn record_failure(&self, id: ObjectId, error: &str) -> Result<(), ClientRepoError> {
+        self.collection
+            .update_one(
+                doc! { "_id": id },
+                doc! {
+                    "$set": { "last_error": error, "updated_at": bson::DateTime::now() },
+                    "$inc": { "attempts": 1 },
+                },
+            )
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Lists unfinished sagas that have not moved for `idle`, which means the
+    /// request driving them died or gave up.
+    pub async fn list_stale(&self, idle: Duration) -> Result<Vec<PurchaseSaga>, ClientRepoError> {
+        let cutoff = bson::DateTime::from_millis(
+            bson::DateTime::now().timestamp_millis() - idle.as_millis() as i64,
+        );
+
+        let cursor = self
+            .collection
+            .find(doc! {
+                "state": { "$in": [
+                    SagaState::Started.as_str(),
+                    SagaState::Reserved.as_str(),
+                    SagaState::Compensating.as_str(),
+                ] },
+                "updated_at": { "$lt": cutoff },
+            })
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        cursor
+            .try_collect()
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))
+    }
+
+    /// Takes over a stale saga by bumping its `updated_at`, so that only one
+    /// recovery run works on it at a time.
+    pub async fn claim(&self, saga: &PurchaseSaga) -> Result<bool, ClientRepoError> {
+        let result = self
+            .collection
+            .update_one(
+                doc! {
+                    "_id": saga.id,
+                    "state": saga.state.as_str(),
+                    "updated_at": saga.updated_at,
+                },
+                doc! { "$set": { "updated_at": bson::DateTime::now() } },
+            )
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        Ok(result.modified_count == 1)
+    }
+
+    /// Ticket codes of sagas that have not finished yet. Their tickets may
+    /// legitimately exist on one side only.
+    pub async fn list_in_flight_codes(&self) -> Result<Vec<String>, ClientRepoError> {
+        let cursor = self
+            .collection
+            .find(doc! {
+                "state": { "$in": [
+                    SagaState::Started.as_str(),
+                    SagaState::Reserved.as_str(),
+                    SagaState::Compensating.as_str(),
+                ] },
+            })
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        let sagas: Vec<PurchaseSaga> = cursor
+            .try_collect()
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        Ok(sagas.into_iter().flat_map(|saga| saga.ticket_codes).collect())
+    }
+}
@@ -0,0 +1,94 @@
+use bson::doc;
+use futures::stream::TryStreamExt;
+use mongodb::options::FindOptions;
+use mongodb::{Collection, Database};
+
+use crate::models::ticket_transfer::{TicketTransfer, TransferStatus};
+use crate::repositories::idempotency_repo::is_duplicate_key;
+use crate::utils::error::ClientRepoError;
+
+pub struct TicketTransferRepo {
+    collection: Collection<TicketTransfer>,
+}
+
+impl TicketTransferRepo {
+    pub fn new(db: Database) -> Self {
+        Self {
+            collection: db.collection("ticket_transfers"),
+        }
+    }
+
+    /// Stores a new transfer. Returns `false` when the ticket already has a
+    /// transfer in flight.
+    pub async fn create(&self, transfer: &TicketTransfer) -> Result<bool, ClientRepoError> {
+        match self.collection.insert_one(transfer).await {
+            Ok(_) => Ok(true),
+            Err(e) if is_duplicate_key(&e) => Ok(false),
+            Err(e) => Err(ClientRepoError::DatabaseError(e.to_string())),
+        }
+    }
+
+    pub async fn get(&self, id: &str) -> Result<TicketTransfer, ClientRepoError> {
+        self.collection
+            .find_one(doc! { "_id": id })
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| ClientRepoError::NotFound(format!("Transfer {} not found", id)))
+    }
+
+    /// Moves a transfer from `from` to `to`, stamping `resolved_at` when `to`
+    /// is final. Returns `false` when the transfer was no longer in `from`.
+    pub async fn advance(
+        &self,
+        id: &str,
+        from: TransferStatus,
+        to: TransferStatus,
+    ) -> Result<bool, ClientRepoError> {
+        let mut set = doc! { "status": to.as_str() };
+        if to != TransferStatus::Accepting {
+            set.insert("resolved_at", now_rfc3339());
+        }
+
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": id, "status": from.as_str() },
+                doc! { "$set": set },
+            )
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        Ok(result.modified_count == 1)
+    }
+
+    /// Transfers sent or received by a client, newest first.
+    pub async fn list_for_client(
+        &self,
+        client_id: &str,
+    ) -> Result<Vec<TicketTransfer>, ClientRepoError> {
+        let cursor = self
+            .collection
+            .find(doc! { "$or": [
+                { "from_client_id": client_id },
+                { "to_client_id": client_id },
+            ] })
+            .with_options(
+                FindOptions::builder()
+                    .sort(doc! { "created_at": -1 })
+                    .build(),
+            )
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))?;
+
+        cursor
+            .try_collect()
+            .await
+            .map_err(|e| ClientRepoError::DatabaseError(e.to_string()))
+    }
+}
+
+pub(crate) fn now_rfc3339() -> String {
+    bson::DateTime::now()
+        .try_to_rfc3339_string()
+        .unwrap_or_default()
+}
@@ -0,0 +1,245 @@
+use crate::handlers::auth::auth::GetUserIdByEmailRequest;
+use crate::handlers::auth::auth::auth_service_client::AuthServiceClient;
+use crate::repositories::client_repo::ClientRepo;
+use crate::services::event_manager::{EventManagerClient, ExternalServiceError, delete_ticket};
+use common::rabbitmq::RabbitMQ;
+use common::rabbitmq::messages::{
+    EventCancelled, QUEUE_EVENT_CANCELLED_CLIENT, ROUTING_KEY_EVENT_CANCELLED,
+    ROUTING_KEY_REFUND_REQUESTED, RefundRequested,
+};
+use futures::StreamExt;
+use lapin::options::{BasicAckOptions, BasicConsumeOptions, BasicNackOptions};
+use lapin::types::FieldTable;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+pub struct EventCancelledConsumer {
+    rabbitmq: Arc<RabbitMQ>,
+    client_repo: Arc<ClientRepo>,
+    event_manager_client: Arc<EventManagerClient>,
+    service_token: String,
+    auth_service_url: String,
+}
+
+impl EventCancelledConsumer {
+    pub fn new(
+        rabbitmq: Arc<RabbitMQ>,
+        client_repo: Arc<ClientRepo>,
+        event_manager_client: Arc<EventManagerClient>,
+        service_token: String,
+        auth_service_url: String,
+    ) -> Self {
+        Self {
+            rabbitmq,
+            client_repo,
+            event_manager_client,
+            service_token,
+            auth_service_url,
+        }
+    }
+
+    pub async fn start(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.rabbitmq
+            .declare_queue(QUEUE_EVENT_CANCELLED_CLIENT, ROUTING_KEY_EVENT_CANCELLED)
+            .await
+            .map_err(|e| format!("Failed to declare queue: {:?}", e))?;
+
+        let channel = self
+            .rabbitmq
+            .get_channel()
+            .await
+            .ok_or("Channel not available")?;
+
+        let consumer = channel
+            .basic_consume(
+                QUEUE_EVENT_CANCELLED_CLIENT,
+                "client-service-event-cancelled-consumer",
+                BasicConsumeOptions::default(),
+                FieldTable::default(),
+            )
+            .await
+            .map_err(|e| format!("Failed to create consumer: {:?}", e))?;
+
+        info!("Started consuming event cancellation messages");
+
+        let mut consumer = consumer;
+        while let Some(delivery) = consumer.next().await {
+            match delivery {
+                Ok(delivery) => {
+                    let should_ack = match serde_json::from_slice::<EventCancelled>(&delivery.data)
+                    {
+                        Ok(message) => {
+                            info!(
+                                "Received cancellation of '{}' with {} ticket(s)",
+                                message.name,
+                                message.ticket_codes.len()
+                            );
+
+                            match self.process_cancellation(&message).await {
+                                Ok(_) => true,
+                                Err(e) => {
+                                    error!(
+                                        "Failed to process cancellation: {:?}. Message will be requeued.",
+                                        e
+                                    );
+                                    false
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            warn!(
+                                "Failed to deserialize event cancellation message: {:?}. Discarding.",
+                                e
+                            );
+                            true
+                        }
+                    };
+
+                    let result = if should_ack {
+                        delivery.ack(BasicAckOptions::default()).await
+                    } else {
+                        delivery
+                            .nack(BasicNackOptions {
+                                requeue: true,
+                                ..Default::default()
+                            })
+                            .await
+                    };
+                    if let Err(e) = result {
+                        error!("Failed to acknowledge message: {:?}", e);
+                    }
+                }
+                Err(e) => {
+                    error!("Consumer error: {:?}", e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn process_cancellation(
+        &self,
+        message: &EventCancelled,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut requested = 0;
+        for ticket_cod in &message.ticket_codes {
+            let Some(client) = self
+                .client_repo
+                .find_client_by_ticket_code(ticket_cod)
+                .await
+                .map_err(|e| format!("Failed to find client: {:?}", e))?
+            else {
+                self.delete_unheld_ticket(ticket_cod).await?;
+                continue;
+            };
+
+            let refund_status = client
+                .lista_bilete
+                .iter()
+                .find(|t| &t.cod == ticket_cod)
+                .and_then(|t| t.refund_status.as_deref());
+            if matches!(refund_status, Some("PENDING" | "APPROVED")) {
+                info!(
+                    "Ticket {} already has a {} refund, not requesting another",
+                    ticket_cod,
+                    refund_status.unwrap_or_default()
+                );
+                continue;
+            }
+
+            let Some(requester_id) = self.get_user_id(&client.email).await? else {
+                warn!(
+                    "No account in auth service for {}; ticket {} is left for the owner to refund",
+                    client.email, ticket_cod
+                );
+                continue;
+            };
+
+            let refund = RefundRequested {
+                request_id: 0,
+                ticket_cod: ticket_cod.clone(),
+                requester_id,
+                requester_email: client.email.clone(),
+                event_id: message.event_id,
+                packet_id: message.packet_id,
+                event_owner_id: message.owner_id,
+                reason: format!(
+                    "'{}' was cancelled, your ticket has been refunded automatically",
+                    message.name
+                ),
+                auto_approve: true,
+            };
+
+            let json = serde_json::to_vec(&refund)?;
+            self.rabbitmq
+                .publish(ROUTING_KEY_REFUND_REQUESTED, &json)
+                .await
+                .map_err(|e| format!("Failed to publish refund request: {:?}", e))?;
+            requested += 1;
+
+            if let Err(e) = self
+                .client_repo
+                .update_ticket_refund_status(&client.id.to_hex(), ticket_cod, Some("PENDING"))
+                .await
+            {
+                error!("Failed to update ticket refund status: {:?}", e);
+            }
+        }
+
+        info!(
+            "Requested automatic refunds for {} of {} ticket(s) of '{}'",
+            requested,
+            message.ticket_codes.len(),
+            message.name
+        );
+
+        Ok(())
+    }
+
+    /// Nobody paid for a ticket no client holds, so there is nothing to
+    /// refund; it is deleted in event-service to free its seat instead.
+    async fn delete_unheld_ticket(
+        &self,
+        ticket_cod: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        match delete_ticket(&self.event_manager_client, ticket_cod, &self.service_token).await {
+            Ok(()) => {
+                info!("Deleted ticket {} that no client holds", ticket_cod);
+                Ok(())
+            }
+            Err(ExternalServiceError::NotFound(_)) => Ok(()),
+            Err(ExternalServiceError::CheckedIn(_)) => {
+                warn!(
+                    "Ticket {} was checked in and is kept although no client holds it",
+                    ticket_cod
+                );
+                Ok(())
+            }
+            Err(e) => Err(format!("Failed to delete ticket {}: {:?}", ticket_cod, e).into()),
+        }
+    }
+
+    /// Looks up the account behind a client's email. `None` means there is
+    /// none, which retrying will not change.
+    async fn get_user_id(
+        &self,
+        email: &str,
+    ) -> Result<Option<i32>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut auth_client = AuthServiceClient::connect(self.auth_service_url.clone())
+            .await
+            .map_err(|e| format!("Failed to connect to auth service: {}", e))?;
+
+        let request = GetUserIdByEmailRequest {
+            email: email.to_string(),
+        };
+
+        let resp = auth_client
+            .get_user_id_by_email(request)
+            .await
+            .map_err(|e| format!("Failed to get user id from auth service: {}", e))?
+            .into_inner();
+
+        Ok(resp.success.then_some(resp.user_id))
+    }
+}
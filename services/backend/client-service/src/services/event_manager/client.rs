@@ -49,14 +49,16 @@ impl EventManagerClient {
             })
     }
 
-    pub async fn post_with_auth(
+    pub async fn post_with_auth<T: serde::Serialize>(
         &self,
         path: &str,
+        body: &T,
         token: &str,
     ) -> Result<Response, ExternalServiceError> {
         self.http_client
             .post(self.url(path))
             .header("Authorization", format!("Bearer {}", token))
+            .json(body)
             .send()
             .await
             .map_err(|e| {
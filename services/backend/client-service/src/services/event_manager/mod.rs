@@ -3,9 +3,11 @@ pub mod event_service_client;
 pub mod packet_service;
 pub mod ticket_service;
 pub mod types;
+pub mod waitlist_service;
 
 pub use client::EventManagerClient;
 pub use event_service_client::*;
 pub use packet_service::*;
 pub use ticket_service::*;
 pub use types::*;
+pub use waitlist_service::*;
@@ -2,10 +2,14 @@ use super::client::EventManagerClient;
 use super::event_service_client;
 use super::packet_service;
 use super::types::{
-    BindTicketRequest, ExternalServiceError, HateoasRequest, TicketDetails, TicketInfo,
+    BatchTicketRequest, BindTicketRequest, Buyer, ExternalServiceError, HateoasRequest,
+    TicketCalendarRequest, TicketDetails, TicketInfo, TicketRefundStatusInfo,
 };
+use common::pagination::NEXT_CURSOR_HEADER;
+use reqwest::StatusCode;
 use tracing::info;
-use uuid::Uuid;
+
+const TICKET_PAGE_SIZE: i64 = 100;
 
 pub async fn validate_ticket(
     client: &EventManagerClient,
@@ -17,6 +21,92 @@ pub async fn validate_ticket(
     Ok(())
 }
 
+/// Every ticket, read page by page through the `X-Next-Cursor` header.
+pub async fn list_tickets(
+    client: &EventManagerClient,
+    service_token: &str,
+) -> Result<Vec<TicketInfo>, ExternalServiceError> {
+    let mut tickets = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let mut path = format!(
+            "/api/event-manager/tickets?items_per_page={}",
+            TICKET_PAGE_SIZE
+        );
+        if let Some(cursor) = &cursor {
+            path.push_str(&format!("&cursor={}", cursor));
+        }
+
+        let response = client.get_with_auth(&path, service_token).await?;
+        client.check_status(&response, "Ticket", "*")?;
+
+        cursor = response
+            .headers()
+            .get(NEXT_CURSOR_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        let response_data: Vec<HateoasRequest<TicketInfo>> =
+            response.json().await.map_err(|e| {
+                ExternalServiceError::DeserializationError(format!(
+                    "Failed to parse ticket list response: {}",
+                    e
+                ))
+            })?;
+
+        tickets.extend(response_data.into_iter().map(|ticket| ticket.data));
+
+        if cursor.is_none() {
+            return Ok(tickets);
+        }
+    }
+}
+
+/// iCalendar text with every event the given tickets admit to.
+pub async fn get_tickets_calendar(
+    client: &EventManagerClient,
+    codes: Vec<String>,
+    service_token: &str,
+) -> Result<String, ExternalServiceError> {
+    let path = "/api/event-manager/tickets/calendar";
+    let payload = TicketCalendarRequest { codes };
+
+    let response = client.post_with_auth(path, &payload, service_token).await?;
+    client.check_status(&response, "Calendar", "*")?;
+
+    response.text().await.map_err(|e| {
+        ExternalServiceError::DeserializationError(format!(
+            "Failed to read ticket calendar response: {}",
+            e
+        ))
+    })
+}
+
+pub async fn list_ticket_refund_statuses(
+    client: &EventManagerClient,
+    service_token: &str,
+) -> Result<Vec<TicketRefundStatusInfo>, ExternalServiceError> {
+    #[derive(serde::Deserialize)]
+    struct RefundStatusList {
+        data: Vec<TicketRefundStatusInfo>,
+    }
+
+    let path = "/api/event-manager/refunds/statuses";
+
+    let response = client.get_with_auth(path, service_token).await?;
+    client.check_status(&response, "Refund", "*")?;
+
+    let response_data: RefundStatusList = response.json().await.map_err(|e| {
+        ExternalServiceError::DeserializationError(format!(
+            "Failed to parse refund status response: {}",
+            e
+        ))
+    })?;
+
+    Ok(response_data.data)
+}
+
 pub async fn get_ticket_details(
     client: &EventManagerClient,
     ticket_cod: &str,
@@ -61,11 +151,13 @@ pub async fn get_ticket_details(
 
 pub async fn create_ticket_for_event(
     client: &EventManagerClient,
+    ticket_code: &str,
     event_id: i32,
+    tier_id: Option<i32>,
+    hold_id: Option<String>,
+    buyer: Option<Buyer>,
     service_token: &str,
 ) -> Result<TicketDetails, ExternalServiceError> {
-    let ticket_code = Uuid::now_v7().to_string();
-
     let path = format!("/api/event-manager/tickets/{}", ticket_code);
 
     info!(
@@ -76,10 +168,14 @@ pub async fn create_ticket_for_event(
     let payload = BindTicketRequest {
         id_event: Some(event_id),
         id_pachet: None,
+        tier_id,
+        hold_id,
+        promo_code: buyer.as_ref().and_then(|b| b.promo_code.clone()),
+        buyer_id: buyer.map(|b| b.id),
     };
 
     let response = client.put_with_auth(&path, &payload, service_token).await?;
-    client.check_status(&response, "Ticket", &ticket_code)?;
+    client.check_status(&response, "Ticket", ticket_code)?;
 
     let response_data: HateoasRequest<TicketInfo> = response.json().await.map_err(|e| {
         ExternalServiceError::DeserializationError(format!(
@@ -99,11 +195,13 @@ pub async fn create_ticket_for_event(
 
 pub async fn create_ticket_for_packet(
     client: &EventManagerClient,
+    ticket_code: &str,
     packet_id: i32,
+    tier_id: Option<i32>,
+    hold_id: Option<String>,
+    buyer: Option<Buyer>,
     service_token: &str,
 ) -> Result<TicketDetails, ExternalServiceError> {
-    let ticket_code = Uuid::now_v7().to_string();
-
     let path = format!("/api/event-manager/tickets/{}", ticket_code);
 
     info!(
@@ -114,10 +212,14 @@ pub async fn create_ticket_for_packet(
     let payload = BindTicketRequest {
         id_event: None,
         id_pachet: Some(packet_id),
+        tier_id,
+        hold_id,
+        promo_code: buyer.as_ref().and_then(|b| b.promo_code.clone()),
+        buyer_id: buyer.map(|b| b.id),
     };
 
     let response = client.put_with_auth(&path, &payload, service_token).await?;
-    client.check_status(&response, "Ticket", &ticket_code)?;
+    client.check_status(&response, "Ticket", ticket_code)?;
 
     let response_data: HateoasRequest<TicketInfo> = response.json().await.map_err(|e| {
         ExternalServiceError::DeserializationError(format!(
@@ -135,6 +237,102 @@ pub async fn create_ticket_for_packet(
     })
 }
 
+pub async fn create_tickets_for_event(
+    client: &EventManagerClient,
+    codes: Vec<String>,
+    event_id: i32,
+    tier_id: Option<i32>,
+    hold_id: Option<String>,
+    buyer: Option<Buyer>,
+    service_token: &str,
+) -> Result<Vec<TicketDetails>, ExternalServiceError> {
+    let path = format!("/api/event-manager/events/{}/tickets/batch", event_id);
+
+    info!(
+        "Creating {} tickets for event {} (using service token)",
+        codes.len(),
+        event_id
+    );
+
+    let payload = BatchTicketRequest {
+        quantity: codes.len() as i32,
+        tier_id,
+        hold_id,
+        promo_code: buyer.as_ref().and_then(|b| b.promo_code.clone()),
+        buyer_id: buyer.map(|b| b.id),
+        codes,
+    };
+
+    let response = client.post_with_auth(&path, &payload, service_token).await?;
+    client.check_status(&response, "Event", &event_id.to_string())?;
+
+    let response_data: Vec<HateoasRequest<TicketInfo>> = response.json().await.map_err(|e| {
+        ExternalServiceError::DeserializationError(format!(
+            "Failed to parse ticket response: {}",
+            e
+        ))
+    })?;
+
+    let event_info = event_service_client::get_event(client, event_id).await.ok();
+
+    Ok(response_data
+        .into_iter()
+        .map(|ticket| TicketDetails {
+            ticket: ticket.data,
+            event: event_info.clone(),
+            packet: None,
+        })
+        .collect())
+}
+
+pub async fn create_tickets_for_packet(
+    client: &EventManagerClient,
+    codes: Vec<String>,
+    packet_id: i32,
+    tier_id: Option<i32>,
+    hold_id: Option<String>,
+    buyer: Option<Buyer>,
+    service_token: &str,
+) -> Result<Vec<TicketDetails>, ExternalServiceError> {
+    let path = format!("/api/event-manager/event-packets/{}/tickets/batch", packet_id);
+
+    info!(
+        "Creating {} tickets for packet {} (using service token)",
+        codes.len(),
+        packet_id
+    );
+
+    let payload = BatchTicketRequest {
+        quantity: codes.len() as i32,
+        tier_id,
+        hold_id,
+        promo_code: buyer.as_ref().and_then(|b| b.promo_code.clone()),
+        buyer_id: buyer.map(|b| b.id),
+        codes,
+    };
+
+    let response = client.post_with_auth(&path, &payload, service_token).await?;
+    client.check_status(&response, "Packet", &packet_id.to_string())?;
+
+    let response_data: Vec<HateoasRequest<TicketInfo>> = response.json().await.map_err(|e| {
+        ExternalServiceError::DeserializationError(format!(
+            "Failed to parse ticket response: {}",
+            e
+        ))
+    })?;
+
+    let packet_info = packet_service::get_packet(client, packet_id).await.ok();
+
+    Ok(response_data
+        .into_iter()
+        .map(|ticket| TicketDetails {
+            ticket: ticket.data,
+            event: None,
+            packet: packet_info.clone(),
+        })
+        .collect())
+}
+
 pub async fn delete_ticket(
     client: &EventManagerClient,
     ticket_cod: &str,
@@ -148,6 +346,12 @@ pub async fn delete_ticket(
     );
 
     let response = client.delete_with_auth(&path, service_token).await?;
+    if response.status() == StatusCode::CONFLICT {
+        return Err(ExternalServiceError::CheckedIn(format!(
+            "Ticket {} has been checked in and is kept",
+            ticket_cod
+        )));
+    }
     client.check_status(&response, "Ticket", ticket_cod)?;
 
     Ok(())
@@ -9,6 +9,7 @@ pub enum ExternalServiceError {
     HttpError(String),
     DeserializationError(String),
     NoSeatsAvailable(String),
+    CheckedIn(String),
     InvalidReference(String),
     Forbidden(String),
     Unauthorized(String),
@@ -19,6 +20,22 @@ pub struct TicketInfo {
     pub cod: String,
     pub pachetid: Option<i32>,
     pub evenimentid: Option<i32>,
+    #[serde(default)]
+    pub tier_id: Option<i32>,
+    #[serde(default)]
+    pub price_minor: Option<i64>,
+    #[serde(default)]
+    pub currency: Option<String>,
+    #[serde(default)]
+    pub promo_code_id: Option<i32>,
+    #[serde(default)]
+    pub discount_minor: Option<i64>,
+    #[serde(default)]
+    pub checked_in_at: Option<String>,
+    #[serde(default)]
+    pub seat_id: Option<i32>,
+    #[serde(default)]
+    pub seat_label: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
@@ -27,6 +44,66 @@ pub struct BindTicketRequest {
     pub id_event: Option<i32>,
     #[serde(rename = "pachetid")]
     pub id_pachet: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tier_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hold_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub promo_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buyer_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct BatchTicketRequest {
+    pub quantity: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tier_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hold_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub promo_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buyer_id: Option<i32>,
+    pub codes: Vec<String>,
+}
+
+/// Client a purchase is made for. Seat holds must belong to them, and a
+/// promo code entered at checkout counts against their per-user cap.
+#[derive(Debug, Clone)]
+pub struct Buyer {
+    pub id: i32,
+    pub promo_code: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TicketCalendarRequest {
+    pub codes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct TicketRefundStatusInfo {
+    pub ticket_cod: String,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct JoinWaitlistRequest {
+    pub user_id: i32,
+    pub email: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct WaitlistEntryInfo {
+    pub id: i32,
+    pub event_id: Option<i32>,
+    pub packet_id: Option<i32>,
+    pub user_id: i32,
+    pub email: String,
+    pub status: String,
+    pub hold_id: Option<String>,
+    pub offer_expires_at: Option<String>,
+    pub created_at: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
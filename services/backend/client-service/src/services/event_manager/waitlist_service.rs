@@ -0,0 +1,88 @@
+use super::client::EventManagerClient;
+use super::types::{ExternalServiceError, JoinWaitlistRequest, WaitlistEntryInfo};
+use tracing::info;
+
+pub async fn join_waitlist(
+    client: &EventManagerClient,
+    event_id: Option<i32>,
+    packet_id: Option<i32>,
+    user_id: i32,
+    email: &str,
+    service_token: &str,
+) -> Result<WaitlistEntryInfo, ExternalServiceError> {
+    let (path, resource_type, resource_id) = match (event_id, packet_id) {
+        (Some(event_id), _) => (
+            format!("/api/event-manager/events/{}/waitlist", event_id),
+            "Event",
+            event_id,
+        ),
+        (None, Some(packet_id)) => (
+            format!("/api/event-manager/event-packets/{}/waitlist", packet_id),
+            "Packet",
+            packet_id,
+        ),
+        (None, None) => {
+            return Err(ExternalServiceError::InvalidReference(
+                "Must specify either evenimentid or pachetid".to_string(),
+            ));
+        }
+    };
+
+    info!(
+        "Adding user {} to the waitlist of {} {} (using service token)",
+        user_id, resource_type, resource_id
+    );
+
+    let payload = JoinWaitlistRequest {
+        user_id,
+        email: email.to_string(),
+    };
+
+    let response = client
+        .post_with_auth(&path, &payload, service_token)
+        .await?;
+    client.check_status(&response, resource_type, &resource_id.to_string())?;
+
+    response.json().await.map_err(|e| {
+        ExternalServiceError::DeserializationError(format!(
+            "Failed to parse waitlist response: {}",
+            e
+        ))
+    })
+}
+
+pub async fn get_waitlist_entry(
+    client: &EventManagerClient,
+    entry_id: i32,
+    service_token: &str,
+) -> Result<WaitlistEntryInfo, ExternalServiceError> {
+    let path = format!("/api/event-manager/waitlist/{}", entry_id);
+
+    let response = client.get_with_auth(&path, service_token).await?;
+    client.check_status(&response, "Waitlist entry", &entry_id.to_string())?;
+
+    response.json().await.map_err(|e| {
+        ExternalServiceError::DeserializationError(format!(
+            "Failed to parse waitlist response: {}",
+            e
+        ))
+    })
+}
+
+pub async fn leave_waitlist(
+    client: &EventManagerClient,
+    entry_id: i32,
+    service_token: &str,
+) -> Result<(), ExternalServiceError> {
+    let path = format!("/api/event-manager/waitlist/{}", entry_id);
+
+    info!(
+        "Removing waitlist entry {} from event-service (using service token)",
+        entry_id
+    );
+
+    let response = client.delete_with_auth(&path, service_token).await?;
+    client.check_status(&response, "Waitlist entry", &entry_id.to_string())?;
+
+    Ok(())
+}
@@ -1,6 +1,12 @@
+pub mod event_cancelled_consumer;
 pub mod event_manager;
+pub mod purchase_saga;
+pub mod reconciliation;
 pub mod refund_consumer;
 
+pub use event_cancelled_consumer::EventCancelledConsumer;
+pub use purchase_saga::{PurchaseSagaCoordinator, PurchaseSagaRecoveryWorker};
+pub use reconciliation::{TicketReconciler, TicketReconcilerWorker};
 pub use refund_consumer::RefundConsumer;
 
 pub mod event_service {
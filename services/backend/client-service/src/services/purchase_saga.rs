@@ -0,0 +1,412 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use bson::oid::ObjectId;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::models::client::{AddTicket, Client, TicketRef};
+use crate::models::purchase_saga::{PurchaseSaga, SagaState};
+use crate::repositories::client_repo::ClientRepo;
+use crate::repositories::purchase_saga_repo::PurchaseSagaRepo;
+use crate::services::event_service::{
+    self, Buyer, EventManagerClient, EventServiceError, TicketDetails,
+};
+use crate::utils::error::{ClientApiError, ClientRepoError, map_event_service_error};
+
+const ADD_TICKET_ATTEMPTS: u32 = 3;
+const ADD_TICKET_BACKOFF: Duration = Duration::from_millis(200);
+const RECOVERY_INTERVAL: Duration = Duration::from_secs(30);
+const STALE_AFTER: Duration = Duration::from_secs(300);
+
+/// Drives ticket purchases as persisted sagas: the ticket codes are stored
+/// before event-service is called, so a crash at any step leaves enough
+/// behind for the recovery worker to either finish or undo the purchase.
+pub struct PurchaseSagaCoordinator {
+    saga_repo: Arc<PurchaseSagaRepo>,
+    client_repo: Arc<ClientRepo>,
+    event_manager_client: Arc<EventManagerClient>,
+    service_token: String,
+}
+
+impl PurchaseSagaCoordinator {
+    pub fn new(
+        saga_repo: Arc<PurchaseSagaRepo>,
+        client_repo: Arc<ClientRepo>,
+        event_manager_client: Arc<EventManagerClient>,
+        service_token: String,
+    ) -> Self {
+        Self {
+            saga_repo,
+            client_repo,
+            event_manager_client,
+            service_token,
+        }
+    }
+
+    pub async fn purchase(
+        &self,
+        client_id: &str,
+        buyer_id: i32,
+        payload: &AddTicket,
+    ) -> Result<Client, ClientApiError> {
+        let quantity = payload.quantity.unwrap_or(1);
+        let now = bson::DateTime::now();
+        let saga = PurchaseSaga {
+            id: ObjectId::new(),
+            client_id: client_id.to_string(),
+            ticket_codes: (0..quantity).map(|_| Uuid::now_v7().to_string()).collect(),
+            id_event: payload.id_event,
+            id_pachet: payload.id_pachet,
+            tier_id: payload.tier_id,
+            hold_id: payload.hold_id.clone(),
+            promo_code: payload.promo_code.clone(),
+            buyer_id: Some(buyer_id),
+            state: SagaState::Started,
+            ticket_refs: vec![],
+            attempts: 0,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        self.saga_repo.create(&saga).await?;
+
+        let ticket_refs = match self.reserve(&saga, payload.quantity.is_some()).await {
+            Ok(ticket_refs) => ticket_refs,
+            Err(e) => {
+                self.note_failure(&saga, &format!("{:?}", e)).await;
+                if is_definitive(&e) {
+                    self.saga_repo
+                        .advance(saga.id, SagaState::Started, SagaState::Compensated)
+                        .await?;
+                } else if self
+                    .saga_repo
+                    .advance(saga.id, SagaState::Started, SagaState::Compensating)
+                    .await?
+                {
+                    self.compensate(&saga).await;
+                }
+                return Err(map_event_service_error(e));
+            }
+        };
+
+        if !self.saga_repo.mark_reserved(saga.id, &ticket_refs).await? {
+            warn!(
+                "Purchase saga {} was taken over while reserving; releasing its tickets",
+                saga.id
+            );
+            self.release_tickets(&saga.ticket_codes).await;
+            return Err(ClientApiError::Conflict(
+                "The purchase timed out and was rolled back".to_string(),
+            ));
+        }
+
+        match self.add_to_client(client_id, ticket_refs).await {
+            Ok(client) => {
+                self.saga_repo
+                    .advance(saga.id, SagaState::Reserved, SagaState::Completed)
+                    .await?;
+                info!("Purchase saga {} completed", saga.id);
+                Ok(client)
+            }
+            Err(e) => {
+                self.note_failure(&saga, &format!("{:?}", e)).await;
+                if self
+                    .saga_repo
+                    .advance(saga.id, SagaState::Reserved, SagaState::Compensating)
+                    .await?
+                {
+                    self.compensate(&saga).await;
+                }
+                Err(ClientApiError::Client(e))
+            }
+        }
+    }
+
+    /// Finishes or rolls back every saga whose request stopped making
+    /// progress, e.g. because the process crashed mid-purchase.
+    pub async fn recover_stale(&self) {
+        let stale = match self.saga_repo.list_stale(STALE_AFTER).await {
+            Ok(stale) => stale,
+            Err(e) => {
+                error!("Failed to list stale purchase sagas: {:?}", e);
+                return;
+            }
+        };
+
+        for saga in stale {
+            match self.saga_repo.claim(&saga).await {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    error!("Failed to claim purchase saga {}: {:?}", saga.id, e);
+                    continue;
+                }
+            }
+
+            info!(
+                "Recovering purchase saga {} in state {:?}",
+                saga.id, saga.state
+            );
+
+            match saga.state {
+                SagaState::Started => {
+                    if let Ok(true) = self
+                        .saga_repo
+                        .advance(saga.id, SagaState::Started, SagaState::Compensating)
+                        .await
+                    {
+                        self.compensate(&saga).await;
+                    }
+                }
+                SagaState::Reserved => {
+                    match self
+                        .add_to_client(&saga.client_id, saga.ticket_refs.clone())
+                        .await
+                    {
+                        Ok(_) => {
+                            if let Err(e) = self
+                                .saga_repo
+                                .advance(saga.id, SagaState::Reserved, SagaState::Completed)
+                                .await
+                            {
+                                error!("Failed to complete purchase saga {}: {:?}", saga.id, e);
+                            }
+                        }
+                        Err(ClientRepoError::NotFound(_))
+                        | Err(ClientRepoError::InvalidObjectId(_)) => {
+                            if let Ok(true) = self
+                                .saga_repo
+                                .advance(saga.id, SagaState::Reserved, SagaState::Compensating)
+                                .await
+                            {
+                                self.compensate(&saga).await;
+                            }
+                        }
+                        Err(e) => self.note_failure(&saga, &format!("{:?}", e)).await,
+                    }
+                }
+                SagaState::Compensating => self.compensate(&saga).await,
+                SagaState::Completed | SagaState::Compensated => {}
+            }
+        }
+    }
+
+    async fn reserve(
+        &self,
+        saga: &PurchaseSaga,
+        batch: bool,
+    ) -> Result<Vec<TicketRef>, EventServiceError> {
+        let client = &self.event_manager_client;
+        let token = &self.service_token;
+        let buyer = saga.buyer_id.map(|id| Buyer {
+            id,
+            promo_code: saga.promo_code.clone(),
+        });
+
+        let tickets = match (saga.id_event, saga.id_pachet, batch) {
+            (Some(event_id), None, false) => vec![
+                event_service::create_ticket_for_event(
+                    client,
+                    &saga.ticket_codes[0],
+                    event_id,
+                    saga.tier_id,
+                    saga.hold_id.clone(),
+                    buyer.clone(),
+                    token,
+                )
+                .await?,
+            ],
+            (None, Some(packet_id), false) => vec![
+                event_service::create_ticket_for_packet(
+                    client,
+                    &saga.ticket_codes[0],
+                    packet_id,
+                    saga.tier_id,
+                    saga.hold_id.clone(),
+                    buyer.clone(),
+                    token,
+                )
+                .await?,
+            ],
+            (Some(event_id), None, true) => {
+                event_service::create_tickets_for_event(
+                    client,
+                    saga.ticket_codes.clone(),
+                    event_id,
+                    saga.tier_id,
+                    saga.hold_id.clone(),
+                    buyer.clone(),
+                    token,
+                )
+                .await?
+            }
+            (None, Some(packet_id), true) => {
+                event_service::create_tickets_for_packet(
+                    client,
+                    saga.ticket_codes.clone(),
+                    packet_id,
+                    saga.tier_id,
+                    saga.hold_id.clone(),
+                    buyer.clone(),
+                    token,
+                )
+                .await?
+            }
+            _ => {
+                return Err(EventServiceError::InvalidReference(
+                    "Must specify either evenimentid or pachetid".to_string(),
+                ));
+            }
+        };
+
+        Ok(tickets.iter().map(build_ticket_ref_from_details).collect())
+    }
+
+    async fn add_to_client(
+        &self,
+        client_id: &str,
+        ticket_refs: Vec<TicketRef>,
+    ) -> Result<Client, ClientRepoError> {
+        let mut attempt = 1;
+        loop {
+            match self
+                .client_repo
+                .add_ticket_refs_to_client(client_id, ticket_refs.clone())
+                .await
+            {
+                Ok(client) => return Ok(client),
+                Err(e @ ClientRepoError::DatabaseError(_)) if attempt < ADD_TICKET_ATTEMPTS => {
+                    warn!(
+                        "Failed to add tickets to client {} (attempt {}): {:?}",
+                        client_id, attempt, e
+                    );
+                    tokio::time::sleep(ADD_TICKET_BACKOFF * attempt).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Takes the saga's tickets back out of the client and releases them in
+    /// event-service. Anything that fails is left in `Compensating` and picked
+    /// up again by the recovery worker.
+    async fn compensate(&self, saga: &PurchaseSaga) {
+        if let Err(e) = self
+            .client_repo
+            .remove_ticket_refs_from_client(&saga.client_id, &saga.ticket_codes)
+            .await
+            && !matches!(e, ClientRepoError::InvalidObjectId(_))
+        {
+            self.note_failure(saga, &format!("{:?}", e)).await;
+            return;
+        }
+
+        if !self.release_tickets(&saga.ticket_codes).await {
+            self.note_failure(saga, "Failed to release tickets in event-service")
+                .await;
+            return;
+        }
+
+        match self
+            .saga_repo
+            .advance(saga.id, SagaState::Compensating, SagaState::Compensated)
+            .await
+        {
+            Ok(_) => info!("Purchase saga {} rolled back", saga.id),
+            Err(e) => error!(
+                "Failed to mark purchase saga {} rolled back: {:?}",
+                saga.id, e
+            ),
+        }
+    }
+
+    async fn release_tickets(&self, ticket_codes: &[String]) -> bool {
+        let mut released = true;
+
+        for ticket_code in ticket_codes {
+            match event_service::delete_ticket(
+                &self.event_manager_client,
+                ticket_code,
+                &self.service_token,
+            )
+            .await
+            {
+                Ok(()) | Err(EventServiceError::NotFound(_)) => {}
+                Err(e) => {
+                    error!(
+                        "Failed to release ticket {} from event-service: {:?}",
+                        ticket_code, e
+                    );
+                    released = false;
+                }
+            }
+        }
+
+        released
+    }
+
+    async fn note_failure(&self, saga: &PurchaseSaga, error: &str) {
+        warn!("Purchase saga {} step failed: {}", saga.id, error);
+        if let Err(e) = self.saga_repo.record_failure(saga.id, error).await {
+            error!(
+                "Failed to record purchase saga {} failure: {:?}",
+                saga.id, e
+            );
+        }
+    }
+}
+
+/// Errors after which event-service is known not to have created anything.
+fn is_definitive(error: &EventServiceError) -> bool {
+    !matches!(
+        error,
+        EventServiceError::HttpError(_) | EventServiceError::DeserializationError(_)
+    )
+}
+
+pub(crate) fn build_ticket_ref_from_details(ticket_details: &TicketDetails) -> TicketRef {
+    TicketRef {
+        cod: ticket_details.ticket.cod.clone(),
+        nume_eveniment: ticket_details
+            .event
+            .as_ref()
+            .map(|e| e.nume.clone())
+            .or_else(|| ticket_details.packet.as_ref().map(|p| p.nume.clone())),
+        locatie: ticket_details
+            .event
+            .as_ref()
+            .map(|e| e.locatie.clone())
+            .or_else(|| ticket_details.packet.as_ref().map(|p| p.locatie.clone())),
+        descriere: ticket_details
+            .event
+            .as_ref()
+            .map(|e| e.descriere.clone())
+            .or_else(|| ticket_details.packet.as_ref().map(|p| p.descriere.clone())),
+        refund_status: None,
+        transfer_history: Vec::new(),
+    }
+}
+
+pub struct PurchaseSagaRecoveryWorker {
+    coordinator: Arc<PurchaseSagaCoordinator>,
+}
+
+impl PurchaseSagaRecoveryWorker {
+    pub fn new(coordinator: Arc<PurchaseSagaCoordinator>) -> Self {
+        Self { coordinator }
+    }
+
+    pub async fn start(&self) {
+        let mut interval = tokio::time::interval(RECOVERY_INTERVAL);
+
+        info!("Started recovering stale purchase sagas");
+
+        loop {
+            interval.tick().await;
+            self.coordinator.recover_stale().await;
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing::{error, info, warn};
+
+use crate::models::client::TicketRef;
+use crate::models::reconciliation::{
+    DriftReport, DuplicateReference, OrphanedTicket, PhantomReference, StaleRefundStatus,
+};
+use crate::repositories::client_repo::ClientRepo;
+use crate::repositories::purchase_saga_repo::PurchaseSagaRepo;
+use crate::services::event_service::{self, EventManagerClient};
+use crate::utils::error::{ClientApiError, map_event_service_error};
+
+const RECONCILE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Compares the tickets in event-service with the references stored on
+/// clients. Only drift with an unambiguous fix is repaired: phantom
+/// references are dropped and refund statuses are aligned with a pending or
+/// rejected refund request. Orphaned and duplicated tickets are only reported
+/// since deciding who owns them needs a human.
+pub struct TicketReconciler {
+    client_repo: Arc<ClientRepo>,
+    saga_repo: Arc<PurchaseSagaRepo>,
+    event_manager_client: Arc<EventManagerClient>,
+    service_token: String,
+}
+
+impl TicketReconciler {
+    pub fn new(
+        client_repo: Arc<ClientRepo>,
+        saga_repo: Arc<PurchaseSagaRepo>,
+        event_manager_client: Arc<EventManagerClient>,
+        service_token: String,
+    ) -> Self {
+        Self {
+            client_repo,
+            saga_repo,
+            event_manager_client,
+            service_token,
+        }
+    }
+
+    pub async fn reconcile(&self, repair: bool) -> Result<DriftReport, ClientApiError> {
+        // Clients are read before tickets: a reference can only point at a
+        // ticket that already existed, so a missing ticket is really gone.
+        let holders = self.client_repo.list_ticket_holders().await?;
+        let tickets = event_service::list_tickets(&self.event_manager_client, &self.service_token)
+            .await
+            .map_err(map_event_service_error)?;
+        let refund_statuses: HashMap<String, String> = event_service::list_ticket_refund_statuses(
+            &self.event_manager_client,
+            &self.service_token,
+        )
+        .await
+        .map_err(map_event_service_error)?
+        .into_iter()
+        .map(|status| (status.ticket_cod, status.status))
+        .collect();
+        let in_flight: HashSet<String> = self
+            .saga_repo
+            .list_in_flight_codes()
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut references: BTreeMap<&str, Vec<(String, &TicketRef)>> = BTreeMap::new();
+        for client in &holders {
+            let client_id = client.id.to_hex();
+            for ticket_ref in &client.lista_bilete {
+                references
+                    .entry(ticket_ref.cod.as_str())
+                    .or_default()
+                    .push((client_id.clone(), ticket_ref));
+            }
+        }
+
+        let existing: HashSet<&str> = tickets.iter().map(|t| t.cod.as_str()).collect();
+
+        let mut report = DriftReport {
+            generated_at: bson::DateTime::now()
+                .try_to_rfc3339_string()
+                .unwrap_or_default(),
+            tickets_checked: tickets.len(),
+            references_checked: references.values().map(Vec::len).sum(),
+            ..Default::default()
+        };
+
+        for (ticket_cod, holders) in &references {
+            if holders.len() > 1 {
+                report.duplicate_references.push(DuplicateReference {
+                    ticket_cod: ticket_cod.to_string(),
+                    client_ids: holders.iter().map(|(id, _)| id.clone()).collect(),
+                });
+            }
+
+            if in_flight.contains(*ticket_cod) {
+                continue;
+            }
+
+            if !existing.contains(ticket_cod) {
+                for (client_id, _) in holders {
+                    let repaired = repair && self.remove_reference(client_id, ticket_cod).await;
+                    report.phantom_references.push(PhantomReference {
+                        client_id: client_id.clone(),
+                        ticket_cod: ticket_cod.to_string(),
+                        repaired,
+                    });
+                }
+                continue;
+            }
+
+            let event_service_status = refund_statuses.get(*ticket_cod).cloned();
+            let expected = event_service_status
+                .as_deref()
+                .filter(|status| matches!(*status, "PENDING" | "REJECTED"));
+
+            for (client_id, ticket_ref) in holders {
+                if ticket_ref.refund_status.as_deref() == expected {
+                    continue;
+                }
+
+                // Without a pending or rejected request there is nothing safe
+                // to copy over: the request may still be in flight, or an
+                // approved refund failed to release the ticket.
+                let repaired = match expected {
+                    Some(status) if repair => {
+                        self.set_refund_status(client_id, ticket_cod, status).await
+                    }
+                    _ => false,
+                };
+
+                report.stale_refund_statuses.push(StaleRefundStatus {
+                    client_id: client_id.clone(),
+                    ticket_cod: ticket_cod.to_string(),
+                    client_status: ticket_ref.refund_status.clone(),
+                    event_service_status: event_service_status.clone(),
+                    repaired,
+                });
+            }
+        }
+
+        for ticket in &tickets {
+            if references.contains_key(ticket.cod.as_str()) || in_flight.contains(&ticket.cod) {
+                continue;
+            }
+
+            // The ticket may have been bought after the clients were read.
+            match self
+                .client_repo
+                .find_client_by_ticket_code(&ticket.cod)
+                .await
+            {
+                Ok(Some(_)) => continue,
+                Ok(None) => {}
+                Err(e) => {
+                    warn!("Failed to re-check ticket {}: {:?}", ticket.cod, e);
+                    continue;
+                }
+            }
+
+            report.orphaned_tickets.push(OrphanedTicket {
+                ticket_cod: ticket.cod.clone(),
+                evenimentid: ticket.evenimentid,
+                pachetid: ticket.pachetid,
+            });
+        }
+
+        Ok(report)
+    }
+
+    async fn remove_reference(&self, client_id: &str, ticket_cod: &str) -> bool {
+        match self
+            .client_repo
+            .remove_ticket_refs_from_client(client_id, &[ticket_cod.to_string()])
+            .await
+        {
+            Ok(()) => {
+                info!(
+                    "Removed phantom ticket {} from client {}",
+                    ticket_cod, client_id
+                );
+                true
+            }
+            Err(e) => {
+                error!(
+                    "Failed to remove phantom ticket {} from client {}: {:?}",
+                    ticket_cod, client_id, e
+                );
+                false
+            }
+        }
+    }
+
+    async fn set_refund_status(&self, client_id: &str, ticket_cod: &str, status: &str) -> bool {
+        match self
+            .client_repo
+            .update_ticket_refund_status(client_id, ticket_cod, Some(status))
+            .await
+        {
+            Ok(()) => {
+                info!(
+                    "Set refund status of ticket {} for client {} to {}",
+                    ticket_cod, client_id, status
+                );
+                true
+            }
+            Err(e) => {
+                error!(
+                    "Failed to set refund status of ticket {} for client {}: {:?}",
+                    ticket_cod, client_id, e
+                );
+                false
+            }
+        }
+    }
+}
+
+pub struct TicketReconcilerWorker {
+    reconciler: Arc<TicketReconciler>,
+    repair: bool,
+}
+
+impl TicketReconcilerWorker {
+    pub fn new(reconciler: Arc<TicketReconciler>, repair: bool) -> Self {
+        Self { reconciler, repair }
+    }
+
+    pub async fn start(&self) {
+        let mut interval = tokio::time::interval(RECONCILE_INTERVAL);
+
+        info!("Started ticket reconciliation (repair: {})", self.repair);
+
+        loop {
+            interval.tick().await;
+
+            match self.reconciler.reconcile(self.repair).await {
+                Ok(report) if report.drift_count() == 0 => {
+                    info!(
+                        "Ticket reconciliation found no drift ({} tickets, {} references)",
+                        report.tickets_checked, report.references_checked
+                    );
+                }
+                Ok(report) => {
+                    warn!(
+                        "Ticket reconciliation found drift: {} orphaned, {} phantom, {} duplicate, {} stale refund status",
+                        report.orphaned_tickets.len(),
+                        report.phantom_references.len(),
+                        report.duplicate_references.len(),
+                        report.stale_refund_statuses.len()
+                    );
+                }
+                Err(e) => error!("Ticket reconciliation failed: {:?}", e),
+            }
+        }
+    }
+}
@@ -1,12 +1,28 @@
 use crate::handlers::auth::*;
+use crate::handlers::calendar::*;
 use crate::handlers::client::*;
+use crate::handlers::reconciliation::*;
+use crate::handlers::ticket_pass::*;
+use crate::handlers::ticket_transfer::*;
 use crate::models::auth::{
     LoginRequest, LoginResponse, RegisterRequest, RegisterResponse, ResendVerificationRequest,
     ResendVerificationResponse, UpdateRoleRequest, UpdateRoleResponse, VerifyEmailRequest,
     VerifyEmailResponse,
 };
-use crate::models::client::{AddTicket, Client, CreateClient, SocialMedia, TicketRef, UpdateClient};
+use crate::models::calendar::CalendarFeedLink;
+use crate::models::client::{
+    AddTicket, Client, CreateClient, SocialMedia, TicketBuyer, TicketBuyerInfo,
+    TicketBuyersRequest, TicketRef, UpdateClient,
+};
+use crate::models::reconciliation::{
+    DriftReport, DuplicateReference, OrphanedTicket, PhantomReference, StaleRefundStatus,
+};
+use crate::models::ticket_pass::{QrFormat, SignedTicketPass, TicketSigningKey, VerifyTicketPass};
+use crate::models::ticket_transfer::{
+    ClientTransfers, StartTransfer, TicketTransfer, TicketTransferRecord, TransferStatus,
+};
 use crate::services::event_service::{EventInfo, PacketInfo, TicketDetails, TicketInfo};
+use common::ticket_pass::TicketPass;
 use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::{Modify, OpenApi};
 
@@ -27,7 +43,21 @@ use utoipa::{Modify, OpenApi};
         get_client_tickets,
         add_ticket_to_client,
         remove_ticket_from_client,
+        get_clients_by_tickets,
         update_user_role,
+        run_reconciliation,
+        get_ticket_pass,
+        get_ticket_qr,
+        get_signing_key,
+        verify_ticket_pass,
+        start_transfer,
+        list_transfers,
+        accept_transfer,
+        decline_transfer,
+        cancel_transfer,
+        get_calendar_feed,
+        revoke_calendar_feed,
+        get_calendar,
     ),
     components(schemas(
         Client,
@@ -36,6 +66,9 @@ use utoipa::{Modify, OpenApi};
         AddTicket,
         TicketRef,
         SocialMedia,
+        TicketBuyerInfo,
+        TicketBuyersRequest,
+        TicketBuyer,
         TicketDetails,
         TicketInfo,
         EventInfo,
@@ -50,11 +83,31 @@ use utoipa::{Modify, OpenApi};
         ResendVerificationResponse,
         UpdateRoleRequest,
         UpdateRoleResponse,
+        DriftReport,
+        OrphanedTicket,
+        PhantomReference,
+        DuplicateReference,
+        StaleRefundStatus,
+        TicketPass,
+        SignedTicketPass,
+        VerifyTicketPass,
+        TicketSigningKey,
+        QrFormat,
+        TicketTransfer,
+        TicketTransferRecord,
+        TransferStatus,
+        StartTransfer,
+        ClientTransfers,
+        CalendarFeedLink,
     )),
     modifiers(&SecurityAddon),
     tags(
         (name = "auth", description = "Authentication and authorization endpoints"),
         (name = "clients", description = "Client management endpoints"),
+        (name = "Ticket passes", description = "Signed ticket passes and QR codes"),
+        (name = "Ticket transfers", description = "Handing tickets over to other clients"),
+        (name = "Reconciliation", description = "Drift checks between event-service tickets and client ticket lists"),
+        (name = "Calendar", description = "Subscribable iCalendar feed of a client's tickets"),
     )
 )]
 pub struct ApiDoc;
@@ -30,6 +30,7 @@ pub enum ClientRepoError {
     DuplicateEmail(String),
     DatabaseError(String),
     InvalidObjectId(String),
+    InvalidCursor(String),
 }
 
 impl IntoResponse for ClientApiError {
@@ -64,6 +65,11 @@ impl IntoResponse for ClientApiError {
                     "Invalid Object ID".to_string(),
                     Some(vec![msg]),
                 ),
+                ClientRepoError::InvalidCursor(msg) => (
+                    StatusCode::BAD_REQUEST,
+                    "Invalid Cursor".to_string(),
+                    Some(vec![msg]),
+                ),
             },
             ClientApiError::BadRequest(msg) => (
                 StatusCode::BAD_REQUEST,
@@ -148,6 +154,7 @@ pub fn map_event_service_error(error: EventServiceError) -> ClientApiError {
     match error {
         EventServiceError::InvalidReference(msg) => ClientApiError::NotFound(msg),
         EventServiceError::NoSeatsAvailable(msg) => ClientApiError::Conflict(msg),
+        EventServiceError::CheckedIn(msg) => ClientApiError::Conflict(msg),
         EventServiceError::HttpError(msg) => ClientApiError::ExternalServiceError(msg),
         EventServiceError::DeserializationError(msg) => ClientApiError::ExternalServiceError(msg),
         EventServiceError::NotFound(msg) => ClientApiError::NotFound(msg),
@@ -0,0 +1,82 @@
+use std::future::Future;
+
+use axum::Json;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+
+use crate::models::idempotency::IdempotencyClaim;
+use crate::repositories::idempotency_repo::IdempotencyRepo;
+use crate::utils::error::ClientApiError;
+
+pub use common::idempotency::request_fingerprint;
+
+pub fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, ClientApiError> {
+    common::idempotency::idempotency_key(headers).map_err(ClientApiError::BadRequest)
+}
+
+/// Runs `work` at most once per idempotency key. Retries with the same key
+/// and request get the stored response of the first successful run; failed
+/// runs are not stored, so the client can retry them. Reusing a key for a
+/// request with another `fingerprint` is refused.
+pub async fn run_idempotent<T, F>(
+    repo: &IdempotencyRepo,
+    scope: &str,
+    key: Option<&str>,
+    fingerprint: &str,
+    work: F,
+) -> Result<Response, ClientApiError>
+where
+    T: Serialize,
+    F: Future<Output = Result<(StatusCode, Json<T>), ClientApiError>>,
+{
+    let Some(key) = key else {
+        return work.await.map(IntoResponse::into_response);
+    };
+
+    match repo.claim(scope, key, fingerprint).await? {
+        IdempotencyClaim::Claimed => {}
+        IdempotencyClaim::Completed { status, body } => {
+            tracing::info!("Replaying stored response for idempotency key {}", key);
+            let status = StatusCode::from_u16(status).unwrap_or(StatusCode::OK);
+            return Ok((status, Json(body)).into_response());
+        }
+        IdempotencyClaim::InProgress => {
+            return Err(ClientApiError::Conflict(
+                "A request with this Idempotency-Key is still being processed".to_string(),
+            ));
+        }
+        IdempotencyClaim::Mismatch => {
+            return Err(ClientApiError::Conflict(
+                "This Idempotency-Key was already used for a different request".to_string(),
+            ));
+        }
+    }
+
+    match work.await {
+        Ok((status, Json(body))) => {
+            let body = serde_json::to_value(&body)
+                .map_err(|e| ClientApiError::InternalError(e.to_string()))?;
+
+            if let Err(e) = repo.complete(scope, key, status.as_u16(), &body).await {
+                tracing::error!(
+                    "Failed to store response for idempotency key {}: {:?}",
+                    key,
+                    e
+                );
+            }
+
+            Ok((status, Json(body)).into_response())
+        }
+        Err(e) => {
+            if let Err(release_error) = repo.release(scope, key).await {
+                tracing::error!(
+                    "Failed to release idempotency key {}: {:?}",
+                    key,
+                    release_error
+                );
+            }
+            Err(e)
+        }
+    }
+}
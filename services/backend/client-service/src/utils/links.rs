@@ -1,6 +1,7 @@
 use crate::models::client::{Client, ClientQuery, TicketBuyerInfo, TicketRef};
 
 pub use common::links::{Link, Links, Response, ResponseBuilder};
+use common::pagination::Page;
 use hateoas_macros::{hateoas_filtered, hateoas_lookup, hateoas_nested, hateoas_simple};
 
 #[hateoas_simple(
@@ -22,11 +23,14 @@ pub fn build_simple_client(client: Client, base_url: &str) -> Response<Client> {
     query_fields(
         ("email", "email"),
         ("prenume", "prenume"),
-        ("nume", "nume")
+        ("nume", "nume"),
+        ("items_per_page", "items_per_page"),
+        ("cursor", "cursor"),
+        ("include_total", "include_total")
     )
 )]
 pub fn build_filtered_client(
-    clients: Vec<Client>,
+    clients: Page<Client>,
     params: &ClientQuery,
     base_url: &str,
 ) -> Vec<Response<Client>> {
@@ -1,4 +1,6 @@
 pub mod auth_links;
 pub mod doc;
 pub mod error;
+pub mod idempotency;
 pub mod links;
+pub mod qr;
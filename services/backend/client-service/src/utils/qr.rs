@@ -0,0 +1,59 @@
+use qrcode::render::svg;
+use qrcode::{Color, EcLevel, QrCode};
+
+const QUIET_ZONE: usize = 4;
+const MODULE_PIXELS: usize = 8;
+
+fn encode(data: &str) -> Result<QrCode, String> {
+    QrCode::with_error_correction_level(data.as_bytes(), EcLevel::M)
+        .map_err(|e| format!("Failed to encode QR code: {}", e))
+}
+
+pub fn render_svg(data: &str) -> Result<String, String> {
+    let code = encode(data)?;
+
+    Ok(code
+        .render::<svg::Color>()
+        .min_dimensions(256, 256)
+        .quiet_zone(true)
+        .build())
+}
+
+/// Renders `data` as an 8-bit grayscale PNG with a 4-module quiet zone.
+pub fn render_png(data: &str) -> Result<Vec<u8>, String> {
+    let code = encode(data)?;
+    let modules = code.width();
+    let colors = code.to_colors();
+
+    let side = (modules + 2 * QUIET_ZONE) * MODULE_PIXELS;
+    let mut pixels = vec![255u8; side * side];
+
+    for (index, color) in colors.iter().enumerate() {
+        if *color != Color::Dark {
+            continue;
+        }
+
+        let x = (index % modules + QUIET_ZONE) * MODULE_PIXELS;
+        let y = (index / modules + QUIET_ZONE) * MODULE_PIXELS;
+        for row in y..y + MODULE_PIXELS {
+            pixels[row * side + x..row * side + x + MODULE_PIXELS].fill(0);
+        }
+    }
+
+    let mut output = Vec::new();
+    let mut encoder = png::Encoder::new(&mut output, side as u32, side as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder
+        .write_header()
+        .map_err(|e| format!("Failed to write PNG header: {}", e))?;
+    writer
+        .write_image_data(&pixels)
+        .map_err(|e| format!("Failed to write PNG data: {}", e))?;
+    writer
+        .finish()
+        .map_err(|e| format!("Failed to finish PNG: {}", e))?;
+
+    Ok(output)
+}
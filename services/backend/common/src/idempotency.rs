@@ -0,0 +1,44 @@
+use axum::http::HeaderMap;
+use ring::digest::{SHA256, digest};
+use serde::Serialize;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+/// A claim whose request never finished (e.g. the service died mid-request)
+/// is handed to the next retry after this many seconds.
+pub const IDEMPOTENCY_CLAIM_TIMEOUT_SECS: i64 = 5 * 60;
+/// Stored responses are replayed for this many seconds and then deleted.
+pub const IDEMPOTENCY_KEY_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// Reads the optional `Idempotency-Key` header. Returns an error message when
+/// the header is present but unusable.
+pub fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, String> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+
+    let key = value
+        .to_str()
+        .map_err(|_| "Idempotency-Key must be valid ASCII".to_string())?
+        .trim();
+
+    if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(format!(
+            "Idempotency-Key must be between 1 and {} characters",
+            MAX_IDEMPOTENCY_KEY_LEN
+        ));
+    }
+
+    Ok(Some(key.to_string()))
+}
+
+/// Hex SHA-256 of the request, stored with its key so that reusing a key for
+/// a different request can be refused instead of replaying the wrong response.
+pub fn request_fingerprint<T: Serialize>(request: &T) -> String {
+    let body = serde_json::to_vec(request).unwrap_or_default();
+    digest(&SHA256, &body)
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
@@ -1,5 +1,8 @@
 pub mod authorization;
 pub mod error;
+pub mod idempotency;
 pub mod links;
+pub mod pagination;
 pub mod rabbitmq;
+pub mod ticket_pass;
 pub mod websocket;
@@ -0,0 +1,118 @@
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+pub const NEXT_CURSOR_HEADER: &str = "x-next-cursor";
+
+/// Opaque position in a keyset-ordered list: the sort key of the row the
+/// next page starts after (or, when `before` is set, ends before).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cursor<K> {
+    #[serde(rename = "k")]
+    pub key: K,
+    #[serde(rename = "b", default, skip_serializing_if = "std::ops::Not::not")]
+    pub before: bool,
+}
+
+impl<K> Cursor<K>
+where
+    K: Serialize + DeserializeOwned,
+{
+    pub fn after(key: K) -> Self {
+        Self { key, before: false }
+    }
+
+    pub fn before(key: K) -> Self {
+        Self { key, before: true }
+    }
+
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
+    pub total: Option<i64>,
+}
+
+impl<T> Page<T> {
+    /// Builds a page from up to `limit + 1` rows in query order; the extra row
+    /// only tells that more rows follow. `backward` pages were read in reverse
+    /// and are flipped back here. `skipped` says whether rows precede a forward
+    /// page (it started from a cursor or an offset).
+    pub fn from_rows<K>(
+        mut rows: Vec<T>,
+        limit: usize,
+        backward: bool,
+        skipped: bool,
+        key: impl Fn(&T) -> K,
+    ) -> Self
+    where
+        K: Serialize + DeserializeOwned,
+    {
+        let has_more = rows.len() > limit;
+        rows.truncate(limit);
+
+        let (has_prev, has_next) = if backward {
+            rows.reverse();
+            (has_more, true)
+        } else {
+            (skipped, has_more)
+        };
+
+        let next_cursor = rows
+            .last()
+            .filter(|_| has_next)
+            .map(|row| Cursor::after(key(row)).encode());
+        let prev_cursor = rows
+            .first()
+            .filter(|_| has_prev)
+            .map(|row| Cursor::before(key(row)).encode());
+
+        Self {
+            items: rows,
+            next_cursor,
+            prev_cursor,
+            total: None,
+        }
+    }
+
+    pub fn with_total(mut self, total: Option<i64>) -> Self {
+        self.total = total;
+        self
+    }
+
+    pub fn has_more_pages(&self) -> bool {
+        self.next_cursor.is_some() || self.prev_cursor.is_some()
+    }
+
+    /// `X-Total-Count` (when counted) and `X-Next-Cursor`, for clients that
+    /// page through a list without reading the HATEOAS links.
+    pub fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(total) = self.total {
+            headers.insert(
+                HeaderName::from_static(TOTAL_COUNT_HEADER),
+                HeaderValue::from(total),
+            );
+        }
+        if let Some(cursor) = self
+            .next_cursor
+            .as_deref()
+            .and_then(|cursor| HeaderValue::from_str(cursor).ok())
+        {
+            headers.insert(HeaderName::from_static(NEXT_CURSOR_HEADER), cursor);
+        }
+        headers
+    }
+}
@@ -2,10 +2,16 @@ use serde::{Deserialize, Serialize};
 
 pub const ROUTING_KEY_REFUND_REQUESTED: &str = "refund.requested";
 pub const ROUTING_KEY_REFUND_RESOLVED: &str = "refund.resolved";
+pub const ROUTING_KEY_EVENT_CANCELLED: &str = "event.cancelled";
+pub const ROUTING_KEY_WAITLIST_OFFER: &str = "waitlist.offer";
+pub const ROUTING_KEY_TICKET_TRANSFER: &str = "ticket.transfer";
 
 pub const QUEUE_REFUND_REQUESTED: &str = "refund.requested.queue";
 pub const QUEUE_REFUND_RESOLVED_EMAIL: &str = "refund.resolved.email.queue";
 pub const QUEUE_REFUND_RESOLVED_CLIENT: &str = "refund.resolved.client.queue";
+pub const QUEUE_EVENT_CANCELLED_CLIENT: &str = "event.cancelled.client.queue";
+pub const QUEUE_WAITLIST_OFFER_EMAIL: &str = "waitlist.offer.email.queue";
+pub const QUEUE_TICKET_TRANSFER_EMAIL: &str = "ticket.transfer.email.queue";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RefundRequested {
@@ -17,6 +23,8 @@ pub struct RefundRequested {
     pub packet_id: Option<i32>,
     pub event_owner_id: i32,
     pub reason: String,
+    #[serde(default)]
+    pub auto_approve: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,9 +37,50 @@ pub struct RefundResolved {
     pub message: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventCancelled {
+    pub event_id: Option<i32>,
+    pub packet_id: Option<i32>,
+    pub name: String,
+    pub owner_id: i32,
+    #[serde(default)]
+    pub ticket_codes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaitlistOfferMade {
+    pub entry_id: i32,
+    pub user_id: i32,
+    pub email: String,
+    pub event_id: Option<i32>,
+    pub packet_id: Option<i32>,
+    pub name: String,
+    pub hold_id: String,
+    pub expires_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TicketTransferNotice {
+    pub transfer_id: String,
+    pub ticket_cod: String,
+    pub event_name: Option<String>,
+    pub from_email: String,
+    pub to_email: String,
+    pub status: TicketTransferStatus,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum RefundStatus {
     Approved,
     Rejected,
 }
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TicketTransferStatus {
+    Requested,
+    Accepted,
+    Declined,
+    Cancelled,
+}
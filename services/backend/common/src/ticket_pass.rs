@@ -0,0 +1,139 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use ring::signature::{ED25519, Ed25519KeyPair, KeyPair, UnparsedPublicKey};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub const TICKET_PASS_VERSION: u8 = 1;
+
+/// Claims carried by a signed ticket. The encoded pass is
+/// `base64url(json).base64url(ed25519 signature)`, so anyone holding the
+/// public key can check it without calling back into the services.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TicketPass {
+    pub v: u8,
+    pub cod: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evenimentid: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pachetid: Option<i32>,
+    /// Client ID of the ticket holder.
+    pub holder: String,
+    /// Unix timestamp (seconds) at which the pass was issued.
+    pub iat: i64,
+}
+
+impl TicketPass {
+    pub fn new(
+        cod: String,
+        evenimentid: Option<i32>,
+        pachetid: Option<i32>,
+        holder: String,
+    ) -> Self {
+        let iat = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+
+        Self {
+            v: TICKET_PASS_VERSION,
+            cod,
+            evenimentid,
+            pachetid,
+            holder,
+            iat,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum TicketPassError {
+    InvalidKey(String),
+    Malformed(String),
+    InvalidSignature,
+}
+
+pub struct TicketSigner {
+    key_pair: Ed25519KeyPair,
+}
+
+impl TicketSigner {
+    /// Builds a signer from a base64-encoded 32-byte Ed25519 seed, e.g. the
+    /// output of `openssl rand -base64 32`.
+    pub fn from_seed(seed: &str) -> Result<Self, TicketPassError> {
+        let seed = STANDARD
+            .decode(seed.trim())
+            .map_err(|e| TicketPassError::InvalidKey(e.to_string()))?;
+
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(&seed)
+            .map_err(|e| TicketPassError::InvalidKey(e.to_string()))?;
+
+        Ok(Self { key_pair })
+    }
+
+    /// Public key as base64url, for distribution to door devices.
+    pub fn public_key(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.key_pair.public_key().as_ref())
+    }
+
+    pub fn verifier(&self) -> TicketVerifier {
+        TicketVerifier {
+            public_key: self.key_pair.public_key().as_ref().to_vec(),
+        }
+    }
+
+    pub fn sign(&self, pass: &TicketPass) -> String {
+        let payload = URL_SAFE_NO_PAD
+            .encode(serde_json::to_vec(pass).expect("ticket pass always serializes"));
+        let signature = self.key_pair.sign(payload.as_bytes());
+
+        format!("{}.{}", payload, URL_SAFE_NO_PAD.encode(signature.as_ref()))
+    }
+}
+
+pub struct TicketVerifier {
+    public_key: Vec<u8>,
+}
+
+impl TicketVerifier {
+    pub fn from_public_key(public_key: &str) -> Result<Self, TicketPassError> {
+        let public_key = URL_SAFE_NO_PAD
+            .decode(public_key.trim())
+            .map_err(|e| TicketPassError::InvalidKey(e.to_string()))?;
+
+        Ok(Self { public_key })
+    }
+
+    pub fn verify(&self, token: &str) -> Result<TicketPass, TicketPassError> {
+        let (payload, signature) = token
+            .trim()
+            .split_once('.')
+            .ok_or_else(|| TicketPassError::Malformed("Missing signature".to_string()))?;
+
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|e| TicketPassError::Malformed(e.to_string()))?;
+
+        UnparsedPublicKey::new(&ED25519, &self.public_key)
+            .verify(payload.as_bytes(), &signature)
+            .map_err(|_| TicketPassError::InvalidSignature)?;
+
+        let payload = URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|e| TicketPassError::Malformed(e.to_string()))?;
+
+        let pass: TicketPass = serde_json::from_slice(&payload)
+            .map_err(|e| TicketPassError::Malformed(e.to_string()))?;
+
+        if pass.v != TICKET_PASS_VERSION {
+            return Err(TicketPassError::Malformed(format!(
+                "Unsupported ticket pass version {}",
+                pass.v
+            )));
+        }
+
+        Ok(pass)
+    }
+}
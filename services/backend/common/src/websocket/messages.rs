@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 pub enum WebSocketMessage {
     RefundStatusChanged(RefundStatusChanged),
     NewRefundRequest(NewRefundRequest),
+    WaitlistOffer(WaitlistOffer),
 }
 
 
@@ -31,5 +32,16 @@ pub struct NewRefundRequest {
     pub event_owner_id: i32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaitlistOffer {
+    pub entry_id: i32,
+    pub event_id: Option<i32>,
+    pub packet_id: Option<i32>,
+    pub name: String,
+    pub hold_id: String,
+    pub expires_at: String,
+    pub user_id: i32,
+}
+
 pub const ROUTING_KEY_WS_BROADCAST: &str = "ws.broadcast";
 pub const QUEUE_WS_BROADCAST: &str = "ws.broadcast.queue";
@@ -7,6 +7,8 @@ use crate::grpc_service::EmailServiceImpl;
 use crate::repository::verification_repository::VerificationRepository;
 use crate::services::email_service::EmailService;
 use crate::services::refund_consumer::RefundConsumer;
+use crate::services::ticket_transfer_consumer::TicketTransferConsumer;
+use crate::services::waitlist_consumer::WaitlistOfferConsumer;
 use anyhow::Result;
 use axum::{routing::get, Router};
 use common::rabbitmq::RabbitMQ;
@@ -66,6 +68,22 @@ async fn main() -> Result<()> {
             }
             info!("RabbitMQ connection established for refund consumer");
 
+            let waitlist_consumer =
+                WaitlistOfferConsumer::new(Arc::clone(&rabbitmq), Arc::clone(&email_service));
+            tokio::spawn(async move {
+                if let Err(e) = waitlist_consumer.start().await {
+                    error!("Waitlist offer consumer error: {:?}", e);
+                }
+            });
+
+            let transfer_consumer =
+                TicketTransferConsumer::new(Arc::clone(&rabbitmq), Arc::clone(&email_service));
+            tokio::spawn(async move {
+                if let Err(e) = transfer_consumer.start().await {
+                    error!("Ticket transfer consumer error: {:?}", e);
+                }
+            });
+
             let consumer = RefundConsumer::new(rabbitmq, email_service);
             if let Err(e) = consumer.start().await {
                 error!("Refund consumer error: {:?}", e);
@@ -209,6 +209,110 @@ impl EmailService {
             .await
     }
 
+    pub async fn send_waitlist_offer_email(
+        &self,
+        to_email: &str,
+        event_name: &str,
+        hold_id: &str,
+        expires_at: &str,
+    ) -> Result<()> {
+        let html_body = html! {
+            (DOCTYPE)
+            html {
+                head {
+                    style {
+                        r#"
+                        body { font-family: Arial, sans-serif; line-height: 1.6; color: #333; }
+                        .container { max-width: 600px; margin: 0 auto; padding: 20px; }
+                        .header { background-color: #007bff; color: white; padding: 20px; text-align: center; }
+                        .content { background-color: #f9f9f9; padding: 20px; }
+                        .ticket { font-size: 18px; font-weight: bold; color: #007bff; text-align: center; padding: 15px; background-color: white; border: 2px solid #007bff; margin: 20px 0; }
+                        .footer { text-align: center; padding: 20px; color: #777; font-size: 12px; }
+                        "#
+                    }
+                }
+                body {
+                    div.container {
+                        div.header {
+                            h1 { "A Seat Opened Up" }
+                        }
+                        div.content {
+                            p { "Good news!" }
+                            p { "A seat became available and it is being held for you:" }
+
+                            div.ticket {
+                                p { "Event: " (event_name) }
+                                p { "Hold: " (hold_id) }
+                            }
+
+                            p { "Complete your purchase before " (expires_at) ". After that the seat goes to the next person on the waitlist." }
+                        }
+                        div.footer {
+                            p { "This is an automated message, please do not reply." }
+                        }
+                    }
+                }
+            }
+        };
+
+        self.send_html_email(
+            to_email,
+            "A Seat Is Waiting For You",
+            html_body.into_string(),
+        )
+        .await
+    }
+
+    pub async fn send_ticket_transfer_email(
+        &self,
+        to_email: &str,
+        subject: &str,
+        message: &str,
+        ticket_cod: &str,
+        event_name: Option<&str>,
+    ) -> Result<()> {
+        let html_body = html! {
+            (DOCTYPE)
+            html {
+                head {
+                    style {
+                        r#"
+                        body { font-family: Arial, sans-serif; line-height: 1.6; color: #333; }
+                        .container { max-width: 600px; margin: 0 auto; padding: 20px; }
+                        .header { background-color: #007bff; color: white; padding: 20px; text-align: center; }
+                        .content { background-color: #f9f9f9; padding: 20px; }
+                        .ticket { font-size: 18px; font-weight: bold; color: #007bff; text-align: center; padding: 15px; background-color: white; border: 2px solid #007bff; margin: 20px 0; }
+                        .footer { text-align: center; padding: 20px; color: #777; font-size: 12px; }
+                        "#
+                    }
+                }
+                body {
+                    div.container {
+                        div.header {
+                            h1 { (subject) }
+                        }
+                        div.content {
+                            p { (message) }
+
+                            div.ticket {
+                                @if let Some(name) = event_name {
+                                    p { "Event: " (name) }
+                                }
+                                p { "Ticket: " (ticket_cod) }
+                            }
+                        }
+                        div.footer {
+                            p { "This is an automated message, please do not reply." }
+                        }
+                    }
+                }
+            }
+        };
+
+        self.send_html_email(to_email, subject, html_body.into_string())
+            .await
+    }
+
     pub async fn send_password_reset_email(&self, to_email: &str, reset_code: &str) -> Result<()> {
         let html_body = html! {
             (DOCTYPE)
@@ -1,2 +1,4 @@
 pub mod email_service;
 pub mod refund_consumer;
+pub mod ticket_transfer_consumer;
+pub mod waitlist_consumer;
@@ -0,0 +1,158 @@
+use crate::services::email_service::EmailService;
+use anyhow::{Context, Result};
+use common::rabbitmq::messages::{
+    TicketTransferNotice, TicketTransferStatus, QUEUE_TICKET_TRANSFER_EMAIL,
+    ROUTING_KEY_TICKET_TRANSFER,
+};
+use common::rabbitmq::RabbitMQ;
+use futures::StreamExt;
+use lapin::options::{BasicAckOptions, BasicConsumeOptions};
+use lapin::types::FieldTable;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+pub struct TicketTransferConsumer {
+    rabbitmq: Arc<RabbitMQ>,
+    email_service: Arc<EmailService>,
+}
+
+impl TicketTransferConsumer {
+    pub fn new(rabbitmq: Arc<RabbitMQ>, email_service: Arc<EmailService>) -> Self {
+        Self {
+            rabbitmq,
+            email_service,
+        }
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        self.rabbitmq
+            .declare_queue(QUEUE_TICKET_TRANSFER_EMAIL, ROUTING_KEY_TICKET_TRANSFER)
+            .await
+            .context("Failed to declare queue")?;
+
+        let channel = self
+            .rabbitmq
+            .get_channel()
+            .await
+            .context("Channel not available")?;
+
+        let consumer = channel
+            .basic_consume(
+                QUEUE_TICKET_TRANSFER_EMAIL,
+                "email-service-ticket-transfer-consumer",
+                BasicConsumeOptions::default(),
+                FieldTable::default(),
+            )
+            .await
+            .context("Failed to create consumer")?;
+
+        info!("Started consuming ticket transfer messages");
+
+        let email_service = Arc::clone(&self.email_service);
+
+        let mut consumer = consumer;
+        while let Some(delivery) = consumer.next().await {
+            match delivery {
+                Ok(delivery) => {
+                    match serde_json::from_slice::<TicketTransferNotice>(&delivery.data) {
+                        Ok(notice) => {
+                            info!(
+                                "Received ticket transfer notice: transfer={}, status={:?}",
+                                notice.transfer_id, notice.status
+                            );
+
+                            let email_service = Arc::clone(&email_service);
+                            tokio::spawn(async move {
+                                for (to_email, subject, message) in emails_for(&notice) {
+                                    if let Err(e) = email_service
+                                        .send_ticket_transfer_email(
+                                            &to_email,
+                                            subject,
+                                            &message,
+                                            &notice.ticket_cod,
+                                            notice.event_name.as_deref(),
+                                        )
+                                        .await
+                                    {
+                                        error!("Failed to send ticket transfer email: {:?}", e);
+                                    } else {
+                                        info!("Sent ticket transfer notification to {}", to_email);
+                                    }
+                                }
+                            });
+                        }
+                        Err(e) => {
+                            warn!(
+                                "Failed to deserialize ticket transfer message: {:?}. Discarding message.",
+                                e
+                            );
+                        }
+                    }
+
+                    if let Err(e) = delivery.ack(BasicAckOptions::default()).await {
+                        error!("Failed to ack message: {:?}", e);
+                    }
+                }
+                Err(e) => {
+                    error!("Consumer error: {:?}", e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn emails_for(notice: &TicketTransferNotice) -> Vec<(String, &'static str, String)> {
+    let from = notice.from_email.clone();
+    let to = notice.to_email.clone();
+
+    match notice.status {
+        TicketTransferStatus::Requested => vec![(
+            to,
+            "A Ticket Is Waiting For You",
+            format!(
+                "{} wants to transfer a ticket to you. Open your account to accept or decline it.",
+                from
+            ),
+        )],
+        TicketTransferStatus::Accepted => vec![
+            (
+                to,
+                "Ticket Received",
+                format!("The ticket from {} is now in your account.", from),
+            ),
+            (
+                from,
+                "Ticket Transferred",
+                format!(
+                    "{} accepted your ticket. It is no longer in your account.",
+                    notice.to_email
+                ),
+            ),
+        ],
+        TicketTransferStatus::Declined => vec![(
+            from,
+            "Ticket Transfer Declined",
+            format!(
+                "{} declined your ticket transfer. The ticket stays in your account.",
+                to
+            ),
+        )],
+        TicketTransferStatus::Cancelled => vec![
+            (
+                to,
+                "Ticket Transfer Cancelled",
+                format!("The ticket transfer from {} was cancelled.", from),
+            ),
+            (
+                from,
+                "Ticket Transfer Cancelled",
+                format!(
+                    "Your ticket transfer to {} was cancelled. The ticket stays in your account.",
+                    notice.to_email
+                ),
+            ),
+        ],
+    }
+}
@@ -0,0 +1,100 @@
+use crate::services::email_service::EmailService;
+use anyhow::{Context, Result};
+use common::rabbitmq::messages::{
+    WaitlistOfferMade, QUEUE_WAITLIST_OFFER_EMAIL, ROUTING_KEY_WAITLIST_OFFER,
+};
+use common::rabbitmq::RabbitMQ;
+use futures::StreamExt;
+use lapin::options::{BasicAckOptions, BasicConsumeOptions};
+use lapin::types::FieldTable;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+pub struct WaitlistOfferConsumer {
+    rabbitmq: Arc<RabbitMQ>,
+    email_service: Arc<EmailService>,
+}
+
+impl WaitlistOfferConsumer {
+    pub fn new(rabbitmq: Arc<RabbitMQ>, email_service: Arc<EmailService>) -> Self {
+        Self {
+            rabbitmq,
+            email_service,
+        }
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        self.rabbitmq
+            .declare_queue(QUEUE_WAITLIST_OFFER_EMAIL, ROUTING_KEY_WAITLIST_OFFER)
+            .await
+            .context("Failed to declare queue")?;
+
+        let channel = self
+            .rabbitmq
+            .get_channel()
+            .await
+            .context("Channel not available")?;
+
+        let consumer = channel
+            .basic_consume(
+                QUEUE_WAITLIST_OFFER_EMAIL,
+                "email-service-waitlist-consumer",
+                BasicConsumeOptions::default(),
+                FieldTable::default(),
+            )
+            .await
+            .context("Failed to create consumer")?;
+
+        info!("Started consuming waitlist offer messages");
+
+        let email_service = Arc::clone(&self.email_service);
+
+        let mut consumer = consumer;
+        while let Some(delivery) = consumer.next().await {
+            match delivery {
+                Ok(delivery) => {
+                    match serde_json::from_slice::<WaitlistOfferMade>(&delivery.data) {
+                        Ok(message) => {
+                            info!(
+                                "Received waitlist offer: entry={}, hold={}",
+                                message.entry_id, message.hold_id
+                            );
+
+                            let email_service = Arc::clone(&email_service);
+                            tokio::spawn(async move {
+                                if let Err(e) = email_service
+                                    .send_waitlist_offer_email(
+                                        &message.email,
+                                        &message.name,
+                                        &message.hold_id,
+                                        &message.expires_at,
+                                    )
+                                    .await
+                                {
+                                    error!("Failed to send waitlist offer email: {:?}", e);
+                                } else {
+                                    info!("Sent waitlist offer notification to {}", message.email);
+                                }
+                            });
+                        }
+                        Err(e) => {
+                            warn!(
+                                "Failed to deserialize waitlist offer message: {:?}. Discarding message.",
+                                e
+                            );
+                        }
+                    }
+
+                    if let Err(e) = delivery.ack(BasicAckOptions::default()).await {
+                        error!("Failed to ack message: {:?}", e);
+                    }
+                }
+                Err(e) => {
+                    error!("Consumer error: {:?}", e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,228 @@
+use crate::AppState;
+use crate::middleware::{Authorization, UserClaims};
+use crate::models::analytics::{
+    AnalyticsQuery, SalesBucket, SalesSummary, SellThrough, TierRevenue,
+};
+use crate::utils::error::{ApiError, map_authorization_error};
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    routing::get,
+};
+use std::sync::Arc;
+use validator::Validate;
+
+pub fn analytics_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/analytics/summary", get(get_summary))
+        .route("/analytics/sales", get(get_sales))
+        .route("/analytics/events", get(get_event_sell_through))
+        .route("/analytics/event-packets", get(get_packet_sell_through))
+        .route("/analytics/revenue", get(get_tier_revenue))
+}
+
+/// Validates the query and narrows it to what the caller may see: owners are
+/// pinned to their own events and packets, admins may pick any owner or none.
+async fn scope_query(
+    state: &AppState,
+    user_claims: &UserClaims,
+    mut params: AnalyticsQuery,
+) -> Result<AnalyticsQuery, ApiError> {
+    Authorization::require_owner_event_or_admin(user_claims).map_err(map_authorization_error)?;
+    params.validate()?;
+
+    if !user_claims.is_admin() {
+        if params
+            .owner_id
+            .is_some_and(|owner_id| owner_id != user_claims.user_id)
+        {
+            return Err(ApiError::Forbidden(
+                "Only admins can view the analytics of other owners".to_string(),
+            ));
+        }
+        params.owner_id = Some(user_claims.user_id);
+    }
+
+    if let Some(event_id) = params.event_id {
+        let event = state.event_repo.get_event(event_id).await?;
+        Authorization::can_access_resource(user_claims, &event, None)
+            .map_err(map_authorization_error)?;
+    }
+    if let Some(packet_id) = params.packet_id {
+        let packet = state.event_packet_repo.get_event_packet(packet_id).await?;
+        Authorization::can_access_resource(user_claims, &packet, None)
+            .map_err(map_authorization_error)?;
+    }
+
+    Ok(params)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/analytics/summary",
+    params(
+        ("owner_id" = Option<i32>, Query, description = "Owner to report on (admins only)"),
+        ("event_id" = Option<i32>, Query, description = "Only tickets bought for this event"),
+        ("packet_id" = Option<i32>, Query, description = "Only tickets bought for this packet"),
+        ("from" = Option<String>, Query, description = "Tickets sold at or after this RFC 3339 instant"),
+        ("to" = Option<String>, Query, description = "Tickets sold before this RFC 3339 instant")
+    ),
+    responses(
+        (status = 200, description = "Tickets sold, refunded and checked in", body = SalesSummary),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only event owners and admins can view analytics"),
+        (status = 404, description = "Event or packet not found"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Analytics",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_summary(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Json<SalesSummary>, ApiError> {
+    let params = scope_query(&state, &user_claims, params).await?;
+
+    let summary = state.analytics_repo.get_summary(&params).await?;
+
+    Ok(Json(summary))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/analytics/sales",
+    params(
+        ("owner_id" = Option<i32>, Query, description = "Owner to report on (admins only)"),
+        ("event_id" = Option<i32>, Query, description = "Only tickets bought for this event"),
+        ("packet_id" = Option<i32>, Query, description = "Only tickets bought for this packet"),
+        ("from" = Option<String>, Query, description = "Tickets sold at or after this RFC 3339 instant"),
+        ("to" = Option<String>, Query, description = "Tickets sold before this RFC 3339 instant"),
+        ("granularity" = Option<String>, Query, description = "Bucket size: day (default), week or month, in UTC")
+    ),
+    responses(
+        (status = 200, description = "Tickets sold and gross revenue per period and currency, oldest first", body = [SalesBucket]),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only event owners and admins can view analytics"),
+        (status = 404, description = "Event or packet not found"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Analytics",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_sales(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Json<Vec<SalesBucket>>, ApiError> {
+    let params = scope_query(&state, &user_claims, params).await?;
+
+    let buckets = state.analytics_repo.get_sales(&params).await?;
+
+    Ok(Json(buckets))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/analytics/events",
+    params(
+        ("owner_id" = Option<i32>, Query, description = "Owner to report on (admins only)"),
+        ("event_id" = Option<i32>, Query, description = "Only this event"),
+        ("packet_id" = Option<i32>, Query, description = "Only the events of this packet")
+    ),
+    responses(
+        (status = 200, description = "Sell-through, refund and check-in rates per event since sales opened", body = [SellThrough]),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only event owners and admins can view analytics"),
+        (status = 404, description = "Event or packet not found"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Analytics",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_event_sell_through(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Json<Vec<SellThrough>>, ApiError> {
+    let params = scope_query(&state, &user_claims, params).await?;
+
+    let events = state.analytics_repo.get_event_sell_through(&params).await?;
+
+    Ok(Json(events))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/analytics/event-packets",
+    params(
+        ("owner_id" = Option<i32>, Query, description = "Owner to report on (admins only)"),
+        ("packet_id" = Option<i32>, Query, description = "Only this packet"),
+        ("event_id" = Option<i32>, Query, description = "Only the packets containing this event")
+    ),
+    responses(
+        (status = 200, description = "Sell-through, refund and check-in rates per packet since sales opened", body = [SellThrough]),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only event owners and admins can view analytics"),
+        (status = 404, description = "Event or packet not found"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Analytics",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_packet_sell_through(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Json<Vec<SellThrough>>, ApiError> {
+    let params = scope_query(&state, &user_claims, params).await?;
+
+    let packets = state
+        .analytics_repo
+        .get_packet_sell_through(&params)
+        .await?;
+
+    Ok(Json(packets))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/analytics/revenue",
+    params(
+        ("owner_id" = Option<i32>, Query, description = "Owner to report on (admins only)"),
+        ("event_id" = Option<i32>, Query, description = "Only tickets bought for this event"),
+        ("packet_id" = Option<i32>, Query, description = "Only tickets bought for this packet"),
+        ("from" = Option<String>, Query, description = "Tickets sold at or after this RFC 3339 instant"),
+        ("to" = Option<String>, Query, description = "Tickets sold before this RFC 3339 instant")
+    ),
+    responses(
+        (status = 200, description = "Revenue per price tier, net of approved refunds", body = [TierRevenue]),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only event owners and admins can view analytics"),
+        (status = 404, description = "Event or packet not found"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Analytics",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_tier_revenue(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Json<Vec<TierRevenue>>, ApiError> {
+    let params = scope_query(&state, &user_claims, params).await?;
+
+    let tiers = state.analytics_repo.get_tier_revenue(&params).await?;
+
+    Ok(Json(tiers))
+}
@@ -0,0 +1,159 @@
+use crate::AppState;
+use crate::middleware::UserClaims;
+use crate::models::event::EventStatus;
+use crate::models::ticket::TicketCalendarRequest;
+use crate::utils::error::{ApiError, EventPacketRepoError, EventRepoError};
+use crate::utils::ical::{CALENDAR_CONTENT_TYPE, render_calendar};
+use axum::extract::rejection::JsonRejection;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    routing::{get, post},
+};
+use std::sync::Arc;
+use validator::Validate;
+
+pub fn public_calendar_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/events/{id}/calendar", get(get_event_calendar))
+        .route(
+            "/event-packets/{id}/calendar",
+            get(get_event_packet_calendar),
+        )
+}
+
+pub fn calendar_router() -> Router<Arc<AppState>> {
+    Router::new().route("/tickets/calendar", post(get_tickets_calendar))
+}
+
+fn calendar_attachment(body: String, filename: String) -> impl IntoResponse {
+    (
+        [
+            (header::CONTENT_TYPE, CALENDAR_CONTENT_TYPE.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/events/{id}/calendar",
+    params(
+        ("id" = i32, Path, description = "Event ID")
+    ),
+    responses(
+        (status = 200, description = "iCalendar file with the event", content_type = "text/calendar", body = String),
+        (status = 404, description = "Event not found"),
+        (status = 409, description = "The event has no start time yet")
+    ),
+    tag = "Calendar"
+)]
+pub async fn get_event_calendar(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let event = state.event_repo.get_event(id).await?;
+    if event.status == EventStatus::Draft {
+        return Err(EventRepoError::NotFound.into());
+    }
+    if event.starts_at.is_none() {
+        return Err(ApiError::Conflict(
+            "The event has no start time to put in a calendar".to_string(),
+        ));
+    }
+
+    let name = event.nume.clone();
+    let body = render_calendar(&name, &[event], &state.base_url);
+
+    Ok(calendar_attachment(body, format!("event-{}.ics", id)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/event-packets/{id}/calendar",
+    params(
+        ("id" = i32, Path, description = "Event packet ID")
+    ),
+    responses(
+        (status = 200, description = "iCalendar file with every scheduled event of the packet", content_type = "text/calendar", body = String),
+        (status = 404, description = "Event packet not found")
+    ),
+    tag = "Calendar"
+)]
+pub async fn get_event_packet_calendar(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let packet = state.event_packet_repo.get_event_packet(id).await?;
+    if packet.status == EventStatus::Draft {
+        return Err(EventPacketRepoError::NotFound.into());
+    }
+
+    let mut events: Vec<_> = state
+        .join_repo
+        .get_events_for_packet(id)
+        .await?
+        .into_iter()
+        .filter(|event| event.status != EventStatus::Draft)
+        .collect();
+    events.sort_by_key(|event| (event.starts_at, event.id));
+
+    let body = render_calendar(&packet.nume, &events, &state.base_url);
+
+    Ok(calendar_attachment(
+        body,
+        format!("event-packet-{}.ics", id),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/tickets/calendar",
+    request_body = TicketCalendarRequest,
+    responses(
+        (status = 200, description = "iCalendar feed with the events the tickets admit to", content_type = "text/calendar", body = String),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only admins and clients-service can build ticket calendars"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Calendar",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_tickets_calendar(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    payload: Result<Json<TicketCalendarRequest>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    if !user_claims.is_admin() && !user_claims.is_clients_service() {
+        return Err(ApiError::Forbidden(
+            "Only admins can build ticket calendars".to_string(),
+        ));
+    }
+
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    let events = state
+        .event_repo
+        .list_events_for_tickets(&payload.codes)
+        .await?;
+    let body = render_calendar("SweatFindr tickets", &events, &state.base_url);
+
+    Ok(([(header::CONTENT_TYPE, CALENDAR_CONTENT_TYPE)], body))
+}
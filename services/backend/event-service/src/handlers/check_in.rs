@@ -0,0 +1,254 @@
+use crate::AppState;
+use crate::middleware::{Authorization, UserClaims};
+use crate::models::check_in::{AddScanner, Attendance, CheckIn, CheckInRequest, EventScanner};
+use crate::models::event::{Event, EventStatus};
+use crate::utils::error::{ApiError, map_authorization_error};
+use axum::extract::rejection::JsonRejection;
+use axum::response::IntoResponse;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+};
+use common::ticket_pass::TicketPassError;
+use std::sync::Arc;
+use tracing::info;
+use validator::Validate;
+
+pub fn check_in_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/events/{id}/check-ins", post(check_in_ticket))
+        .route("/events/{id}/attendance", get(get_attendance))
+        .route(
+            "/events/{id}/scanners",
+            get(list_scanners).post(add_scanner),
+        )
+        .route("/events/{id}/scanners/{user_id}", delete(remove_scanner))
+}
+
+/// Owners and admins can scan any ticket of their events; scanner accounts
+/// only the events they were assigned to.
+async fn authorize_scanner(
+    state: &AppState,
+    user_claims: &UserClaims,
+    event: &Event,
+) -> Result<(), ApiError> {
+    if Authorization::can_modify_resource(user_claims, event, None).is_ok() {
+        return Ok(());
+    }
+
+    if state
+        .check_in_repo
+        .is_scanner(event.id, user_claims.user_id)
+        .await?
+    {
+        return Ok(());
+    }
+
+    Err(ApiError::Forbidden(
+        "Only the event owner or its scanners can check tickets in".to_string(),
+    ))
+}
+
+fn ticket_code_from_pass(state: &AppState, pass: &str) -> Result<String, ApiError> {
+    let verifier = state.ticket_verifier.as_ref().ok_or_else(|| {
+        ApiError::BadRequest("Ticket passes cannot be verified, scan the ticket code".to_string())
+    })?;
+
+    match verifier.verify(pass) {
+        Ok(pass) => Ok(pass.cod),
+        Err(TicketPassError::InvalidSignature) => Err(ApiError::BadRequest(
+            "Ticket signature is not valid".to_string(),
+        )),
+        Err(TicketPassError::Malformed(msg)) | Err(TicketPassError::InvalidKey(msg)) => Err(
+            ApiError::BadRequest(format!("Malformed ticket pass: {}", msg)),
+        ),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/events/{id}/check-ins",
+    params(
+        ("id" = i32, Path, description = "ID of the event being entered")
+    ),
+    request_body = CheckInRequest,
+    responses(
+        (status = 201, description = "Ticket checked in", body = CheckIn),
+        (status = 400, description = "Invalid ticket pass"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the event owner or its scanners can check tickets in"),
+        (status = 404, description = "Event or ticket not found"),
+        (status = 409, description = "Ticket already checked in, refunded, or not valid for this event"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Check-in",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn check_in_ticket(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    payload: Result<Json<CheckInRequest>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    let event = state.event_repo.get_event(id).await?;
+    authorize_scanner(&state, &user_claims, &event).await?;
+
+    if matches!(event.status, EventStatus::Draft | EventStatus::Cancelled) {
+        return Err(ApiError::Conflict(format!(
+            "Cannot check tickets in for an event that is {}",
+            event.status
+        )));
+    }
+
+    let ticket_cod = match (payload.cod, payload.pass) {
+        (Some(cod), _) => cod,
+        (None, Some(pass)) => ticket_code_from_pass(&state, &pass)?,
+        (None, None) => unreachable!("validated above"),
+    };
+
+    let check_in = state
+        .check_in_repo
+        .check_in(id, &ticket_cod, &payload.gate, user_claims.user_id)
+        .await?;
+
+    info!(
+        "Checked in ticket {} for event {} at gate {}",
+        check_in.ticket_cod, id, check_in.gate
+    );
+
+    Ok((StatusCode::CREATED, Json(check_in)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/events/{id}/attendance",
+    params(
+        ("id" = i32, Path, description = "ID of the event")
+    ),
+    responses(
+        (status = 200, description = "Live check-in counts", body = Attendance),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the event owner or its scanners can view attendance"),
+        (status = 404, description = "Event not found")
+    ),
+    tag = "Check-in",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_attendance(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<Json<Attendance>, ApiError> {
+    let event = state.event_repo.get_event(id).await?;
+    authorize_scanner(&state, &user_claims, &event).await?;
+
+    Ok(Json(state.check_in_repo.attendance(id).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/events/{id}/scanners",
+    params(
+        ("id" = i32, Path, description = "ID of the event")
+    ),
+    responses(
+        (status = 200, description = "Accounts allowed to check tickets in", body = [EventScanner]),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the event owner can manage scanners"),
+        (status = 404, description = "Event not found")
+    ),
+    tag = "Check-in",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn list_scanners(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<Json<Vec<EventScanner>>, ApiError> {
+    let event = state.event_repo.get_event(id).await?;
+    Authorization::can_modify_resource(&user_claims, &event, None)
+        .map_err(map_authorization_error)?;
+
+    Ok(Json(state.check_in_repo.list_scanners(id).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/events/{id}/scanners",
+    params(
+        ("id" = i32, Path, description = "ID of the event")
+    ),
+    request_body = AddScanner,
+    responses(
+        (status = 201, description = "Scanner added", body = EventScanner),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the event owner can manage scanners"),
+        (status = 404, description = "Event not found"),
+        (status = 409, description = "User is already a scanner"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Check-in",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn add_scanner(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    payload: Result<Json<AddScanner>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    let event = state.event_repo.get_event(id).await?;
+    Authorization::can_modify_resource(&user_claims, &event, None)
+        .map_err(map_authorization_error)?;
+
+    let scanner = state.check_in_repo.add_scanner(id, payload.user_id).await?;
+
+    Ok((StatusCode::CREATED, Json(scanner)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/event-manager/events/{id}/scanners/{user_id}",
+    params(
+        ("id" = i32, Path, description = "ID of the event"),
+        ("user_id" = i32, Path, description = "ID of the scanner account")
+    ),
+    responses(
+        (status = 204, description = "Scanner removed"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the event owner can manage scanners"),
+        (status = 404, description = "Event or scanner not found")
+    ),
+    tag = "Check-in",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn remove_scanner(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path((id, user_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, ApiError> {
+    let event = state.event_repo.get_event(id).await?;
+    Authorization::can_modify_resource(&user_claims, &event, None)
+        .map_err(map_authorization_error)?;
+
+    state.check_in_repo.remove_scanner(id, user_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
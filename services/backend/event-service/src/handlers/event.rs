@@ -1,8 +1,11 @@
 use crate::AppState;
-use crate::handlers::ticket;
+use crate::handlers::{promo_code, ticket};
 use crate::middleware::{Authorization, UserClaims};
-use crate::models::event::{CreateEvent, Event, EventQuery, PatchEvent, UpdateEvent};
-use crate::utils::error::{ApiError, map_authorization_error};
+use crate::models::event::{
+    CreateEvent, Event, EventQuery, EventStatus, Facets, PatchEvent, UpdateEvent,
+};
+use crate::models::venue::VenueRoom;
+use crate::utils::error::{ApiError, EventRepoError, VenueRepoError, map_authorization_error};
 use crate::utils::links::{Response, build_filtered_event, build_simple_event};
 use axum::extract::rejection::JsonRejection;
 use axum::response::IntoResponse;
@@ -18,6 +21,10 @@ use validator::Validate;
 pub fn event_manager_router() -> Router<Arc<AppState>> {
     Router::new()
         .route("/events", post(create_event))
+        .route("/events/owned", get(list_owned_events))
+        .route("/events/{id}/publish", post(publish_event))
+        .route("/events/{id}/close-sales", post(close_event_sales))
+        .route("/events/{id}/cancel", post(cancel_event))
         .route(
             "/events/{id}",
             put(update_event)
@@ -29,16 +36,30 @@ pub fn event_manager_router() -> Router<Arc<AppState>> {
             get(ticket::list_tickets_for_event)
                 .post(ticket::create_ticket_for_event),
         )
+        .route("/events/{id}/tickets/batch", post(ticket::create_tickets_for_event))
         .route(
             "/events/{id}/tickets/{cod}",
             get(ticket::get_ticket_for_event)
                 .delete(ticket::delete_ticket_for_event),
         )
+        .route(
+            "/events/{id}/promo-codes",
+            get(promo_code::list_promo_codes_for_event)
+                .post(promo_code::create_promo_code_for_event),
+        )
+        .route(
+            "/promo-codes/{id}",
+            get(promo_code::get_promo_code)
+                .patch(promo_code::update_promo_code)
+                .delete(promo_code::delete_promo_code),
+        )
+        .route("/promo-codes/{id}/stats", get(promo_code::get_promo_code_stats))
 }
 
 pub fn public_event_router() -> Router<Arc<AppState>> {
     Router::new()
         .route("/events", get(list_events))
+        .route("/events/facets", get(list_event_facets))
         .route("/events/{id}", get(get_event))
 }
 
@@ -47,7 +68,18 @@ pub fn public_event_router() -> Router<Arc<AppState>> {
     path = "/api/event-manager/events",
     params(
         ("location" = Option<String>, Query, description = "Filter by location of the event"),
-        ("name" = Option<String>, Query, description = "Filter by event name")
+        ("name" = Option<String>, Query, description = "Filter by event name"),
+        ("from" = Option<String>, Query, description = "Only events still running at or after this RFC 3339 instant"),
+        ("to" = Option<String>, Query, description = "Only events starting at or before this RFC 3339 instant"),
+        ("upcoming_only" = Option<bool>, Query, description = "Only events that have not started yet"),
+        ("category" = Option<String>, Query, description = "Comma-separated categories; matches any of them"),
+        ("tags" = Option<String>, Query, description = "Comma-separated tags; matches any of them"),
+        ("near" = Option<String>, Query, description = "`lat,lng` point; adds `distance_km` and sorts nearest first"),
+        ("radius_km" = Option<f64>, Query, description = "Only results within this many km of `near`"),
+        ("page" = Option<i64>, Query, description = "Page number for offset paging; ignored when `cursor` is given"),
+        ("items_per_page" = Option<i64>, Query, description = "Items per page (1-100)"),
+        ("cursor" = Option<String>, Query, description = "Opaque position from a `next`/`prev` link or the `X-Next-Cursor` header"),
+        ("include_total" = Option<bool>, Query, description = "Return the number of matching items in `X-Total-Count`")
     ),
     responses(
         (status = 200, description = "List events (optionally filtered by location or name)", body = [Response<Event>]),
@@ -65,24 +97,71 @@ pub async fn list_events(
 ) -> Result<impl IntoResponse, ApiError> {
     params.validate()?;
 
-    let events: Vec<Event> = state.event_repo.list_events(params.clone()).await?;
+    let events = state
+        .event_repo
+        .list_events(params.clone(), Some(EventStatus::Published))
+        .await?;
+    let headers = events.headers();
 
     let has_filters = params.locatie.is_some()
         || params.nume.is_some()
         || params.owner_id.is_some()
+        || params.from.is_some()
+        || params.to.is_some()
+        || params.upcoming_only.is_some()
+        || params.category.is_some()
+        || params.tags.is_some()
+        || params.near.is_some()
         || params.paginare.page.is_some()
-        || params.paginare.items_per_page.is_some();
+        || params.paginare.items_per_page.is_some()
+        || params.paginare.cursor.is_some()
+        || events.has_more_pages();
 
     let response: Vec<Response<Event>> = if has_filters {
         build_filtered_event(events, &params, &state.base_url)
     } else {
         events
+            .items
             .into_iter()
             .map(|event| build_simple_event(event, &state.base_url))
             .collect()
     };
 
-    Ok((StatusCode::OK, Json(response)))
+    Ok((StatusCode::OK, headers, Json(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/events/facets",
+    params(
+        ("location" = Option<String>, Query, description = "Filter by location of the event"),
+        ("name" = Option<String>, Query, description = "Filter by event name"),
+        ("from" = Option<String>, Query, description = "Only events still running at or after this RFC 3339 instant"),
+        ("to" = Option<String>, Query, description = "Only events starting at or before this RFC 3339 instant"),
+        ("upcoming_only" = Option<bool>, Query, description = "Only events that have not started yet"),
+        ("category" = Option<String>, Query, description = "Comma-separated categories; matches any of them"),
+        ("tags" = Option<String>, Query, description = "Comma-separated tags; matches any of them"),
+        ("near" = Option<String>, Query, description = "`lat,lng` point; adds `distance_km` and sorts nearest first"),
+        ("radius_km" = Option<f64>, Query, description = "Only results within this many km of `near`")
+    ),
+    responses(
+        (status = 200, description = "Category and tag counts of published events matching the filters", body = Facets),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Events"
+)]
+pub async fn list_event_facets(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<EventQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    params.validate()?;
+
+    let facets = state
+        .event_repo
+        .event_facets(&params, Some(EventStatus::Published))
+        .await?;
+
+    Ok(Json(facets))
 }
 
 #[utoipa::path(
@@ -109,12 +188,183 @@ pub async fn get_event(
         return Err(ApiError::BadRequest("ID cannot be negative".into()));
     }
     let event = state.event_repo.get_event(id).await?;
+    if event.status == EventStatus::Draft {
+        return Err(EventRepoError::NotFound.into());
+    }
 
     let event_response = build_simple_event(event, &state.base_url);
 
     Ok(Json(event_response))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/events/owned",
+    params(
+        ("location" = Option<String>, Query, description = "Filter by location of the event"),
+        ("name" = Option<String>, Query, description = "Filter by event name"),
+        ("owner_id" = Option<i32>, Query, description = "Filter by owner (admins only)"),
+        ("from" = Option<String>, Query, description = "Only events still running at or after this RFC 3339 instant"),
+        ("to" = Option<String>, Query, description = "Only events starting at or before this RFC 3339 instant"),
+        ("upcoming_only" = Option<bool>, Query, description = "Only events that have not started yet"),
+        ("category" = Option<String>, Query, description = "Comma-separated categories; matches any of them"),
+        ("tags" = Option<String>, Query, description = "Comma-separated tags; matches any of them"),
+        ("near" = Option<String>, Query, description = "`lat,lng` point; adds `distance_km` and sorts nearest first"),
+        ("radius_km" = Option<f64>, Query, description = "Only results within this many km of `near`"),
+        ("page" = Option<i64>, Query, description = "Page number for offset paging; ignored when `cursor` is given"),
+        ("items_per_page" = Option<i64>, Query, description = "Items per page (1-100)"),
+        ("cursor" = Option<String>, Query, description = "Opaque position from a `next`/`prev` link or the `X-Next-Cursor` header"),
+        ("include_total" = Option<bool>, Query, description = "Return the number of matching items in `X-Total-Count`")
+    ),
+    responses(
+        (status = 200, description = "Events of the caller in every lifecycle state", body = [Response<Event>]),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Requires owner-event role or admin")
+    ),
+    tag = "Events",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn list_owned_events(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Query(mut params): Query<EventQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    Authorization::require_owner_event_or_admin(&user_claims).map_err(map_authorization_error)?;
+    params.validate()?;
+
+    if !user_claims.is_admin() {
+        params.owner_id = Some(user_claims.user_id);
+    }
+
+    let events = state.event_repo.list_events(params, None).await?;
+    let headers = events.headers();
+
+    let response: Vec<Response<Event>> = events
+        .items
+        .into_iter()
+        .map(|event| build_simple_event(event, &state.base_url))
+        .collect();
+
+    Ok((headers, Json(response)))
+}
+
+async fn transition_event(
+    state: &AppState,
+    user_claims: &UserClaims,
+    id: i32,
+    next: EventStatus,
+) -> Result<Event, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let event = state.event_repo.get_event(id).await?;
+    Authorization::can_modify_resource(user_claims, &event, None)
+        .map_err(map_authorization_error)?;
+
+    if !event.status.can_transition_to(next) {
+        return Err(ApiError::Conflict(format!(
+            "Cannot move an event from {} to {}",
+            event.status, next
+        )));
+    }
+
+    if next == EventStatus::Cancelled {
+        return Ok(state.event_repo.cancel_event(id, event.status).await?);
+    }
+
+    Ok(state
+        .event_repo
+        .set_event_status(id, event.status, next)
+        .await?)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/events/{id}/publish",
+    params(
+        ("id" = i32, Path, description = "ID of the event to publish")
+    ),
+    responses(
+        (status = 200, description = "Event published", body = Response<Event>),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only event owner or admin can publish"),
+        (status = 404, description = "Event not found"),
+        (status = 409, description = "Event cannot be published from its current state")
+    ),
+    tag = "Events",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn publish_event(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    let event = transition_event(&state, &user_claims, id, EventStatus::Published).await?;
+
+    Ok(Json(build_simple_event(event, &state.base_url)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/events/{id}/close-sales",
+    params(
+        ("id" = i32, Path, description = "ID of the event to close sales for")
+    ),
+    responses(
+        (status = 200, description = "Ticket sales closed", body = Response<Event>),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only event owner or admin can close sales"),
+        (status = 404, description = "Event not found"),
+        (status = 409, description = "Only published events can close sales")
+    ),
+    tag = "Events",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn close_event_sales(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    let event = transition_event(&state, &user_claims, id, EventStatus::SalesClosed).await?;
+
+    Ok(Json(build_simple_event(event, &state.base_url)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/events/{id}/cancel",
+    params(
+        ("id" = i32, Path, description = "ID of the event to cancel")
+    ),
+    responses(
+        (status = 200, description = "Event cancelled", body = Response<Event>),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only event owner or admin can cancel"),
+        (status = 404, description = "Event not found"),
+        (status = 409, description = "Event is already cancelled")
+    ),
+    tag = "Events",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn cancel_event(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    let event = transition_event(&state, &user_claims, id, EventStatus::Cancelled).await?;
+
+    Ok(Json(build_simple_event(event, &state.base_url)))
+}
+
 #[utoipa::path(
     put,
     path = "/api/event-manager/events/{id}",
@@ -150,6 +400,17 @@ pub async fn update_event(
     let Json(payload) = payload?;
     payload.validate()?;
 
+    if payload.room_id.is_some_and(|room_id| Some(room_id) != existing_event.room_id)
+        && state.seat_repo.has_inventory(id).await?
+    {
+        return Err(ApiError::Conflict(
+            "Remove the seat inventory before moving a seated event to another room".to_string(),
+        ));
+    }
+    if let Some(room_id) = payload.room_id.or(existing_event.room_id) {
+        check_room_capacity(&state, room_id, payload.locuri.or(existing_event.locuri)).await?;
+    }
+
     state.event_repo.update_event(id, payload).await?;
 
     Ok(StatusCode::NO_CONTENT)
@@ -190,12 +451,68 @@ pub async fn patch_event(
     let Json(payload) = payload?;
     payload.validate()?;
 
+    if payload.room_id.is_some_and(|room_id| Some(room_id) != existing_event.room_id)
+        && state.seat_repo.has_inventory(id).await?
+    {
+        return Err(ApiError::Conflict(
+            "Remove the seat inventory before moving a seated event to another room".to_string(),
+        ));
+    }
+    if let Some(room_id) = payload.room_id.or(existing_event.room_id) {
+        check_room_capacity(&state, room_id, payload.locuri.or(existing_event.locuri)).await?;
+    }
+
     let event = state.event_repo.patch_event(id, payload).await?;
     let event_response = build_simple_event(event, &state.base_url);
 
     Ok(Json(event_response))
 }
 
+/// An event cannot offer more seats than the room it is held in.
+async fn check_room_capacity(
+    state: &AppState,
+    room_id: i32,
+    seats: Option<i32>,
+) -> Result<VenueRoom, ApiError> {
+    let room = state.venue_repo.get_room(room_id).await.map_err(|e| match e {
+        VenueRepoError::NotFound => ApiError::BadRequest(format!("Room {} does not exist", room_id)),
+        e => e.into(),
+    })?;
+
+    if seats.is_some_and(|seats| seats > room.capacity) {
+        return Err(ApiError::BadRequest(format!(
+            "Room '{}' only holds {} people",
+            room.name, room.capacity
+        )));
+    }
+
+    Ok(room)
+}
+
+/// Checks the room of a new event and fills in its capacity, location and
+/// coordinates from the room and venue where the payload leaves them out.
+pub(crate) async fn apply_room_defaults(
+    state: &AppState,
+    payload: &mut CreateEvent,
+) -> Result<(), ApiError> {
+    let Some(room_id) = payload.room_id else {
+        return Ok(());
+    };
+
+    let room = check_room_capacity(state, room_id, payload.locuri).await?;
+    payload.locuri.get_or_insert(room.capacity);
+    if payload.locatie.is_none() || payload.latitude.is_none() {
+        let venue = state.venue_repo.get_venue(room.venue_id).await?;
+        payload.locatie.get_or_insert_with(|| venue.location());
+        if payload.latitude.is_none() {
+            payload.latitude = venue.latitude;
+            payload.longitude = venue.longitude;
+        }
+    }
+
+    Ok(())
+}
+
 #[utoipa::path(
     post,
     path = "/api/event-manager/events",
@@ -217,8 +534,9 @@ pub async fn create_event(
 ) -> Result<impl IntoResponse, ApiError> {
     Authorization::require_owner_event_or_admin(&user_claims).map_err(map_authorization_error)?;
 
-    let Json(payload) = payload?;
+    let Json(mut payload) = payload?;
     payload.validate()?;
+    apply_room_defaults(&state, &mut payload).await?;
 
     let event = state
         .event_repo
@@ -237,11 +555,12 @@ pub async fn create_event(
         ("id" = i32, Path, description = "ID of the event to delete")
     ),
     responses(
+        (status = 202, description = "Event has sold tickets; it was cancelled and buyers are being refunded, retry once refunds complete"),
         (status = 204, description = "Event deleted successfully"),
         (status = 401, description = "Missing or invalid authentication token"),
         (status = 403, description = "Forbidden - Only event owner or admin can delete"),
         (status = 404, description = "Event not found"),
-        (status = 409, description = "Cannot delete event with sold tickets")
+        (status = 409, description = "Only checked-in tickets are left; they are kept, and so is the event")
     ),
     tag = "Events",
     security(
@@ -260,12 +579,23 @@ pub async fn delete_event(
         return Err(ApiError::BadRequest("ID cannot be negative".into()));
     }
 
-    let ticket_count = state.ticket_repo.count_tickets_for_event(id).await?;
-    if ticket_count > 0 {
-        return Err(ApiError::Conflict(format!(
-            "Cannot delete event with {} sold ticket(s). Please cancel the tickets first.",
-            ticket_count
-        )));
+    let tickets = state.ticket_repo.list_tickets_for_event(id).await?;
+    if !tickets.is_empty() {
+        if tickets.iter().all(|t| t.checked_in_at.is_some()) {
+            return Err(ApiError::Conflict(
+                "The event has checked-in tickets, which are kept as its attendance record."
+                    .to_string(),
+            ));
+        }
+
+        if existing_event.status != EventStatus::Cancelled {
+            state
+                .event_repo
+                .cancel_event(id, existing_event.status)
+                .await?;
+        }
+
+        return Ok(StatusCode::ACCEPTED);
     }
 
     state
@@ -1,10 +1,11 @@
 use crate::AppState;
-use crate::handlers::ticket;
+use crate::handlers::{promo_code, ticket};
 use crate::middleware::{Authorization, UserClaims};
+use crate::models::event::{EventStatus, Facets};
 use crate::models::event_packets::{
     CreateEventPacket, EventPacketQuery, EventPackets, PatchEventPacket, UpdateEventPacket,
 };
-use crate::utils::error::{ApiError, map_authorization_error};
+use crate::utils::error::{ApiError, EventPacketRepoError, map_authorization_error};
 use crate::utils::links::{Response, build_filtered_event_packets, build_simple_event_packet};
 use axum::extract::Query;
 use axum::extract::rejection::JsonRejection;
@@ -21,6 +22,10 @@ use validator::Validate;
 pub fn event_packet_manager_router() -> Router<Arc<AppState>> {
     Router::new()
         .route("/event-packets", post(create_event_packet))
+        .route("/event-packets/owned", get(list_owned_event_packets))
+        .route("/event-packets/{id}/publish", post(publish_event_packet))
+        .route("/event-packets/{id}/close-sales", post(close_event_packet_sales))
+        .route("/event-packets/{id}/cancel", post(cancel_event_packet))
         .route(
             "/event-packets/{id}",
             put(update_event_packet)
@@ -32,16 +37,23 @@ pub fn event_packet_manager_router() -> Router<Arc<AppState>> {
             get(ticket::list_tickets_for_packet)
                 .post(ticket::create_ticket_for_packet),
         )
+        .route("/event-packets/{id}/tickets/batch", post(ticket::create_tickets_for_packet))
         .route(
             "/event-packets/{id}/tickets/{ticket_cod}",
             get(ticket::get_ticket_for_packet)
                 .delete(ticket::delete_ticket_for_packet),
         )
+        .route(
+            "/event-packets/{id}/promo-codes",
+            get(promo_code::list_promo_codes_for_packet)
+                .post(promo_code::create_promo_code_for_packet),
+        )
 }
 
 pub fn public_event_packet_router() -> Router<Arc<AppState>> {
     Router::new()
         .route("/event-packets", get(list_event_packets))
+        .route("/event-packets/facets", get(list_event_packet_facets))
         .route("/event-packets/{id}", get(get_event_packet))
 }
 
@@ -51,8 +63,14 @@ pub fn public_event_packet_router() -> Router<Arc<AppState>> {
     params(
         ("type" = Option<String>, Query, description = "Filter event packets by description/type"),
         ("available_tickets" = Option<i32>, Query, description = "Filter event packets by available tickets"),
-        ("page" = Option<i64>, Query, description = "Pagination page number"),
-        ("items_per_page" = Option<i64>, Query, description = "Items per page for pagination")
+        ("category" = Option<String>, Query, description = "Comma-separated categories; matches any of them"),
+        ("tags" = Option<String>, Query, description = "Comma-separated tags; matches any of them"),
+        ("near" = Option<String>, Query, description = "`lat,lng` point; adds `distance_km` and sorts nearest first"),
+        ("radius_km" = Option<f64>, Query, description = "Only results within this many km of `near`"),
+        ("page" = Option<i64>, Query, description = "Page number for offset paging; ignored when `cursor` is given"),
+        ("items_per_page" = Option<i64>, Query, description = "Items per page (1-100)"),
+        ("cursor" = Option<String>, Query, description = "Opaque position from a `next`/`prev` link or the `X-Next-Cursor` header"),
+        ("include_total" = Option<bool>, Query, description = "Return the number of matching items in `X-Total-Count`")
     ),
     responses(
         (status = 200, description = "List all event packets (optionally filtered)", body = [Response<EventPackets>]),
@@ -72,24 +90,62 @@ pub async fn list_event_packets(
 
     let event_packets = state
         .event_packet_repo
-        .list_event_packets(params.clone())
+        .list_event_packets(params.clone(), None, Some(EventStatus::Published))
         .await?;
+    let headers = event_packets.headers();
 
     let has_filters = params.descriere.is_some()
         || params.bilete.is_some()
+        || params.category.is_some()
+        || params.tags.is_some()
+        || params.near.is_some()
         || params.paginare.page.is_some()
-        || params.paginare.items_per_page.is_some();
+        || params.paginare.items_per_page.is_some()
+        || params.paginare.cursor.is_some()
+        || event_packets.has_more_pages();
 
     let response: Vec<Response<EventPackets>> = if has_filters {
         build_filtered_event_packets(event_packets, &params, &state.base_url)
     } else {
         event_packets
+            .items
             .into_iter()
             .map(|e| build_simple_event_packet(e, &state.base_url))
             .collect()
     };
 
-    Ok(Json(response))
+    Ok((StatusCode::OK, headers, Json(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/event-packets/facets",
+    params(
+        ("type" = Option<String>, Query, description = "Filter event packets by description/type"),
+        ("available_tickets" = Option<i32>, Query, description = "Filter event packets by available tickets"),
+        ("category" = Option<String>, Query, description = "Comma-separated categories; matches any of them"),
+        ("tags" = Option<String>, Query, description = "Comma-separated tags; matches any of them"),
+        ("near" = Option<String>, Query, description = "`lat,lng` point; adds `distance_km` and sorts nearest first"),
+        ("radius_km" = Option<f64>, Query, description = "Only results within this many km of `near`"),
+    ),
+    responses(
+        (status = 200, description = "Category and tag counts of published packets matching the filters", body = Facets),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Event Packets"
+)]
+pub async fn list_event_packet_facets(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<EventPacketQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    params.validate()?;
+
+    let facets = state
+        .event_packet_repo
+        .event_packet_facets(&params, Some(EventStatus::Published))
+        .await?;
+
+    Ok(Json(facets))
 }
 
 #[utoipa::path(
@@ -114,11 +170,177 @@ pub async fn get_event_packet(
         return Err(ApiError::BadRequest("ID cannot be negative".into()));
     }
     let event_packet = state.event_packet_repo.get_event_packet(id).await?;
+    if event_packet.status == EventStatus::Draft {
+        return Err(EventPacketRepoError::NotFound.into());
+    }
 
     let packet_response = build_simple_event_packet(event_packet, &state.base_url);
     Ok(Json(packet_response))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/event-packets/owned",
+    params(
+        ("type" = Option<String>, Query, description = "Filter event packets by description/type"),
+        ("available_tickets" = Option<i32>, Query, description = "Filter event packets by available tickets"),
+        ("category" = Option<String>, Query, description = "Comma-separated categories; matches any of them"),
+        ("tags" = Option<String>, Query, description = "Comma-separated tags; matches any of them"),
+        ("near" = Option<String>, Query, description = "`lat,lng` point; adds `distance_km` and sorts nearest first"),
+        ("radius_km" = Option<f64>, Query, description = "Only results within this many km of `near`"),
+        ("page" = Option<i64>, Query, description = "Page number for offset paging; ignored when `cursor` is given"),
+        ("items_per_page" = Option<i64>, Query, description = "Items per page (1-100)"),
+        ("cursor" = Option<String>, Query, description = "Opaque position from a `next`/`prev` link or the `X-Next-Cursor` header"),
+        ("include_total" = Option<bool>, Query, description = "Return the number of matching items in `X-Total-Count`")
+    ),
+    responses(
+        (status = 200, description = "Event packets of the caller in every lifecycle state", body = [Response<EventPackets>]),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Requires owner-event role or admin")
+    ),
+    tag = "Event Packets",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn list_owned_event_packets(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Query(params): Query<EventPacketQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    Authorization::require_owner_event_or_admin(&user_claims).map_err(map_authorization_error)?;
+    params.validate()?;
+
+    let owner_id = (!user_claims.is_admin()).then_some(user_claims.user_id);
+
+    let event_packets = state
+        .event_packet_repo
+        .list_event_packets(params, owner_id, None)
+        .await?;
+    let headers = event_packets.headers();
+
+    let response: Vec<Response<EventPackets>> = event_packets
+        .items
+        .into_iter()
+        .map(|e| build_simple_event_packet(e, &state.base_url))
+        .collect();
+
+    Ok((headers, Json(response)))
+}
+
+async fn transition_event_packet(
+    state: &AppState,
+    user_claims: &UserClaims,
+    id: i32,
+    next: EventStatus,
+) -> Result<EventPackets, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let packet = state.event_packet_repo.get_event_packet(id).await?;
+    Authorization::can_modify_resource(user_claims, &packet, None)
+        .map_err(map_authorization_error)?;
+
+    if !packet.status.can_transition_to(next) {
+        return Err(ApiError::Conflict(format!(
+            "Cannot move an event packet from {} to {}",
+            packet.status, next
+        )));
+    }
+
+    if next == EventStatus::Cancelled {
+        return Ok(state
+            .event_packet_repo
+            .cancel_event_packet(id, packet.status)
+            .await?);
+    }
+
+    Ok(state
+        .event_packet_repo
+        .set_event_packet_status(id, packet.status, next)
+        .await?)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/event-packets/{id}/publish",
+    params(("id" = i32, Path, description = "Event packet ID")),
+    responses(
+        (status = 200, description = "Event packet published", body = Response<EventPackets>),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only packet owner or admin can publish"),
+        (status = 404, description = "Event packet not found"),
+        (status = 409, description = "Event packet cannot be published from its current state")
+    ),
+    tag = "Event Packets",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn publish_event_packet(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    let packet = transition_event_packet(&state, &user_claims, id, EventStatus::Published).await?;
+
+    Ok(Json(build_simple_event_packet(packet, &state.base_url)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/event-packets/{id}/close-sales",
+    params(("id" = i32, Path, description = "Event packet ID")),
+    responses(
+        (status = 200, description = "Ticket sales closed", body = Response<EventPackets>),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only packet owner or admin can close sales"),
+        (status = 404, description = "Event packet not found"),
+        (status = 409, description = "Only published event packets can close sales")
+    ),
+    tag = "Event Packets",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn close_event_packet_sales(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    let packet =
+        transition_event_packet(&state, &user_claims, id, EventStatus::SalesClosed).await?;
+
+    Ok(Json(build_simple_event_packet(packet, &state.base_url)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/event-packets/{id}/cancel",
+    params(("id" = i32, Path, description = "Event packet ID")),
+    responses(
+        (status = 200, description = "Event packet cancelled", body = Response<EventPackets>),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only packet owner or admin can cancel"),
+        (status = 404, description = "Event packet not found"),
+        (status = 409, description = "Event packet is already cancelled")
+    ),
+    tag = "Event Packets",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn cancel_event_packet(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    let packet = transition_event_packet(&state, &user_claims, id, EventStatus::Cancelled).await?;
+
+    Ok(Json(build_simple_event_packet(packet, &state.base_url)))
+}
+
 #[utoipa::path(
     put,
     path = "/api/event-manager/event-packets/{id}",
@@ -261,11 +483,12 @@ pub async fn create_event_packet(
     path = "/api/event-manager/event-packets/{id}",
     params(("id" = i32, Path, description = "Event packet ID")),
     responses(
+        (status = 202, description = "Event packet has sold tickets; it was cancelled and buyers are being refunded, retry once refunds complete"),
         (status = 204, description = "Event packet deleted successfully"),
         (status = 401, description = "Missing or invalid authentication token"),
         (status = 403, description = "Forbidden - Only packet owner or admin can delete"),
         (status = 404, description = "Event packet not found"),
-        (status = 409, description = "Cannot delete package with sold tickets")
+        (status = 409, description = "Only checked-in tickets are left; they are kept, and so is the event packet")
     ),
     tag = "Event Packets",
     security(
@@ -285,12 +508,23 @@ pub async fn delete_event_packet(
     Authorization::can_modify_resource(&user_claims, &existing_packet, None)
         .map_err(map_authorization_error)?;
 
-    let ticket_count = state.ticket_repo.count_tickets_for_packet(id).await?;
-    if ticket_count > 0 {
-        return Err(ApiError::Conflict(format!(
-            "Cannot delete package with {} sold ticket(s). Please cancel the tickets first.",
-            ticket_count
-        )));
+    let tickets = state.ticket_repo.list_tickets_for_packet(id).await?;
+    if !tickets.is_empty() {
+        if tickets.iter().all(|t| t.checked_in_at.is_some()) {
+            return Err(ApiError::Conflict(
+                "The event packet has checked-in tickets, which are kept as its attendance record."
+                    .to_string(),
+            ));
+        }
+
+        if existing_packet.status != EventStatus::Cancelled {
+            state
+                .event_packet_repo
+                .cancel_event_packet(id, existing_packet.status)
+                .await?;
+        }
+
+        return Ok(StatusCode::ACCEPTED);
     }
 
     state.event_packet_repo.delete_event_packet(id).await?;
@@ -0,0 +1,208 @@
+use crate::AppState;
+use crate::middleware::{Authorization, UserClaims};
+use crate::models::export::{Attendee, ExportFormat, ExportQuery};
+use crate::services::client_service::ClientServiceClient;
+use crate::utils::error::{ApiError, ExportError, map_authorization_error};
+use crate::utils::export::{
+    Buyers, CSV_CONTENT_TYPE, XLSX_CONTENT_TYPE, csv_header, csv_rows, index_buyers, render_xlsx,
+};
+use axum::body::Body;
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum::{
+    Extension, Router,
+    extract::{Path, Query, State},
+    routing::get,
+};
+use futures::{StreamExt, future, stream};
+use std::io;
+use std::sync::Arc;
+
+const BUYER_LOOKUP_BATCH: usize = 500;
+
+pub fn export_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/events/{id}/attendees/export", get(export_event_attendees))
+        .route(
+            "/event-packets/{id}/attendees/export",
+            get(export_event_packet_attendees),
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/events/{id}/attendees/export",
+    params(
+        ("id" = i32, Path, description = "Event ID"),
+        ("format" = Option<String>, Query, description = "csv (default) or xlsx")
+    ),
+    responses(
+        (status = 200, description = "Attendee list, including holders of packet tickets", content(
+            (String = "text/csv"),
+            (String = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+        )),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the event owner or an admin can export attendees"),
+        (status = 404, description = "Event not found"),
+        (status = 502, description = "Buyer details could not be loaded from client-service")
+    ),
+    tag = "Export",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn export_event_attendees(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    Query(params): Query<ExportQuery>,
+) -> Result<Response, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let event = state.event_repo.get_event(id).await?;
+    Authorization::can_modify_resource(&user_claims, &event, None)
+        .map_err(map_authorization_error)?;
+
+    let attendees = state.ticket_repo.list_event_attendees(id).await?;
+
+    export_attendees(
+        &state,
+        attendees,
+        params.format.unwrap_or_default(),
+        format!("event-{}-attendees", id),
+    )
+    .await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/event-packets/{id}/attendees/export",
+    params(
+        ("id" = i32, Path, description = "Event packet ID"),
+        ("format" = Option<String>, Query, description = "csv (default) or xlsx")
+    ),
+    responses(
+        (status = 200, description = "Attendee list of the packet", content(
+            (String = "text/csv"),
+            (String = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+        )),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the packet owner or an admin can export attendees"),
+        (status = 404, description = "Event packet not found"),
+        (status = 502, description = "Buyer details could not be loaded from client-service")
+    ),
+    tag = "Export",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn export_event_packet_attendees(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    Query(params): Query<ExportQuery>,
+) -> Result<Response, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let packet = state.event_packet_repo.get_event_packet(id).await?;
+    Authorization::can_modify_resource(&user_claims, &packet, None)
+        .map_err(map_authorization_error)?;
+
+    let attendees = state.ticket_repo.list_packet_attendees(id).await?;
+
+    export_attendees(
+        &state,
+        attendees,
+        params.format.unwrap_or_default(),
+        format!("event-packet-{}-attendees", id),
+    )
+    .await
+}
+
+async fn export_attendees(
+    state: &AppState,
+    attendees: Vec<Attendee>,
+    format: ExportFormat,
+    filename: String,
+) -> Result<Response, ApiError> {
+    match format {
+        ExportFormat::Xlsx => {
+            let mut buyers = Buyers::new();
+            for chunk in attendees.chunks(BUYER_LOOKUP_BATCH) {
+                buyers.extend(lookup_buyers(&state.client_service, chunk).await?);
+            }
+            let body = render_xlsx(&attendees, &buyers)?;
+
+            Ok(attachment(
+                XLSX_CONTENT_TYPE,
+                format!("{}.xlsx", filename),
+                Body::from(body),
+            ))
+        }
+        ExportFormat::Csv => {
+            let mut chunks = attendees
+                .chunks(BUYER_LOOKUP_BATCH)
+                .map(<[Attendee]>::to_vec)
+                .collect::<Vec<_>>()
+                .into_iter();
+
+            // The first batch is resolved before answering, so an unreachable
+            // client-service still gets a proper error status; the rest is
+            // streamed batch by batch.
+            let mut head = csv_header();
+            if let Some(chunk) = chunks.next() {
+                let buyers = lookup_buyers(&state.client_service, &chunk).await?;
+                head.push_str(&csv_rows(&chunk, &buyers));
+            }
+
+            let client = Arc::clone(&state.client_service);
+            let rest = stream::iter(chunks).then(move |chunk| {
+                let client = Arc::clone(&client);
+                async move {
+                    let buyers = lookup_buyers(&client, &chunk).await.map_err(|e| {
+                        tracing::error!("Attendee export aborted: {:?}", e);
+                        io::Error::other(format!("{:?}", e))
+                    })?;
+                    Ok::<_, io::Error>(csv_rows(&chunk, &buyers))
+                }
+            });
+
+            Ok(attachment(
+                CSV_CONTENT_TYPE,
+                format!("{}.csv", filename),
+                Body::from_stream(stream::once(future::ready(Ok(head))).chain(rest)),
+            ))
+        }
+    }
+}
+
+async fn lookup_buyers(
+    client: &ClientServiceClient,
+    attendees: &[Attendee],
+) -> Result<Buyers, ExportError> {
+    let codes: Vec<String> = attendees
+        .iter()
+        .map(|attendee| attendee.cod.clone())
+        .collect();
+    let buyers = client.ticket_buyers(&codes).await?;
+
+    Ok(index_buyers(buyers))
+}
+
+fn attachment(content_type: &'static str, filename: String, body: Body) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
@@ -0,0 +1,254 @@
+use crate::AppState;
+use crate::handlers::event::apply_room_defaults;
+use crate::middleware::{Authorization, UserClaims};
+use crate::models::event::CreateEvent;
+use crate::models::event_packets::CreateEventPacket;
+use crate::models::import::{
+    ImportQuery, ImportReport, ImportRequest, ImportRowKind, ImportRowResult, ImportedEvent,
+    ImportedPacket,
+};
+use crate::utils::error::{ApiError, flatten_validation_errors, map_authorization_error};
+use crate::utils::import::{csv_rows, json_rows, take_packet_events};
+use axum::body::Bytes;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    routing::post,
+};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use validator::Validate;
+
+const MAX_IMPORT_ROWS: usize = 500;
+
+pub fn import_router() -> Router<Arc<AppState>> {
+    Router::new().route("/imports", post(import_catalog))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/imports",
+    params(
+        ("dry_run" = Option<bool>, Query, description = "Validate and report without writing anything")
+    ),
+    request_body(
+        description = "Events and packets as JSON, or as CSV with a `kind` column (event or packet)",
+        content(
+            (ImportRequest = "application/json"),
+            (String = "text/csv")
+        )
+    ),
+    responses(
+        (status = 200, description = "Dry run: every row would be imported", body = ImportReport),
+        (status = 201, description = "All rows imported", body = ImportReport),
+        (status = 400, description = "The upload could not be read, is empty or has too many rows"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Requires owner-event role or admin"),
+        (status = 422, description = "Some rows are invalid; nothing was imported", body = ImportReport)
+    ),
+    tag = "Import",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn import_catalog(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Query(params): Query<ImportQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<(StatusCode, Json<ImportReport>), ApiError> {
+    Authorization::require_owner_event_or_admin(&user_claims).map_err(map_authorization_error)?;
+    let dry_run = params.dry_run.unwrap_or(false);
+
+    let is_csv = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("text/csv"));
+    let rows = if is_csv {
+        csv_rows(&body).map_err(ApiError::BadRequest)?
+    } else {
+        let request: ImportRequest = serde_json::from_slice(&body)
+            .map_err(|e| ApiError::BadRequest(format!("Invalid import document: {}", e)))?;
+        json_rows(request)
+    };
+
+    if rows.is_empty() {
+        return Err(ApiError::BadRequest(
+            "The import contains no events or packets".to_string(),
+        ));
+    }
+    if rows.len() > MAX_IMPORT_ROWS {
+        return Err(ApiError::BadRequest(format!(
+            "At most {} rows can be imported at once",
+            MAX_IMPORT_ROWS
+        )));
+    }
+
+    let mut results = Vec::with_capacity(rows.len());
+    let mut events = Vec::new();
+    let mut packets = Vec::new();
+    for mut row in rows {
+        let index = results.len();
+        let mut result = ImportRowResult {
+            kind: row.kind,
+            row: row.row,
+            nume: row
+                .value
+                .get("nume")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            id: None,
+            errors: Vec::new(),
+        };
+
+        match row.kind {
+            ImportRowKind::Event => match serde_json::from_value::<CreateEvent>(row.value) {
+                Ok(mut event) => {
+                    result.errors = check_event(&state, &mut event).await?;
+                    if result.errors.is_empty() {
+                        events.push(ImportedEvent { index, event });
+                    }
+                }
+                Err(e) => result.errors.push(e.to_string()),
+            },
+            ImportRowKind::Packet => {
+                let packet = take_packet_events(&mut row.value).and_then(|names| {
+                    serde_json::from_value::<CreateEventPacket>(row.value)
+                        .map(|packet| (packet, names))
+                });
+                match packet {
+                    Ok((packet, names)) => match packet.validate() {
+                        Ok(()) => packets.push(ImportedPacket {
+                            index,
+                            packet,
+                            events: names,
+                        }),
+                        Err(errors) => result.errors = flatten_validation_errors(&errors),
+                    },
+                    Err(e) => result.errors.push(e.to_string()),
+                }
+            }
+        }
+
+        results.push(result);
+    }
+
+    let existing = resolve_existing_events(&state, &user_claims, &mut results, &packets).await?;
+    packets.retain(|packet| results[packet.index].errors.is_empty());
+
+    let valid = results.iter().all(|result| result.errors.is_empty());
+    let outcome = state
+        .import_repo
+        .run_import(
+            user_claims.user_id,
+            &events,
+            &packets,
+            &existing,
+            valid && !dry_run,
+        )
+        .await?;
+
+    let mut events_created = 0;
+    for (imported, written) in events.iter().zip(outcome.events) {
+        match written {
+            Ok(id) => {
+                events_created += 1;
+                results[imported.index].id = outcome.committed.then_some(id);
+            }
+            Err(message) => results[imported.index].errors.push(message),
+        }
+    }
+    let mut packets_created = 0;
+    for (imported, written) in packets.iter().zip(outcome.packets) {
+        match written {
+            Ok(id) => {
+                packets_created += 1;
+                results[imported.index].id = outcome.committed.then_some(id);
+            }
+            Err(message) => results[imported.index].errors.push(message),
+        }
+    }
+
+    let status = if results.iter().any(|result| !result.errors.is_empty()) {
+        StatusCode::UNPROCESSABLE_ENTITY
+    } else if outcome.committed {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+
+    Ok((
+        status,
+        Json(ImportReport {
+            dry_run,
+            committed: outcome.committed,
+            events_created,
+            packets_created,
+            links_created: outcome.links_created,
+            rows: results,
+        }),
+    ))
+}
+
+/// Runs the checks of `POST /events` on an import row, returning them as row
+/// errors instead of failing the request.
+async fn check_event(state: &AppState, event: &mut CreateEvent) -> Result<Vec<String>, ApiError> {
+    if let Err(errors) = event.validate() {
+        return Ok(flatten_validation_errors(&errors));
+    }
+
+    match apply_room_defaults(state, event).await {
+        Ok(()) => Ok(Vec::new()),
+        Err(ApiError::BadRequest(message)) => Ok(vec![message]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Looks up packet events that are not part of the import. Only events the
+/// caller could link through the API are usable; any other name becomes an
+/// error on the packet row.
+async fn resolve_existing_events(
+    state: &AppState,
+    user_claims: &UserClaims,
+    results: &mut [ImportRowResult],
+    packets: &[ImportedPacket],
+) -> Result<HashMap<String, i32>, ApiError> {
+    let imported: HashSet<String> = results
+        .iter()
+        .filter(|result| result.kind == ImportRowKind::Event)
+        .filter_map(|result| result.nume.clone())
+        .collect();
+    let outside: Vec<String> = packets
+        .iter()
+        .flat_map(|packet| &packet.events)
+        .filter(|name| !imported.contains(*name))
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    let mut existing = HashMap::new();
+    if !outside.is_empty() {
+        for event in state.import_repo.find_events_by_name(&outside).await? {
+            if Authorization::can_modify_resource(user_claims, &event, None).is_ok() {
+                existing.insert(event.nume.clone(), event.id);
+            }
+        }
+    }
+
+    for packet in packets {
+        for name in &packet.events {
+            if !imported.contains(name) && !existing.contains_key(name) {
+                results[packet.index].errors.push(format!(
+                    "Event '{}' is neither part of the import nor one of your events",
+                    name
+                ));
+            }
+        }
+    }
+
+    Ok(existing)
+}
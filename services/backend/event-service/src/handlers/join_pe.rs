@@ -1,5 +1,5 @@
 use crate::AppState;
-use crate::models::event::Event;
+use crate::models::event::{Event, EventStatus};
 use crate::models::event_packets::EventPackets;
 use crate::utils::error::{ApiError, map_authorization_error};
 use crate::utils::links::{Response, build_event_over_packet, build_packet_over_event};
@@ -56,6 +56,7 @@ pub async fn list_packets_for_event(
 
     let wrapped: Vec<Response<EventPackets>> = packets
         .into_iter()
+        .filter(|p| p.status == EventStatus::Published)
         .map(|e| build_packet_over_event(e, id, &state.base_url))
         .collect();
 
@@ -90,6 +91,7 @@ pub async fn list_events_for_packet(
 
     let wrapped: Vec<Response<Event>> = events
         .into_iter()
+        .filter(|e| e.status == EventStatus::Published)
         .map(|e| build_event_over_packet(e, id, &state.base_url))
         .collect();
 
@@ -1,15 +1,38 @@
+pub mod analytics;
+pub mod calendar;
+pub mod check_in;
 pub mod event;
 pub mod event_packets;
+pub mod export;
+pub mod import;
 pub mod join_pe;
+pub mod price_tier;
+pub mod promo_code;
 pub mod refund;
+pub mod search;
+pub mod seat;
+pub mod seat_hold;
 pub mod ticket;
+pub mod venue;
+pub mod waitlist;
 
 use crate::AppState;
+use crate::handlers::analytics::analytics_router;
+use crate::handlers::calendar::{calendar_router, public_calendar_router};
+use crate::handlers::check_in::check_in_router;
 use crate::handlers::event::{event_manager_router, public_event_router};
 use crate::handlers::event_packets::{event_packet_manager_router, public_event_packet_router};
+use crate::handlers::export::export_router;
+use crate::handlers::import::import_router;
 use crate::handlers::join_pe::{join_pe_manager_router, public_join_pe_router};
+use crate::handlers::price_tier::{price_tier_manager_router, public_price_tier_router};
 use crate::handlers::refund::refund_router;
+use crate::handlers::search::public_search_router;
+use crate::handlers::seat::{public_seat_router, seat_manager_router};
+use crate::handlers::seat_hold::seat_hold_router;
 use crate::handlers::ticket::ticket_manager_router;
+use crate::handlers::venue::{public_venue_router, venue_manager_router};
+use crate::handlers::waitlist::waitlist_router;
 use crate::utils::doc::ApiDoc;
 use axum::Router;
 use std::sync::Arc;
@@ -22,7 +45,17 @@ pub fn authenticated_api_router() -> Router<Arc<AppState>> {
         .merge(event_packet_manager_router())
         .merge(ticket_manager_router())
         .merge(join_pe_manager_router())
+        .merge(price_tier_manager_router())
         .merge(refund_router())
+        .merge(seat_hold_router())
+        .merge(waitlist_router())
+        .merge(check_in_router())
+        .merge(venue_manager_router())
+        .merge(seat_manager_router())
+        .merge(calendar_router())
+        .merge(analytics_router())
+        .merge(export_router())
+        .merge(import_router())
 }
 
 pub fn public_api_router() -> Router<Arc<AppState>> {
@@ -30,6 +63,11 @@ pub fn public_api_router() -> Router<Arc<AppState>> {
         .merge(public_event_router())
         .merge(public_event_packet_router())
         .merge(public_join_pe_router())
+        .merge(public_price_tier_router())
+        .merge(public_venue_router())
+        .merge(public_seat_router())
+        .merge(public_search_router())
+        .merge(public_calendar_router())
 }
 
 pub fn swagger_router() -> Router<Arc<AppState>> {
@@ -0,0 +1,331 @@
+use crate::AppState;
+use crate::middleware::{Authorization, UserClaims};
+use crate::models::price_tier::{CreatePriceTier, PriceTier, UpdatePriceTier};
+use crate::utils::error::{ApiError, map_authorization_error};
+use crate::utils::links::{Response, build_price_tier};
+use axum::extract::rejection::JsonRejection;
+use axum::response::IntoResponse;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, patch, post},
+};
+use std::sync::Arc;
+use validator::Validate;
+
+pub fn price_tier_manager_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/events/{id}/price-tiers", post(create_price_tier_for_event))
+        .route(
+            "/event-packets/{id}/price-tiers",
+            post(create_price_tier_for_packet),
+        )
+        .route(
+            "/price-tiers/{id}",
+            patch(update_price_tier).delete(delete_price_tier),
+        )
+}
+
+pub fn public_price_tier_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/events/{id}/price-tiers", get(list_price_tiers_for_event))
+        .route(
+            "/event-packets/{id}/price-tiers",
+            get(list_price_tiers_for_packet),
+        )
+        .route("/price-tiers/{id}", get(get_price_tier))
+}
+
+async fn authorize_tier_owner(
+    state: &AppState,
+    user_claims: &UserClaims,
+    tier: &PriceTier,
+) -> Result<(), ApiError> {
+    if let Some(event_id) = tier.event_id {
+        let event = state.event_repo.get_event(event_id).await?;
+        Authorization::can_modify_resource(user_claims, &event, None)
+            .map_err(map_authorization_error)?;
+    } else if let Some(packet_id) = tier.packet_id {
+        let packet = state.event_packet_repo.get_event_packet(packet_id).await?;
+        Authorization::can_modify_resource(user_claims, &packet, None)
+            .map_err(map_authorization_error)?;
+    }
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/events/{id}/price-tiers",
+    params(
+        ("id" = i32, Path, description = "ID of the event")
+    ),
+    responses(
+        (status = 200, description = "Price tiers of the event, cheapest first", body = [Response<PriceTier>]),
+        (status = 404, description = "Event not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "PriceTiers"
+)]
+pub async fn list_price_tiers_for_event(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    state.event_repo.get_event(id).await?;
+    let tiers = state.price_tier_repo.list_tiers_for_event(id).await?;
+
+    let wrapped: Vec<Response<PriceTier>> = tiers
+        .into_iter()
+        .map(|t| {
+            let tier_id = t.id;
+            build_price_tier(t, tier_id, &state.base_url)
+        })
+        .collect();
+
+    Ok(Json(wrapped))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/event-packets/{id}/price-tiers",
+    params(
+        ("id" = i32, Path, description = "ID of the event packet")
+    ),
+    responses(
+        (status = 200, description = "Price tiers of the packet, cheapest first", body = [Response<PriceTier>]),
+        (status = 404, description = "Event packet not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "PriceTiers"
+)]
+pub async fn list_price_tiers_for_packet(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    state.event_packet_repo.get_event_packet(id).await?;
+    let tiers = state.price_tier_repo.list_tiers_for_packet(id).await?;
+
+    let wrapped: Vec<Response<PriceTier>> = tiers
+        .into_iter()
+        .map(|t| {
+            let tier_id = t.id;
+            build_price_tier(t, tier_id, &state.base_url)
+        })
+        .collect();
+
+    Ok(Json(wrapped))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/price-tiers/{id}",
+    params(
+        ("id" = i32, Path, description = "ID of the price tier")
+    ),
+    responses(
+        (status = 200, description = "Price tier found", body = Response<PriceTier>),
+        (status = 404, description = "Price tier not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "PriceTiers"
+)]
+pub async fn get_price_tier(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let tier = state.price_tier_repo.get_tier(id).await?;
+
+    Ok(Json(build_price_tier(tier, id, &state.base_url)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/events/{id}/price-tiers",
+    params(
+        ("id" = i32, Path, description = "ID of the event")
+    ),
+    request_body = CreatePriceTier,
+    responses(
+        (status = 201, description = "Price tier created", body = Response<PriceTier>),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only event owner or admin can add price tiers"),
+        (status = 404, description = "Event not found"),
+        (status = 409, description = "A tier with this name already exists"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "PriceTiers",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn create_price_tier_for_event(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    payload: Result<Json<CreatePriceTier>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let event = state.event_repo.get_event(id).await?;
+    Authorization::can_modify_resource(&user_claims, &event, None)
+        .map_err(map_authorization_error)?;
+
+    let tier = state
+        .price_tier_repo
+        .create_tier_for_event(id, payload)
+        .await?;
+    let tier_id = tier.id;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(build_price_tier(tier, tier_id, &state.base_url)),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/event-packets/{id}/price-tiers",
+    params(
+        ("id" = i32, Path, description = "ID of the event packet")
+    ),
+    request_body = CreatePriceTier,
+    responses(
+        (status = 201, description = "Price tier created", body = Response<PriceTier>),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only packet owner or admin can add price tiers"),
+        (status = 404, description = "Event packet not found"),
+        (status = 409, description = "A tier with this name already exists"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "PriceTiers",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn create_price_tier_for_packet(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    payload: Result<Json<CreatePriceTier>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let packet = state.event_packet_repo.get_event_packet(id).await?;
+    Authorization::can_modify_resource(&user_claims, &packet, None)
+        .map_err(map_authorization_error)?;
+
+    let tier = state
+        .price_tier_repo
+        .create_tier_for_packet(id, payload)
+        .await?;
+    let tier_id = tier.id;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(build_price_tier(tier, tier_id, &state.base_url)),
+    ))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/event-manager/price-tiers/{id}",
+    params(
+        ("id" = i32, Path, description = "ID of the price tier")
+    ),
+    request_body = UpdatePriceTier,
+    responses(
+        (status = 200, description = "Price tier updated", body = Response<PriceTier>),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the owner or admin can update price tiers"),
+        (status = 404, description = "Price tier not found"),
+        (status = 409, description = "Capacity below tickets already sold or duplicate name"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "PriceTiers",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn update_price_tier(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    payload: Result<Json<UpdatePriceTier>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let tier = state.price_tier_repo.get_tier(id).await?;
+    authorize_tier_owner(&state, &user_claims, &tier).await?;
+
+    let updated = state.price_tier_repo.update_tier(id, payload).await?;
+
+    Ok(Json(build_price_tier(updated, id, &state.base_url)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/event-manager/price-tiers/{id}",
+    params(
+        ("id" = i32, Path, description = "ID of the price tier")
+    ),
+    responses(
+        (status = 204, description = "Price tier deleted"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the owner or admin can delete price tiers"),
+        (status = 404, description = "Price tier not found"),
+        (status = 409, description = "Cannot delete a tier that already sold tickets")
+    ),
+    tag = "PriceTiers",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn delete_price_tier(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let tier = state.price_tier_repo.get_tier(id).await?;
+    authorize_tier_owner(&state, &user_claims, &tier).await?;
+
+    if tier.sold > 0 {
+        return Err(ApiError::Conflict(
+            "Cannot delete a price tier that already sold tickets".to_string(),
+        ));
+    }
+
+    state.price_tier_repo.delete_tier(id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
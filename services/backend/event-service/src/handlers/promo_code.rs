@@ -0,0 +1,339 @@
+use crate::AppState;
+use crate::middleware::{Authorization, UserClaims};
+use crate::models::promo_code::{CreatePromoCode, PromoCode, PromoCodeStats, UpdatePromoCode};
+use crate::utils::error::{ApiError, map_authorization_error};
+use axum::extract::rejection::JsonRejection;
+use axum::response::IntoResponse;
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use std::sync::Arc;
+use validator::Validate;
+
+async fn authorize_code_owner(
+    state: &AppState,
+    user_claims: &UserClaims,
+    code: &PromoCode,
+) -> Result<(), ApiError> {
+    if let Some(event_id) = code.event_id {
+        let event = state.event_repo.get_event(event_id).await?;
+        Authorization::can_modify_resource(user_claims, &event, None)
+            .map_err(map_authorization_error)?;
+    } else if let Some(packet_id) = code.packet_id {
+        let packet = state.event_packet_repo.get_event_packet(packet_id).await?;
+        Authorization::can_modify_resource(user_claims, &packet, None)
+            .map_err(map_authorization_error)?;
+    }
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/events/{id}/promo-codes",
+    params(
+        ("id" = i32, Path, description = "ID of the event")
+    ),
+    responses(
+        (status = 200, description = "Promo codes of the event, newest first", body = [PromoCode]),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only event owner or admin can see promo codes"),
+        (status = 404, description = "Event not found")
+    ),
+    tag = "Promo codes",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn list_promo_codes_for_event(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let event = state.event_repo.get_event(id).await?;
+    Authorization::can_modify_resource(&user_claims, &event, None)
+        .map_err(map_authorization_error)?;
+
+    let codes = state.promo_code_repo.list_codes_for_event(id).await?;
+
+    Ok(Json(codes))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/event-packets/{id}/promo-codes",
+    params(
+        ("id" = i32, Path, description = "ID of the event packet")
+    ),
+    responses(
+        (status = 200, description = "Promo codes of the packet, newest first", body = [PromoCode]),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only packet owner or admin can see promo codes"),
+        (status = 404, description = "Event packet not found")
+    ),
+    tag = "Promo codes",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn list_promo_codes_for_packet(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let packet = state.event_packet_repo.get_event_packet(id).await?;
+    Authorization::can_modify_resource(&user_claims, &packet, None)
+        .map_err(map_authorization_error)?;
+
+    let codes = state.promo_code_repo.list_codes_for_packet(id).await?;
+
+    Ok(Json(codes))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/events/{id}/promo-codes",
+    params(
+        ("id" = i32, Path, description = "ID of the event")
+    ),
+    request_body = CreatePromoCode,
+    responses(
+        (status = 201, description = "Promo code created", body = PromoCode),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only event owner or admin can add promo codes"),
+        (status = 404, description = "Event not found"),
+        (status = 409, description = "A promo code with this code already exists"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Promo codes",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn create_promo_code_for_event(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    payload: Result<Json<CreatePromoCode>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let event = state.event_repo.get_event(id).await?;
+    Authorization::can_modify_resource(&user_claims, &event, None)
+        .map_err(map_authorization_error)?;
+
+    let code = state
+        .promo_code_repo
+        .create_code_for_event(id, payload)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(code)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/event-packets/{id}/promo-codes",
+    params(
+        ("id" = i32, Path, description = "ID of the event packet")
+    ),
+    request_body = CreatePromoCode,
+    responses(
+        (status = 201, description = "Promo code created", body = PromoCode),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only packet owner or admin can add promo codes"),
+        (status = 404, description = "Event packet not found"),
+        (status = 409, description = "A promo code with this code already exists"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Promo codes",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn create_promo_code_for_packet(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    payload: Result<Json<CreatePromoCode>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let packet = state.event_packet_repo.get_event_packet(id).await?;
+    Authorization::can_modify_resource(&user_claims, &packet, None)
+        .map_err(map_authorization_error)?;
+
+    let code = state
+        .promo_code_repo
+        .create_code_for_packet(id, payload)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(code)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/promo-codes/{id}",
+    params(
+        ("id" = i32, Path, description = "ID of the promo code")
+    ),
+    responses(
+        (status = 200, description = "Promo code found", body = PromoCode),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the owner or admin can see promo codes"),
+        (status = 404, description = "Promo code not found")
+    ),
+    tag = "Promo codes",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_promo_code(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let code = state.promo_code_repo.get_code(id).await?;
+    authorize_code_owner(&state, &user_claims, &code).await?;
+
+    Ok(Json(code))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/event-manager/promo-codes/{id}",
+    params(
+        ("id" = i32, Path, description = "ID of the promo code")
+    ),
+    request_body = UpdatePromoCode,
+    responses(
+        (status = 200, description = "Promo code updated", body = PromoCode),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the owner or admin can update promo codes"),
+        (status = 404, description = "Promo code not found"),
+        (status = 409, description = "Usage limit below the number of redemptions"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Promo codes",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn update_promo_code(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    payload: Result<Json<UpdatePromoCode>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let code = state.promo_code_repo.get_code(id).await?;
+    authorize_code_owner(&state, &user_claims, &code).await?;
+
+    let updated = state.promo_code_repo.update_code(id, payload).await?;
+
+    Ok(Json(updated))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/event-manager/promo-codes/{id}",
+    params(
+        ("id" = i32, Path, description = "ID of the promo code")
+    ),
+    responses(
+        (status = 204, description = "Promo code deleted"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the owner or admin can delete promo codes"),
+        (status = 404, description = "Promo code not found"),
+        (status = 409, description = "Cannot delete a promo code that was already redeemed")
+    ),
+    tag = "Promo codes",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn delete_promo_code(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let code = state.promo_code_repo.get_code(id).await?;
+    authorize_code_owner(&state, &user_claims, &code).await?;
+
+    if code.uses > 0 {
+        return Err(ApiError::Conflict(
+            "Cannot delete a promo code that was already redeemed; deactivate it instead"
+                .to_string(),
+        ));
+    }
+
+    state.promo_code_repo.delete_code(id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/promo-codes/{id}/stats",
+    params(
+        ("id" = i32, Path, description = "ID of the promo code")
+    ),
+    responses(
+        (status = 200, description = "Redemption statistics of the promo code", body = PromoCodeStats),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the owner or admin can see promo code statistics"),
+        (status = 404, description = "Promo code not found")
+    ),
+    tag = "Promo codes",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_promo_code_stats(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let code = state.promo_code_repo.get_code(id).await?;
+    authorize_code_owner(&state, &user_claims, &code).await?;
+
+    let stats = state.promo_code_repo.stats(id).await?;
+
+    Ok(Json(stats))
+}
@@ -1,6 +1,7 @@
 use crate::AppState;
 use crate::middleware::UserClaims;
-use crate::models::refund::{RefundRequest, RejectRefundRequest};
+use crate::models::refund::{RefundRequest, RejectRefundRequest, TicketRefundStatus};
+use crate::services::waitlist::promote_waitlist;
 use crate::utils::error::{ApiError, RefundRepoError};
 use axum::extract::rejection::JsonRejection;
 use axum::response::IntoResponse;
@@ -9,16 +10,15 @@ use axum::{
     extract::{Path, State},
     routing::{get, post},
 };
-use common::rabbitmq::messages::{ROUTING_KEY_REFUND_RESOLVED, RefundResolved, RefundStatus};
-use common::websocket::messages::{ROUTING_KEY_WS_BROADCAST, RefundStatusChanged, WebSocketMessage};
 use std::sync::Arc;
-use tracing::{error, info, warn};
+use tracing::{error, info};
 use validator::Validate;
 
 pub fn refund_router() -> Router<Arc<AppState>> {
     Router::new()
         .route("/refunds", get(list_pending_refunds))
         .route("/refunds/history", get(list_client_refund_history))
+        .route("/refunds/statuses", get(list_ticket_refund_statuses))
         .route("/refunds/{id}", get(get_refund))
         .route("/refunds/{id}/approve", post(approve_refund))
         .route("/refunds/{id}/reject", post(reject_refund))
@@ -28,6 +28,10 @@ fn map_refund_error(e: crate::repositories::refund_repo::RefundRepoError) -> Api
     use crate::repositories::refund_repo::RefundRepoError as RepoError;
     match e {
         RepoError::NotFound => ApiError::Refund(RefundRepoError::NotFound),
+        RepoError::TicketCheckedIn => ApiError::Conflict(
+            "The ticket has already been checked in and cannot be refunded".to_string(),
+        ),
+        RepoError::Ticket(err) => ApiError::from(err),
         RepoError::InternalError(err) => {
             error!("Database error: {:?}", err);
             ApiError::Refund(RefundRepoError::InternalError(err))
@@ -108,6 +112,38 @@ pub async fn list_client_refund_history(
     Ok(Json(serde_json::json!({ "data": refunds })))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/refunds/statuses",
+    responses(
+        (status = 200, description = "Status of the latest refund request for every ticket that has one", body = Vec<TicketRefundStatus>),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only admins and clients-service can list refund statuses")
+    ),
+    tag = "Refunds",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn list_ticket_refund_statuses(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+) -> Result<impl IntoResponse, ApiError> {
+    if !user_claims.is_admin() && !user_claims.is_clients_service() {
+        return Err(ApiError::Forbidden(
+            "Only admins can list refund statuses".to_string(),
+        ));
+    }
+
+    let statuses = state
+        .refund_repo
+        .list_latest_statuses()
+        .await
+        .map_err(map_refund_error)?;
+
+    Ok(Json(serde_json::json!({ "data": statuses })))
+}
+
 #[utoipa::path(
     get,
     path = "/api/event-manager/refunds/{id}",
@@ -179,68 +215,17 @@ pub async fn approve_refund(
         .await
         .map_err(map_refund_error)?;
 
-    match state.ticket_repo.delete_ticket(&refund.ticket_cod).await {
-        Ok(_) => {
-            info!(
-                "Deleted ticket {} and restored seat count (refund approved)",
-                refund.ticket_cod
-            );
-        }
-        Err(e) => {
-            warn!(
-                "Failed to delete ticket {} from BILETE table: {:?}. Seat count may not be restored.",
-                refund.ticket_cod, e
-            );
-        }
-    }
-
-    let event_name = state.refund_repo.get_event_name_for_refund(&refund).await;
-
-    let message = RefundResolved {
-        request_id: refund.id,
-        ticket_cod: refund.ticket_cod.clone(),
-        requester_email: refund.requester_email.clone(),
-        status: RefundStatus::Approved,
-        event_name,
-        message: None,
-    };
-
-    if let Ok(json) = serde_json::to_vec(&message) {
-        if let Err(e) = state
-            .rabbitmq
-            .publish(ROUTING_KEY_REFUND_RESOLVED, &json)
-            .await
-        {
-            error!("Failed to publish refund.resolved message: {:?}", e);
-        } else {
-            info!(
-                "Published refund.resolved (approved) for request {}",
-                refund.id
-            );
-        }
-    }
-
-
-    let ws_message = WebSocketMessage::RefundStatusChanged(RefundStatusChanged {
-        request_id: refund.id,
-        ticket_cod: refund.ticket_cod.clone(),
-        status: "APPROVED".to_string(),
-        event_name: state.refund_repo.get_event_name_for_refund(&refund).await,
-        message: Some("Your refund has been approved".to_string()),
-        user_id: refund.requester_id,
-    });
-
-    if let Ok(json) = serde_json::to_vec(&ws_message) {
-        if let Err(e) = state
-            .rabbitmq
-            .publish(ROUTING_KEY_WS_BROADCAST, &json)
-            .await
-        {
-            error!("Failed to publish WebSocket message: {:?}", e);
-        } else {
-            info!("Published WebSocket notification for refund approval {}", refund.id);
-        }
-    }
+    info!(
+        "Deleted ticket {} and restored seat count (refund approved)",
+        refund.ticket_cod
+    );
+    promote_waitlist(
+        &state.rabbitmq,
+        &state.waitlist_repo,
+        refund.event_id,
+        refund.packet_id,
+    )
+    .await;
 
     Ok(Json(refund))
 }
@@ -279,61 +264,11 @@ pub async fn reject_refund(
     let Json(payload) = payload?;
     payload.validate()?;
 
-    let rejection_message = payload.message.clone();
-
     let refund = state
         .refund_repo
         .reject_refund(id, user_claims.user_id, &payload.message)
         .await
         .map_err(map_refund_error)?;
 
-    let event_name = state.refund_repo.get_event_name_for_refund(&refund).await;
-
-    let message = RefundResolved {
-        request_id: refund.id,
-        ticket_cod: refund.ticket_cod.clone(),
-        requester_email: refund.requester_email.clone(),
-        status: RefundStatus::Rejected,
-        event_name: event_name.clone(),
-        message: Some(rejection_message.clone()),
-    };
-
-    if let Ok(json) = serde_json::to_vec(&message) {
-        if let Err(e) = state
-            .rabbitmq
-            .publish(ROUTING_KEY_REFUND_RESOLVED, &json)
-            .await
-        {
-            error!("Failed to publish refund.resolved message: {:?}", e);
-        } else {
-            info!(
-                "Published refund.resolved (rejected) for request {}",
-                refund.id
-            );
-        }
-    }
-
-
-    let ws_message = WebSocketMessage::RefundStatusChanged(RefundStatusChanged {
-        request_id: refund.id,
-        ticket_cod: refund.ticket_cod.clone(),
-        status: "REJECTED".to_string(),
-        event_name,
-        message: Some(rejection_message),
-        user_id: refund.requester_id,
-    });
-
-    if let Ok(json) = serde_json::to_vec(&ws_message) {
-        if let Err(e) = state
-            .rabbitmq
-            .publish(ROUTING_KEY_WS_BROADCAST, &json)
-            .await
-        {
-            error!("Failed to publish WebSocket message: {:?}", e);
-        } else {
-            info!("Published WebSocket notification for refund rejection {}", refund.id);
-        }
-    }
-
     Ok(Json(refund))
 }
@@ -0,0 +1,44 @@
+use crate::AppState;
+use crate::models::search::{SearchHit, SearchQuery};
+use crate::utils::error::ApiError;
+use axum::response::IntoResponse;
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    routing::get,
+};
+use std::sync::Arc;
+use validator::Validate;
+
+pub fn public_search_router() -> Router<Arc<AppState>> {
+    Router::new().route("/search", get(search))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/search",
+    params(
+        ("q" = String, Query, description = "Search text; supports quoted phrases, `or` and `-word`"),
+        ("kind" = Option<String>, Query, description = "Only EVENT or PACKET results"),
+        ("page" = Option<i64>, Query, description = "Page number, starting at 1"),
+        ("items_per_page" = Option<i64>, Query, description = "Results per page (1-100)"),
+        ("cursor" = Option<String>, Query, description = "Opaque position from the `X-Next-Cursor` header of the previous page"),
+        ("include_total" = Option<bool>, Query, description = "Return the number of matches in `X-Total-Count`")
+    ),
+    responses(
+        (status = 200, description = "Published events and packets, best match first", body = [SearchHit]),
+        (status = 400, description = "Invalid cursor"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Search"
+)]
+pub async fn search(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SearchQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    params.validate()?;
+
+    let hits = state.search_repo.search(params).await?;
+
+    Ok((hits.headers(), Json(hits.items)))
+}
@@ -0,0 +1,244 @@
+use crate::AppState;
+use crate::middleware::{Authorization, UserClaims};
+use crate::models::event::EventStatus;
+use crate::models::seat::{EventSeat, EventSeatQuery, RoomSeat, SeatMap, SectionAvailability};
+use crate::models::venue::VenueRoom;
+use crate::utils::error::{ApiError, EventRepoError, VenueRepoError, map_authorization_error};
+use axum::extract::rejection::JsonRejection;
+use axum::response::IntoResponse;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post, put},
+};
+use std::sync::Arc;
+use validator::Validate;
+
+pub fn seat_manager_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/venues/{id}/rooms/{room_id}/seats", put(replace_seat_map))
+        .route(
+            "/events/{id}/seats",
+            post(open_seat_inventory).delete(close_seat_inventory),
+        )
+}
+
+pub fn public_seat_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/venues/{id}/rooms/{room_id}/seats", get(get_seat_map))
+        .route("/events/{id}/seats", get(list_event_seats))
+        .route("/events/{id}/seats/sections", get(list_event_sections))
+}
+
+async fn venue_room(state: &AppState, venue_id: i32, room_id: i32) -> Result<VenueRoom, ApiError> {
+    if venue_id < 0 || room_id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let room = state.venue_repo.get_room(room_id).await?;
+    if room.venue_id != venue_id {
+        return Err(VenueRepoError::NotFound.into());
+    }
+    Ok(room)
+}
+
+async fn visible_event(state: &AppState, event_id: i32) -> Result<(), ApiError> {
+    if event_id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let event = state.event_repo.get_event(event_id).await?;
+    if event.status == EventStatus::Draft {
+        return Err(EventRepoError::NotFound.into());
+    }
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/venues/{id}/rooms/{room_id}/seats",
+    params(
+        ("id" = i32, Path, description = "ID of the venue"),
+        ("room_id" = i32, Path, description = "ID of the room")
+    ),
+    responses(
+        (status = 200, description = "Seats of the room in seat-map order", body = [RoomSeat]),
+        (status = 404, description = "Venue or room not found")
+    ),
+    tag = "Seating"
+)]
+pub async fn get_seat_map(
+    State(state): State<Arc<AppState>>,
+    Path((id, room_id)): Path<(i32, i32)>,
+) -> Result<impl IntoResponse, ApiError> {
+    venue_room(&state, id, room_id).await?;
+
+    let seats = state.seat_repo.get_seat_map(room_id).await?;
+
+    Ok(Json(seats))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/event-manager/venues/{id}/rooms/{room_id}/seats",
+    params(
+        ("id" = i32, Path, description = "ID of the venue"),
+        ("room_id" = i32, Path, description = "ID of the room")
+    ),
+    request_body = SeatMap,
+    responses(
+        (status = 200, description = "Seat map replaced; the room capacity is now the number of seats", body = [RoomSeat]),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the venue owner or admin can change seat maps"),
+        (status = 404, description = "Venue or room not found"),
+        (status = 409, description = "An event still sells seats from the current seat map"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Seating",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn replace_seat_map(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path((id, room_id)): Path<(i32, i32)>,
+    payload: Result<Json<SeatMap>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    venue_room(&state, id, room_id).await?;
+    let venue = state.venue_repo.get_venue(id).await?;
+    Authorization::can_modify_resource(&user_claims, &venue, None)
+        .map_err(map_authorization_error)?;
+
+    let seats = state.seat_repo.replace_seat_map(room_id, payload).await?;
+
+    Ok(Json(seats))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/events/{id}/seats",
+    params(
+        ("id" = i32, Path, description = "ID of the event")
+    ),
+    responses(
+        (status = 201, description = "Seat inventory created from the room's seat map", body = [SectionAvailability]),
+        (status = 400, description = "The event is not held in a room with a seat map"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only event owner or admin can set up seating"),
+        (status = 404, description = "Event not found"),
+        (status = 409, description = "Seating already set up or tickets already sold")
+    ),
+    tag = "Seating",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn open_seat_inventory(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let event = state.event_repo.get_event(id).await?;
+    Authorization::can_modify_resource(&user_claims, &event, None)
+        .map_err(map_authorization_error)?;
+
+    let sections = state.seat_repo.open_inventory(id).await?;
+
+    Ok((StatusCode::CREATED, Json(sections)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/event-manager/events/{id}/seats",
+    params(
+        ("id" = i32, Path, description = "ID of the event")
+    ),
+    responses(
+        (status = 204, description = "Seat inventory removed; the event sells general admission"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only event owner or admin can remove seating"),
+        (status = 404, description = "Event not found or not seated"),
+        (status = 409, description = "Seats were already sold")
+    ),
+    tag = "Seating",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn close_seat_inventory(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let event = state.event_repo.get_event(id).await?;
+    Authorization::can_modify_resource(&user_claims, &event, None)
+        .map_err(map_authorization_error)?;
+
+    state.seat_repo.close_inventory(id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/events/{id}/seats",
+    params(
+        ("id" = i32, Path, description = "ID of the event"),
+        ("section" = Option<String>, Query, description = "Only seats in this section"),
+        ("available_only" = Option<bool>, Query, description = "Hide seats that were already sold")
+    ),
+    responses(
+        (status = 200, description = "Seats of the event with their availability", body = [EventSeat]),
+        (status = 404, description = "Event not found"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Seating"
+)]
+pub async fn list_event_seats(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Query(params): Query<EventSeatQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    params.validate()?;
+    visible_event(&state, id).await?;
+
+    let seats = state.seat_repo.list_event_seats(id, params).await?;
+
+    Ok(Json(seats))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/events/{id}/seats/sections",
+    params(
+        ("id" = i32, Path, description = "ID of the event")
+    ),
+    responses(
+        (status = 200, description = "Seats left per section", body = [SectionAvailability]),
+        (status = 404, description = "Event not found")
+    ),
+    tag = "Seating"
+)]
+pub async fn list_event_sections(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    visible_event(&state, id).await?;
+
+    let sections = state.seat_repo.section_availability(id).await?;
+
+    Ok(Json(sections))
+}
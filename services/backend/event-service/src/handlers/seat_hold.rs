@@ -0,0 +1,191 @@
+use crate::AppState;
+use crate::middleware::UserClaims;
+use crate::models::seat_hold::{CreateSeatHold, DEFAULT_HOLD_TTL_SECONDS, SeatHold};
+use crate::services::waitlist::promote_waitlist;
+use crate::utils::error::ApiError;
+use axum::extract::rejection::JsonRejection;
+use axum::response::IntoResponse;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use std::sync::Arc;
+use validator::Validate;
+
+pub fn seat_hold_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/events/{id}/holds", post(create_hold_for_event))
+        .route("/event-packets/{id}/holds", post(create_hold_for_packet))
+        .route("/holds/{id}", get(get_hold).delete(release_hold))
+}
+
+async fn owned_hold(
+    state: &AppState,
+    user_claims: &UserClaims,
+    id: &str,
+) -> Result<SeatHold, ApiError> {
+    let hold = state.seat_hold_repo.get_hold(id).await?;
+
+    if hold.holder_id != user_claims.user_id && !user_claims.is_admin() {
+        return Err(ApiError::Forbidden(
+            "You can only access your own seat holds".to_string(),
+        ));
+    }
+
+    Ok(hold)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/events/{id}/holds",
+    params(
+        ("id" = i32, Path, description = "ID of the event")
+    ),
+    request_body = CreateSeatHold,
+    responses(
+        (status = 201, description = "Seats held until the hold expires", body = SeatHold),
+        (status = 400, description = "Referenced event does not exist"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 409, description = "Not enough seats, event not on sale or already ended"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Holds",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn create_hold_for_event(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    payload: Result<Json<CreateSeatHold>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let hold = state
+        .seat_hold_repo
+        .create_hold_for_event(
+            id,
+            user_claims.user_id,
+            payload.quantity,
+            payload.ttl_seconds.unwrap_or(DEFAULT_HOLD_TTL_SECONDS),
+        )
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(hold)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/event-packets/{id}/holds",
+    params(
+        ("id" = i32, Path, description = "ID of the event packet")
+    ),
+    request_body = CreateSeatHold,
+    responses(
+        (status = 201, description = "Seats held on every event of the packet until the hold expires", body = SeatHold),
+        (status = 400, description = "Referenced packet does not exist or has no events"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 409, description = "Not enough seats, packet not on sale or an event already ended"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Holds",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn create_hold_for_packet(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    payload: Result<Json<CreateSeatHold>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let hold = state
+        .seat_hold_repo
+        .create_hold_for_packet(
+            id,
+            user_claims.user_id,
+            payload.quantity,
+            payload.ttl_seconds.unwrap_or(DEFAULT_HOLD_TTL_SECONDS),
+        )
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(hold)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/holds/{id}",
+    params(
+        ("id" = String, Path, description = "Seat hold ID")
+    ),
+    responses(
+        (status = 200, description = "Seat hold found", body = SeatHold),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Can only view own holds"),
+        (status = 404, description = "Seat hold not found or expired")
+    ),
+    tag = "Holds",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_hold(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let hold = owned_hold(&state, &user_claims, &id).await?;
+
+    Ok(Json(hold))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/event-manager/holds/{id}",
+    params(
+        ("id" = String, Path, description = "Seat hold ID")
+    ),
+    responses(
+        (status = 204, description = "Seat hold released and its seats returned to sale"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Can only release own holds"),
+        (status = 404, description = "Seat hold not found or expired")
+    ),
+    tag = "Holds",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn release_hold(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    owned_hold(&state, &user_claims, &id).await?;
+    let hold = state.seat_hold_repo.release_hold(&id).await?;
+
+    promote_waitlist(
+        &state.rabbitmq,
+        &state.waitlist_repo,
+        hold.event_id,
+        hold.packet_id,
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,20 +1,70 @@
 use crate::AppState;
 use crate::middleware::{Authorization, UserClaims};
-use crate::models::ticket::{Ticket, UpdateTicket};
+use crate::models::event_packets::PaginationParams;
+use crate::models::promo_code::PromoApplication;
+use crate::models::seat::SeatChoice;
+use crate::models::seat_hold::HoldClaim;
+use crate::models::ticket::{BatchPurchaseOptions, PurchaseOptions, Ticket, UpdateTicket};
+use crate::services::waitlist::promote_waitlist;
 use crate::utils::error::{ApiError, map_authorization_error};
+use crate::utils::idempotency::run_idempotent;
 use crate::utils::links;
 use crate::utils::links::{Response, build_ticket_over_event, build_ticket_over_packet};
 use axum::extract::rejection::JsonRejection;
 use axum::response::IntoResponse;
+use common::idempotency::{idempotency_key, request_fingerprint};
 use axum::{
     Extension, Json, Router,
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
     routing::get,
 };
 use std::sync::Arc;
+use uuid::Uuid;
 use validator::Validate;
 
+/// User a purchase is made for: clients-service buys on a client's behalf and
+/// names them, so their promo code cap and seat holds apply; everyone else
+/// buys for themselves.
+fn buyer(user_claims: &UserClaims, buyer_id: Option<i32>) -> i32 {
+    match buyer_id {
+        Some(buyer_id) if user_claims.is_clients_service() => buyer_id,
+        _ => user_claims.user_id,
+    }
+}
+
+fn promo_application(
+    user_claims: &UserClaims,
+    promo_code: Option<&str>,
+    buyer_id: Option<i32>,
+) -> Option<PromoApplication> {
+    promo_code.map(|code| PromoApplication {
+        code: code.to_string(),
+        buyer_id: buyer(user_claims, buyer_id),
+    })
+}
+
+fn hold_claim(
+    user_claims: &UserClaims,
+    hold_id: Option<&str>,
+    buyer_id: Option<i32>,
+) -> Option<HoldClaim> {
+    hold_id.map(|hold_id| HoldClaim {
+        hold_id: hold_id.to_string(),
+        holder_id: buyer(user_claims, buyer_id),
+    })
+}
+
+/// Packets span several events, so their tickets are never tied to a seat.
+fn reject_packet_seat(seat_id: Option<i32>, section: Option<&str>) -> Result<(), ApiError> {
+    if seat_id.is_some() || section.is_some() {
+        return Err(ApiError::BadRequest(
+            "Seats can only be chosen for event tickets".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 pub fn ticket_manager_router() -> Router<Arc<AppState>> {
     Router::new().route("/tickets", get(list_tickets)).route(
         "/tickets/{cod}",
@@ -66,8 +116,15 @@ pub async fn get_ticket(
 #[utoipa::path(
     get,
     path = "/api/event-manager/tickets",
+    params(
+        ("page" = Option<i64>, Query, description = "Page number for offset paging; ignored when `cursor` is given"),
+        ("items_per_page" = Option<i64>, Query, description = "Items per page (1-100, default 10)"),
+        ("cursor" = Option<String>, Query, description = "Opaque position from a `next`/`prev` link or the `X-Next-Cursor` header"),
+        ("include_total" = Option<bool>, Query, description = "Return the number of tickets in `X-Total-Count`")
+    ),
     responses(
-        (status = 200, description = "List of all tickets", body = [Response<Ticket>]),
+        (status = 200, description = "One page of tickets ordered by code", body = [Response<Ticket>]),
+        (status = 400, description = "Invalid cursor"),
         (status = 401, description = "Missing or invalid authentication token"),
         (status = 500, description = "Internal server error")
     ),
@@ -79,21 +136,33 @@ pub async fn get_ticket(
 pub async fn list_tickets(
     State(state): State<Arc<AppState>>,
     Extension(user_claims): Extension<UserClaims>,
+    Query(params): Query<PaginationParams>,
 ) -> Result<impl IntoResponse, ApiError> {
     if !user_claims.is_admin() && !user_claims.is_clients_service() {
         return Err(ApiError::Forbidden(
             "Only admins can list all tickets".to_string(),
         ));
     }
+    params.validate()?;
 
-    let tickets = state.ticket_repo.list_tickets().await?;
+    let tickets = state.ticket_repo.list_tickets(params.clone()).await?;
+    let headers = tickets.headers();
 
-    let wrapped: Vec<Response<Ticket>> = tickets
-        .into_iter()
-        .map(|e| links::build_simple_ticket(e, &state.base_url))
-        .collect();
-
-    Ok(Json(wrapped))
+    let wrapped: Vec<Response<Ticket>> = if params.page.is_some()
+        || params.items_per_page.is_some()
+        || params.cursor.is_some()
+        || tickets.has_more_pages()
+    {
+        links::build_filtered_tickets(tickets, &params, &state.base_url)
+    } else {
+        tickets
+            .items
+            .into_iter()
+            .map(|e| links::build_simple_ticket(e, &state.base_url))
+            .collect()
+    };
+
+    Ok((headers, Json(wrapped)))
 }
 
 #[utoipa::path(
@@ -101,14 +170,16 @@ pub async fn list_tickets(
     path = "/api/event-manager/tickets/{cod}",
     request_body = UpdateTicket,
     params(
-        ("cod" = String, Path, description = "Ticket code")
+        ("cod" = String, Path, description = "Ticket code"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key replay the first response")
     ),
     responses(
+        (status = 200, description = "Ticket already exists (PUT re-sent for an existing ticket code); the stored ticket is returned unchanged", body = Response<Ticket>),
         (status = 201, description = "Ticket created (PUT with non-existing ticket code)", body = Response<Ticket>),
-        (status = 204, description = "Ticket updated (PUT with existing ticket code)"),
         (status = 401, description = "Missing or invalid authentication token"),
         (status = 403, description = "Forbidden - Only event/packet owner or admin can update tickets"),
         (status = 404, description = "Ticket not found"),
+        (status = 409, description = "The ticket exists for another event, packet, tier or seat"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Tickets",
@@ -120,49 +191,91 @@ pub async fn update_ticket(
     State(state): State<Arc<AppState>>,
     Extension(user_claims): Extension<UserClaims>,
     Path(cod): Path<String>,
+    headers: HeaderMap,
     payload: Result<Json<UpdateTicket>, JsonRejection>,
 ) -> Result<impl IntoResponse, ApiError> {
     let Json(payload) = payload?;
     payload.validate()?;
 
-    let existing_ticket_opt = state.ticket_repo.get_ticket(&cod).await.ok();
+    let key = idempotency_key(&headers)?;
+    let scope = format!("ticket_put:{}:{}", user_claims.user_id, cod);
+    let fingerprint = request_fingerprint(&payload);
+
+    run_idempotent(
+        &state.idempotency_repo,
+        &scope,
+        key.as_deref(),
+        &fingerprint,
+        async { upsert_ticket(&state, &user_claims, &cod, payload).await },
+    )
+    .await
+}
+
+async fn upsert_ticket(
+    state: &AppState,
+    user_claims: &UserClaims,
+    cod: &str,
+    payload: UpdateTicket,
+) -> Result<(StatusCode, Option<Json<Response<Ticket>>>), ApiError> {
+    let existing_ticket_opt = state.ticket_repo.get_ticket(cod).await.ok();
 
     if let Some(existing_ticket) = existing_ticket_opt {
         if let Some(event_id) = existing_ticket.id_event {
             let event = state.event_repo.get_event(event_id).await?;
-            Authorization::can_modify_resource(&user_claims, &event, None)
+            Authorization::can_modify_resource(user_claims, &event, None)
                 .map_err(map_authorization_error)?;
         } else if let Some(packet_id) = existing_ticket.id_pachet {
             let packet = state.event_packet_repo.get_event_packet(packet_id).await?;
-            Authorization::can_modify_resource(&user_claims, &packet, None)
+            Authorization::can_modify_resource(user_claims, &packet, None)
                 .map_err(map_authorization_error)?;
         }
 
-        state.ticket_repo.update_ticket(&cod, payload).await?;
-        return Ok(StatusCode::NO_CONTENT.into_response());
+        let ticket = state.ticket_repo.update_ticket(cod, payload).await?;
+        let ticket_response = links::build_simple_ticket(ticket, &state.base_url);
+        return Ok((StatusCode::OK, Some(Json(ticket_response))));
     }
 
     if !user_claims.is_clients_service() {
         if let Some(event_id) = payload.id_event {
             let event = state.event_repo.get_event(event_id).await?;
-            Authorization::can_modify_resource(&user_claims, &event, None)
+            Authorization::can_modify_resource(user_claims, &event, None)
                 .map_err(map_authorization_error)?;
         } else if let Some(packet_id) = payload.id_pachet {
             let packet = state.event_packet_repo.get_event_packet(packet_id).await?;
-            Authorization::can_modify_resource(&user_claims, &packet, None)
+            Authorization::can_modify_resource(user_claims, &packet, None)
                 .map_err(map_authorization_error)?;
         }
     }
 
+    let promo = promo_application(user_claims, payload.promo_code.as_deref(), payload.buyer_id);
+    let hold = hold_claim(user_claims, payload.hold_id.as_deref(), payload.buyer_id);
+
     if let Some(event_id) = payload.id_event {
         state
             .ticket_repo
-            .create_ticket_with_code_for_event(cod.clone(), event_id)
+            .create_ticket_for_event(
+                event_id,
+                Some(cod.to_string()),
+                payload.tier_id,
+                hold.as_ref(),
+                promo.as_ref(),
+                &SeatChoice {
+                    seat_id: payload.seat_id,
+                    section: payload.section.clone(),
+                },
+            )
             .await?;
     } else if let Some(packet_id) = payload.id_pachet {
+        reject_packet_seat(payload.seat_id, payload.section.as_deref())?;
         state
             .ticket_repo
-            .create_ticket_with_code_for_packet(cod.clone(), packet_id)
+            .create_ticket_for_packet(
+                packet_id,
+                Some(cod.to_string()),
+                payload.tier_id,
+                hold.as_ref(),
+                promo.as_ref(),
+            )
             .await?;
     } else {
         return Err(ApiError::BadRequest(
@@ -170,9 +283,9 @@ pub async fn update_ticket(
         ));
     }
 
-    let ticket = state.ticket_repo.get_ticket(&cod).await?;
+    let ticket = state.ticket_repo.get_ticket(cod).await?;
     let ticket_response = links::build_simple_ticket(ticket, &state.base_url);
-    Ok((StatusCode::CREATED, Json(ticket_response)).into_response())
+    Ok((StatusCode::CREATED, Some(Json(ticket_response))))
 }
 
 #[utoipa::path(
@@ -186,6 +299,7 @@ pub async fn update_ticket(
         (status = 401, description = "Missing or invalid authentication token"),
         (status = 403, description = "Forbidden - Only event/packet owner or admin can delete tickets"),
         (status = 404, description = "Ticket not found"),
+        (status = 409, description = "The ticket has been checked in and is kept"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Tickets",
@@ -213,6 +327,15 @@ pub async fn delete_ticket(
     }
 
     state.ticket_repo.delete_ticket(&cod).await?;
+
+    promote_waitlist(
+        &state.rabbitmq,
+        &state.waitlist_repo,
+        existing_ticket.id_event,
+        existing_ticket.id_pachet,
+    )
+    .await;
+
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -303,11 +426,21 @@ pub async fn list_tickets_for_event(
 #[utoipa::path(
     post,
     path = "/api/event-manager/events/{event_id}/tickets",
+    params(
+        ("event_id" = i32, Path, description = "Event ID"),
+        ("tier_id" = Option<i32>, Query, description = "Price tier to sell from; defaults to the cheapest tier on sale"),
+        ("hold_id" = Option<String>, Query, description = "Seat hold to consume instead of taking a fresh seat"),
+        ("promo_code" = Option<String>, Query, description = "Promo code to apply to the ticket price"),
+        ("seat_id" = Option<i32>, Query, description = "Seat to reserve, for events with a seat map"),
+        ("section" = Option<String>, Query, description = "Section to take the next free seat from")
+    ),
     responses(
         (status = 201, description = "Ticket created for event", body = Response<Ticket>),
+        (status = 400, description = "Seat or section is not part of the event's seat inventory"),
         (status = 401, description = "Missing or invalid authentication token"),
         (status = 403, description = "Forbidden - Only event owner, admin, or clients-service can create tickets"),
         (status = 404, description = "Event not found"),
+        (status = 409, description = "Seat already taken or no seats left"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Tickets",
@@ -319,10 +452,12 @@ pub async fn create_ticket_for_event(
     State(state): State<Arc<AppState>>,
     Extension(user_claims): Extension<UserClaims>,
     Path(event_id): Path<i32>,
+    Query(options): Query<PurchaseOptions>,
 ) -> Result<impl IntoResponse, ApiError> {
     if event_id < 0 {
         return Err(ApiError::BadRequest("ID cannot be negative".into()));
     }
+    options.validate()?;
 
     if !user_claims.is_clients_service() {
         let event = state.event_repo.get_event(event_id).await?;
@@ -330,13 +465,110 @@ pub async fn create_ticket_for_event(
             .map_err(map_authorization_error)?;
     }
 
-    let ticket = state.ticket_repo.create_ticket_for_event(event_id).await?;
+    let promo = promo_application(
+        &user_claims,
+        options.promo_code.as_deref(),
+        options.buyer_id,
+    );
+    let hold = hold_claim(&user_claims, options.hold_id.as_deref(), options.buyer_id);
+
+    let ticket = state
+        .ticket_repo
+        .create_ticket_for_event(
+            event_id,
+            None,
+            options.tier_id,
+            hold.as_ref(),
+            promo.as_ref(),
+            &SeatChoice {
+                seat_id: options.seat_id,
+                section: options.section,
+            },
+        )
+        .await?;
 
     let ticket_response = build_ticket_over_event(ticket, event_id, &state.base_url);
 
     Ok((StatusCode::CREATED, Json(ticket_response)))
 }
 
+fn ticket_codes(options: &BatchPurchaseOptions) -> Vec<String> {
+    options.codes.clone().unwrap_or_else(|| {
+        (0..options.quantity)
+            .map(|_| Uuid::now_v7().to_string())
+            .collect()
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/events/{event_id}/tickets/batch",
+    params(
+        ("event_id" = i32, Path, description = "Event ID"),
+    ),
+    request_body = BatchPurchaseOptions,
+    responses(
+        (status = 201, description = "All tickets created for event", body = [Response<Ticket>]),
+        (status = 400, description = "Invalid hold or referenced event does not exist"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only event owner, admin, or clients-service can create tickets"),
+        (status = 409, description = "Not enough seats; no ticket was created"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Tickets",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn create_tickets_for_event(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(event_id): Path<i32>,
+    payload: Result<Json<BatchPurchaseOptions>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(options) = payload?;
+    options.validate()?;
+
+    if event_id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    if !user_claims.is_clients_service() {
+        let event = state.event_repo.get_event(event_id).await?;
+        Authorization::can_modify_resource(&user_claims, &event, None)
+            .map_err(map_authorization_error)?;
+    }
+
+    let promo = promo_application(
+        &user_claims,
+        options.promo_code.as_deref(),
+        options.buyer_id,
+    );
+    let hold = hold_claim(&user_claims, options.hold_id.as_deref(), options.buyer_id);
+
+    let tickets = state
+        .ticket_repo
+        .create_tickets_for_event(
+            event_id,
+            ticket_codes(&options),
+            options.tier_id,
+            hold.as_ref(),
+            promo.as_ref(),
+            &SeatChoice {
+                seat_id: None,
+                section: options.section.clone(),
+            },
+        )
+        .await?;
+
+    let wrapped: Vec<Response<Ticket>> = tickets
+        .into_iter()
+        .map(|ticket| build_ticket_over_event(ticket, event_id, &state.base_url))
+        .collect();
+
+    Ok((StatusCode::CREATED, Json(wrapped)))
+}
+
 #[utoipa::path(
     delete,
     path = "/api/event-manager/events/{event_id}/tickets/{ticket_cod}",
@@ -349,6 +581,7 @@ pub async fn create_ticket_for_event(
         (status = 401, description = "Missing or invalid authentication token"),
         (status = 403, description = "Forbidden - Only event owner or admin can delete tickets"),
         (status = 404, description = "Ticket not found"),
+        (status = 409, description = "The ticket has been checked in and is kept"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Tickets",
@@ -371,10 +604,19 @@ pub async fn delete_ticket_for_event(
             .map_err(map_authorization_error)?;
     }
 
-    state
+    let removed = state
         .ticket_repo
-        .delete_ticket_for_event(event_id, ticket_cod)
+        .delete_ticket_for_event(event_id, &ticket_cod)
         .await?;
+
+    promote_waitlist(
+        &state.rabbitmq,
+        &state.waitlist_repo,
+        removed.id_event,
+        removed.id_pachet,
+    )
+    .await;
+
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -465,6 +707,12 @@ pub async fn get_ticket_for_packet(
 #[utoipa::path(
     post,
     path = "/api/event-manager/event-packets/{packet_id}/tickets",
+    params(
+        ("packet_id" = i32, Path, description = "Packet ID"),
+        ("tier_id" = Option<i32>, Query, description = "Price tier to sell from; defaults to the cheapest tier on sale"),
+        ("hold_id" = Option<String>, Query, description = "Seat hold to consume instead of taking a fresh seat"),
+        ("promo_code" = Option<String>, Query, description = "Promo code to apply to the ticket price")
+    ),
     responses(
         (status = 201, description = "Ticket created for packet", body = Response<Ticket>),
         (status = 401, description = "Missing or invalid authentication token"),
@@ -481,10 +729,13 @@ pub async fn create_ticket_for_packet(
     State(state): State<Arc<AppState>>,
     Extension(user_claims): Extension<UserClaims>,
     Path(packet_id): Path<i32>,
+    Query(options): Query<PurchaseOptions>,
 ) -> Result<impl IntoResponse, ApiError> {
     if packet_id < 0 {
         return Err(ApiError::BadRequest("ID cannot be negative".into()));
     }
+    options.validate()?;
+    reject_packet_seat(options.seat_id, options.section.as_deref())?;
 
     if !user_claims.is_clients_service() {
         let packet = state.event_packet_repo.get_event_packet(packet_id).await?;
@@ -492,9 +743,22 @@ pub async fn create_ticket_for_packet(
             .map_err(map_authorization_error)?;
     }
 
+    let promo = promo_application(
+        &user_claims,
+        options.promo_code.as_deref(),
+        options.buyer_id,
+    );
+    let hold = hold_claim(&user_claims, options.hold_id.as_deref(), options.buyer_id);
+
     let ticket = state
         .ticket_repo
-        .create_ticket_for_packet(packet_id)
+        .create_ticket_for_packet(
+            packet_id,
+            None,
+            options.tier_id,
+            hold.as_ref(),
+            promo.as_ref(),
+        )
         .await?;
 
     let ticket_response = build_ticket_over_packet(ticket, packet_id, &state.base_url);
@@ -502,6 +766,72 @@ pub async fn create_ticket_for_packet(
     Ok((StatusCode::CREATED, Json(ticket_response)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/event-packets/{packet_id}/tickets/batch",
+    params(
+        ("packet_id" = i32, Path, description = "Event packet ID"),
+    ),
+    request_body = BatchPurchaseOptions,
+    responses(
+        (status = 201, description = "All tickets created for packet", body = [Response<Ticket>]),
+        (status = 400, description = "Invalid hold or referenced packet does not exist"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only packet owner, admin, or clients-service can create tickets"),
+        (status = 409, description = "Not enough seats; no ticket was created"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Tickets",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn create_tickets_for_packet(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(packet_id): Path<i32>,
+    payload: Result<Json<BatchPurchaseOptions>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(options) = payload?;
+    options.validate()?;
+    reject_packet_seat(None, options.section.as_deref())?;
+
+    if packet_id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    if !user_claims.is_clients_service() {
+        let packet = state.event_packet_repo.get_event_packet(packet_id).await?;
+        Authorization::can_modify_resource(&user_claims, &packet, None)
+            .map_err(map_authorization_error)?;
+    }
+
+    let promo = promo_application(
+        &user_claims,
+        options.promo_code.as_deref(),
+        options.buyer_id,
+    );
+    let hold = hold_claim(&user_claims, options.hold_id.as_deref(), options.buyer_id);
+
+    let tickets = state
+        .ticket_repo
+        .create_tickets_for_packet(
+            packet_id,
+            ticket_codes(&options),
+            options.tier_id,
+            hold.as_ref(),
+            promo.as_ref(),
+        )
+        .await?;
+
+    let wrapped: Vec<Response<Ticket>> = tickets
+        .into_iter()
+        .map(|ticket| build_ticket_over_packet(ticket, packet_id, &state.base_url))
+        .collect();
+
+    Ok((StatusCode::CREATED, Json(wrapped)))
+}
+
 #[utoipa::path(
     delete,
     path = "/api/event-manager/event-packets/{packet_id}/tickets/{ticket_cod}",
@@ -514,6 +844,7 @@ pub async fn create_ticket_for_packet(
         (status = 401, description = "Missing or invalid authentication token"),
         (status = 403, description = "Forbidden - Only packet owner or admin can delete tickets"),
         (status = 404, description = "Ticket not found"),
+        (status = 409, description = "The ticket has been checked in and is kept"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Tickets",
@@ -536,9 +867,18 @@ pub async fn delete_ticket_for_packet(
             .map_err(map_authorization_error)?;
     }
 
-    state
+    let removed = state
         .ticket_repo
         .delete_ticket_for_packet(packet_id, &ticket_cod)
         .await?;
+
+    promote_waitlist(
+        &state.rabbitmq,
+        &state.waitlist_repo,
+        removed.id_event,
+        removed.id_pachet,
+    )
+    .await;
+
     Ok(StatusCode::NO_CONTENT)
 }
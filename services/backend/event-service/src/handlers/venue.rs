@@ -0,0 +1,316 @@
+use crate::AppState;
+use crate::middleware::{Authorization, UserClaims};
+use crate::models::venue::{
+    CreateVenue, CreateVenueRoom, UpdateVenue, UpdateVenueRoom, Venue, VenueQuery, VenueRoom,
+};
+use crate::utils::error::{ApiError, map_authorization_error};
+use axum::extract::rejection::JsonRejection;
+use axum::response::IntoResponse;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, patch, post},
+};
+use std::sync::Arc;
+use validator::Validate;
+
+pub fn venue_manager_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/venues", post(create_venue))
+        .route("/venues/{id}", patch(update_venue).delete(delete_venue))
+        .route("/venues/{id}/rooms", post(create_room))
+        .route(
+            "/venues/{id}/rooms/{room_id}",
+            patch(update_room).delete(delete_room),
+        )
+}
+
+pub fn public_venue_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/venues", get(list_venues))
+        .route("/venues/{id}", get(get_venue))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/venues",
+    params(
+        ("city" = Option<String>, Query, description = "Only venues in this city (case-insensitive)"),
+        ("country" = Option<String>, Query, description = "Only venues in this ISO 3166 country")
+    ),
+    responses(
+        (status = 200, description = "Venues with their rooms, by name", body = [Venue]),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Venues"
+)]
+pub async fn list_venues(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<VenueQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    params.validate()?;
+
+    let venues = state.venue_repo.list_venues(params).await?;
+
+    Ok(Json(venues))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/venues/{id}",
+    params(
+        ("id" = i32, Path, description = "ID of the venue")
+    ),
+    responses(
+        (status = 200, description = "Venue found", body = Venue),
+        (status = 404, description = "Venue not found")
+    ),
+    tag = "Venues"
+)]
+pub async fn get_venue(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let venue = state.venue_repo.get_venue(id).await?;
+
+    Ok(Json(venue))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/venues",
+    request_body = CreateVenue,
+    responses(
+        (status = 201, description = "Venue created with its rooms", body = Venue),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Requires owner-event role or admin"),
+        (status = 409, description = "A venue with this name already exists"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Venues",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn create_venue(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    payload: Result<Json<CreateVenue>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    Authorization::require_owner_event_or_admin(&user_claims).map_err(map_authorization_error)?;
+
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    let venue = state
+        .venue_repo
+        .create_venue(user_claims.user_id, payload)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(venue)))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/event-manager/venues/{id}",
+    params(
+        ("id" = i32, Path, description = "ID of the venue")
+    ),
+    request_body = UpdateVenue,
+    responses(
+        (status = 200, description = "Venue updated", body = Venue),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the venue owner or admin can update it"),
+        (status = 404, description = "Venue not found"),
+        (status = 409, description = "A venue with this name already exists"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Venues",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn update_venue(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    payload: Result<Json<UpdateVenue>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let venue = state.venue_repo.get_venue(id).await?;
+    Authorization::can_modify_resource(&user_claims, &venue, None)
+        .map_err(map_authorization_error)?;
+
+    let updated = state.venue_repo.update_venue(id, payload).await?;
+
+    Ok(Json(updated))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/event-manager/venues/{id}",
+    params(
+        ("id" = i32, Path, description = "ID of the venue")
+    ),
+    responses(
+        (status = 204, description = "Venue and its rooms deleted"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the venue owner or admin can delete it"),
+        (status = 404, description = "Venue not found"),
+        (status = 409, description = "Events are still scheduled in one of its rooms")
+    ),
+    tag = "Venues",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn delete_venue(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let venue = state.venue_repo.get_venue(id).await?;
+    Authorization::can_modify_resource(&user_claims, &venue, None)
+        .map_err(map_authorization_error)?;
+
+    state.venue_repo.delete_venue(id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/venues/{id}/rooms",
+    params(
+        ("id" = i32, Path, description = "ID of the venue")
+    ),
+    request_body = CreateVenueRoom,
+    responses(
+        (status = 201, description = "Room added to the venue", body = VenueRoom),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the venue owner or admin can add rooms"),
+        (status = 404, description = "Venue not found"),
+        (status = 409, description = "The venue already has a room with this name"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Venues",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn create_room(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    payload: Result<Json<CreateVenueRoom>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let venue = state.venue_repo.get_venue(id).await?;
+    Authorization::can_modify_resource(&user_claims, &venue, None)
+        .map_err(map_authorization_error)?;
+
+    let room = state.venue_repo.create_room(id, payload).await?;
+
+    Ok((StatusCode::CREATED, Json(room)))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/event-manager/venues/{id}/rooms/{room_id}",
+    params(
+        ("id" = i32, Path, description = "ID of the venue"),
+        ("room_id" = i32, Path, description = "ID of the room")
+    ),
+    request_body = UpdateVenueRoom,
+    responses(
+        (status = 200, description = "Room updated", body = VenueRoom),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the venue owner or admin can update rooms"),
+        (status = 404, description = "Venue or room not found"),
+        (status = 409, description = "The venue already has a room with this name"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Venues",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn update_room(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path((id, room_id)): Path<(i32, i32)>,
+    payload: Result<Json<UpdateVenueRoom>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    if id < 0 || room_id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let venue = state.venue_repo.get_venue(id).await?;
+    Authorization::can_modify_resource(&user_claims, &venue, None)
+        .map_err(map_authorization_error)?;
+
+    let room = state.venue_repo.update_room(id, room_id, payload).await?;
+
+    Ok(Json(room))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/event-manager/venues/{id}/rooms/{room_id}",
+    params(
+        ("id" = i32, Path, description = "ID of the venue"),
+        ("room_id" = i32, Path, description = "ID of the room")
+    ),
+    responses(
+        (status = 204, description = "Room deleted"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the venue owner or admin can delete rooms"),
+        (status = 404, description = "Venue or room not found"),
+        (status = 409, description = "Events are still scheduled in this room")
+    ),
+    tag = "Venues",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn delete_room(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path((id, room_id)): Path<(i32, i32)>,
+) -> Result<impl IntoResponse, ApiError> {
+    if id < 0 || room_id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let venue = state.venue_repo.get_venue(id).await?;
+    Authorization::can_modify_resource(&user_claims, &venue, None)
+        .map_err(map_authorization_error)?;
+
+    state.venue_repo.delete_room(id, room_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,230 @@
+use crate::AppState;
+use crate::middleware::UserClaims;
+use crate::models::event::EventStatus;
+use crate::models::waitlist::{JoinWaitlist, WaitlistEntry, WaitlistStatus};
+use crate::services::waitlist::promote_waitlist;
+use crate::utils::error::{ApiError, SeatHoldRepoError};
+use axum::extract::rejection::JsonRejection;
+use axum::response::IntoResponse;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use std::sync::Arc;
+use validator::Validate;
+
+pub fn waitlist_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/events/{id}/waitlist", post(join_event_waitlist))
+        .route("/event-packets/{id}/waitlist", post(join_packet_waitlist))
+        .route(
+            "/waitlist/{id}",
+            get(get_waitlist_entry).delete(leave_waitlist),
+        )
+}
+
+fn ensure_clients_service(user_claims: &UserClaims) -> Result<(), ApiError> {
+    if !user_claims.is_clients_service() && !user_claims.is_admin() {
+        return Err(ApiError::Forbidden(
+            "Waitlists are joined through the client service".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+async fn owned_entry(
+    state: &AppState,
+    user_claims: &UserClaims,
+    id: i32,
+) -> Result<WaitlistEntry, ApiError> {
+    let entry = state.waitlist_repo.get_entry(id).await?;
+
+    if entry.user_id != user_claims.user_id
+        && !user_claims.is_clients_service()
+        && !user_claims.is_admin()
+    {
+        return Err(ApiError::Forbidden(
+            "You can only access your own waitlist entries".to_string(),
+        ));
+    }
+
+    Ok(entry)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/events/{id}/waitlist",
+    params(
+        ("id" = i32, Path, description = "ID of the event")
+    ),
+    request_body = JoinWaitlist,
+    responses(
+        (status = 201, description = "Joined the waitlist; an offer follows as soon as a seat is free", body = WaitlistEntry),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the client service can add users to a waitlist"),
+        (status = 404, description = "Event not found"),
+        (status = 409, description = "Event not on sale or user already waiting"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Waitlist",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn join_event_waitlist(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    payload: Result<Json<JoinWaitlist>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    ensure_clients_service(&user_claims)?;
+
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let event = state.event_repo.get_event(id).await?;
+    if event.status != EventStatus::Published {
+        return Err(ApiError::Conflict(format!(
+            "Cannot join the waitlist of an event that is {}",
+            event.status
+        )));
+    }
+
+    let entry = state.waitlist_repo.join_event_waitlist(id, payload).await?;
+
+    promote_waitlist(&state.rabbitmq, &state.waitlist_repo, Some(id), None).await;
+    let entry = state.waitlist_repo.get_entry(entry.id).await?;
+
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/event-manager/event-packets/{id}/waitlist",
+    params(
+        ("id" = i32, Path, description = "ID of the event packet")
+    ),
+    request_body = JoinWaitlist,
+    responses(
+        (status = 201, description = "Joined the waitlist; an offer follows as soon as a seat is free", body = WaitlistEntry),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Only the client service can add users to a waitlist"),
+        (status = 404, description = "Event packet not found"),
+        (status = 409, description = "Packet not on sale or user already waiting"),
+        (status = 422, description = "Validation failed")
+    ),
+    tag = "Waitlist",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn join_packet_waitlist(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+    payload: Result<Json<JoinWaitlist>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    ensure_clients_service(&user_claims)?;
+
+    let Json(payload) = payload?;
+    payload.validate()?;
+
+    if id < 0 {
+        return Err(ApiError::BadRequest("ID cannot be negative".into()));
+    }
+
+    let packet = state.event_packet_repo.get_event_packet(id).await?;
+    if packet.status != EventStatus::Published {
+        return Err(ApiError::Conflict(format!(
+            "Cannot join the waitlist of an event packet that is {}",
+            packet.status
+        )));
+    }
+
+    let entry = state
+        .waitlist_repo
+        .join_packet_waitlist(id, payload)
+        .await?;
+
+    promote_waitlist(&state.rabbitmq, &state.waitlist_repo, None, Some(id)).await;
+    let entry = state.waitlist_repo.get_entry(entry.id).await?;
+
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/event-manager/waitlist/{id}",
+    params(
+        ("id" = i32, Path, description = "Waitlist entry ID")
+    ),
+    responses(
+        (status = 200, description = "Waitlist entry found", body = WaitlistEntry),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Can only view own waitlist entries"),
+        (status = 404, description = "Waitlist entry not found")
+    ),
+    tag = "Waitlist",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn get_waitlist_entry(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    let entry = owned_entry(&state, &user_claims, id).await?;
+
+    Ok(Json(entry))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/event-manager/waitlist/{id}",
+    params(
+        ("id" = i32, Path, description = "Waitlist entry ID")
+    ),
+    responses(
+        (status = 204, description = "Left the waitlist; a pending offer is passed on to the next in line"),
+        (status = 401, description = "Missing or invalid authentication token"),
+        (status = 403, description = "Forbidden - Can only leave own waitlist entries"),
+        (status = 404, description = "Waitlist entry not found")
+    ),
+    tag = "Waitlist",
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+pub async fn leave_waitlist(
+    State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ApiError> {
+    owned_entry(&state, &user_claims, id).await?;
+    let entry = state.waitlist_repo.leave(id).await?;
+
+    if entry.status == WaitlistStatus::Offered
+        && let Some(hold_id) = entry.hold_id.as_deref()
+    {
+        match state.seat_hold_repo.release_hold(hold_id).await {
+            Ok(_) | Err(SeatHoldRepoError::NotFound) => {}
+            Err(e) => return Err(e.into()),
+        }
+        promote_waitlist(
+            &state.rabbitmq,
+            &state.waitlist_repo,
+            entry.event_id,
+            entry.packet_id,
+        )
+        .await;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -5,12 +5,25 @@ pub mod repositories;
 pub mod services;
 pub mod utils;
 
+use crate::repositories::analytics_repo::AnalyticsRepo;
+use crate::repositories::check_in_repo::CheckInRepo;
 use crate::repositories::event_packets_repo::EventPacketRepo;
 use crate::repositories::event_repo::EventRepo;
+use crate::repositories::idempotency_repo::IdempotencyRepo;
+use crate::repositories::import_repo::ImportRepo;
 use crate::repositories::join_pe_repo::JoinPeRepo;
+use crate::repositories::price_tier_repo::PriceTierRepo;
+use crate::repositories::promo_code_repo::PromoCodeRepo;
 use crate::repositories::refund_repo::RefundRepo;
+use crate::repositories::search_repo::SearchRepo;
+use crate::repositories::seat_hold_repo::SeatHoldRepo;
+use crate::repositories::seat_repo::SeatRepo;
 use crate::repositories::ticket_repo::TicketRepo;
+use crate::repositories::venue_repo::VenueRepo;
+use crate::repositories::waitlist_repo::WaitlistRepo;
+use crate::services::client_service::ClientServiceClient;
 use common::rabbitmq::RabbitMQ;
+use common::ticket_pass::TicketVerifier;
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -19,7 +32,20 @@ pub struct AppState {
     pub event_packet_repo: Arc<EventPacketRepo>,
     pub ticket_repo: Arc<TicketRepo>,
     pub join_repo: Arc<JoinPeRepo>,
+    pub price_tier_repo: Arc<PriceTierRepo>,
+    pub promo_code_repo: Arc<PromoCodeRepo>,
     pub refund_repo: Arc<RefundRepo>,
+    pub seat_hold_repo: Arc<SeatHoldRepo>,
+    pub waitlist_repo: Arc<WaitlistRepo>,
+    pub idempotency_repo: Arc<IdempotencyRepo>,
+    pub check_in_repo: Arc<CheckInRepo>,
+    pub venue_repo: Arc<VenueRepo>,
+    pub seat_repo: Arc<SeatRepo>,
+    pub search_repo: Arc<SearchRepo>,
+    pub analytics_repo: Arc<AnalyticsRepo>,
+    pub import_repo: Arc<ImportRepo>,
+    pub client_service: Arc<ClientServiceClient>,
+    pub ticket_verifier: Option<Arc<TicketVerifier>>,
     pub rabbitmq: Arc<RabbitMQ>,
     pub base_url: String,
     pub auth_service_url: String,
@@ -1,15 +1,29 @@
 use anyhow::Result;
+use axum::http::HeaderName;
 use axum::middleware;
 use axum::{Router, extract::State, routing::get};
 use axum_prometheus::PrometheusMetricLayer;
+use common::pagination::{NEXT_CURSOR_HEADER, TOTAL_COUNT_HEADER};
 use common::rabbitmq::RabbitMQ;
+use common::ticket_pass::TicketVerifier;
 use event_service::middleware::auth::auth_middleware;
+use event_service::middleware::auth::middleware::AuthRequest;
+use event_service::middleware::auth::middleware::auth_service_client::AuthServiceClient;
+use event_service::services::client_service::ClientServiceClient;
+use event_service::services::hold_expiry::HoldExpiryWorker;
+use event_service::services::idempotency_purge::IdempotencyPurgeWorker;
+use event_service::services::outbox_relay::OutboxRelay;
 use event_service::services::refund_consumer::RefundRequestConsumer;
 use event_service::{
     AppState, handlers,
     repositories::{
-        event_packets_repo::EventPacketRepo, event_repo::EventRepo, join_pe_repo::JoinPeRepo,
-        refund_repo::RefundRepo, ticket_repo::TicketRepo,
+        analytics_repo::AnalyticsRepo, check_in_repo::CheckInRepo,
+        event_packets_repo::EventPacketRepo, event_repo::EventRepo,
+        idempotency_repo::IdempotencyRepo, import_repo::ImportRepo, join_pe_repo::JoinPeRepo,
+        outbox_repo::OutboxRepo, price_tier_repo::PriceTierRepo, promo_code_repo::PromoCodeRepo,
+        refund_repo::RefundRepo, search_repo::SearchRepo, seat_hold_repo::SeatHoldRepo,
+        seat_repo::SeatRepo, ticket_repo::TicketRepo, venue_repo::VenueRepo,
+        waitlist_repo::WaitlistRepo,
     },
 };
 use sqlx::postgres::PgPoolOptions;
@@ -53,14 +67,69 @@ async fn main() -> Result<()> {
     let auth_service_url = std::env::var("AUTH_SERVICE_URL")
         .unwrap_or_else(|_| "http://auth-service:50051".to_string());
 
+    let client_service_url = std::env::var("CLIENT_SERVICE_URL")
+        .unwrap_or_else(|_| "http://client-service:8080".to_string());
+
+    let service_username = std::env::var("SERVICE_USERNAME").expect("SERVICE_USERNAME must be set");
+    let service_password = std::env::var("SERVICE_PASSWORD").expect("SERVICE_PASSWORD must be set");
+
+    info!("{:<12} - Authenticating as service user...", "AUTH");
+
+    let mut auth_client = AuthServiceClient::connect(auth_service_url.clone())
+        .await
+        .expect("Failed to connect to auth service for service authentication");
+
+    let auth_response = auth_client
+        .authenticate(tonic::Request::new(AuthRequest {
+            username: service_username,
+            password: service_password,
+        }))
+        .await
+        .expect("Failed to authenticate service user")
+        .into_inner();
+
+    if !auth_response.success {
+        panic!("Service authentication failed: {}", auth_response.message);
+    }
+
+    let service_token = auth_response.token_value;
+    info!("{:<12} - Service authentication successful", "AUTH");
+
+    let ticket_verifier = match std::env::var("TICKET_VERIFY_KEY") {
+        Ok(key) if !key.is_empty() => Some(Arc::new(
+            TicketVerifier::from_public_key(&key).expect("TICKET_VERIFY_KEY is not a valid key"),
+        )),
+        _ => {
+            warn!("TICKET_VERIFY_KEY is not set. Check-in will only accept ticket codes.");
+            None
+        }
+    };
+
     let refund_repo = Arc::new(RefundRepo::new(pool.clone()));
+    let ticket_repo = Arc::new(TicketRepo::new(pool.clone()));
+    let seat_hold_repo = Arc::new(SeatHoldRepo::new(pool.clone()));
+    let idempotency_repo = Arc::new(IdempotencyRepo::new(pool.clone()));
+    let waitlist_repo = Arc::new(WaitlistRepo::new(pool.clone()));
 
     let app_state = Arc::new(AppState {
         event_repo: Arc::new(EventRepo::new(pool.clone())),
         event_packet_repo: Arc::new(EventPacketRepo::new(pool.clone())),
-        ticket_repo: Arc::new(TicketRepo::new(pool.clone())),
+        ticket_repo: Arc::clone(&ticket_repo),
         join_repo: Arc::new(JoinPeRepo::new(pool.clone())),
+        price_tier_repo: Arc::new(PriceTierRepo::new(pool.clone())),
+        promo_code_repo: Arc::new(PromoCodeRepo::new(pool.clone())),
         refund_repo: Arc::clone(&refund_repo),
+        seat_hold_repo: Arc::clone(&seat_hold_repo),
+        waitlist_repo: Arc::clone(&waitlist_repo),
+        idempotency_repo: Arc::clone(&idempotency_repo),
+        check_in_repo: Arc::new(CheckInRepo::new(pool.clone())),
+        venue_repo: Arc::new(VenueRepo::new(pool.clone())),
+        seat_repo: Arc::new(SeatRepo::new(pool.clone())),
+        search_repo: Arc::new(SearchRepo::new(pool.clone())),
+        analytics_repo: Arc::new(AnalyticsRepo::new(pool.clone())),
+        import_repo: Arc::new(ImportRepo::new(pool.clone())),
+        client_service: Arc::new(ClientServiceClient::new(client_service_url, service_token)),
+        ticket_verifier,
         rabbitmq: Arc::clone(&rabbitmq),
         base_url: "http://localhost:8001/api/event-manager".to_string(),
         auth_service_url,
@@ -68,17 +137,43 @@ async fn main() -> Result<()> {
 
     let consumer_rabbitmq = Arc::clone(&rabbitmq);
     let consumer_refund_repo = Arc::clone(&refund_repo);
+    let consumer_ticket_repo = Arc::clone(&ticket_repo);
     tokio::spawn(async move {
-        let consumer = RefundRequestConsumer::new(consumer_rabbitmq, consumer_refund_repo);
+        let consumer = RefundRequestConsumer::new(
+            consumer_rabbitmq,
+            consumer_refund_repo,
+            consumer_ticket_repo,
+        );
         if let Err(e) = consumer.start().await {
             error!("Refund request consumer error: {:?}", e);
         }
     });
 
+    let expiry_rabbitmq = Arc::clone(&rabbitmq);
+    tokio::spawn(async move {
+        HoldExpiryWorker::new(expiry_rabbitmq, seat_hold_repo, waitlist_repo)
+            .start()
+            .await;
+    });
+
+    tokio::spawn(async move {
+        IdempotencyPurgeWorker::new(idempotency_repo).start().await;
+    });
+
+    let relay_rabbitmq = Arc::clone(&rabbitmq);
+    let outbox_repo = Arc::new(OutboxRepo::new(pool.clone()));
+    tokio::spawn(async move {
+        OutboxRelay::new(relay_rabbitmq, outbox_repo).start().await;
+    });
+
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
-        .allow_headers(Any);
+        .allow_headers(Any)
+        .expose_headers([
+            HeaderName::from_static(TOTAL_COUNT_HEADER),
+            HeaderName::from_static(NEXT_CURSOR_HEADER),
+        ]);
 
     let (prometheus_layer, metric_handle) = PrometheusMetricLayer::pair();
 
@@ -0,0 +1,164 @@
+use crate::models::event::EventStatus;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl Granularity {
+    /// Field name understood by `date_trunc`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Granularity::Day => "day",
+            Granularity::Week => "week",
+            Granularity::Month => "month",
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_analytics_query"))]
+#[serde(deny_unknown_fields)]
+pub struct AnalyticsQuery {
+    /// Admins only; owners always see their own events and packets.
+    pub owner_id: Option<i32>,
+    pub event_id: Option<i32>,
+    pub packet_id: Option<i32>,
+    /// Only tickets sold at or after this RFC 3339 instant.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub from: Option<DateTime<Utc>>,
+    /// Only tickets sold before this RFC 3339 instant.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub to: Option<DateTime<Utc>>,
+    pub granularity: Option<Granularity>,
+}
+
+/// Tickets sold in one period, split by currency; free tickets have none.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct SalesBucket {
+    pub period_start: DateTime<Utc>,
+    pub currency: Option<String>,
+    pub tickets_sold: i64,
+    pub revenue_minor: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SalesSummary {
+    pub tickets_sold: i64,
+    pub tickets_refunded: i64,
+    pub refund_rate: Option<f64>,
+    pub tickets_checked_in: i64,
+    pub check_in_rate: Option<f64>,
+}
+
+/// Counts behind `SalesSummary`, read in one row.
+#[derive(Debug, FromRow)]
+pub struct SalesTotals {
+    pub tickets_sold: i64,
+    pub tickets_refunded: i64,
+    pub tickets_checked_in: i64,
+}
+
+#[derive(Debug, FromRow)]
+pub struct SellThroughRow {
+    pub id: i32,
+    pub name: String,
+    pub status: EventStatus,
+    pub tickets_sold: i64,
+    pub tickets_refunded: i64,
+    pub tickets_checked_in: i64,
+    pub seats_remaining: Option<i32>,
+}
+
+/// Sell-through of an event or packet. Event figures include the tickets of
+/// every packet the event is part of, since those hold a seat at the event too.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SellThrough {
+    pub id: i32,
+    pub name: String,
+    pub status: EventStatus,
+    pub tickets_sold: i64,
+    pub seats_remaining: Option<i32>,
+    /// Share of all seats that were sold; absent when capacity is unlimited.
+    pub sell_through: Option<f64>,
+    pub tickets_refunded: i64,
+    pub refund_rate: Option<f64>,
+    pub tickets_checked_in: i64,
+    pub check_in_rate: Option<f64>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct TierRevenue {
+    pub tier_id: i32,
+    pub tier_name: String,
+    pub event_id: Option<i32>,
+    pub packet_id: Option<i32>,
+    pub currency: String,
+    pub tickets_sold: i64,
+    /// Paid amounts, net of approved refunds.
+    pub revenue_minor: i64,
+    pub refunded_minor: i64,
+    pub discount_minor: i64,
+}
+
+/// `part / whole`, or nothing when there is nothing to divide by.
+pub fn ratio(part: i64, whole: i64) -> Option<f64> {
+    (whole > 0).then(|| part as f64 / whole as f64)
+}
+
+impl From<SalesTotals> for SalesSummary {
+    fn from(totals: SalesTotals) -> Self {
+        Self {
+            tickets_sold: totals.tickets_sold,
+            tickets_refunded: totals.tickets_refunded,
+            refund_rate: ratio(totals.tickets_refunded, totals.tickets_sold),
+            tickets_checked_in: totals.tickets_checked_in,
+            check_in_rate: ratio(totals.tickets_checked_in, totals.tickets_sold),
+        }
+    }
+}
+
+impl From<SellThroughRow> for SellThrough {
+    fn from(row: SellThroughRow) -> Self {
+        let sell_through = row
+            .seats_remaining
+            .and_then(|remaining| ratio(row.tickets_sold, row.tickets_sold + i64::from(remaining)));
+
+        Self {
+            id: row.id,
+            name: row.name,
+            status: row.status,
+            tickets_sold: row.tickets_sold,
+            seats_remaining: row.seats_remaining,
+            sell_through,
+            tickets_refunded: row.tickets_refunded,
+            refund_rate: ratio(row.tickets_refunded, row.tickets_sold),
+            tickets_checked_in: row.tickets_checked_in,
+            check_in_rate: ratio(row.tickets_checked_in, row.tickets_sold),
+        }
+    }
+}
+
+fn validate_analytics_query(query: &AnalyticsQuery) -> Result<(), ValidationError> {
+    if query.event_id.is_some() && query.packet_id.is_some() {
+        return Err(ValidationError::new("scope")
+            .with_message("Pass either event_id or packet_id, not both".into()));
+    }
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from >= to
+    {
+        return Err(ValidationError::new("interval").with_message("from must be before to".into()));
+    }
+    Ok(())
+}
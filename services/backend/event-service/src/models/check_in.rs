@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct CheckIn {
+    pub ticket_cod: String,
+    pub event_id: i32,
+    pub gate: String,
+    pub scanned_by: i32,
+    pub checked_in_at: DateTime<Utc>,
+}
+
+/// A scan at the door: either the bare ticket code or the signed pass read
+/// from the ticket's QR code.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_check_in"))]
+#[serde(deny_unknown_fields)]
+pub struct CheckInRequest {
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Ticket code must be between 1 and 50 characters"
+    ))]
+    pub cod: Option<String>,
+    #[validate(length(
+        min = 1,
+        max = 2048,
+        message = "Pass must be between 1 and 2048 characters"
+    ))]
+    pub pass: Option<String>,
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Gate must be between 1 and 50 characters"
+    ))]
+    pub gate: String,
+}
+
+fn validate_check_in(request: &CheckInRequest) -> Result<(), ValidationError> {
+    if request.cod.is_some() == request.pass.is_some() {
+        let mut err = ValidationError::new("exclusive_ticket");
+        err.message = Some("Provide EITHER a ticket code OR a pass.".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct GateAttendance {
+    pub gate: String,
+    pub checked_in: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Attendance {
+    pub event_id: i32,
+    /// Tickets for the event itself plus tickets for packets that include it.
+    pub tickets_sold: i64,
+    pub checked_in: i64,
+    pub gates: Vec<GateAttendance>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct EventScanner {
+    pub event_id: i32,
+    pub user_id: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct AddScanner {
+    #[validate(range(min = 0, message = "User ID cannot be negative"))]
+    pub user_id: i32,
+}
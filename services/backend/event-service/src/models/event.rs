@@ -1,8 +1,91 @@
+use crate::models::venue::validate_coordinates;
+use chrono::{DateTime, Utc};
 use common::authorization::{ResourceOwnership, UserClaims};
 use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
 use sqlx::prelude::FromRow;
 use utoipa::ToSchema;
-use validator::Validate;
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[sqlx(type_name = "varchar", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EventStatus {
+    Draft,
+    Published,
+    SalesClosed,
+    Cancelled,
+}
+
+impl EventStatus {
+    pub fn can_transition_to(self, next: EventStatus) -> bool {
+        use EventStatus::*;
+        matches!(
+            (self, next),
+            (Draft, Published)
+                | (Published, SalesClosed)
+                | (SalesClosed, Published)
+                | (Draft | Published | SalesClosed, Cancelled)
+        )
+    }
+}
+
+impl std::fmt::Display for EventStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            EventStatus::Draft => "DRAFT",
+            EventStatus::Published => "PUBLISHED",
+            EventStatus::SalesClosed => "SALES_CLOSED",
+            EventStatus::Cancelled => "CANCELLED",
+        };
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[sqlx(type_name = "varchar", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EventCategory {
+    ProgrammingLanguages,
+    Web,
+    Mobile,
+    CloudDevops,
+    DataAi,
+    Security,
+    Other,
+}
+
+impl std::fmt::Display for EventCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            EventCategory::ProgrammingLanguages => "PROGRAMMING_LANGUAGES",
+            EventCategory::Web => "WEB",
+            EventCategory::Mobile => "MOBILE",
+            EventCategory::CloudDevops => "CLOUD_DEVOPS",
+            EventCategory::DataAi => "DATA_AI",
+            EventCategory::Security => "SECURITY",
+            EventCategory::Other => "OTHER",
+        };
+        f.write_str(s)
+    }
+}
+
+impl std::str::FromStr for EventCategory {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "PROGRAMMING_LANGUAGES" => Ok(EventCategory::ProgrammingLanguages),
+            "WEB" => Ok(EventCategory::Web),
+            "MOBILE" => Ok(EventCategory::Mobile),
+            "CLOUD_DEVOPS" => Ok(EventCategory::CloudDevops),
+            "DATA_AI" => Ok(EventCategory::DataAi),
+            "SECURITY" => Ok(EventCategory::Security),
+            "OTHER" => Ok(EventCategory::Other),
+            _ => Err(()),
+        }
+    }
+}
 
 #[derive(Debug, Deserialize, Serialize, FromRow, ToSchema)]
 pub struct Event {
@@ -14,6 +97,20 @@ pub struct Event {
     #[serde(rename = "numarlocuri")]
     #[sqlx(rename = "numarlocuri")]
     pub locuri: Option<i32>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub timezone: Option<String>,
+    pub status: EventStatus,
+    /// Venue room the event is held in; a room hosts one event at a time.
+    pub room_id: Option<i32>,
+    pub category: Option<EventCategory>,
+    pub tags: Vec<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Kilometres from the `near` point of a listing; absent elsewhere.
+    #[sqlx(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
 }
 
 impl ResourceOwnership for Event {
@@ -27,6 +124,7 @@ impl ResourceOwnership for Event {
 }
 
 #[derive(Debug, Deserialize, Serialize, FromRow, ToSchema, Validate)]
+#[validate(schema(function = "validate_create_event"))]
 #[serde(deny_unknown_fields)]
 pub struct CreateEvent {
     #[validate(length(
@@ -47,9 +145,27 @@ pub struct CreateEvent {
     #[serde(rename = "numarlocuri")]
     #[sqlx(rename = "numarlocuri")]
     pub locuri: Option<i32>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    #[validate(custom(function = "validate_timezone"))]
+    pub timezone: Option<String>,
+    #[validate(range(min = 0, message = "Room ID cannot be negative"))]
+    pub room_id: Option<i32>,
+    pub category: Option<EventCategory>,
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Option<Vec<String>>,
+    #[validate(range(min = -90.0, max = 90.0, message = "Latitude must be between -90 and 90"))]
+    pub latitude: Option<f64>,
+    #[validate(range(
+        min = -180.0,
+        max = 180.0,
+        message = "Longitude must be between -180 and 180"
+    ))]
+    pub longitude: Option<f64>,
 }
 
 #[derive(Debug, Deserialize, Serialize, FromRow, ToSchema, Validate)]
+#[validate(schema(function = "validate_update_event"))]
 #[serde(deny_unknown_fields)]
 pub struct UpdateEvent {
     pub id_owner: Option<i32>,
@@ -71,9 +187,27 @@ pub struct UpdateEvent {
     #[serde(rename = "numarlocuri")]
     #[sqlx(rename = "numarlocuri")]
     pub locuri: Option<i32>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    #[validate(custom(function = "validate_timezone"))]
+    pub timezone: Option<String>,
+    #[validate(range(min = 0, message = "Room ID cannot be negative"))]
+    pub room_id: Option<i32>,
+    pub category: Option<EventCategory>,
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Option<Vec<String>>,
+    #[validate(range(min = -90.0, max = 90.0, message = "Latitude must be between -90 and 90"))]
+    pub latitude: Option<f64>,
+    #[validate(range(
+        min = -180.0,
+        max = 180.0,
+        message = "Longitude must be between -180 and 180"
+    ))]
+    pub longitude: Option<f64>,
 }
 
 #[derive(Debug, Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_patch_event"))]
 pub struct PatchEvent {
     pub id_owner: Option<i32>,
     #[validate(length(
@@ -93,9 +227,28 @@ pub struct PatchEvent {
     #[validate(range(min = 1, max = 50000, message = "Seats must be between 1 and 50,000"))]
     #[serde(rename = "numarlocuri")]
     pub locuri: Option<i32>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    #[validate(custom(function = "validate_timezone"))]
+    pub timezone: Option<String>,
+    #[validate(range(min = 0, message = "Room ID cannot be negative"))]
+    pub room_id: Option<i32>,
+    pub category: Option<EventCategory>,
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Option<Vec<String>>,
+    #[validate(range(min = -90.0, max = 90.0, message = "Latitude must be between -90 and 90"))]
+    pub latitude: Option<f64>,
+    #[validate(range(
+        min = -180.0,
+        max = 180.0,
+        message = "Longitude must be between -180 and 180"
+    ))]
+    pub longitude: Option<f64>,
 }
 
+#[serde_as]
 #[derive(Deserialize, Clone, ToSchema, Validate)]
+#[validate(schema(function = "validate_event_query"))]
 #[serde(deny_unknown_fields)]
 pub struct EventQuery {
     #[validate(length(max = 50, message = "Location filter must be less than 50 characters"))]
@@ -105,7 +258,194 @@ pub struct EventQuery {
     #[serde(rename = "name")]
     pub nume: Option<String>,
     pub owner_id: Option<i32>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub from: Option<DateTime<Utc>>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub to: Option<DateTime<Utc>>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub upcoming_only: Option<bool>,
+    /// Comma-separated categories; an event matches any of them.
+    #[validate(custom(function = "validate_category_filter"))]
+    pub category: Option<String>,
+    /// Comma-separated tags; an event matches if it has any of them.
+    #[validate(custom(function = "validate_tag_filter"))]
+    pub tags: Option<String>,
+    /// `lat,lng` to measure distances from; results are sorted nearest first.
+    #[validate(custom(function = "validate_near"))]
+    pub near: Option<String>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[validate(range(
+        min = 1.0,
+        max = 20000.0,
+        message = "Radius must be between 1 and 20,000 km"
+    ))]
+    pub radius_km: Option<f64>,
     #[serde(flatten)]
     #[validate(nested)]
     pub paginare: crate::models::event_packets::PaginationParams,
 }
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: i64,
+}
+
+/// Counts for the filter sidebar. Each dimension is counted with every other
+/// filter applied but not its own, so picking a category keeps the others visible.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Facets {
+    pub categories: Vec<FacetCount>,
+    pub tags: Vec<FacetCount>,
+}
+
+const MAX_TAGS: usize = 10;
+const MAX_TAG_LENGTH: usize = 30;
+
+fn is_valid_tag(tag: &str) -> bool {
+    let tag = tag.trim();
+    !tag.is_empty()
+        && tag.chars().count() <= MAX_TAG_LENGTH
+        && tag
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '+' | '#' | '.'))
+}
+
+/// Tags are stored trimmed, lowercased and without duplicates.
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+pub fn parse_category_filter(filter: Option<&str>) -> Vec<String> {
+    filter
+        .unwrap_or_default()
+        .split(',')
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty())
+        .collect()
+}
+
+pub fn parse_tag_filter(filter: Option<&str>) -> Vec<String> {
+    filter
+        .unwrap_or_default()
+        .split(',')
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Parses a `lat,lng` pair, e.g. `44.4268,26.1025`.
+pub fn parse_near(near: &str) -> Option<(f64, f64)> {
+    let (lat, lng) = near.split_once(',')?;
+    let lat: f64 = lat.trim().parse().ok()?;
+    let lng: f64 = lng.trim().parse().ok()?;
+    ((-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lng)).then_some((lat, lng))
+}
+
+pub(crate) fn validate_near(near: &str) -> Result<(), ValidationError> {
+    if parse_near(near).is_none() {
+        let mut err = ValidationError::new("invalid_near");
+        err.message = Some("Near must be 'latitude,longitude' (e.g. 44.4268,26.1025)".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_radius(
+    near: Option<&str>,
+    radius_km: Option<f64>,
+) -> Result<(), ValidationError> {
+    if radius_km.is_some() && near.is_none() {
+        let mut err = ValidationError::new("radius_without_near");
+        err.message = Some("radius_km requires a near point".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_tags(tags: &[String]) -> Result<(), ValidationError> {
+    if tags.len() > MAX_TAGS {
+        let mut err = ValidationError::new("too_many_tags");
+        err.message = Some(format!("At most {MAX_TAGS} tags are allowed").into());
+        return Err(err);
+    }
+    if !tags.iter().all(|tag| is_valid_tag(tag)) {
+        let mut err = ValidationError::new("invalid_tag");
+        err.message = Some(
+            format!(
+                "Tags must be 1-{MAX_TAG_LENGTH} characters of letters, digits, '-', '+', '#' or '.'"
+            )
+            .into(),
+        );
+        return Err(err);
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_category_filter(filter: &str) -> Result<(), ValidationError> {
+    let valid = parse_category_filter(Some(filter))
+        .iter()
+        .all(|c| c.parse::<EventCategory>().is_ok());
+    if !valid {
+        let mut err = ValidationError::new("invalid_category");
+        err.message = Some(
+            "Category must be one of PROGRAMMING_LANGUAGES, WEB, MOBILE, CLOUD_DEVOPS, DATA_AI, SECURITY, OTHER"
+                .into(),
+        );
+        return Err(err);
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_tag_filter(filter: &str) -> Result<(), ValidationError> {
+    validate_tags(&parse_tag_filter(Some(filter)))
+}
+
+fn validate_timezone(timezone: &str) -> Result<(), ValidationError> {
+    if timezone.parse::<chrono_tz::Tz>().is_err() {
+        let mut err = ValidationError::new("invalid_timezone");
+        err.message = Some("Timezone must be a valid IANA time zone (e.g. Europe/Bucharest)".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+fn validate_interval(
+    starts_at: Option<DateTime<Utc>>,
+    ends_at: Option<DateTime<Utc>>,
+) -> Result<(), ValidationError> {
+    match (starts_at, ends_at) {
+        (Some(start), Some(end)) if end <= start => {
+            let mut err = ValidationError::new("invalid_interval");
+            err.message = Some("Event end time must be after its start time".into());
+            Err(err)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate_create_event(event: &CreateEvent) -> Result<(), ValidationError> {
+    validate_interval(event.starts_at, event.ends_at)?;
+    validate_coordinates(event.latitude, event.longitude)
+}
+
+fn validate_update_event(event: &UpdateEvent) -> Result<(), ValidationError> {
+    validate_interval(event.starts_at, event.ends_at)?;
+    validate_coordinates(event.latitude, event.longitude)
+}
+
+fn validate_patch_event(event: &PatchEvent) -> Result<(), ValidationError> {
+    validate_interval(event.starts_at, event.ends_at)?;
+    validate_coordinates(event.latitude, event.longitude)
+}
+
+fn validate_event_query(query: &EventQuery) -> Result<(), ValidationError> {
+    validate_radius(query.near.as_deref(), query.radius_km)
+}
@@ -1,9 +1,15 @@
+use crate::models::event::{
+    EventCategory, EventStatus, validate_category_filter, validate_near, validate_radius,
+    validate_tag_filter, validate_tags,
+};
+use crate::models::venue::validate_coordinates;
+use chrono::{DateTime, Utc};
 use common::authorization::{ResourceOwnership, UserClaims};
 use serde::{Deserialize, Serialize};
 use serde_with::{DisplayFromStr, serde_as};
 use sqlx::prelude::FromRow;
 use utoipa::ToSchema;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 #[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct EventPackets {
@@ -13,6 +19,17 @@ pub struct EventPackets {
     pub locatie: Option<String>,
     pub descriere: Option<String>,
     pub numarlocuri: Option<i32>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub status: EventStatus,
+    pub category: Option<EventCategory>,
+    pub tags: Vec<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Kilometres from the `near` point of a listing; absent elsewhere.
+    #[sqlx(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
 }
 
 impl ResourceOwnership for EventPackets {
@@ -26,6 +43,7 @@ impl ResourceOwnership for EventPackets {
 }
 
 #[derive(Debug, Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_create_event_packet"))]
 #[serde(deny_unknown_fields)]
 pub struct CreateEventPacket {
     #[validate(length(
@@ -44,9 +62,21 @@ pub struct CreateEventPacket {
     pub descriere: Option<String>,
     #[validate(range(min = 1, max = 50000, message = "Seats must be between 1 and 50,000"))]
     pub numarlocuri: Option<i32>,
+    pub category: Option<EventCategory>,
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Option<Vec<String>>,
+    #[validate(range(min = -90.0, max = 90.0, message = "Latitude must be between -90 and 90"))]
+    pub latitude: Option<f64>,
+    #[validate(range(
+        min = -180.0,
+        max = 180.0,
+        message = "Longitude must be between -180 and 180"
+    ))]
+    pub longitude: Option<f64>,
 }
 
 #[derive(Debug, Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_update_event_packet"))]
 #[serde(deny_unknown_fields)]
 pub struct UpdateEventPacket {
     pub id_owner: Option<i32>,
@@ -66,9 +96,21 @@ pub struct UpdateEventPacket {
     pub descriere: Option<String>,
     #[validate(range(min = 1, max = 50000, message = "Seats must be between 1 and 50,000"))]
     pub numarlocuri: Option<i32>,
+    pub category: Option<EventCategory>,
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Option<Vec<String>>,
+    #[validate(range(min = -90.0, max = 90.0, message = "Latitude must be between -90 and 90"))]
+    pub latitude: Option<f64>,
+    #[validate(range(
+        min = -180.0,
+        max = 180.0,
+        message = "Longitude must be between -180 and 180"
+    ))]
+    pub longitude: Option<f64>,
 }
 
 #[derive(Debug, Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_patch_event_packet"))]
 pub struct PatchEventPacket {
     pub id_owner: Option<i32>,
     #[validate(length(
@@ -87,9 +129,22 @@ pub struct PatchEventPacket {
     pub descriere: Option<String>,
     #[validate(range(min = 1, max = 50000, message = "Seats must be between 1 and 50,000"))]
     pub numarlocuri: Option<i32>,
+    pub category: Option<EventCategory>,
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Option<Vec<String>>,
+    #[validate(range(min = -90.0, max = 90.0, message = "Latitude must be between -90 and 90"))]
+    pub latitude: Option<f64>,
+    #[validate(range(
+        min = -180.0,
+        max = 180.0,
+        message = "Longitude must be between -180 and 180"
+    ))]
+    pub longitude: Option<f64>,
 }
 
+#[serde_as]
 #[derive(Debug, Deserialize, Clone, ToSchema, Validate)]
+#[validate(schema(function = "validate_event_packet_query"))]
 #[serde(deny_unknown_fields)]
 pub struct EventPacketQuery {
     #[serde(rename = "type")]
@@ -106,6 +161,22 @@ pub struct EventPacketQuery {
     ))]
     #[serde(rename = "available_tickets")]
     pub bilete: Option<i32>,
+    /// Comma-separated categories; a packet matches any of them.
+    #[validate(custom(function = "validate_category_filter"))]
+    pub category: Option<String>,
+    /// Comma-separated tags; a packet matches if it has any of them.
+    #[validate(custom(function = "validate_tag_filter"))]
+    pub tags: Option<String>,
+    /// `lat,lng` to measure distances from; results are sorted nearest first.
+    #[validate(custom(function = "validate_near"))]
+    pub near: Option<String>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[validate(range(
+        min = 1.0,
+        max = 20000.0,
+        message = "Radius must be between 1 and 20,000 km"
+    ))]
+    pub radius_km: Option<f64>,
     #[serde(flatten)]
     #[validate(nested)]
     pub paginare: PaginationParams,
@@ -125,4 +196,26 @@ pub struct PaginationParams {
         message = "Items per page must be between 1 and 100"
     ))]
     pub items_per_page: Option<i64>,
+    /// Opaque position taken from a `next`/`prev` link; wins over `page`.
+    #[validate(length(max = 512, message = "Cursor must be less than 512 characters"))]
+    pub cursor: Option<String>,
+    /// Also count every matching row and return it in `X-Total-Count`.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub include_total: Option<bool>,
+}
+
+fn validate_create_event_packet(packet: &CreateEventPacket) -> Result<(), ValidationError> {
+    validate_coordinates(packet.latitude, packet.longitude)
+}
+
+fn validate_update_event_packet(packet: &UpdateEventPacket) -> Result<(), ValidationError> {
+    validate_coordinates(packet.latitude, packet.longitude)
+}
+
+fn validate_patch_event_packet(packet: &PatchEventPacket) -> Result<(), ValidationError> {
+    validate_coordinates(packet.latitude, packet.longitude)
+}
+
+fn validate_event_packet_query(query: &EventPacketQuery) -> Result<(), ValidationError> {
+    validate_radius(query.near.as_deref(), query.radius_km)
 }
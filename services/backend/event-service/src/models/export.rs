@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Xlsx,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExportQuery {
+    pub format: Option<ExportFormat>,
+}
+
+/// A ticket holder as listed in an export; check-in refers to the exported
+/// event (for packets, to the first event scanned).
+#[derive(Debug, Clone, FromRow)]
+pub struct Attendee {
+    pub cod: String,
+    pub purchased_at: DateTime<Utc>,
+    pub tier_name: Option<String>,
+    pub price_minor: Option<i64>,
+    pub currency: Option<String>,
+    pub seat_label: Option<String>,
+    pub checked_in_at: Option<DateTime<Utc>>,
+    pub checked_in_gate: Option<String>,
+}
+
+/// Buyer of a ticket as returned by client-service; the email and names are
+/// only sent for clients who made their info public.
+#[derive(Debug, Deserialize)]
+pub struct TicketBuyer {
+    pub ticket_code: String,
+    pub email: Option<String>,
+    pub prenume: Option<String>,
+    pub nume: Option<String>,
+    pub public_info: bool,
+}
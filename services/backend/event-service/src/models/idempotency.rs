@@ -0,0 +1,9 @@
+/// Outcome of trying to claim an idempotency key before running a request.
+#[derive(Debug)]
+pub enum IdempotencyClaim {
+    Claimed,
+    InProgress,
+    /// The key was first used with a different request.
+    Mismatch,
+    Completed { status: u16, body: Option<String> },
+}
@@ -0,0 +1,98 @@
+use crate::models::event::CreateEvent;
+use crate::models::event_packets::CreateEventPacket;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// JSON form of an import. Rows are kept raw so that one malformed row is
+/// reported on its own instead of rejecting the whole document.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ImportRequest {
+    #[serde(default)]
+    #[schema(value_type = Vec<CreateEvent>)]
+    pub events: Vec<serde_json::Value>,
+    #[serde(default)]
+    #[schema(value_type = Vec<ImportPacket>)]
+    pub packets: Vec<serde_json::Value>,
+}
+
+/// Shape of a packet row. `events` is split off before the rest is read as a
+/// `CreateEventPacket`, since unknown fields are not rejected through a
+/// flattened struct.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportPacket {
+    #[serde(flatten)]
+    pub packet: CreateEventPacket,
+    /// Names of the events in the packet: events of the same import, or
+    /// existing events of the importing owner.
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImportQuery {
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ImportRowKind {
+    Event,
+    Packet,
+}
+
+/// One uploaded row before it is parsed.
+#[derive(Debug)]
+pub struct ImportRow {
+    pub kind: ImportRowKind,
+    pub row: usize,
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportRowResult {
+    pub kind: ImportRowKind,
+    /// Line of the row in a CSV upload, or its position in the JSON array,
+    /// both counted from 1.
+    pub row: usize,
+    pub nume: Option<String>,
+    /// ID of the created event or packet; only set once the import is committed.
+    pub id: Option<i32>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    /// Whether the rows were written. Imports are all or nothing: a single
+    /// failing row leaves the database untouched.
+    pub committed: bool,
+    pub events_created: usize,
+    pub packets_created: usize,
+    pub links_created: usize,
+    pub rows: Vec<ImportRowResult>,
+}
+
+/// Event that passed validation, with the index of its row in the report.
+#[derive(Debug)]
+pub struct ImportedEvent {
+    pub index: usize,
+    pub event: CreateEvent,
+}
+
+#[derive(Debug)]
+pub struct ImportedPacket {
+    pub index: usize,
+    pub packet: CreateEventPacket,
+    pub events: Vec<String>,
+}
+
+/// What writing the validated rows produced, aligned with the rows passed in.
+#[derive(Debug)]
+pub struct ImportOutcome {
+    pub events: Vec<Result<i32, String>>,
+    pub packets: Vec<Result<i32, String>>,
+    pub links_created: usize,
+    pub committed: bool,
+}
@@ -1,5 +1,18 @@
+pub mod analytics;
+pub mod check_in;
 pub mod event;
 pub mod event_packets;
+pub mod export;
+pub mod idempotency;
+pub mod import;
 pub mod join_pe;
+pub mod outbox;
+pub mod price_tier;
+pub mod promo_code;
 pub mod refund;
+pub mod search;
+pub mod seat;
+pub mod seat_hold;
 pub mod ticket;
+pub mod venue;
+pub mod waitlist;
@@ -0,0 +1,10 @@
+use sqlx::prelude::FromRow;
+
+/// A message waiting in the outbox to be published to RabbitMQ.
+#[derive(Debug, FromRow)]
+pub struct OutboxMessage {
+    pub id: i64,
+    pub routing_key: String,
+    pub payload: String,
+    pub attempts: i32,
+}
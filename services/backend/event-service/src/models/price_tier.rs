@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct PriceTier {
+    pub id: i32,
+    pub event_id: Option<i32>,
+    pub packet_id: Option<i32>,
+    pub name: String,
+    pub currency: String,
+    pub price_minor: i64,
+    pub capacity: Option<i32>,
+    pub sold: i32,
+    pub sales_start: Option<DateTime<Utc>>,
+    pub sales_end: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_create_tier_window"))]
+#[serde(deny_unknown_fields)]
+pub struct CreatePriceTier {
+    #[validate(length(min = 2, max = 50, message = "Name must be between 2 and 50 characters"))]
+    pub name: String,
+    #[validate(custom(function = "validate_currency"))]
+    pub currency: String,
+    #[validate(range(min = 0, message = "Price cannot be negative"))]
+    pub price_minor: i64,
+    #[validate(range(min = 1, max = 50000, message = "Capacity must be between 1 and 50,000"))]
+    pub capacity: Option<i32>,
+    pub sales_start: Option<DateTime<Utc>>,
+    pub sales_end: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_update_tier_window"))]
+#[serde(deny_unknown_fields)]
+pub struct UpdatePriceTier {
+    #[validate(length(min = 2, max = 50, message = "Name must be between 2 and 50 characters"))]
+    pub name: Option<String>,
+    #[validate(custom(function = "validate_currency"))]
+    pub currency: Option<String>,
+    #[validate(range(min = 0, message = "Price cannot be negative"))]
+    pub price_minor: Option<i64>,
+    #[validate(range(min = 1, max = 50000, message = "Capacity must be between 1 and 50,000"))]
+    pub capacity: Option<i32>,
+    pub sales_start: Option<DateTime<Utc>>,
+    pub sales_end: Option<DateTime<Utc>>,
+}
+
+fn validate_currency(currency: &str) -> Result<(), ValidationError> {
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_uppercase()) {
+        let mut err = ValidationError::new("invalid_currency");
+        err.message = Some("Currency must be a 3-letter ISO 4217 code (e.g. EUR, RON)".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+fn validate_window(
+    sales_start: Option<DateTime<Utc>>,
+    sales_end: Option<DateTime<Utc>>,
+) -> Result<(), ValidationError> {
+    match (sales_start, sales_end) {
+        (Some(start), Some(end)) if end <= start => {
+            let mut err = ValidationError::new("invalid_sales_window");
+            err.message = Some("Sales end must be after sales start".into());
+            Err(err)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate_create_tier_window(tier: &CreatePriceTier) -> Result<(), ValidationError> {
+    validate_window(tier.sales_start, tier.sales_end)
+}
+
+fn validate_update_tier_window(tier: &UpdatePriceTier) -> Result<(), ValidationError> {
+    validate_window(tier.sales_start, tier.sales_end)
+}
@@ -0,0 +1,134 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[sqlx(type_name = "varchar", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DiscountType {
+    Percent,
+    Fixed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct PromoCode {
+    pub id: i32,
+    pub event_id: Option<i32>,
+    pub packet_id: Option<i32>,
+    pub code: String,
+    pub discount_type: DiscountType,
+    /// Percentage (1-100) for `PERCENT`, amount in minor units for `FIXED`.
+    pub discount_value: i64,
+    pub currency: Option<String>,
+    pub max_uses: Option<i32>,
+    pub per_user_limit: Option<i32>,
+    pub uses: i32,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PromoCode {
+    /// Discount on one ticket sold at `price_minor`, never more than the price.
+    /// `None` when the code cannot apply to that price.
+    pub fn discount_for(&self, price_minor: i64, currency: &str) -> Option<i64> {
+        let discount = match self.discount_type {
+            DiscountType::Percent => price_minor * self.discount_value / 100,
+            DiscountType::Fixed if self.currency.as_deref() == Some(currency) => {
+                self.discount_value
+            }
+            DiscountType::Fixed => return None,
+        };
+        Some(discount.min(price_minor))
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_create_promo_code"))]
+#[serde(deny_unknown_fields)]
+pub struct CreatePromoCode {
+    #[validate(custom(function = "validate_code"))]
+    pub code: String,
+    pub discount_type: DiscountType,
+    #[validate(range(min = 1, message = "Discount must be positive"))]
+    pub discount_value: i64,
+    pub currency: Option<String>,
+    #[validate(range(min = 1, message = "Usage limit must be at least 1"))]
+    pub max_uses: Option<i32>,
+    #[validate(range(min = 1, message = "Per-user limit must be at least 1"))]
+    pub per_user_limit: Option<i32>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct UpdatePromoCode {
+    #[validate(range(min = 1, message = "Usage limit must be at least 1"))]
+    pub max_uses: Option<i32>,
+    #[validate(range(min = 1, message = "Per-user limit must be at least 1"))]
+    pub per_user_limit: Option<i32>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct PromoCodeStats {
+    pub promo_code_id: i32,
+    pub code: String,
+    pub uses: i32,
+    pub max_uses: Option<i32>,
+    pub unique_buyers: i64,
+    pub total_discount_minor: i64,
+    pub revenue_minor: i64,
+    pub first_redeemed_at: Option<DateTime<Utc>>,
+    pub last_redeemed_at: Option<DateTime<Utc>>,
+}
+
+/// Promo code a buyer entered at checkout.
+pub struct PromoApplication {
+    pub code: String,
+    pub buyer_id: i32,
+}
+
+fn validate_code(code: &str) -> Result<(), ValidationError> {
+    let valid_chars = code
+        .chars()
+        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if code.len() < 3 || code.len() > 50 || !valid_chars {
+        let mut err = ValidationError::new("invalid_code");
+        err.message =
+            Some("Code must be 3-50 characters of uppercase letters, digits, '-' or '_'".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+fn validate_create_promo_code(promo: &CreatePromoCode) -> Result<(), ValidationError> {
+    match (promo.discount_type, promo.currency.as_deref()) {
+        (DiscountType::Percent, _) if promo.discount_value > 100 => {
+            let mut err = ValidationError::new("invalid_percentage");
+            err.message = Some("A percentage discount must be between 1 and 100".into());
+            Err(err)
+        }
+        (DiscountType::Percent, Some(_)) => {
+            let mut err = ValidationError::new("unexpected_currency");
+            err.message = Some("Only fixed discounts have a currency".into());
+            Err(err)
+        }
+        (DiscountType::Fixed, None) => {
+            let mut err = ValidationError::new("missing_currency");
+            err.message = Some("A fixed discount needs a currency".into());
+            Err(err)
+        }
+        (DiscountType::Fixed, Some(currency))
+            if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_uppercase()) =>
+        {
+            let mut err = ValidationError::new("invalid_currency");
+            err.message = Some("Currency must be a 3-letter ISO 4217 code (e.g. EUR, RON)".into());
+            Err(err)
+        }
+        _ => Ok(()),
+    }
+}
@@ -19,6 +19,13 @@ pub struct RefundRequest {
     pub resolved_at: Option<String>,
 }
 
+/// Status of the most recent refund request filed for a ticket.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TicketRefundStatus {
+    pub ticket_cod: String,
+    pub status: String,
+}
+
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct CreateRefundRequest {
     pub ticket_cod: String,
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[sqlx(type_name = "varchar", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SearchHitKind {
+    Event,
+    Packet,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    pub id: i32,
+    pub name: String,
+    pub location: Option<String>,
+    /// Packets have no start time of their own.
+    pub starts_at: Option<DateTime<Utc>>,
+    pub rank: f32,
+    /// Matching part of the description, location or name with hits wrapped in `<mark>`.
+    pub snippet: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct SearchQuery {
+    #[validate(length(
+        min = 2,
+        max = 100,
+        message = "Search text must be between 2 and 100 characters"
+    ))]
+    pub q: String,
+    pub kind: Option<SearchHitKind>,
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub paginare: crate::models::event_packets::PaginationParams,
+}
@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+const MAX_SEATS_PER_ROOM: usize = 50000;
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct RoomSeat {
+    pub id: i32,
+    pub room_id: i32,
+    pub section: String,
+    pub row_label: String,
+    pub seat_number: i32,
+    pub accessible: bool,
+}
+
+impl RoomSeat {
+    /// Human-readable seat printed on the ticket.
+    pub fn label(&self) -> String {
+        format!(
+            "Section {}, Row {}, Seat {}",
+            self.section, self.row_label, self.seat_number
+        )
+    }
+}
+
+/// Full layout of a room; replacing it renumbers every seat.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_seat_map"))]
+#[serde(deny_unknown_fields)]
+pub struct SeatMap {
+    #[validate(
+        length(min = 1, max = 50, message = "A seat map needs 1 to 50 sections"),
+        nested
+    )]
+    pub sections: Vec<SeatMapSection>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct SeatMapSection {
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Section name must be between 1 and 50 characters"
+    ))]
+    pub name: String,
+    #[validate(
+        length(min = 1, max = 200, message = "A section needs 1 to 200 rows"),
+        nested
+    )]
+    pub rows: Vec<SeatMapRow>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_seat_map_row"))]
+#[serde(deny_unknown_fields)]
+pub struct SeatMapRow {
+    #[validate(length(
+        min = 1,
+        max = 10,
+        message = "Row label must be between 1 and 10 characters"
+    ))]
+    pub label: String,
+    /// Seats in the row, numbered from 1.
+    #[validate(range(min = 1, max = 500, message = "A row holds 1 to 500 seats"))]
+    pub seats: i32,
+    /// Seat numbers reserved for wheelchair users and companions.
+    #[serde(default)]
+    pub accessible_seats: Vec<i32>,
+}
+
+impl SeatMap {
+    pub fn seat_count(&self) -> usize {
+        self.sections
+            .iter()
+            .flat_map(|section| &section.rows)
+            .map(|row| row.seats as usize)
+            .sum()
+    }
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct EventSeat {
+    pub seat_id: i32,
+    pub section: String,
+    pub row_label: String,
+    pub seat_number: i32,
+    pub accessible: bool,
+    pub available: bool,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct SectionAvailability {
+    pub section: String,
+    pub total: i64,
+    pub available: i64,
+    pub accessible_available: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct EventSeatQuery {
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Section must be between 1 and 50 characters"
+    ))]
+    pub section: Option<String>,
+    pub available_only: Option<bool>,
+}
+
+/// Seat asked for at checkout; with neither field set the next free seat is assigned.
+#[derive(Debug, Default)]
+pub struct SeatChoice {
+    pub seat_id: Option<i32>,
+    pub section: Option<String>,
+}
+
+fn validate_seat_map(map: &SeatMap) -> Result<(), ValidationError> {
+    if map.seat_count() > MAX_SEATS_PER_ROOM {
+        let mut err = ValidationError::new("seat_count");
+        err.message = Some("A room cannot hold more than 50,000 seats".into());
+        return Err(err);
+    }
+
+    let mut names: Vec<&str> = map.sections.iter().map(|s| s.name.as_str()).collect();
+    names.sort_unstable();
+    names.dedup();
+    if names.len() != map.sections.len() {
+        let mut err = ValidationError::new("duplicate_section");
+        err.message = Some("Section names must be unique".into());
+        return Err(err);
+    }
+
+    for section in &map.sections {
+        let mut labels: Vec<&str> = section.rows.iter().map(|r| r.label.as_str()).collect();
+        labels.sort_unstable();
+        labels.dedup();
+        if labels.len() != section.rows.len() {
+            let mut err = ValidationError::new("duplicate_row");
+            err.message = Some("Row labels must be unique within a section".into());
+            return Err(err);
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_seat_map_row(row: &SeatMapRow) -> Result<(), ValidationError> {
+    if row
+        .accessible_seats
+        .iter()
+        .any(|seat| *seat < 1 || *seat > row.seats)
+    {
+        let mut err = ValidationError::new("accessible_seats");
+        err.message = Some("Accessible seats must be seat numbers of the row".into());
+        return Err(err);
+    }
+    Ok(())
+}
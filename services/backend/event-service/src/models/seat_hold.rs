@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use validator::Validate;
+
+pub const DEFAULT_HOLD_TTL_SECONDS: i64 = 600;
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SeatHold {
+    pub id: String,
+    pub event_id: Option<i32>,
+    pub packet_id: Option<i32>,
+    pub holder_id: i32,
+    pub quantity: i32,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Hold a purchase is paid from, and the buyer it has to belong to.
+#[derive(Debug, Clone)]
+pub struct HoldClaim {
+    pub hold_id: String,
+    pub holder_id: i32,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct CreateSeatHold {
+    #[validate(range(min = 1, max = 10, message = "Quantity must be between 1 and 10"))]
+    pub quantity: i32,
+    #[validate(range(
+        min = 60,
+        max = 1800,
+        message = "TTL must be between 60 and 1800 seconds"
+    ))]
+    pub ttl_seconds: Option<i64>,
+}
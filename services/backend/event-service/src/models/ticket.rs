@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::prelude::FromRow;
 use utoipa::ToSchema;
@@ -15,9 +16,31 @@ pub struct Ticket {
     #[sqlx(rename = "evenimentid")]
     #[serde(rename = "evenimentid")]
     pub id_event: Option<i32>,
+
+    pub tier_id: Option<i32>,
+    pub price_minor: Option<i64>,
+    pub currency: Option<String>,
+
+    /// Promo code redeemed at checkout; `price_minor` is what was paid after `discount_minor`.
+    #[serde(default)]
+    pub promo_code_id: Option<i32>,
+    #[serde(default)]
+    pub discount_minor: Option<i64>,
+
+    /// First time the ticket was scanned at a gate; a used ticket cannot be refunded.
+    #[serde(default)]
+    pub checked_in_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub checked_in_gate: Option<String>,
+
+    /// Reserved seat, for events sold from a seat map.
+    #[serde(default)]
+    pub seat_id: Option<i32>,
+    #[serde(default)]
+    pub seat_label: Option<String>,
 }
 
-#[derive(Debug, Deserialize, FromRow, ToSchema, Validate)]
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema, Validate)]
 #[validate(schema(function = "validate_update_ticket"))]
 #[serde(deny_unknown_fields)]
 pub struct UpdateTicket {
@@ -28,6 +51,111 @@ pub struct UpdateTicket {
     #[sqlx(rename = "evenimentid")]
     #[serde(rename = "evenimentid")]
     pub id_event: Option<i32>,
+
+    #[validate(range(min = 0, message = "Tier ID cannot be negative"))]
+    pub tier_id: Option<i32>,
+
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Hold ID must be between 1 and 50 characters"
+    ))]
+    pub hold_id: Option<String>,
+    #[validate(length(
+        min = 3,
+        max = 50,
+        message = "Promo code must be between 3 and 50 characters"
+    ))]
+    pub promo_code: Option<String>,
+    /// User the promo code is redeemed for when clients-service buys on a client's behalf.
+    pub buyer_id: Option<i32>,
+    #[validate(range(min = 0, message = "Seat ID cannot be negative"))]
+    pub seat_id: Option<i32>,
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Section must be between 1 and 50 characters"
+    ))]
+    pub section: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct PurchaseOptions {
+    #[validate(range(min = 0, message = "Tier ID cannot be negative"))]
+    pub tier_id: Option<i32>,
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Hold ID must be between 1 and 50 characters"
+    ))]
+    pub hold_id: Option<String>,
+    #[validate(length(
+        min = 3,
+        max = 50,
+        message = "Promo code must be between 3 and 50 characters"
+    ))]
+    pub promo_code: Option<String>,
+    pub buyer_id: Option<i32>,
+    #[validate(range(min = 0, message = "Seat ID cannot be negative"))]
+    pub seat_id: Option<i32>,
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Section must be between 1 and 50 characters"
+    ))]
+    pub section: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_batch_purchase"))]
+#[serde(deny_unknown_fields)]
+pub struct BatchPurchaseOptions {
+    #[validate(range(min = 1, max = 10, message = "Quantity must be between 1 and 10"))]
+    pub quantity: i32,
+    #[validate(range(min = 0, message = "Tier ID cannot be negative"))]
+    pub tier_id: Option<i32>,
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Hold ID must be between 1 and 50 characters"
+    ))]
+    pub hold_id: Option<String>,
+    #[validate(length(
+        min = 3,
+        max = 50,
+        message = "Promo code must be between 3 and 50 characters"
+    ))]
+    pub promo_code: Option<String>,
+    pub buyer_id: Option<i32>,
+    /// Section to take seats from, in seat-map order, when the event has a seat map.
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Section must be between 1 and 50 characters"
+    ))]
+    pub section: Option<String>,
+    pub codes: Option<Vec<String>>,
+}
+
+fn validate_batch_purchase(options: &BatchPurchaseOptions) -> Result<(), ValidationError> {
+    let Some(codes) = &options.codes else {
+        return Ok(());
+    };
+
+    if codes.len() != options.quantity as usize {
+        let mut err = ValidationError::new("codes_quantity");
+        err.message = Some("Exactly one ticket code must be supplied per ticket.".into());
+        return Err(err);
+    }
+
+    if codes.iter().any(|cod| cod.is_empty() || cod.len() > 50) {
+        let mut err = ValidationError::new("codes_length");
+        err.message = Some("Ticket codes must be between 1 and 50 characters.".into());
+        return Err(err);
+    }
+
+    Ok(())
 }
 
 fn validate_exclusive_ids(ticket: &impl ExclusiveTicketIds) -> Result<(), ValidationError> {
@@ -64,3 +192,13 @@ impl ExclusiveTicketIds for UpdateTicket {
         self.id_event
     }
 }
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct TicketCalendarRequest {
+    #[validate(length(
+        max = 1000,
+        message = "At most 1000 ticket codes can be requested at once"
+    ))]
+    pub codes: Vec<String>,
+}
@@ -0,0 +1,190 @@
+use chrono::{DateTime, Utc};
+use common::authorization::{ResourceOwnership, UserClaims};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Venue {
+    pub id: i32,
+    pub id_owner: i32,
+    pub name: String,
+    pub address: String,
+    pub city: String,
+    pub country: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub created_at: DateTime<Utc>,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub rooms: Vec<VenueRoom>,
+}
+
+impl Venue {
+    /// Free-text location copied onto events held here.
+    pub fn location(&self) -> String {
+        format!("{}, {}", self.name, self.city)
+    }
+}
+
+impl ResourceOwnership for Venue {
+    fn is_owned_by(&self, claims: &UserClaims, _user_email: Option<&str>) -> bool {
+        claims.is_owner_event() && self.id_owner == claims.user_id
+    }
+
+    fn resource_description(&self) -> String {
+        format!("venue '{}'", self.name)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct VenueRoom {
+    pub id: i32,
+    pub venue_id: i32,
+    pub name: String,
+    pub capacity: i32,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_create_venue"))]
+#[serde(deny_unknown_fields)]
+pub struct CreateVenue {
+    #[validate(length(
+        min = 3,
+        max = 100,
+        message = "Name must be between 3 and 100 characters"
+    ))]
+    pub name: String,
+    #[validate(length(
+        min = 3,
+        max = 255,
+        message = "Address must be between 3 and 255 characters"
+    ))]
+    pub address: String,
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "City must be between 1 and 100 characters"
+    ))]
+    pub city: String,
+    #[validate(custom(function = "validate_country"))]
+    pub country: String,
+    #[validate(range(min = -90.0, max = 90.0, message = "Latitude must be between -90 and 90"))]
+    pub latitude: Option<f64>,
+    #[validate(range(
+        min = -180.0,
+        max = 180.0,
+        message = "Longitude must be between -180 and 180"
+    ))]
+    pub longitude: Option<f64>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub rooms: Vec<CreateVenueRoom>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_update_venue"))]
+#[serde(deny_unknown_fields)]
+pub struct UpdateVenue {
+    #[validate(length(
+        min = 3,
+        max = 100,
+        message = "Name must be between 3 and 100 characters"
+    ))]
+    pub name: Option<String>,
+    #[validate(length(
+        min = 3,
+        max = 255,
+        message = "Address must be between 3 and 255 characters"
+    ))]
+    pub address: Option<String>,
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "City must be between 1 and 100 characters"
+    ))]
+    pub city: Option<String>,
+    #[validate(custom(function = "validate_country"))]
+    pub country: Option<String>,
+    #[validate(range(min = -90.0, max = 90.0, message = "Latitude must be between -90 and 90"))]
+    pub latitude: Option<f64>,
+    #[validate(range(
+        min = -180.0,
+        max = 180.0,
+        message = "Longitude must be between -180 and 180"
+    ))]
+    pub longitude: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct CreateVenueRoom {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Room name must be between 1 and 100 characters"
+    ))]
+    pub name: String,
+    #[validate(range(
+        min = 1,
+        max = 50000,
+        message = "Capacity must be between 1 and 50,000"
+    ))]
+    pub capacity: i32,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateVenueRoom {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Room name must be between 1 and 100 characters"
+    ))]
+    pub name: Option<String>,
+    #[validate(range(
+        min = 1,
+        max = 50000,
+        message = "Capacity must be between 1 and 50,000"
+    ))]
+    pub capacity: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct VenueQuery {
+    #[validate(length(max = 100, message = "City filter must be less than 100 characters"))]
+    pub city: Option<String>,
+    #[validate(custom(function = "validate_country"))]
+    pub country: Option<String>,
+}
+
+fn validate_country(country: &str) -> Result<(), ValidationError> {
+    if country.len() != 2 || !country.chars().all(|c| c.is_ascii_uppercase()) {
+        let mut err = ValidationError::new("invalid_country");
+        err.message = Some("Country must be a 2-letter ISO 3166 code (e.g. RO, DE)".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_coordinates(
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+) -> Result<(), ValidationError> {
+    if latitude.is_some() != longitude.is_some() {
+        let mut err = ValidationError::new("incomplete_coordinates");
+        err.message = Some("Latitude and longitude must be given together".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+fn validate_create_venue(venue: &CreateVenue) -> Result<(), ValidationError> {
+    validate_coordinates(venue.latitude, venue.longitude)
+}
+
+fn validate_update_venue(venue: &UpdateVenue) -> Result<(), ValidationError> {
+    validate_coordinates(venue.latitude, venue.longitude)
+}
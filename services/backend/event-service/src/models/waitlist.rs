@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use validator::Validate;
+
+pub const WAITLIST_OFFER_TTL_SECONDS: i64 = 900;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[sqlx(type_name = "varchar", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WaitlistStatus {
+    Waiting,
+    Offered,
+    Accepted,
+    Expired,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct WaitlistEntry {
+    pub id: i32,
+    pub event_id: Option<i32>,
+    pub packet_id: Option<i32>,
+    pub user_id: i32,
+    pub email: String,
+    pub status: WaitlistStatus,
+    pub hold_id: Option<String>,
+    pub offer_expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct JoinWaitlist {
+    #[validate(range(min = 0, message = "User ID cannot be negative"))]
+    pub user_id: i32,
+    #[validate(email(message = "Invalid email address"))]
+    pub email: String,
+}
+
+/// An entry that was just handed a seat hold, along with the name of what it
+/// was waiting for so the offer can be announced without another lookup.
+#[derive(Debug)]
+pub struct WaitlistOffer {
+    pub entry: WaitlistEntry,
+    pub name: String,
+}
@@ -0,0 +1,217 @@
+use crate::models::analytics::{
+    AnalyticsQuery, Granularity, SalesBucket, SalesSummary, SalesTotals, SellThrough,
+    SellThroughRow, TierRevenue,
+};
+use crate::utils::error::AnalyticsRepoError;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+/// Every ticket ever sold: the tickets still in `BILETE`, plus the refunded
+/// ones, which approval deletes after copying their sale onto the refund.
+const SOLD: &str = "SELECT b.cod, b.evenimentid AS event_id, b.pachetid AS packet_id, b.tier_id, \
+     b.price_minor, b.currency, b.discount_minor, b.created_at, \
+     FALSE AS refunded, EXISTS (SELECT 1 FROM CHECK_INS c WHERE c.ticket_cod = b.cod) AS checked_in \
+     FROM BILETE b \
+     WHERE NOT EXISTS (SELECT 1 FROM REFUND_REQUESTS r WHERE r.ticket_cod = b.cod AND r.status = 'APPROVED') \
+     UNION ALL \
+     SELECT r.ticket_cod, r.event_id, r.packet_id, r.tier_id, r.price_minor, r.currency, \
+     r.discount_minor, r.purchased_at, TRUE, FALSE \
+     FROM REFUND_REQUESTS r \
+     WHERE r.status = 'APPROVED' AND r.purchased_at IS NOT NULL";
+
+pub struct AnalyticsRepo {
+    pool: PgPool,
+}
+
+impl AnalyticsRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get_summary(
+        &self,
+        params: &AnalyticsQuery,
+    ) -> Result<SalesSummary, AnalyticsRepoError> {
+        let mut query_builder = scoped_tickets(params);
+        query_builder.push(
+            " SELECT COUNT(*) AS tickets_sold, \
+             COUNT(*) FILTER (WHERE refunded) AS tickets_refunded, \
+             COUNT(*) FILTER (WHERE checked_in) AS tickets_checked_in \
+             FROM scoped",
+        );
+
+        let totals = query_builder
+            .build_query_as::<SalesTotals>()
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AnalyticsRepoError::InternalError)?;
+
+        Ok(totals.into())
+    }
+
+    /// Tickets sold and gross revenue per period, in UTC calendar buckets.
+    pub async fn get_sales(
+        &self,
+        params: &AnalyticsQuery,
+    ) -> Result<Vec<SalesBucket>, AnalyticsRepoError> {
+        let granularity = params.granularity.unwrap_or(Granularity::Day);
+
+        let mut query_builder = scoped_tickets(params);
+        query_builder.push(" SELECT date_trunc(");
+        query_builder.push_bind(granularity.as_str());
+        query_builder.push(
+            ", created_at, 'UTC') AS period_start, currency, \
+             COUNT(*) AS tickets_sold, \
+             COALESCE(SUM(price_minor), 0)::BIGINT AS revenue_minor \
+             FROM scoped GROUP BY 1, 2 ORDER BY 1, 2",
+        );
+
+        query_builder
+            .build_query_as::<SalesBucket>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AnalyticsRepoError::InternalError)
+    }
+
+    /// Revenue per price tier that sold at least one ticket in the range.
+    pub async fn get_tier_revenue(
+        &self,
+        params: &AnalyticsQuery,
+    ) -> Result<Vec<TierRevenue>, AnalyticsRepoError> {
+        let mut query_builder = scoped_tickets(params);
+        query_builder.push(
+            " SELECT t.id AS tier_id, t.name AS tier_name, t.event_id, t.packet_id, t.currency, \
+             COUNT(*) AS tickets_sold, \
+             COALESCE(SUM(s.price_minor) FILTER (WHERE NOT s.refunded), 0)::BIGINT AS revenue_minor, \
+             COALESCE(SUM(s.price_minor) FILTER (WHERE s.refunded), 0)::BIGINT AS refunded_minor, \
+             COALESCE(SUM(s.discount_minor), 0)::BIGINT AS discount_minor \
+             FROM scoped s JOIN PRICE_TIERS t ON t.id = s.tier_id \
+             GROUP BY t.id ORDER BY t.id",
+        );
+
+        query_builder
+            .build_query_as::<TierRevenue>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AnalyticsRepoError::InternalError)
+    }
+
+    /// Sell-through of each event in scope. Packet tickets count towards every
+    /// event of the packet, as buying one took a seat at each of them.
+    pub async fn get_event_sell_through(
+        &self,
+        params: &AnalyticsQuery,
+    ) -> Result<Vec<SellThrough>, AnalyticsRepoError> {
+        let mut query_builder = QueryBuilder::<Postgres>::new(format!(
+            "SELECT e.id, e.nume AS name, e.status, e.numarlocuri AS seats_remaining, \
+             s.tickets_sold, s.tickets_refunded, \
+             (SELECT COUNT(*) FROM CHECK_INS c WHERE c.event_id = e.id) AS tickets_checked_in \
+             FROM EVENIMENTE e \
+             CROSS JOIN LATERAL ( \
+                 SELECT COUNT(*) AS tickets_sold, \
+                 COUNT(*) FILTER (WHERE t.refunded) AS tickets_refunded \
+                 FROM ({SOLD}) t \
+                 WHERE t.event_id = e.id \
+                 OR t.packet_id IN (SELECT j.pachetid FROM JOIN_PE j WHERE j.evenimentid = e.id) \
+             ) s \
+             WHERE TRUE"
+        ));
+        if let Some(owner_id) = params.owner_id {
+            query_builder.push(" AND e.id_owner = ");
+            query_builder.push_bind(owner_id);
+        }
+        if let Some(event_id) = params.event_id {
+            query_builder.push(" AND e.id = ");
+            query_builder.push_bind(event_id);
+        }
+        if let Some(packet_id) = params.packet_id {
+            query_builder.push(" AND e.id IN (SELECT evenimentid FROM JOIN_PE WHERE pachetid = ");
+            query_builder.push_bind(packet_id);
+            query_builder.push(")");
+        }
+        query_builder.push(" ORDER BY e.id");
+
+        let rows = query_builder
+            .build_query_as::<SellThroughRow>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AnalyticsRepoError::InternalError)?;
+
+        Ok(rows.into_iter().map(SellThrough::from).collect())
+    }
+
+    pub async fn get_packet_sell_through(
+        &self,
+        params: &AnalyticsQuery,
+    ) -> Result<Vec<SellThrough>, AnalyticsRepoError> {
+        let mut query_builder = QueryBuilder::<Postgres>::new(format!(
+            "SELECT p.id, p.nume AS name, p.status, p.numarlocuri AS seats_remaining, \
+             s.tickets_sold, s.tickets_refunded, s.tickets_checked_in \
+             FROM PACHETE p \
+             CROSS JOIN LATERAL ( \
+                 SELECT COUNT(*) AS tickets_sold, \
+                 COUNT(*) FILTER (WHERE t.refunded) AS tickets_refunded, \
+                 COUNT(*) FILTER (WHERE t.checked_in) AS tickets_checked_in \
+                 FROM ({SOLD}) t WHERE t.packet_id = p.id \
+             ) s \
+             WHERE TRUE"
+        ));
+        if let Some(owner_id) = params.owner_id {
+            query_builder.push(" AND p.id_owner = ");
+            query_builder.push_bind(owner_id);
+        }
+        if let Some(packet_id) = params.packet_id {
+            query_builder.push(" AND p.id = ");
+            query_builder.push_bind(packet_id);
+        }
+        if let Some(event_id) = params.event_id {
+            query_builder.push(" AND p.id IN (SELECT pachetid FROM JOIN_PE WHERE evenimentid = ");
+            query_builder.push_bind(event_id);
+            query_builder.push(")");
+        }
+        query_builder.push(" ORDER BY p.id");
+
+        let rows = query_builder
+            .build_query_as::<SellThroughRow>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AnalyticsRepoError::InternalError)?;
+
+        Ok(rows.into_iter().map(SellThrough::from).collect())
+    }
+}
+
+/// Starts a query with a `scoped` CTE: the tickets sold in the requested range
+/// for events and packets in scope, flagged as refunded and checked in. Event
+/// scope covers tickets bought for the event itself; packet sales are reported
+/// under the packet.
+fn scoped_tickets(params: &AnalyticsQuery) -> QueryBuilder<'static, Postgres> {
+    let mut query_builder = QueryBuilder::<Postgres>::new(format!(
+        "WITH scoped AS ( \
+         SELECT t.* FROM ({SOLD}) t \
+         LEFT JOIN EVENIMENTE e ON e.id = t.event_id \
+         LEFT JOIN PACHETE p ON p.id = t.packet_id \
+         WHERE COALESCE(e.id_owner, p.id_owner) IS NOT NULL"
+    ));
+    if let Some(owner_id) = params.owner_id {
+        query_builder.push(" AND COALESCE(e.id_owner, p.id_owner) = ");
+        query_builder.push_bind(owner_id);
+    }
+    if let Some(event_id) = params.event_id {
+        query_builder.push(" AND t.event_id = ");
+        query_builder.push_bind(event_id);
+    }
+    if let Some(packet_id) = params.packet_id {
+        query_builder.push(" AND t.packet_id = ");
+        query_builder.push_bind(packet_id);
+    }
+    if let Some(from) = params.from {
+        query_builder.push(" AND t.created_at >= ");
+        query_builder.push_bind(from);
+    }
+    if let Some(to) = params.to {
+        query_builder.push(" AND t.created_at < ");
+        query_builder.push_bind(to);
+    }
+    query_builder.push(")");
+    query_builder
+}
@@ -0,0 +1,224 @@
+use crate::models::check_in::{Attendance, CheckIn, EventScanner, GateAttendance};
+use crate::utils::error::CheckInRepoError;
+use sqlx::PgPool;
+
+const CHECK_IN_COLUMNS: &str = "ticket_cod, event_id, gate, scanned_by, checked_in_at";
+
+pub struct CheckInRepo {
+    pool: PgPool,
+}
+
+impl CheckInRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Admits a ticket to an event. Packet tickets can be checked in once at
+    /// every event of the packet; the first check-in is also recorded on the
+    /// `BILETE` row, which marks the ticket as used.
+    pub async fn check_in(
+        &self,
+        event_id: i32,
+        ticket_cod: &str,
+        gate: &str,
+        scanned_by: i32,
+    ) -> Result<CheckIn, CheckInRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(CheckInRepoError::InternalError)?;
+
+        let ticket: Option<(Option<i32>, Option<i32>)> =
+            sqlx::query_as("SELECT evenimentid, pachetid FROM BILETE WHERE cod = $1 FOR UPDATE")
+                .bind(ticket_cod)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(CheckInRepoError::InternalError)?;
+
+        let Some((ticket_event_id, ticket_packet_id)) = ticket else {
+            let refunded: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM REFUND_REQUESTS WHERE ticket_cod = $1 AND status = 'APPROVED')",
+            )
+            .bind(ticket_cod)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(CheckInRepoError::InternalError)?;
+
+            return Err(if refunded {
+                CheckInRepoError::Refunded
+            } else {
+                CheckInRepoError::TicketNotFound
+            });
+        };
+
+        let valid_for_event = match (ticket_event_id, ticket_packet_id) {
+            (Some(id), _) => id == event_id,
+            (None, Some(packet_id)) => sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM JOIN_PE WHERE pachetid = $1 AND evenimentid = $2)",
+            )
+            .bind(packet_id)
+            .bind(event_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(CheckInRepoError::InternalError)?,
+            (None, None) => false,
+        };
+
+        if !valid_for_event {
+            return Err(CheckInRepoError::WrongEvent);
+        }
+
+        let check_in = sqlx::query_as::<_, CheckIn>(&format!(
+            r#"
+            INSERT INTO CHECK_INS (ticket_cod, event_id, gate, scanned_by)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (ticket_cod, event_id) DO NOTHING
+            RETURNING {}
+            "#,
+            CHECK_IN_COLUMNS
+        ))
+        .bind(ticket_cod)
+        .bind(event_id)
+        .bind(gate)
+        .bind(scanned_by)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(CheckInRepoError::InternalError)?;
+
+        let Some(check_in) = check_in else {
+            let existing = sqlx::query_as::<_, CheckIn>(&format!(
+                "SELECT {} FROM CHECK_INS WHERE ticket_cod = $1 AND event_id = $2",
+                CHECK_IN_COLUMNS
+            ))
+            .bind(ticket_cod)
+            .bind(event_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(CheckInRepoError::InternalError)?;
+
+            return Err(CheckInRepoError::AlreadyCheckedIn(format!(
+                "Ticket {} was already checked in at gate {} on {}",
+                existing.ticket_cod, existing.gate, existing.checked_in_at
+            )));
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE BILETE
+            SET checked_in_at = COALESCE(checked_in_at, $2),
+                checked_in_gate = COALESCE(checked_in_gate, $3)
+            WHERE cod = $1
+            "#,
+        )
+        .bind(ticket_cod)
+        .bind(check_in.checked_in_at)
+        .bind(gate)
+        .execute(&mut *tx)
+        .await
+        .map_err(CheckInRepoError::InternalError)?;
+
+        tx.commit().await.map_err(CheckInRepoError::InternalError)?;
+
+        Ok(check_in)
+    }
+
+    pub async fn attendance(&self, event_id: i32) -> Result<Attendance, CheckInRepoError> {
+        let tickets_sold: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM BILETE
+            WHERE evenimentid = $1
+               OR pachetid IN (SELECT pachetid FROM JOIN_PE WHERE evenimentid = $1)
+            "#,
+        )
+        .bind(event_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(CheckInRepoError::InternalError)?;
+
+        let gates = sqlx::query_as::<_, GateAttendance>(
+            r#"
+            SELECT gate, COUNT(*) AS checked_in
+            FROM CHECK_INS
+            WHERE event_id = $1
+            GROUP BY gate
+            ORDER BY gate
+            "#,
+        )
+        .bind(event_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(CheckInRepoError::InternalError)?;
+
+        Ok(Attendance {
+            event_id,
+            tickets_sold,
+            checked_in: gates.iter().map(|g| g.checked_in).sum(),
+            gates,
+        })
+    }
+
+    pub async fn list_scanners(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<EventScanner>, CheckInRepoError> {
+        sqlx::query_as::<_, EventScanner>(
+            "SELECT event_id, user_id, created_at FROM EVENT_SCANNERS WHERE event_id = $1 ORDER BY created_at",
+        )
+        .bind(event_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(CheckInRepoError::InternalError)
+    }
+
+    pub async fn add_scanner(
+        &self,
+        event_id: i32,
+        user_id: i32,
+    ) -> Result<EventScanner, CheckInRepoError> {
+        sqlx::query_as::<_, EventScanner>(
+            r#"
+            INSERT INTO EVENT_SCANNERS (event_id, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            RETURNING event_id, user_id, created_at
+            "#,
+        )
+        .bind(event_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(CheckInRepoError::InternalError)?
+        .ok_or(CheckInRepoError::DuplicateScanner)
+    }
+
+    pub async fn remove_scanner(
+        &self,
+        event_id: i32,
+        user_id: i32,
+    ) -> Result<(), CheckInRepoError> {
+        let result = sqlx::query("DELETE FROM EVENT_SCANNERS WHERE event_id = $1 AND user_id = $2")
+            .bind(event_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(CheckInRepoError::InternalError)?;
+
+        if result.rows_affected() == 0 {
+            return Err(CheckInRepoError::ScannerNotFound);
+        }
+
+        Ok(())
+    }
+
+    pub async fn is_scanner(&self, event_id: i32, user_id: i32) -> Result<bool, CheckInRepoError> {
+        sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM EVENT_SCANNERS WHERE event_id = $1 AND user_id = $2)",
+        )
+        .bind(event_id)
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(CheckInRepoError::InternalError)
+    }
+}
@@ -1,12 +1,31 @@
+use crate::models::event::{
+    EventStatus, FacetCount, Facets, normalize_tags, parse_category_filter, parse_near,
+    parse_tag_filter,
+};
 use crate::models::event_packets::{
     CreateEventPacket, EventPacketQuery, EventPackets, PaginationParams, UpdateEventPacket,
 };
+use crate::repositories::event_repo::{Facet, push_distance_km, push_within_radius};
+use crate::repositories::keyset::{NameKey, Ordering, push_name_keyset};
+use crate::repositories::outbox_repo::OutboxRepo;
+use crate::repositories::ticket_repo::TicketRepo;
 use crate::utils::error::*;
 use anyhow::Result;
-use sqlx::{Error, PgPool, Postgres, QueryBuilder};
+use common::pagination::{Cursor, Page};
+use common::rabbitmq::messages::{EventCancelled, ROUTING_KEY_EVENT_CANCELLED};
+use sqlx::{Error, PgExecutor, PgPool, Postgres, QueryBuilder};
+
+pub(crate) const PACKET_COLUMNS: &str = r#"
+    id, id_owner, nume, locatie, descriere, numarlocuri, status, category, tags, latitude, longitude,
+    (SELECT MIN(e.starts_at) FROM EVENIMENTE e JOIN JOIN_PE j ON e.id = j.evenimentid
+        WHERE j.pachetid = PACHETE.id) AS starts_at,
+    (SELECT MAX(COALESCE(e.ends_at, e.starts_at)) FROM EVENIMENTE e JOIN JOIN_PE j ON e.id = j.evenimentid
+        WHERE j.pachetid = PACHETE.id) AS ends_at
+"#;
 
 const DEFAULT_PAGE: i64 = 1;
 const DEFAULT_ITEMS_PER_PAGE: i64 = 10;
+const MAX_TAG_FACETS: i64 = 50;
 
 pub struct EventPacketRepo {
     pool: PgPool,
@@ -20,34 +39,38 @@ impl EventPacketRepo {
     pub async fn list_event_packets(
         &self,
         params: EventPacketQuery,
-    ) -> Result<Vec<EventPackets>, EventPacketRepoError> {
-        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
-            "SELECT ID, ID_OWNER, nume, locatie, descriere, numarlocuri FROM PACHETE",
-        );
-
-        let mut has_condition = false;
-
-        let type_filter = params.descriere.filter(|s| !s.is_empty());
+        owner_id: Option<i32>,
+        status: Option<EventStatus>,
+    ) -> Result<Page<EventPackets>, EventPacketRepoError> {
+        let near = params.near.as_deref().and_then(parse_near);
+        let cursor = match params.paginare.cursor.as_deref() {
+            Some(cursor) => {
+                Some(Cursor::<NameKey>::decode(cursor).ok_or(EventPacketRepoError::InvalidCursor)?)
+            }
+            None => None,
+        };
 
-        if let Some(desc_filter) = type_filter {
-            query_builder.push(" WHERE unaccent(descriere) ILIKE unaccent(");
-            query_builder.push_bind(format!("%{}%", desc_filter));
-            query_builder.push(")");
-            has_condition = true;
+        let mut query_builder: QueryBuilder<Postgres> =
+            QueryBuilder::new(format!("SELECT * FROM (SELECT {}", PACKET_COLUMNS));
+        if let Some(point) = near {
+            query_builder.push(", ");
+            push_distance_km(&mut query_builder, point);
+            query_builder.push(" AS distance_km");
         }
+        query_builder.push(" FROM PACHETE");
 
-        if let Some(min_tickets) = params.bilete {
-            if has_condition {
-                query_builder.push(" AND ");
-            } else {
-                query_builder.push(" WHERE ");
-            }
+        push_packet_filters(&mut query_builder, &params, owner_id, status, None);
+        query_builder.push(") AS page");
 
-            query_builder.push(" PACHETE.numarlocuri >= ");
-            query_builder.push_bind(min_tickets);
-        }
+        let ordering = if near.is_some() {
+            Ordering::Distance
+        } else {
+            Ordering::Name
+        };
+        push_name_keyset(&mut query_builder, ordering, cursor.as_ref());
 
-        self.apply_pagination(&mut query_builder, params.paginare);
+        let (items_per_page, offset) =
+            self.apply_pagination(&mut query_builder, &params.paginare, cursor.is_some());
 
         let query = query_builder.build_query_as::<EventPackets>();
         let packets = query
@@ -55,16 +78,91 @@ impl EventPacketRepo {
             .await
             .map_err(map_sqlx_packet_error)?;
 
-        Ok(packets)
+        let total = if params.paginare.include_total == Some(true) {
+            Some(self.count_event_packets(&params, owner_id, status).await?)
+        } else {
+            None
+        };
+
+        Ok(Page::from_rows(
+            packets,
+            items_per_page as usize,
+            cursor.as_ref().is_some_and(|cursor| cursor.before),
+            cursor.is_some() || offset > 0,
+            |packet| NameKey {
+                name: packet.nume.clone(),
+                starts_at: packet.starts_at,
+                distance_km: packet.distance_km,
+            },
+        )
+        .with_total(total))
+    }
+
+    async fn count_event_packets(
+        &self,
+        params: &EventPacketQuery,
+        owner_id: Option<i32>,
+        status: Option<EventStatus>,
+    ) -> Result<i64, EventPacketRepoError> {
+        let mut query_builder: QueryBuilder<Postgres> =
+            QueryBuilder::new("SELECT COUNT(*) FROM PACHETE");
+        push_packet_filters(&mut query_builder, params, owner_id, status, None);
+
+        query_builder
+            .build_query_scalar::<i64>()
+            .fetch_one(&self.pool)
+            .await
+            .map_err(EventPacketRepoError::InternalError)
+    }
+
+    pub async fn event_packet_facets(
+        &self,
+        params: &EventPacketQuery,
+        status: Option<EventStatus>,
+    ) -> Result<Facets, EventPacketRepoError> {
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT category AS value, COUNT(*) AS count FROM (SELECT category FROM PACHETE",
+        );
+        push_packet_filters(
+            &mut query_builder,
+            params,
+            None,
+            status,
+            Some(Facet::Category),
+        );
+        query_builder.push(
+            ") f WHERE category IS NOT NULL GROUP BY category ORDER BY count DESC, value ASC",
+        );
+        let categories = query_builder
+            .build_query_as::<FacetCount>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(EventPacketRepoError::InternalError)?;
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT tag AS value, COUNT(*) AS count FROM (SELECT tags FROM PACHETE",
+        );
+        push_packet_filters(&mut query_builder, params, None, status, Some(Facet::Tag));
+        query_builder
+            .push(") f, UNNEST(f.tags) AS tag GROUP BY tag ORDER BY count DESC, value ASC");
+        query_builder.push(" LIMIT ");
+        query_builder.push_bind(MAX_TAG_FACETS);
+        let tags = query_builder
+            .build_query_as::<FacetCount>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(EventPacketRepoError::InternalError)?;
+
+        Ok(Facets { categories, tags })
     }
 
+    /// Returns the page size and the offset; a cursor already fixes the start.
     fn apply_pagination(
         &self,
         query_builder: &mut QueryBuilder<Postgres>,
-        pagination: PaginationParams,
-    ) {
-        query_builder.push(" ORDER BY nume ASC");
-
+        pagination: &PaginationParams,
+        has_cursor: bool,
+    ) -> (i64, i64) {
         let page = pagination.page.unwrap_or(DEFAULT_PAGE).max(DEFAULT_PAGE);
 
         let items_per_page = pagination
@@ -72,25 +170,28 @@ impl EventPacketRepo {
             .unwrap_or(DEFAULT_ITEMS_PER_PAGE)
             .max(1);
 
-        let offset = (page - 1) * items_per_page;
+        let offset = if has_cursor {
+            0
+        } else {
+            (page - 1) * items_per_page
+        };
 
         query_builder.push(" LIMIT ");
         query_builder.push_bind(items_per_page + 1);
         query_builder.push(" OFFSET ");
         query_builder.push_bind(offset);
+
+        (items_per_page, offset)
     }
 
     pub async fn get_event_packet(
         &self,
         packet_id: i32,
     ) -> Result<EventPackets, EventPacketRepoError> {
-        let result = sqlx::query_as::<_, EventPackets>(
-            r#"
-            SELECT id, id_owner, nume, locatie, descriere, numarlocuri
-            FROM PACHETE
-            WHERE id = $1
-            "#,
-        )
+        let result = sqlx::query_as::<_, EventPackets>(&format!(
+            "SELECT {} FROM PACHETE WHERE id = $1",
+            PACKET_COLUMNS
+        ))
         .bind(packet_id)
         .fetch_one(&self.pool)
         .await;
@@ -107,19 +208,33 @@ impl EventPacketRepo {
         id_owner: i32,
         payload: CreateEventPacket,
     ) -> Result<EventPackets, EventPacketRepoError> {
-        let result = sqlx::query_as::<_, EventPackets>(
+        Self::insert_event_packet(&self.pool, id_owner, &payload).await
+    }
+
+    pub(crate) async fn insert_event_packet(
+        executor: impl PgExecutor<'_>,
+        id_owner: i32,
+        payload: &CreateEventPacket,
+    ) -> Result<EventPackets, EventPacketRepoError> {
+        let result = sqlx::query_as::<_, EventPackets>(&format!(
             r#"
-            INSERT INTO PACHETE (id_owner, nume, locatie, descriere, numarlocuri)
-            VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, id_owner, nume, locatie, descriere, numarlocuri
+            INSERT INTO PACHETE
+                (id_owner, nume, locatie, descriere, numarlocuri, category, tags, latitude, longitude)
+            VALUES ($1, $2, $3, $4, $5, $6, COALESCE($7, '{{}}'), $8, $9)
+            RETURNING {}
             "#,
-        )
+            PACKET_COLUMNS
+        ))
         .bind(id_owner)
         .bind(&payload.nume)
         .bind(&payload.locatie)
         .bind(&payload.descriere)
         .bind(payload.numarlocuri)
-        .fetch_one(&self.pool)
+        .bind(payload.category)
+        .bind(payload.tags.as_deref().map(normalize_tags))
+        .bind(payload.latitude)
+        .bind(payload.longitude)
+        .fetch_one(executor)
         .await;
 
         result.map_err(map_sqlx_packet_error)
@@ -130,7 +245,7 @@ impl EventPacketRepo {
         packet_id: i32,
         payload: UpdateEventPacket,
     ) -> Result<EventPackets, EventPacketRepoError> {
-        let result = sqlx::query_as::<_, EventPackets>(
+        let result = sqlx::query_as::<_, EventPackets>(&format!(
             r#"
             UPDATE PACHETE
             SET
@@ -138,16 +253,25 @@ impl EventPacketRepo {
                 nume = COALESCE($2, nume),
                 locatie = COALESCE($3, locatie),
                 descriere = COALESCE($4, descriere),
-                numarlocuri = COALESCE($5, numarlocuri)
-            WHERE id = $6
-            RETURNING id, id_owner, nume, locatie, descriere, numarlocuri
+                numarlocuri = COALESCE($5, numarlocuri),
+                category = COALESCE($6, category),
+                tags = COALESCE($7, tags),
+                latitude = COALESCE($8, latitude),
+                longitude = COALESCE($9, longitude)
+            WHERE id = $10
+            RETURNING {}
             "#,
-        )
+            PACKET_COLUMNS
+        ))
         .bind(payload.id_owner)
         .bind(&payload.nume)
         .bind(&payload.locatie)
         .bind(&payload.descriere)
         .bind(payload.numarlocuri)
+        .bind(payload.category)
+        .bind(payload.tags.as_deref().map(normalize_tags))
+        .bind(payload.latitude)
+        .bind(payload.longitude)
         .bind(packet_id)
         .fetch_one(&self.pool)
         .await;
@@ -160,7 +284,7 @@ impl EventPacketRepo {
         packet_id: i32,
         payload: crate::models::event_packets::PatchEventPacket,
     ) -> Result<EventPackets, EventPacketRepoError> {
-        let result = sqlx::query_as::<_, EventPackets>(
+        let result = sqlx::query_as::<_, EventPackets>(&format!(
             r#"
             UPDATE PACHETE
             SET
@@ -168,16 +292,25 @@ impl EventPacketRepo {
                 nume = COALESCE($2, nume),
                 locatie = COALESCE($3, locatie),
                 descriere = COALESCE($4, descriere),
-                numarlocuri = COALESCE($5, numarlocuri)
-            WHERE id = $6
-            RETURNING id, id_owner, nume, locatie, descriere, numarlocuri
+                numarlocuri = COALESCE($5, numarlocuri),
+                category = COALESCE($6, category),
+                tags = COALESCE($7, tags),
+                latitude = COALESCE($8, latitude),
+                longitude = COALESCE($9, longitude)
+            WHERE id = $10
+            RETURNING {}
             "#,
-        )
+            PACKET_COLUMNS
+        ))
         .bind(payload.id_owner)
         .bind(payload.nume.as_deref())
         .bind(payload.locatie.as_deref())
         .bind(payload.descriere.as_deref())
         .bind(payload.numarlocuri)
+        .bind(payload.category)
+        .bind(payload.tags.as_deref().map(normalize_tags))
+        .bind(payload.latitude)
+        .bind(payload.longitude)
         .bind(packet_id)
         .fetch_one(&self.pool)
         .await;
@@ -185,6 +318,78 @@ impl EventPacketRepo {
         result.map_err(map_sqlx_packet_error)
     }
 
+    pub async fn set_event_packet_status(
+        &self,
+        packet_id: i32,
+        from: EventStatus,
+        to: EventStatus,
+    ) -> Result<EventPackets, EventPacketRepoError> {
+        Self::update_status(&self.pool, packet_id, from, to).await
+    }
+
+    async fn update_status<'e, E: PgExecutor<'e>>(
+        executor: E,
+        packet_id: i32,
+        from: EventStatus,
+        to: EventStatus,
+    ) -> Result<EventPackets, EventPacketRepoError> {
+        let result = sqlx::query_as::<_, EventPackets>(&format!(
+            "UPDATE PACHETE SET status = $1 WHERE id = $2 AND status = $3 RETURNING {}",
+            PACKET_COLUMNS
+        ))
+        .bind(to)
+        .bind(packet_id)
+        .bind(from)
+        .fetch_one(executor)
+        .await;
+
+        match result {
+            Ok(packet) => Ok(packet),
+            Err(Error::RowNotFound) => Err(EventPacketRepoError::ConstraintViolation(
+                "The event packet status was changed by another request.".to_string(),
+            )),
+            Err(e) => Err(EventPacketRepoError::InternalError(e)),
+        }
+    }
+
+    /// Cancels the packet and, in the same transaction, queues
+    /// `event.cancelled` for the tickets its buyers are to be refunded for.
+    pub async fn cancel_event_packet(
+        &self,
+        packet_id: i32,
+        from: EventStatus,
+    ) -> Result<EventPackets, EventPacketRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(EventPacketRepoError::InternalError)?;
+
+        let packet = Self::update_status(&mut *tx, packet_id, from, EventStatus::Cancelled).await?;
+
+        let ticket_codes = TicketRepo::refundable_codes(&mut tx, None, Some(packet_id))
+            .await
+            .map_err(EventPacketRepoError::InternalError)?;
+        if !ticket_codes.is_empty() {
+            let message = EventCancelled {
+                event_id: None,
+                packet_id: Some(packet.id),
+                name: packet.nume.clone(),
+                owner_id: packet.id_owner,
+                ticket_codes,
+            };
+            OutboxRepo::enqueue(&mut tx, ROUTING_KEY_EVENT_CANCELLED, &message)
+                .await
+                .map_err(EventPacketRepoError::InternalError)?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(EventPacketRepoError::InternalError)?;
+
+        Ok(packet)
+    }
+
     pub async fn delete_event_packet(&self, packet_id: i32) -> Result<(), EventPacketRepoError> {
         let result = sqlx::query("DELETE FROM PACHETE WHERE id = $1")
             .bind(packet_id)
@@ -199,3 +404,73 @@ impl EventPacketRepo {
         }
     }
 }
+
+fn push_packet_filters(
+    query_builder: &mut QueryBuilder<'_, Postgres>,
+    params: &EventPacketQuery,
+    owner_id: Option<i32>,
+    status: Option<EventStatus>,
+    skip: Option<Facet>,
+) {
+    let mut has_condition = false;
+
+    let type_filter = params.descriere.as_deref().filter(|s| !s.is_empty());
+
+    if let Some(status) = status {
+        query_builder.push(" WHERE status = ");
+        query_builder.push_bind(status);
+        has_condition = true;
+    }
+
+    if let Some(owner_id) = owner_id {
+        query_builder.push(if has_condition { " AND " } else { " WHERE " });
+        query_builder.push("id_owner = ");
+        query_builder.push_bind(owner_id);
+        has_condition = true;
+    }
+
+    if let Some(desc_filter) = type_filter {
+        query_builder.push(if has_condition { " AND " } else { " WHERE " });
+        query_builder.push("unaccent(descriere) ILIKE unaccent(");
+        query_builder.push_bind(format!("%{}%", desc_filter));
+        query_builder.push(")");
+        has_condition = true;
+    }
+
+    if let Some(min_tickets) = params.bilete {
+        if has_condition {
+            query_builder.push(" AND ");
+        } else {
+            query_builder.push(" WHERE ");
+        }
+
+        query_builder.push(" PACHETE.numarlocuri >= ");
+        query_builder.push_bind(min_tickets);
+        has_condition = true;
+    }
+
+    if let (Some(point), Some(radius_km)) = (
+        params.near.as_deref().and_then(parse_near),
+        params.radius_km,
+    ) {
+        query_builder.push(if has_condition { " AND " } else { " WHERE " });
+        push_within_radius(query_builder, point, radius_km);
+        has_condition = true;
+    }
+
+    let categories = parse_category_filter(params.category.as_deref());
+    if !categories.is_empty() && skip != Some(Facet::Category) {
+        query_builder.push(if has_condition { " AND " } else { " WHERE " });
+        query_builder.push("category = ANY(");
+        query_builder.push_bind(categories);
+        query_builder.push(")");
+        has_condition = true;
+    }
+
+    let tags = parse_tag_filter(params.tags.as_deref());
+    if !tags.is_empty() && skip != Some(Facet::Tag) {
+        query_builder.push(if has_condition { " AND " } else { " WHERE " });
+        query_builder.push("tags && ");
+        query_builder.push_bind(tags);
+    }
+}
@@ -1,7 +1,21 @@
-use crate::models::event::{CreateEvent, Event, EventQuery, UpdateEvent};
+use crate::models::event::{
+    CreateEvent, Event, EventQuery, EventStatus, FacetCount, Facets, UpdateEvent, normalize_tags,
+    parse_category_filter, parse_near, parse_tag_filter,
+};
+use crate::repositories::keyset::{NameKey, Ordering, push_name_keyset};
+use crate::repositories::outbox_repo::OutboxRepo;
+use crate::repositories::ticket_repo::TicketRepo;
 use crate::utils::error::*;
 use anyhow::Result;
-use sqlx::{Error, PgPool, Postgres, QueryBuilder};
+use common::pagination::{Cursor, Page};
+use common::rabbitmq::messages::{EventCancelled, ROUTING_KEY_EVENT_CANCELLED};
+use sqlx::{Error, PgExecutor, PgPool, Postgres, QueryBuilder};
+
+pub(crate) const EVENT_COLUMNS: &str =
+    "ID, ID_OWNER, nume, locatie, descriere, numarlocuri, starts_at, ends_at, timezone, status, room_id, category, tags, latitude, longitude";
+
+const MAX_TAG_FACETS: i64 = 50;
+const KM_PER_DEGREE_LATITUDE: f64 = 111.2;
 
 pub struct EventRepo {
     pool: PgPool,
@@ -17,48 +31,50 @@ impl EventRepo {
         Ok(())
     }
 
-    pub async fn list_events(&self, params: EventQuery) -> Result<Vec<Event>, EventRepoError> {
-        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
-            "SELECT ID, ID_OWNER, nume, locatie, descriere, numarlocuri FROM EVENIMENTE",
-        );
-
-        let mut has_condition = false;
-
-        let location = params.locatie.filter(|s| !s.is_empty());
-        let name = params.nume.filter(|s| !s.is_empty());
+    pub async fn list_events(
+        &self,
+        params: EventQuery,
+        status: Option<EventStatus>,
+    ) -> Result<Page<Event>, EventRepoError> {
+        let near = params.near.as_deref().and_then(parse_near);
+        let cursor = match params.paginare.cursor.as_deref() {
+            Some(cursor) => {
+                Some(Cursor::<NameKey>::decode(cursor).ok_or(EventRepoError::InvalidCursor)?)
+            }
+            None => None,
+        };
 
-        if let Some(owner_id) = params.owner_id {
-            query_builder.push(" WHERE id_owner = ");
-            query_builder.push_bind(owner_id);
-            has_condition = true;
+        let mut query_builder: QueryBuilder<Postgres> =
+            QueryBuilder::new(format!("SELECT * FROM (SELECT {}", EVENT_COLUMNS));
+        if let Some(point) = near {
+            query_builder.push(", ");
+            push_distance_km(&mut query_builder, point);
+            query_builder.push(" AS distance_km");
         }
+        query_builder.push(" FROM EVENIMENTE");
 
-        if let Some(location) = location {
-            if has_condition {
-                query_builder.push(" AND unaccent(locatie) ILIKE unaccent(");
-            } else {
-                query_builder.push(" WHERE unaccent(locatie) ILIKE unaccent(");
-            }
-            query_builder.push_bind(format!("%{}%", location));
-            query_builder.push(")");
-            has_condition = true;
-        }
+        push_event_filters(&mut query_builder, &params, status, None);
+        query_builder.push(") AS page");
 
-        if let Some(name) = name {
-            if has_condition {
-                query_builder.push(" AND unaccent(nume) ILIKE unaccent(");
-            } else {
-                query_builder.push(" WHERE unaccent(nume) ILIKE unaccent(");
-            }
-            query_builder.push_bind(format!("%{}%", name));
-            query_builder.push(")");
-        }
+        let has_date_filter =
+            params.from.is_some() || params.to.is_some() || params.upcoming_only == Some(true);
 
-        query_builder.push(" ORDER BY nume ASC");
+        let ordering = if near.is_some() {
+            Ordering::Distance
+        } else if has_date_filter {
+            Ordering::StartsAt
+        } else {
+            Ordering::Name
+        };
+        push_name_keyset(&mut query_builder, ordering, cursor.as_ref());
 
         let page = params.paginare.page.unwrap_or(1);
         let items_per_page = params.paginare.items_per_page.unwrap_or(10);
-        let offset = (page - 1) * items_per_page;
+        let offset = if cursor.is_some() {
+            0
+        } else {
+            (page - 1) * items_per_page
+        };
 
         query_builder.push(" LIMIT ");
         query_builder.push_bind(items_per_page + 1);
@@ -70,17 +86,83 @@ impl EventRepo {
             .fetch_all(&self.pool)
             .await
             .map_err(map_sqlx_event_error)?;
-        Ok(events)
+
+        let total = if params.paginare.include_total == Some(true) {
+            Some(self.count_events(&params, status).await?)
+        } else {
+            None
+        };
+
+        Ok(Page::from_rows(
+            events,
+            items_per_page as usize,
+            cursor.as_ref().is_some_and(|cursor| cursor.before),
+            cursor.is_some() || offset > 0,
+            |event| NameKey {
+                name: event.nume.clone(),
+                starts_at: event.starts_at,
+                distance_km: event.distance_km,
+            },
+        )
+        .with_total(total))
+    }
+
+    async fn count_events(
+        &self,
+        params: &EventQuery,
+        status: Option<EventStatus>,
+    ) -> Result<i64, EventRepoError> {
+        let mut query_builder: QueryBuilder<Postgres> =
+            QueryBuilder::new("SELECT COUNT(*) FROM EVENIMENTE");
+        push_event_filters(&mut query_builder, params, status, None);
+
+        query_builder
+            .build_query_scalar::<i64>()
+            .fetch_one(&self.pool)
+            .await
+            .map_err(EventRepoError::InternalError)
+    }
+
+    pub async fn event_facets(
+        &self,
+        params: &EventQuery,
+        status: Option<EventStatus>,
+    ) -> Result<Facets, EventRepoError> {
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT category AS value, COUNT(*) AS count FROM (SELECT category FROM EVENIMENTE",
+        );
+        push_event_filters(&mut query_builder, params, status, Some(Facet::Category));
+        query_builder.push(
+            ") f WHERE category IS NOT NULL GROUP BY category ORDER BY count DESC, value ASC",
+        );
+        let categories = query_builder
+            .build_query_as::<FacetCount>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(EventRepoError::InternalError)?;
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT tag AS value, COUNT(*) AS count FROM (SELECT tags FROM EVENIMENTE",
+        );
+        push_event_filters(&mut query_builder, params, status, Some(Facet::Tag));
+        query_builder
+            .push(") f, UNNEST(f.tags) AS tag GROUP BY tag ORDER BY count DESC, value ASC");
+        query_builder.push(" LIMIT ");
+        query_builder.push_bind(MAX_TAG_FACETS);
+        let tags = query_builder
+            .build_query_as::<FacetCount>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(EventRepoError::InternalError)?;
+
+        Ok(Facets { categories, tags })
     }
 
     pub async fn get_event(&self, event_id: i32) -> Result<Event, EventRepoError> {
-        let result = sqlx::query_as::<_, Event>(
-            r#"
-            SELECT ID, ID_OWNER, nume, locatie, descriere, numarlocuri
-            FROM EVENIMENTE
-            WHERE ID = $1
-            "#,
-        )
+        let result = sqlx::query_as::<_, Event>(&format!(
+            "SELECT {} FROM EVENIMENTE WHERE ID = $1",
+            EVENT_COLUMNS
+        ))
         .bind(event_id)
         .fetch_one(&self.pool)
         .await;
@@ -92,24 +174,72 @@ impl EventRepo {
         }
     }
 
+    /// Events the given tickets admit to: the event of an event ticket and
+    /// every event of a packet ticket. Drafts are never included.
+    pub async fn list_events_for_tickets(
+        &self,
+        codes: &[String],
+    ) -> Result<Vec<Event>, EventRepoError> {
+        sqlx::query_as::<_, Event>(&format!(
+            r#"
+            SELECT {}
+            FROM EVENIMENTE
+            WHERE status <> 'DRAFT'
+              AND id IN (
+                SELECT evenimentid FROM BILETE WHERE cod = ANY($1)
+                UNION
+                SELECT j.evenimentid
+                FROM BILETE b
+                JOIN JOIN_PE j ON j.pachetid = b.pachetid
+                WHERE b.cod = ANY($1)
+              )
+            ORDER BY starts_at ASC NULLS LAST, id ASC
+            "#,
+            EVENT_COLUMNS
+        ))
+        .bind(codes)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(EventRepoError::InternalError)
+    }
+
     pub async fn create_event(
         &self,
         id_owner: i32,
         payload: CreateEvent,
     ) -> Result<Event, EventRepoError> {
-        let result = sqlx::query_as::<_, Event>(
+        Self::insert_event(&self.pool, id_owner, &payload).await
+    }
+
+    pub(crate) async fn insert_event(
+        executor: impl PgExecutor<'_>,
+        id_owner: i32,
+        payload: &CreateEvent,
+    ) -> Result<Event, EventRepoError> {
+        let result = sqlx::query_as::<_, Event>(&format!(
             r#"
-            INSERT INTO EVENIMENTE (ID_OWNER, nume, locatie, descriere, numarlocuri)
-            VALUES ($1, $2, $3, $4, $5)
-            RETURNING ID, ID_OWNER, nume, locatie, descriere, numarlocuri
+            INSERT INTO EVENIMENTE
+                (ID_OWNER, nume, locatie, descriere, numarlocuri, starts_at, ends_at, timezone, room_id,
+                 category, tags, latitude, longitude)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, COALESCE($11, '{{}}'), $12, $13)
+            RETURNING {}
             "#,
-        )
+            EVENT_COLUMNS
+        ))
         .bind(id_owner)
         .bind(&payload.nume)
         .bind(&payload.locatie)
         .bind(&payload.descriere)
         .bind(payload.locuri)
-        .fetch_one(&self.pool)
+        .bind(payload.starts_at)
+        .bind(payload.ends_at)
+        .bind(&payload.timezone)
+        .bind(payload.room_id)
+        .bind(payload.category)
+        .bind(payload.tags.as_deref().map(normalize_tags))
+        .bind(payload.latitude)
+        .bind(payload.longitude)
+        .fetch_one(executor)
         .await;
 
         result.map_err(map_sqlx_event_error)
@@ -126,7 +256,7 @@ impl EventRepo {
             .await
             .map_err(EventRepoError::InternalError)?;
 
-        let result = sqlx::query_as::<_, Event>(
+        let result = sqlx::query_as::<_, Event>(&format!(
             r#"
         UPDATE EVENIMENTE
         SET
@@ -134,24 +264,40 @@ impl EventRepo {
             nume = COALESCE($2, nume),
             locatie = COALESCE($3, locatie),
             descriere = COALESCE($4, descriere),
-            numarlocuri = COALESCE($5, numarlocuri)
-        WHERE ID = $6
-        RETURNING ID, ID_OWNER, nume, locatie, descriere, numarlocuri
+            numarlocuri = COALESCE($5, numarlocuri),
+            starts_at = COALESCE($6, starts_at),
+            ends_at = COALESCE($7, ends_at),
+            timezone = COALESCE($8, timezone),
+            room_id = COALESCE($9, room_id),
+            category = COALESCE($10, category),
+            tags = COALESCE($11, tags),
+            latitude = COALESCE($12, latitude),
+            longitude = COALESCE($13, longitude)
+        WHERE ID = $14
+        RETURNING {}
         "#,
-        )
+            EVENT_COLUMNS
+        ))
         .bind(payload.id_owner)
         .bind(&payload.nume)
         .bind(&payload.locatie)
         .bind(&payload.descriere)
         .bind(payload.locuri)
+        .bind(payload.starts_at)
+        .bind(payload.ends_at)
+        .bind(payload.timezone.as_deref())
+        .bind(payload.room_id)
+        .bind(payload.category)
+        .bind(payload.tags.as_deref().map(normalize_tags))
+        .bind(payload.latitude)
+        .bind(payload.longitude)
         .bind(event_id)
         .fetch_one(&mut *tx)
         .await;
 
         let event = match result {
             Ok(event) => event,
-            Err(Error::RowNotFound) => return Err(EventRepoError::NotFound),
-            Err(e) => return Err(EventRepoError::InternalError(e)),
+            Err(e) => return Err(map_sqlx_event_error(e)),
         };
 
         if payload.locuri.is_some() {
@@ -175,7 +321,7 @@ impl EventRepo {
             .await
             .map_err(EventRepoError::InternalError)?;
 
-        let result = sqlx::query_as::<_, Event>(
+        let result = sqlx::query_as::<_, Event>(&format!(
             r#"
         UPDATE EVENIMENTE
         SET
@@ -183,24 +329,40 @@ impl EventRepo {
             nume = COALESCE($2, nume),
             locatie = COALESCE($3, locatie),
             descriere = COALESCE($4, descriere),
-            numarlocuri = COALESCE($5, numarlocuri)
-        WHERE ID = $6
-        RETURNING ID, ID_OWNER, nume, locatie, descriere, numarlocuri
+            numarlocuri = COALESCE($5, numarlocuri),
+            starts_at = COALESCE($6, starts_at),
+            ends_at = COALESCE($7, ends_at),
+            timezone = COALESCE($8, timezone),
+            room_id = COALESCE($9, room_id),
+            category = COALESCE($10, category),
+            tags = COALESCE($11, tags),
+            latitude = COALESCE($12, latitude),
+            longitude = COALESCE($13, longitude)
+        WHERE ID = $14
+        RETURNING {}
         "#,
-        )
+            EVENT_COLUMNS
+        ))
         .bind(payload.id_owner)
         .bind(payload.nume.as_deref())
         .bind(payload.locatie.as_deref())
         .bind(payload.descriere.as_deref())
         .bind(payload.locuri)
+        .bind(payload.starts_at)
+        .bind(payload.ends_at)
+        .bind(payload.timezone.as_deref())
+        .bind(payload.room_id)
+        .bind(payload.category)
+        .bind(payload.tags.as_deref().map(normalize_tags))
+        .bind(payload.latitude)
+        .bind(payload.longitude)
         .bind(event_id)
         .fetch_one(&mut *tx)
         .await;
 
         let event = match result {
             Ok(event) => event,
-            Err(Error::RowNotFound) => return Err(EventRepoError::NotFound),
-            Err(e) => return Err(EventRepoError::InternalError(e)),
+            Err(e) => return Err(map_sqlx_event_error(e)),
         };
 
         if payload.locuri.is_some() {
@@ -213,6 +375,76 @@ impl EventRepo {
         Ok(event)
     }
 
+    pub async fn set_event_status(
+        &self,
+        event_id: i32,
+        from: EventStatus,
+        to: EventStatus,
+    ) -> Result<Event, EventRepoError> {
+        Self::update_status(&self.pool, event_id, from, to).await
+    }
+
+    async fn update_status<'e, E: PgExecutor<'e>>(
+        executor: E,
+        event_id: i32,
+        from: EventStatus,
+        to: EventStatus,
+    ) -> Result<Event, EventRepoError> {
+        let result = sqlx::query_as::<_, Event>(&format!(
+            "UPDATE EVENIMENTE SET status = $1 WHERE ID = $2 AND status = $3 RETURNING {}",
+            EVENT_COLUMNS
+        ))
+        .bind(to)
+        .bind(event_id)
+        .bind(from)
+        .fetch_one(executor)
+        .await;
+
+        match result {
+            Ok(event) => Ok(event),
+            Err(Error::RowNotFound) => Err(EventRepoError::ConstraintViolation(
+                "The event status was changed by another request.".to_string(),
+            )),
+            Err(e) => Err(EventRepoError::InternalError(e)),
+        }
+    }
+
+    /// Cancels the event and, in the same transaction, queues
+    /// `event.cancelled` for the tickets its buyers are to be refunded for.
+    pub async fn cancel_event(
+        &self,
+        event_id: i32,
+        from: EventStatus,
+    ) -> Result<Event, EventRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(EventRepoError::InternalError)?;
+
+        let event = Self::update_status(&mut *tx, event_id, from, EventStatus::Cancelled).await?;
+
+        let ticket_codes = TicketRepo::refundable_codes(&mut tx, Some(event_id), None)
+            .await
+            .map_err(EventRepoError::InternalError)?;
+        if !ticket_codes.is_empty() {
+            let message = EventCancelled {
+                event_id: Some(event.id),
+                packet_id: None,
+                name: event.nume.clone(),
+                owner_id: event.id_owner,
+                ticket_codes,
+            };
+            OutboxRepo::enqueue(&mut tx, ROUTING_KEY_EVENT_CANCELLED, &message)
+                .await
+                .map_err(EventRepoError::InternalError)?;
+        }
+
+        tx.commit().await.map_err(EventRepoError::InternalError)?;
+
+        Ok(event)
+    }
+
     pub async fn delete_event(&self, event_id: i32) -> Result<(), EventRepoError> {
         let result = sqlx::query("DELETE FROM EVENIMENTE WHERE ID = $1")
             .bind(event_id)
@@ -265,3 +497,131 @@ impl EventRepo {
         Ok(())
     }
 }
+
+/// Facet dimension left out of the filters while its own counts are computed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Facet {
+    Category,
+    Tag,
+}
+
+fn push_event_filters(
+    query_builder: &mut QueryBuilder<'_, Postgres>,
+    params: &EventQuery,
+    status: Option<EventStatus>,
+    skip: Option<Facet>,
+) {
+    let mut has_condition = false;
+
+    let location = params.locatie.as_deref().filter(|s| !s.is_empty());
+    let name = params.nume.as_deref().filter(|s| !s.is_empty());
+
+    if let Some(status) = status {
+        query_builder.push(" WHERE status = ");
+        query_builder.push_bind(status);
+        has_condition = true;
+    }
+
+    if let Some(owner_id) = params.owner_id {
+        query_builder.push(if has_condition { " AND " } else { " WHERE " });
+        query_builder.push("id_owner = ");
+        query_builder.push_bind(owner_id);
+        has_condition = true;
+    }
+
+    if let Some(location) = location {
+        if has_condition {
+            query_builder.push(" AND unaccent(locatie) ILIKE unaccent(");
+        } else {
+            query_builder.push(" WHERE unaccent(locatie) ILIKE unaccent(");
+        }
+        query_builder.push_bind(format!("%{}%", location));
+        query_builder.push(")");
+        has_condition = true;
+    }
+
+    if let Some(name) = name {
+        if has_condition {
+            query_builder.push(" AND unaccent(nume) ILIKE unaccent(");
+        } else {
+            query_builder.push(" WHERE unaccent(nume) ILIKE unaccent(");
+        }
+        query_builder.push_bind(format!("%{}%", name));
+        query_builder.push(")");
+        has_condition = true;
+    }
+
+    if let Some(from) = params.from {
+        query_builder.push(if has_condition { " AND " } else { " WHERE " });
+        query_builder.push("COALESCE(ends_at, starts_at) >= ");
+        query_builder.push_bind(from);
+        has_condition = true;
+    }
+
+    if let Some(to) = params.to {
+        query_builder.push(if has_condition { " AND " } else { " WHERE " });
+        query_builder.push("starts_at <= ");
+        query_builder.push_bind(to);
+        has_condition = true;
+    }
+
+    if params.upcoming_only == Some(true) {
+        query_builder.push(if has_condition { " AND " } else { " WHERE " });
+        query_builder.push("starts_at >= NOW()");
+        has_condition = true;
+    }
+
+    if let (Some(point), Some(radius_km)) = (
+        params.near.as_deref().and_then(parse_near),
+        params.radius_km,
+    ) {
+        query_builder.push(if has_condition { " AND " } else { " WHERE " });
+        push_within_radius(query_builder, point, radius_km);
+        has_condition = true;
+    }
+
+    let categories = parse_category_filter(params.category.as_deref());
+    if !categories.is_empty() && skip != Some(Facet::Category) {
+        query_builder.push(if has_condition { " AND " } else { " WHERE " });
+        query_builder.push("category = ANY(");
+        query_builder.push_bind(categories);
+        query_builder.push(")");
+        has_condition = true;
+    }
+
+    let tags = parse_tag_filter(params.tags.as_deref());
+    if !tags.is_empty() && skip != Some(Facet::Tag) {
+        query_builder.push(if has_condition { " AND " } else { " WHERE " });
+        query_builder.push("tags && ");
+        query_builder.push_bind(tags);
+    }
+}
+
+/// Great-circle distance from the row's coordinates to `(lat, lng)`.
+pub(crate) fn push_distance_km(
+    query_builder: &mut QueryBuilder<'_, Postgres>,
+    (lat, lng): (f64, f64),
+) {
+    query_builder.push("great_circle_km(latitude, longitude, ");
+    query_builder.push_bind(lat);
+    query_builder.push(", ");
+    query_builder.push_bind(lng);
+    query_builder.push(")");
+}
+
+/// The latitude band lets the index discard far-away rows before the exact distance is computed.
+pub(crate) fn push_within_radius(
+    query_builder: &mut QueryBuilder<'_, Postgres>,
+    point: (f64, f64),
+    radius_km: f64,
+) {
+    let band = radius_km / KM_PER_DEGREE_LATITUDE;
+    query_builder.push("latitude BETWEEN ");
+    query_builder.push_bind(point.0 - band);
+    query_builder.push(" AND ");
+    query_builder.push_bind(point.0 + band);
+    query_builder.push(" AND ");
+    push_distance_km(query_builder, point);
+    query_builder.push(" <= ");
+    query_builder.push_bind(radius_km);
+}
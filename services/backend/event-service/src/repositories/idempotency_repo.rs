@@ -0,0 +1,122 @@
+use crate::models::idempotency::IdempotencyClaim;
+use crate::utils::error::IdempotencyRepoError;
+use anyhow::Result;
+use common::idempotency::{IDEMPOTENCY_CLAIM_TIMEOUT_SECS, IDEMPOTENCY_KEY_TTL_SECS};
+use sqlx::PgPool;
+
+pub struct IdempotencyRepo {
+    pool: PgPool,
+}
+
+impl IdempotencyRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Reserves `key` within `scope` for the request with `fingerprint`. If the
+    /// key was used before, returns the stored response, reports that the
+    /// first request has not finished yet or that it was a different request.
+    /// A claim left unfinished for too long is taken over.
+    pub async fn claim(
+        &self,
+        scope: &str,
+        key: &str,
+        fingerprint: &str,
+    ) -> Result<IdempotencyClaim, IdempotencyRepoError> {
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO IDEMPOTENCY_KEYS (scope, key, fingerprint) VALUES ($1, $2, $3)
+            ON CONFLICT (scope, key) DO UPDATE
+            SET fingerprint = EXCLUDED.fingerprint, created_at = NOW()
+            WHERE IDEMPOTENCY_KEYS.status_code IS NULL
+              AND IDEMPOTENCY_KEYS.created_at < NOW() - make_interval(secs => $4)
+            "#,
+        )
+        .bind(scope)
+        .bind(key)
+        .bind(fingerprint)
+        .bind(IDEMPOTENCY_CLAIM_TIMEOUT_SECS as f64)
+        .execute(&self.pool)
+        .await
+        .map_err(IdempotencyRepoError::InternalError)?;
+
+        if inserted.rows_affected() == 1 {
+            return Ok(IdempotencyClaim::Claimed);
+        }
+
+        let stored: Option<(String, Option<i32>, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT fingerprint, status_code, response_body FROM IDEMPOTENCY_KEYS
+            WHERE scope = $1 AND key = $2
+            "#,
+        )
+        .bind(scope)
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(IdempotencyRepoError::InternalError)?;
+
+        match stored {
+            Some((stored_fingerprint, _, _)) if stored_fingerprint != fingerprint => {
+                Ok(IdempotencyClaim::Mismatch)
+            }
+            Some((_, Some(status), body)) => Ok(IdempotencyClaim::Completed {
+                status: status as u16,
+                body,
+            }),
+            _ => Ok(IdempotencyClaim::InProgress),
+        }
+    }
+
+    pub async fn complete(
+        &self,
+        scope: &str,
+        key: &str,
+        status: u16,
+        body: Option<&str>,
+    ) -> Result<(), IdempotencyRepoError> {
+        sqlx::query(
+            r#"
+            UPDATE IDEMPOTENCY_KEYS
+            SET status_code = $3, response_body = $4
+            WHERE scope = $1 AND key = $2
+            "#,
+        )
+        .bind(scope)
+        .bind(key)
+        .bind(status as i32)
+        .bind(body)
+        .execute(&self.pool)
+        .await
+        .map_err(IdempotencyRepoError::InternalError)?;
+
+        Ok(())
+    }
+
+    /// Frees a key whose request failed so that a retry can run it again.
+    pub async fn release(&self, scope: &str, key: &str) -> Result<(), IdempotencyRepoError> {
+        sqlx::query(
+            "DELETE FROM IDEMPOTENCY_KEYS WHERE scope = $1 AND key = $2 AND status_code IS NULL",
+        )
+        .bind(scope)
+        .bind(key)
+        .execute(&self.pool)
+        .await
+        .map_err(IdempotencyRepoError::InternalError)?;
+
+        Ok(())
+    }
+
+    /// Deletes keys older than the replay window, returning how many went.
+    pub async fn purge_expired(&self) -> Result<u64, IdempotencyRepoError> {
+        let deleted = sqlx::query(
+            "DELETE FROM IDEMPOTENCY_KEYS WHERE created_at < NOW() - make_interval(secs => $1)",
+        )
+        .bind(IDEMPOTENCY_KEY_TTL_SECS as f64)
+        .execute(&self.pool)
+        .await
+        .map_err(IdempotencyRepoError::InternalError)?;
+
+        Ok(deleted.rows_affected())
+    }
+}
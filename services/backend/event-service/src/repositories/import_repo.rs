@@ -0,0 +1,217 @@
+use crate::models::event::Event;
+use crate::models::import::{ImportOutcome, ImportedEvent, ImportedPacket};
+use crate::repositories::event_packets_repo::EventPacketRepo;
+use crate::repositories::event_repo::{EVENT_COLUMNS, EventRepo};
+use crate::utils::error::{EventPacketRepoError, EventRepoError, ImportRepoError};
+use sqlx::{Connection, PgConnection, PgPool};
+use std::collections::HashMap;
+
+pub struct ImportRepo {
+    pool: PgPool,
+}
+
+/// Why a single row could not be written.
+enum RowFailure {
+    Rejected(String),
+    Internal(ImportRepoError),
+}
+
+impl From<sqlx::Error> for RowFailure {
+    fn from(error: sqlx::Error) -> Self {
+        RowFailure::Internal(ImportRepoError::InternalError(error))
+    }
+}
+
+impl ImportRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_events_by_name(
+        &self,
+        names: &[String],
+    ) -> Result<Vec<Event>, ImportRepoError> {
+        sqlx::query_as::<_, Event>(&format!(
+            "SELECT {} FROM EVENIMENTE WHERE nume = ANY($1)",
+            EVENT_COLUMNS
+        ))
+        .bind(names)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(ImportRepoError::InternalError)
+    }
+
+    /// Writes the rows in one transaction, each behind its own savepoint so a
+    /// rejected row does not hide problems in the rows after it. Packets link
+    /// to events by name: first those created here, then `existing`. The
+    /// transaction is only committed when `commit` is set and every row went in.
+    pub async fn run_import(
+        &self,
+        id_owner: i32,
+        events: &[ImportedEvent],
+        packets: &[ImportedPacket],
+        existing: &HashMap<String, i32>,
+        commit: bool,
+    ) -> Result<ImportOutcome, ImportRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(ImportRepoError::InternalError)?;
+
+        let mut created: HashMap<&str, i32> = HashMap::new();
+        let mut rejected = false;
+
+        let mut event_results = Vec::with_capacity(events.len());
+        for imported in events {
+            let mut savepoint = tx.begin().await.map_err(ImportRepoError::InternalError)?;
+            match EventRepo::insert_event(&mut *savepoint, id_owner, &imported.event).await {
+                Ok(event) => {
+                    savepoint
+                        .commit()
+                        .await
+                        .map_err(ImportRepoError::InternalError)?;
+                    created.insert(&imported.event.nume, event.id);
+                    event_results.push(Ok(event.id));
+                }
+                Err(e) => {
+                    savepoint
+                        .rollback()
+                        .await
+                        .map_err(ImportRepoError::InternalError)?;
+                    rejected = true;
+                    event_results.push(Err(describe_event_error(e)?));
+                }
+            }
+        }
+
+        let mut links_created = 0;
+        let mut packet_results = Vec::with_capacity(packets.len());
+        for imported in packets {
+            let mut savepoint = tx.begin().await.map_err(ImportRepoError::InternalError)?;
+            match insert_packet(&mut savepoint, id_owner, imported, &created, existing).await {
+                Ok((packet_id, links)) => {
+                    savepoint
+                        .commit()
+                        .await
+                        .map_err(ImportRepoError::InternalError)?;
+                    links_created += links;
+                    packet_results.push(Ok(packet_id));
+                }
+                Err(RowFailure::Rejected(message)) => {
+                    savepoint
+                        .rollback()
+                        .await
+                        .map_err(ImportRepoError::InternalError)?;
+                    rejected = true;
+                    packet_results.push(Err(message));
+                }
+                Err(RowFailure::Internal(e)) => return Err(e),
+            }
+        }
+
+        let committed = commit && !rejected;
+        if committed {
+            tx.commit().await
+        } else {
+            tx.rollback().await
+        }
+        .map_err(ImportRepoError::InternalError)?;
+
+        Ok(ImportOutcome {
+            events: event_results,
+            packets: packet_results,
+            links_created,
+            committed,
+        })
+    }
+}
+
+async fn insert_packet(
+    conn: &mut PgConnection,
+    id_owner: i32,
+    imported: &ImportedPacket,
+    created: &HashMap<&str, i32>,
+    existing: &HashMap<String, i32>,
+) -> Result<(i32, usize), RowFailure> {
+    let mut event_ids = Vec::with_capacity(imported.events.len());
+    for name in &imported.events {
+        let id = created
+            .get(name.as_str())
+            .or_else(|| existing.get(name))
+            .ok_or_else(|| {
+                RowFailure::Rejected(format!("Event '{}' could not be imported", name))
+            })?;
+        if !event_ids.contains(id) {
+            event_ids.push(*id);
+        }
+    }
+
+    let packet = EventPacketRepo::insert_event_packet(&mut *conn, id_owner, &imported.packet)
+        .await
+        .map_err(describe_packet_error)?;
+
+    if event_ids.is_empty() {
+        return Ok((packet.id, 0));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO JOIN_PE (pachetid, evenimentid)
+        SELECT $1, UNNEST($2::int[])
+        "#,
+    )
+    .bind(packet.id)
+    .bind(&event_ids)
+    .execute(&mut *conn)
+    .await?;
+
+    // Same rule as linking through the API: a packet holds no more seats
+    // than its smallest event.
+    sqlx::query(
+        r#"
+        UPDATE PACHETE
+        SET numarlocuri = (
+            SELECT MIN(e.numarlocuri)
+            FROM EVENIMENTE e
+            JOIN JOIN_PE j ON j.evenimentid = e.id
+            WHERE j.pachetid = $1
+        )
+        WHERE id = $1
+        "#,
+    )
+    .bind(packet.id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok((packet.id, event_ids.len()))
+}
+
+fn describe_event_error(error: EventRepoError) -> Result<String, ImportRepoError> {
+    match error {
+        EventRepoError::DuplicateEntry => Ok("An event with this name already exists.".into()),
+        EventRepoError::InvalidReference => Ok("The room does not exist.".into()),
+        EventRepoError::ConstraintViolation(message) => Ok(message),
+        EventRepoError::InternalError(e) => Err(ImportRepoError::InternalError(e)),
+        EventRepoError::NotFound | EventRepoError::InvalidCursor => {
+            Ok("The event could not be created.".into())
+        }
+    }
+}
+
+fn describe_packet_error(error: EventPacketRepoError) -> RowFailure {
+    match error {
+        EventPacketRepoError::DuplicateName => {
+            RowFailure::Rejected("An event packet with this name already exists.".into())
+        }
+        EventPacketRepoError::ConstraintViolation(message) => RowFailure::Rejected(message),
+        EventPacketRepoError::InternalError(e) => {
+            RowFailure::Internal(ImportRepoError::InternalError(e))
+        }
+        EventPacketRepoError::NotFound
+        | EventPacketRepoError::InvalidEventId
+        | EventPacketRepoError::InvalidCursor => {
+            RowFailure::Rejected("The event packet could not be created.".into())
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use crate::models::event::Event;
 use crate::models::event_packets::EventPackets;
 use crate::models::join_pe::EventPacketRelation;
+use crate::repositories::event_packets_repo::PACKET_COLUMNS;
+use crate::repositories::event_repo::EVENT_COLUMNS;
 use crate::utils::error::{JoinPeRepoError, map_sqlx_join_pe_error};
 use anyhow::Result;
 use rayon::prelude::*;
@@ -55,14 +57,14 @@ impl JoinPeRepo {
         &self,
         pachet_id: i32,
     ) -> Result<Vec<Event>, JoinPeRepoError> {
-        sqlx::query_as::<_, Event>(
+        sqlx::query_as::<_, Event>(&format!(
             r#"
-            SELECT e.id, e.id_owner, e.nume, e.locatie, e.descriere, e.numarlocuri
-            FROM EVENIMENTE e
-            JOIN JOIN_PE j ON e.id = j.evenimentid
-            WHERE j.pachetid = $1
+            SELECT {}
+            FROM EVENIMENTE
+            WHERE id IN (SELECT evenimentid FROM JOIN_PE WHERE pachetid = $1)
             "#,
-        )
+            EVENT_COLUMNS
+        ))
         .bind(pachet_id)
         .fetch_all(&self.pool)
         .await
@@ -73,14 +75,14 @@ impl JoinPeRepo {
         &self,
         eveniment_id: i32,
     ) -> Result<Vec<EventPackets>, JoinPeRepoError> {
-        sqlx::query_as::<_, EventPackets>(
+        sqlx::query_as::<_, EventPackets>(&format!(
             r#"
-            SELECT p.id, p.id_owner, p.nume, p.locatie, p.descriere, p.numarlocuri
-            FROM PACHETE p
-            JOIN JOIN_PE j ON p.id = j.pachetid
-            WHERE j.evenimentid = $1
+            SELECT {}
+            FROM PACHETE
+            WHERE id IN (SELECT pachetid FROM JOIN_PE WHERE evenimentid = $1)
             "#,
-        )
+            PACKET_COLUMNS
+        ))
         .bind(eveniment_id)
         .fetch_all(&self.pool)
         .await
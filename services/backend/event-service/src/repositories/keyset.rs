@@ -0,0 +1,112 @@
+use chrono::{DateTime, Utc};
+use common::pagination::Cursor;
+use serde::{Deserialize, Serialize};
+use sqlx::{Encode, Postgres, QueryBuilder, Type};
+
+/// Cursor key of event and packet listings. `nume` is unique in both tables,
+/// so it breaks every tie left by the leading sort column.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct NameKey {
+    #[serde(rename = "n")]
+    pub name: String,
+    #[serde(rename = "s", default, skip_serializing_if = "Option::is_none")]
+    pub starts_at: Option<DateTime<Utc>>,
+    #[serde(rename = "d", default, skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Ordering {
+    Name,
+    StartsAt,
+    Distance,
+}
+
+/// Pushes the cursor condition and the matching `ORDER BY` over the output
+/// columns of a listing subquery. Pages read backwards come out in reverse
+/// order; `Page::from_rows` flips them.
+pub(crate) fn push_name_keyset(
+    query_builder: &mut QueryBuilder<'_, Postgres>,
+    ordering: Ordering,
+    cursor: Option<&Cursor<NameKey>>,
+) {
+    let backward = cursor.is_some_and(|cursor| cursor.before);
+
+    match ordering {
+        Ordering::Name => {
+            if let Some(cursor) = cursor {
+                query_builder.push(if backward {
+                    " WHERE nume < "
+                } else {
+                    " WHERE nume > "
+                });
+                query_builder.push_bind(cursor.key.name.clone());
+            }
+            query_builder.push(if backward {
+                " ORDER BY nume DESC"
+            } else {
+                " ORDER BY nume ASC"
+            });
+        }
+        Ordering::StartsAt => push_nullable_keyset(
+            query_builder,
+            "starts_at",
+            cursor.map(|cursor| (cursor.key.starts_at, cursor.key.name.clone())),
+            backward,
+        ),
+        Ordering::Distance => push_nullable_keyset(
+            query_builder,
+            "distance_km",
+            cursor.map(|cursor| (cursor.key.distance_km, cursor.key.name.clone())),
+            backward,
+        ),
+    }
+}
+
+/// Keyset over `(column NULLS LAST, nume)`.
+fn push_nullable_keyset<'args, T>(
+    query_builder: &mut QueryBuilder<'args, Postgres>,
+    column: &str,
+    position: Option<(Option<T>, String)>,
+    backward: bool,
+) where
+    T: 'args + Encode<'args, Postgres> + Type<Postgres> + Clone,
+{
+    match position {
+        Some((Some(value), name)) if backward => {
+            query_builder.push(format!(" WHERE ({column} < "));
+            query_builder.push_bind(value.clone());
+            query_builder.push(format!(" OR ({column} = "));
+            query_builder.push_bind(value);
+            query_builder.push(" AND nume < ");
+            query_builder.push_bind(name);
+            query_builder.push("))");
+        }
+        Some((Some(value), name)) => {
+            query_builder.push(format!(" WHERE ({column} > "));
+            query_builder.push_bind(value.clone());
+            query_builder.push(format!(" OR ({column} = "));
+            query_builder.push_bind(value);
+            query_builder.push(" AND nume > ");
+            query_builder.push_bind(name);
+            query_builder.push(format!(") OR {column} IS NULL)"));
+        }
+        Some((None, name)) if backward => {
+            query_builder.push(format!(" WHERE ({column} IS NOT NULL OR nume < "));
+            query_builder.push_bind(name);
+            query_builder.push(")");
+        }
+        Some((None, name)) => {
+            query_builder.push(format!(" WHERE ({column} IS NULL AND nume > "));
+            query_builder.push_bind(name);
+            query_builder.push(")");
+        }
+        None => {}
+    }
+
+    if backward {
+        query_builder.push(format!(" ORDER BY {column} DESC NULLS FIRST, nume DESC"));
+    } else {
+        query_builder.push(format!(" ORDER BY {column} ASC NULLS LAST, nume ASC"));
+    }
+}
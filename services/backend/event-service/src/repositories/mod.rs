@@ -1,5 +1,18 @@
+pub mod analytics_repo;
+pub mod check_in_repo;
 pub mod event_packets_repo;
 pub mod event_repo;
+pub mod idempotency_repo;
+pub mod import_repo;
 pub mod join_pe_repo;
+pub mod keyset;
+pub mod outbox_repo;
+pub mod price_tier_repo;
+pub mod promo_code_repo;
 pub mod refund_repo;
+pub mod search_repo;
+pub mod seat_hold_repo;
+pub mod seat_repo;
 pub mod ticket_repo;
+pub mod venue_repo;
+pub mod waitlist_repo;
@@ -0,0 +1,109 @@
+use crate::models::outbox::OutboxMessage;
+use common::rabbitmq::RabbitMQ;
+use serde::Serialize;
+use sqlx::{Error, PgPool};
+use tracing::{error, info, warn};
+
+const MAX_BACKOFF_SECONDS: i32 = 300;
+
+pub struct OutboxRepo {
+    pool: PgPool,
+}
+
+impl OutboxRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Queues `message` for publishing under `routing_key`. It only becomes
+    /// visible to the relay once the surrounding transaction commits.
+    pub(crate) async fn enqueue<T: Serialize>(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        routing_key: &str,
+        message: &T,
+    ) -> Result<(), Error> {
+        let payload = serde_json::to_string(message).map_err(|e| Error::Encode(Box::new(e)))?;
+
+        sqlx::query("INSERT INTO OUTBOX (routing_key, payload) VALUES ($1, $2)")
+            .bind(routing_key)
+            .bind(payload)
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Publishes up to `limit` due messages in insertion order. Failed ones
+    /// are retried later with exponential backoff. Returns how many were sent.
+    pub async fn relay_pending(&self, rabbitmq: &RabbitMQ, limit: i64) -> Result<usize, Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let pending = sqlx::query_as::<_, OutboxMessage>(
+            r#"
+            SELECT id, routing_key, payload, attempts
+            FROM OUTBOX
+            WHERE sent_at IS NULL AND next_attempt_at <= NOW()
+            ORDER BY id
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut sent = 0;
+        for message in pending {
+            match rabbitmq
+                .publish(&message.routing_key, message.payload.as_bytes())
+                .await
+            {
+                Ok(()) => {
+                    sqlx::query("UPDATE OUTBOX SET sent_at = NOW() WHERE id = $1")
+                        .bind(message.id)
+                        .execute(&mut *tx)
+                        .await?;
+                    info!(
+                        "Relayed outbox message {} to '{}'",
+                        message.id, message.routing_key
+                    );
+                    sent += 1;
+                }
+                Err(e) => {
+                    let backoff = 2_i32
+                        .saturating_pow(message.attempts as u32)
+                        .min(MAX_BACKOFF_SECONDS);
+
+                    sqlx::query(
+                        r#"
+                        UPDATE OUTBOX
+                        SET attempts = attempts + 1,
+                            last_error = $2,
+                            next_attempt_at = NOW() + make_interval(secs => $3)
+                        WHERE id = $1
+                        "#,
+                    )
+                    .bind(message.id)
+                    .bind(e.to_string())
+                    .bind(backoff as f64)
+                    .execute(&mut *tx)
+                    .await?;
+
+                    warn!(
+                        "Failed to relay outbox message {} (attempt {}): {:?}",
+                        message.id,
+                        message.attempts + 1,
+                        e
+                    );
+                }
+            }
+        }
+
+        if let Err(e) = tx.commit().await {
+            error!("Failed to record outbox relay results: {:?}", e);
+            return Err(e);
+        }
+
+        Ok(sent)
+    }
+}
@@ -0,0 +1,236 @@
+use crate::models::price_tier::{CreatePriceTier, PriceTier, UpdatePriceTier};
+use crate::utils::error::{PriceTierRepoError, TicketRepoError, map_sqlx_price_tier_error};
+use anyhow::Result;
+use sqlx::PgPool;
+
+pub(crate) const PRICE_TIER_COLUMNS: &str = "id, event_id, packet_id, name, currency, price_minor, capacity, sold, sales_start, sales_end";
+
+pub struct PriceTierRepo {
+    pool: PgPool,
+}
+
+pub struct SoldAt {
+    pub tier_id: Option<i32>,
+    pub price_minor: Option<i64>,
+    pub currency: Option<String>,
+    pub promo_code_id: Option<i32>,
+    pub discount_minor: Option<i64>,
+}
+
+impl PriceTierRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_tiers_for_event(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<PriceTier>, PriceTierRepoError> {
+        sqlx::query_as::<_, PriceTier>(&format!(
+            "SELECT {} FROM PRICE_TIERS WHERE event_id = $1 ORDER BY price_minor ASC, name ASC",
+            PRICE_TIER_COLUMNS
+        ))
+        .bind(event_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_price_tier_error)
+    }
+
+    pub async fn list_tiers_for_packet(
+        &self,
+        packet_id: i32,
+    ) -> Result<Vec<PriceTier>, PriceTierRepoError> {
+        sqlx::query_as::<_, PriceTier>(&format!(
+            "SELECT {} FROM PRICE_TIERS WHERE packet_id = $1 ORDER BY price_minor ASC, name ASC",
+            PRICE_TIER_COLUMNS
+        ))
+        .bind(packet_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_price_tier_error)
+    }
+
+    pub async fn get_tier(&self, tier_id: i32) -> Result<PriceTier, PriceTierRepoError> {
+        sqlx::query_as::<_, PriceTier>(&format!(
+            "SELECT {} FROM PRICE_TIERS WHERE id = $1",
+            PRICE_TIER_COLUMNS
+        ))
+        .bind(tier_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_price_tier_error)
+    }
+
+    pub async fn create_tier_for_event(
+        &self,
+        event_id: i32,
+        payload: CreatePriceTier,
+    ) -> Result<PriceTier, PriceTierRepoError> {
+        self.create_tier(Some(event_id), None, payload).await
+    }
+
+    pub async fn create_tier_for_packet(
+        &self,
+        packet_id: i32,
+        payload: CreatePriceTier,
+    ) -> Result<PriceTier, PriceTierRepoError> {
+        self.create_tier(None, Some(packet_id), payload).await
+    }
+
+    async fn create_tier(
+        &self,
+        event_id: Option<i32>,
+        packet_id: Option<i32>,
+        payload: CreatePriceTier,
+    ) -> Result<PriceTier, PriceTierRepoError> {
+        sqlx::query_as::<_, PriceTier>(&format!(
+            r#"
+            INSERT INTO PRICE_TIERS
+                (event_id, packet_id, name, currency, price_minor, capacity, sales_start, sales_end)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING {}
+            "#,
+            PRICE_TIER_COLUMNS
+        ))
+        .bind(event_id)
+        .bind(packet_id)
+        .bind(&payload.name)
+        .bind(&payload.currency)
+        .bind(payload.price_minor)
+        .bind(payload.capacity)
+        .bind(payload.sales_start)
+        .bind(payload.sales_end)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_price_tier_error)
+    }
+
+    pub async fn update_tier(
+        &self,
+        tier_id: i32,
+        payload: UpdatePriceTier,
+    ) -> Result<PriceTier, PriceTierRepoError> {
+        sqlx::query_as::<_, PriceTier>(&format!(
+            r#"
+            UPDATE PRICE_TIERS
+            SET
+                name = COALESCE($1, name),
+                currency = COALESCE($2, currency),
+                price_minor = COALESCE($3, price_minor),
+                capacity = COALESCE($4, capacity),
+                sales_start = COALESCE($5, sales_start),
+                sales_end = COALESCE($6, sales_end)
+            WHERE id = $7
+            RETURNING {}
+            "#,
+            PRICE_TIER_COLUMNS
+        ))
+        .bind(payload.name.as_deref())
+        .bind(payload.currency.as_deref())
+        .bind(payload.price_minor)
+        .bind(payload.capacity)
+        .bind(payload.sales_start)
+        .bind(payload.sales_end)
+        .bind(tier_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_price_tier_error)
+    }
+
+    pub async fn delete_tier(&self, tier_id: i32) -> Result<(), PriceTierRepoError> {
+        let result = sqlx::query("DELETE FROM PRICE_TIERS WHERE id = $1")
+            .bind(tier_id)
+            .execute(&self.pool)
+            .await
+            .map_err(PriceTierRepoError::InternalError)?;
+
+        if result.rows_affected() == 0 {
+            Err(PriceTierRepoError::NotFound)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Without an explicit tier the cheapest one on sale is used; targets with no tiers sell unpriced.
+    pub(crate) async fn claim_tier(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Option<i32>,
+        packet_id: Option<i32>,
+        tier_id: Option<i32>,
+    ) -> Result<SoldAt, TicketRepoError> {
+        let claimed: Option<(i32, i64, String)> = sqlx::query_as(
+            r#"
+            UPDATE PRICE_TIERS
+            SET sold = sold + 1
+            WHERE id = (
+                SELECT id FROM PRICE_TIERS
+                WHERE event_id IS NOT DISTINCT FROM $1
+                  AND packet_id IS NOT DISTINCT FROM $2
+                  AND ($3::INTEGER IS NULL OR id = $3)
+                  AND (capacity IS NULL OR sold < capacity)
+                  AND (sales_start IS NULL OR sales_start <= NOW())
+                  AND (sales_end IS NULL OR sales_end > NOW())
+                ORDER BY price_minor ASC, id ASC
+                LIMIT 1
+                FOR UPDATE
+            )
+            RETURNING id, price_minor, currency
+            "#,
+        )
+        .bind(event_id)
+        .bind(packet_id)
+        .bind(tier_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(TicketRepoError::InternalError)?;
+
+        if let Some((id, price_minor, currency)) = claimed {
+            return Ok(SoldAt {
+                tier_id: Some(id),
+                price_minor: Some(price_minor),
+                currency: Some(currency),
+                promo_code_id: None,
+                discount_minor: None,
+            });
+        }
+
+        let tier_count: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM PRICE_TIERS
+            WHERE event_id IS NOT DISTINCT FROM $1
+              AND packet_id IS NOT DISTINCT FROM $2
+              AND ($3::INTEGER IS NULL OR id = $3)
+            "#,
+        )
+        .bind(event_id)
+        .bind(packet_id)
+        .bind(tier_id)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(TicketRepoError::InternalError)?;
+
+        match (tier_id, tier_count) {
+            (Some(_), 0) => Err(TicketRepoError::InvalidTier),
+            (None, 0) => Ok(SoldAt {
+                tier_id: None,
+                price_minor: None,
+                currency: None,
+                promo_code_id: None,
+                discount_minor: None,
+            }),
+            _ => Err(TicketRepoError::TierUnavailable),
+        }
+    }
+
+    pub(crate) async fn release_tier(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        tier_id: i32,
+    ) -> Result<(), TicketRepoError> {
+        sqlx::query("UPDATE PRICE_TIERS SET sold = sold - 1 WHERE id = $1 AND sold > 0")
+            .bind(tier_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(TicketRepoError::InternalError)?;
+        Ok(())
+    }
+}
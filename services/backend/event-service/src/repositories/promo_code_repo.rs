@@ -0,0 +1,291 @@
+use crate::models::promo_code::{
+    CreatePromoCode, PromoApplication, PromoCode, PromoCodeStats, UpdatePromoCode,
+};
+use crate::models::ticket::Ticket;
+use crate::repositories::price_tier_repo::SoldAt;
+use crate::utils::error::{PromoCodeRepoError, TicketRepoError, map_sqlx_promo_code_error};
+use anyhow::Result;
+use sqlx::PgPool;
+
+pub(crate) const PROMO_CODE_COLUMNS: &str = "id, event_id, packet_id, code, discount_type, discount_value, currency, \
+     max_uses, per_user_limit, uses, expires_at, active, created_at";
+
+pub struct PromoCodeRepo {
+    pool: PgPool,
+}
+
+impl PromoCodeRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_codes_for_event(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<PromoCode>, PromoCodeRepoError> {
+        sqlx::query_as::<_, PromoCode>(&format!(
+            "SELECT {} FROM PROMO_CODES WHERE event_id = $1 ORDER BY created_at DESC",
+            PROMO_CODE_COLUMNS
+        ))
+        .bind(event_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_promo_code_error)
+    }
+
+    pub async fn list_codes_for_packet(
+        &self,
+        packet_id: i32,
+    ) -> Result<Vec<PromoCode>, PromoCodeRepoError> {
+        sqlx::query_as::<_, PromoCode>(&format!(
+            "SELECT {} FROM PROMO_CODES WHERE packet_id = $1 ORDER BY created_at DESC",
+            PROMO_CODE_COLUMNS
+        ))
+        .bind(packet_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_promo_code_error)
+    }
+
+    pub async fn get_code(&self, id: i32) -> Result<PromoCode, PromoCodeRepoError> {
+        sqlx::query_as::<_, PromoCode>(&format!(
+            "SELECT {} FROM PROMO_CODES WHERE id = $1",
+            PROMO_CODE_COLUMNS
+        ))
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_promo_code_error)
+    }
+
+    pub async fn create_code_for_event(
+        &self,
+        event_id: i32,
+        payload: CreatePromoCode,
+    ) -> Result<PromoCode, PromoCodeRepoError> {
+        self.create_code(Some(event_id), None, payload).await
+    }
+
+    pub async fn create_code_for_packet(
+        &self,
+        packet_id: i32,
+        payload: CreatePromoCode,
+    ) -> Result<PromoCode, PromoCodeRepoError> {
+        self.create_code(None, Some(packet_id), payload).await
+    }
+
+    async fn create_code(
+        &self,
+        event_id: Option<i32>,
+        packet_id: Option<i32>,
+        payload: CreatePromoCode,
+    ) -> Result<PromoCode, PromoCodeRepoError> {
+        sqlx::query_as::<_, PromoCode>(&format!(
+            r#"
+            INSERT INTO PROMO_CODES
+                (event_id, packet_id, code, discount_type, discount_value, currency,
+                 max_uses, per_user_limit, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING {}
+            "#,
+            PROMO_CODE_COLUMNS
+        ))
+        .bind(event_id)
+        .bind(packet_id)
+        .bind(&payload.code)
+        .bind(payload.discount_type)
+        .bind(payload.discount_value)
+        .bind(&payload.currency)
+        .bind(payload.max_uses)
+        .bind(payload.per_user_limit)
+        .bind(payload.expires_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_promo_code_error)
+    }
+
+    pub async fn update_code(
+        &self,
+        id: i32,
+        payload: UpdatePromoCode,
+    ) -> Result<PromoCode, PromoCodeRepoError> {
+        sqlx::query_as::<_, PromoCode>(&format!(
+            r#"
+            UPDATE PROMO_CODES
+            SET
+                max_uses = COALESCE($1, max_uses),
+                per_user_limit = COALESCE($2, per_user_limit),
+                expires_at = COALESCE($3, expires_at),
+                active = COALESCE($4, active)
+            WHERE id = $5
+            RETURNING {}
+            "#,
+            PROMO_CODE_COLUMNS
+        ))
+        .bind(payload.max_uses)
+        .bind(payload.per_user_limit)
+        .bind(payload.expires_at)
+        .bind(payload.active)
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_promo_code_error)
+    }
+
+    pub async fn delete_code(&self, id: i32) -> Result<(), PromoCodeRepoError> {
+        let result = sqlx::query("DELETE FROM PROMO_CODES WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(PromoCodeRepoError::InternalError)?;
+
+        if result.rows_affected() == 0 {
+            Err(PromoCodeRepoError::NotFound)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Redemptions of a code still backed by a sold ticket.
+    pub async fn stats(&self, id: i32) -> Result<PromoCodeStats, PromoCodeRepoError> {
+        sqlx::query_as::<_, PromoCodeStats>(
+            r#"
+            SELECT
+                p.id AS promo_code_id,
+                p.code,
+                p.uses,
+                p.max_uses,
+                COUNT(DISTINCT r.buyer_id) AS unique_buyers,
+                COALESCE(SUM(r.discount_minor), 0)::BIGINT AS total_discount_minor,
+                COALESCE(SUM(b.price_minor), 0)::BIGINT AS revenue_minor,
+                MIN(r.redeemed_at) AS first_redeemed_at,
+                MAX(r.redeemed_at) AS last_redeemed_at
+            FROM PROMO_CODES p
+            LEFT JOIN PROMO_REDEMPTIONS r ON r.promo_code_id = p.id
+            LEFT JOIN BILETE b ON b.cod = r.ticket_cod
+            WHERE p.id = $1
+            GROUP BY p.id
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_promo_code_error)
+    }
+
+    /// Locks the code for the event or packet being bought and takes `quantity`
+    /// uses from it, enforcing expiry, the global limit and the buyer's cap.
+    pub(crate) async fn redeem(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        promo: &PromoApplication,
+        event_id: Option<i32>,
+        packet_id: Option<i32>,
+        quantity: i32,
+    ) -> Result<PromoCode, TicketRepoError> {
+        let code: Option<PromoCode> = sqlx::query_as::<_, PromoCode>(&format!(
+            r#"
+            SELECT {} FROM PROMO_CODES
+            WHERE event_id IS NOT DISTINCT FROM $1
+              AND packet_id IS NOT DISTINCT FROM $2
+              AND code = $3
+              AND active
+              AND (expires_at IS NULL OR expires_at > NOW())
+            FOR UPDATE
+            "#,
+            PROMO_CODE_COLUMNS
+        ))
+        .bind(event_id)
+        .bind(packet_id)
+        .bind(promo.code.trim().to_uppercase())
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(TicketRepoError::InternalError)?;
+
+        let code = code.ok_or(TicketRepoError::InvalidPromoCode)?;
+
+        if code.max_uses.is_some_and(|max| code.uses + quantity > max) {
+            return Err(TicketRepoError::PromoCodeExhausted);
+        }
+
+        if let Some(limit) = code.per_user_limit {
+            let used: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM PROMO_REDEMPTIONS WHERE promo_code_id = $1 AND buyer_id = $2",
+            )
+            .bind(code.id)
+            .bind(promo.buyer_id)
+            .fetch_one(&mut **tx)
+            .await
+            .map_err(TicketRepoError::InternalError)?;
+
+            if used + quantity as i64 > limit as i64 {
+                return Err(TicketRepoError::PromoCodeUserLimit);
+            }
+        }
+
+        sqlx::query("UPDATE PROMO_CODES SET uses = uses + $2 WHERE id = $1")
+            .bind(code.id)
+            .bind(quantity)
+            .execute(&mut **tx)
+            .await
+            .map_err(TicketRepoError::InternalError)?;
+
+        Ok(code)
+    }
+
+    /// Lowers the claimed tier price by the code's discount.
+    pub(crate) fn apply(code: &PromoCode, sold_at: &mut SoldAt) -> Result<(), TicketRepoError> {
+        let (Some(price_minor), Some(currency)) =
+            (sold_at.price_minor, sold_at.currency.as_deref())
+        else {
+            return Err(TicketRepoError::PromoCodeNotApplicable);
+        };
+
+        let discount = code
+            .discount_for(price_minor, currency)
+            .ok_or(TicketRepoError::PromoCodeNotApplicable)?;
+
+        sold_at.price_minor = Some(price_minor - discount);
+        sold_at.promo_code_id = Some(code.id);
+        sold_at.discount_minor = Some(discount);
+        Ok(())
+    }
+
+    pub(crate) async fn record_redemption(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        ticket: &Ticket,
+        buyer_id: i32,
+    ) -> Result<(), TicketRepoError> {
+        let (Some(promo_code_id), Some(discount_minor)) =
+            (ticket.promo_code_id, ticket.discount_minor)
+        else {
+            return Ok(());
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO PROMO_REDEMPTIONS (ticket_cod, promo_code_id, buyer_id, discount_minor)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(&ticket.cod)
+        .bind(promo_code_id)
+        .bind(buyer_id)
+        .bind(discount_minor)
+        .execute(&mut **tx)
+        .await
+        .map_err(TicketRepoError::InternalError)?;
+        Ok(())
+    }
+
+    pub(crate) async fn release_use(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        promo_code_id: i32,
+    ) -> Result<(), TicketRepoError> {
+        sqlx::query("UPDATE PROMO_CODES SET uses = uses - 1 WHERE id = $1 AND uses > 0")
+            .bind(promo_code_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(TicketRepoError::InternalError)?;
+        Ok(())
+    }
+}
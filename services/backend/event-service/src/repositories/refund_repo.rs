@@ -1,4 +1,11 @@
-use crate::models::refund::RefundRequest;
+use crate::models::refund::{RefundRequest, TicketRefundStatus};
+use crate::repositories::outbox_repo::OutboxRepo;
+use crate::repositories::ticket_repo::TicketRepo;
+use crate::utils::error::TicketRepoError;
+use common::rabbitmq::messages::{
+    ROUTING_KEY_REFUND_RESOLVED, RefundRequested, RefundResolved, RefundStatus,
+};
+use common::websocket::messages::{ROUTING_KEY_WS_BROADCAST, RefundStatusChanged, WebSocketMessage};
 use sqlx::{Error, PgPool};
 
 pub struct RefundRepo {
@@ -8,14 +15,20 @@ pub struct RefundRepo {
 #[derive(Debug)]
 pub enum RefundRepoError {
     NotFound,
+    TicketCheckedIn,
+    Ticket(TicketRepoError),
     InternalError(Error),
 }
 
+const CHECKED_IN_REJECTION: &str = "The ticket has already been used at the event";
+
 impl RefundRepo {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
 
+    /// Files a refund request. Requests for a ticket that was already checked
+    /// in are stored as rejected straight away and their resolution queued.
     pub async fn create_refund_request(
         &self,
         ticket_cod: &str,
@@ -26,11 +39,29 @@ impl RefundRepo {
         event_owner_id: i32,
         reason: &str,
     ) -> Result<RefundRequest, RefundRepoError> {
-        let result = sqlx::query_as::<_, RefundRequest>(
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(RefundRepoError::InternalError)?;
+
+        let checked_in = Self::is_checked_in(&mut tx, ticket_cod)
+            .await
+            .map_err(RefundRepoError::InternalError)?;
+
+        let (status, rejection_message) = if checked_in {
+            ("REJECTED", Some(CHECKED_IN_REJECTION))
+        } else {
+            ("PENDING", None)
+        };
+
+        let refund = sqlx::query_as::<_, RefundRequest>(
             r#"
             INSERT INTO REFUND_REQUESTS
-                (ticket_cod, requester_id, requester_email, event_id, packet_id, event_owner_id, reason)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+                (ticket_cod, requester_id, requester_email, event_id, packet_id, event_owner_id,
+                 reason, status, rejection_message, resolved_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9,
+                    CASE WHEN $10 THEN CURRENT_TIMESTAMP END)
             RETURNING id, ticket_cod, requester_id, requester_email, event_id, packet_id,
                       event_owner_id, status, reason, rejection_message,
                       created_at::text, resolved_at::text
@@ -43,10 +74,28 @@ impl RefundRepo {
         .bind(packet_id)
         .bind(event_owner_id)
         .bind(reason)
-        .fetch_one(&self.pool)
-        .await;
+        .bind(status)
+        .bind(rejection_message)
+        .bind(checked_in)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(RefundRepoError::InternalError)?;
 
-        result.map_err(RefundRepoError::InternalError)
+        if checked_in {
+            Self::enqueue_resolution(
+                &mut tx,
+                &refund,
+                RefundStatus::Rejected,
+                Some(CHECKED_IN_REJECTION.to_string()),
+                CHECKED_IN_REJECTION.to_string(),
+            )
+            .await
+            .map_err(RefundRepoError::InternalError)?;
+        }
+
+        tx.commit().await.map_err(RefundRepoError::InternalError)?;
+
+        Ok(refund)
     }
 
     pub async fn get_refund_request(&self, id: i32) -> Result<RefundRequest, RefundRepoError> {
@@ -91,11 +140,19 @@ impl RefundRepo {
         result.map_err(RefundRepoError::InternalError)
     }
 
+    /// Approves a pending refund, deletes the ticket and queues the
+    /// `refund.resolved` and WebSocket notifications in the same transaction.
     pub async fn approve_refund(
         &self,
         id: i32,
         owner_id: i32,
     ) -> Result<RefundRequest, RefundRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(RefundRepoError::InternalError)?;
+
         let result = sqlx::query_as::<_, RefundRequest>(
             r#"
             UPDATE REFUND_REQUESTS
@@ -108,22 +165,53 @@ impl RefundRepo {
         )
         .bind(id)
         .bind(owner_id)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await;
 
-        match result {
-            Ok(request) => Ok(request),
-            Err(Error::RowNotFound) => Err(RefundRepoError::NotFound),
-            Err(e) => Err(RefundRepoError::InternalError(e)),
+        let refund = match result {
+            Ok(request) => request,
+            Err(Error::RowNotFound) => return Err(RefundRepoError::NotFound),
+            Err(e) => return Err(RefundRepoError::InternalError(e)),
+        };
+
+        if Self::is_checked_in(&mut tx, &refund.ticket_cod)
+            .await
+            .map_err(RefundRepoError::InternalError)?
+        {
+            return Err(RefundRepoError::TicketCheckedIn);
         }
+
+        Self::release_ticket(&mut tx, &refund).await?;
+
+        Self::enqueue_resolution(
+            &mut tx,
+            &refund,
+            RefundStatus::Approved,
+            None,
+            "Your refund has been approved".to_string(),
+        )
+        .await
+        .map_err(RefundRepoError::InternalError)?;
+
+        tx.commit().await.map_err(RefundRepoError::InternalError)?;
+
+        Ok(refund)
     }
 
+    /// Rejects a pending refund and queues the `refund.resolved` and
+    /// WebSocket notifications in the same transaction.
     pub async fn reject_refund(
         &self,
         id: i32,
         owner_id: i32,
         message: &str,
     ) -> Result<RefundRequest, RefundRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(RefundRepoError::InternalError)?;
+
         let result = sqlx::query_as::<_, RefundRequest>(
             r#"
             UPDATE REFUND_REQUESTS
@@ -137,14 +225,219 @@ impl RefundRepo {
         .bind(id)
         .bind(owner_id)
         .bind(message)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await;
 
-        match result {
-            Ok(request) => Ok(request),
-            Err(Error::RowNotFound) => Err(RefundRepoError::NotFound),
-            Err(e) => Err(RefundRepoError::InternalError(e)),
+        let refund = match result {
+            Ok(request) => request,
+            Err(Error::RowNotFound) => return Err(RefundRepoError::NotFound),
+            Err(e) => return Err(RefundRepoError::InternalError(e)),
+        };
+
+        Self::enqueue_resolution(
+            &mut tx,
+            &refund,
+            RefundStatus::Rejected,
+            Some(message.to_string()),
+            message.to_string(),
+        )
+        .await
+        .map_err(RefundRepoError::InternalError)?;
+
+        tx.commit().await.map_err(RefundRepoError::InternalError)?;
+
+        Ok(refund)
+    }
+
+    /// Approves the ticket's pending refund (or files an approved one), deletes
+    /// the ticket and queues the notifications in the same transaction. A
+    /// checked-in ticket is kept and its refund rejected instead.
+    pub async fn auto_approve_refund(
+        &self,
+        message: &RefundRequested,
+    ) -> Result<RefundRequest, RefundRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(RefundRepoError::InternalError)?;
+
+        let checked_in = Self::is_checked_in(&mut tx, &message.ticket_cod)
+            .await
+            .map_err(RefundRepoError::InternalError)?;
+
+        let (status, rejection_message) = if checked_in {
+            ("REJECTED", Some(CHECKED_IN_REJECTION))
+        } else {
+            ("APPROVED", None)
+        };
+
+        let pending = sqlx::query_as::<_, RefundRequest>(
+            r#"
+            UPDATE REFUND_REQUESTS
+            SET status = $2, rejection_message = $3, resolved_at = CURRENT_TIMESTAMP
+            WHERE id = (
+                SELECT id FROM REFUND_REQUESTS
+                WHERE ticket_cod = $1 AND status = 'PENDING'
+                ORDER BY created_at ASC
+                LIMIT 1
+                FOR UPDATE
+            )
+            RETURNING id, ticket_cod, requester_id, requester_email, event_id, packet_id,
+                      event_owner_id, status, reason, rejection_message,
+                      created_at::text, resolved_at::text
+            "#,
+        )
+        .bind(&message.ticket_cod)
+        .bind(status)
+        .bind(rejection_message)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(RefundRepoError::InternalError)?;
+
+        let refund = match pending {
+            Some(refund) => refund,
+            None => sqlx::query_as::<_, RefundRequest>(
+                r#"
+                INSERT INTO REFUND_REQUESTS
+                    (ticket_cod, requester_id, requester_email, event_id, packet_id, event_owner_id,
+                     reason, status, rejection_message, resolved_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, CURRENT_TIMESTAMP)
+                RETURNING id, ticket_cod, requester_id, requester_email, event_id, packet_id,
+                          event_owner_id, status, reason, rejection_message,
+                          created_at::text, resolved_at::text
+                "#,
+            )
+            .bind(&message.ticket_cod)
+            .bind(message.requester_id)
+            .bind(&message.requester_email)
+            .bind(message.event_id)
+            .bind(message.packet_id)
+            .bind(message.event_owner_id)
+            .bind(&message.reason)
+            .bind(status)
+            .bind(rejection_message)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(RefundRepoError::InternalError)?,
+        };
+
+        if checked_in {
+            Self::enqueue_resolution(
+                &mut tx,
+                &refund,
+                RefundStatus::Rejected,
+                Some(CHECKED_IN_REJECTION.to_string()),
+                CHECKED_IN_REJECTION.to_string(),
+            )
+            .await
+            .map_err(RefundRepoError::InternalError)?;
+        } else {
+            Self::release_ticket(&mut tx, &refund).await?;
+
+            Self::enqueue_resolution(
+                &mut tx,
+                &refund,
+                RefundStatus::Approved,
+                Some(message.reason.clone()),
+                message.reason.clone(),
+            )
+            .await
+            .map_err(RefundRepoError::InternalError)?;
         }
+
+        tx.commit().await.map_err(RefundRepoError::InternalError)?;
+
+        Ok(refund)
+    }
+
+    async fn is_checked_in(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        ticket_cod: &str,
+    ) -> Result<bool, Error> {
+        sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM BILETE WHERE cod = $1 AND checked_in_at IS NOT NULL)",
+        )
+        .bind(ticket_cod)
+        .fetch_one(&mut **tx)
+        .await
+    }
+
+    /// Deletes a refunded ticket, giving its seat back. The tier, price and
+    /// purchase time are copied onto the refund first, so sales analytics
+    /// still count the sale. A ticket that is already gone has nothing left to
+    /// release.
+    async fn release_ticket(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        refund: &RefundRequest,
+    ) -> Result<(), RefundRepoError> {
+        sqlx::query(
+            r#"
+            UPDATE REFUND_REQUESTS r
+            SET tier_id = b.tier_id, price_minor = b.price_minor, currency = b.currency,
+                discount_minor = b.discount_minor, purchased_at = b.created_at
+            FROM BILETE b
+            WHERE r.id = $1 AND b.cod = r.ticket_cod
+            "#,
+        )
+        .bind(refund.id)
+        .execute(&mut **tx)
+        .await
+        .map_err(RefundRepoError::InternalError)?;
+
+        match TicketRepo::remove_ticket(tx, &refund.ticket_cod).await {
+            Ok(_) | Err(TicketRepoError::NotFound) => Ok(()),
+            Err(e) => Err(RefundRepoError::Ticket(e)),
+        }
+    }
+
+    async fn enqueue_resolution(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        refund: &RefundRequest,
+        status: RefundStatus,
+        message: Option<String>,
+        ws_message: String,
+    ) -> Result<(), Error> {
+        let event_name: Option<String> = match (refund.event_id, refund.packet_id) {
+            (Some(event_id), _) => {
+                sqlx::query_scalar("SELECT nume FROM EVENIMENTE WHERE id = $1")
+                    .bind(event_id)
+                    .fetch_optional(&mut **tx)
+                    .await?
+            }
+            (None, Some(packet_id)) => {
+                sqlx::query_scalar("SELECT nume FROM PACHETE WHERE id = $1")
+                    .bind(packet_id)
+                    .fetch_optional(&mut **tx)
+                    .await?
+            }
+            (None, None) => None,
+        };
+
+        let ws_status = match status {
+            RefundStatus::Approved => "APPROVED",
+            RefundStatus::Rejected => "REJECTED",
+        };
+
+        let resolved = RefundResolved {
+            request_id: refund.id,
+            ticket_cod: refund.ticket_cod.clone(),
+            requester_email: refund.requester_email.clone(),
+            status,
+            event_name: event_name.clone(),
+            message,
+        };
+        OutboxRepo::enqueue(tx, ROUTING_KEY_REFUND_RESOLVED, &resolved).await?;
+
+        let ws = WebSocketMessage::RefundStatusChanged(RefundStatusChanged {
+            request_id: refund.id,
+            ticket_cod: refund.ticket_cod.clone(),
+            status: ws_status.to_string(),
+            event_name,
+            message: Some(ws_message),
+            user_id: refund.requester_id,
+        });
+        OutboxRepo::enqueue(tx, ROUTING_KEY_WS_BROADCAST, &ws).await
     }
 
     pub async fn get_event_name_for_refund(&self, refund: &RefundRequest) -> Option<String> {
@@ -187,4 +480,17 @@ impl RefundRepo {
 
         result.map_err(RefundRepoError::InternalError)
     }
+
+    pub async fn list_latest_statuses(&self) -> Result<Vec<TicketRefundStatus>, RefundRepoError> {
+        sqlx::query_as::<_, TicketRefundStatus>(
+            r#"
+            SELECT DISTINCT ON (ticket_cod) ticket_cod, status
+            FROM REFUND_REQUESTS
+            ORDER BY ticket_cod, created_at DESC, id DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RefundRepoError::InternalError)
+    }
 }
@@ -0,0 +1,182 @@
+use crate::models::search::{SearchHit, SearchHitKind, SearchQuery};
+use crate::utils::error::SearchRepoError;
+use anyhow::Result;
+use common::pagination::{Cursor, Page};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+const HEADLINE_OPTIONS: &str =
+    "StartSel=<mark>, StopSel=</mark>, MaxWords=25, MinWords=8, MaxFragments=2";
+
+/// Position of a hit in `rank DESC, name, kind, id` order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SearchKey {
+    #[serde(rename = "r")]
+    rank: f32,
+    #[serde(rename = "n")]
+    name: String,
+    #[serde(rename = "t")]
+    kind: SearchHitKind,
+    #[serde(rename = "i")]
+    id: i32,
+}
+
+pub struct SearchRepo {
+    pool: PgPool,
+}
+
+impl SearchRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Ranks published events and packets against `q` in a single query.
+    /// Full-text matches over name, location and description come first;
+    /// names that only resemble the text (typos) are found through trigrams.
+    pub async fn search(&self, params: SearchQuery) -> Result<Page<SearchHit>, SearchRepoError> {
+        let cursor = match params.paginare.cursor.as_deref() {
+            Some(cursor) => {
+                Some(Cursor::<SearchKey>::decode(cursor).ok_or(SearchRepoError::InvalidCursor)?)
+            }
+            None => None,
+        };
+        let backward = cursor.as_ref().is_some_and(|cursor| cursor.before);
+
+        let page = params.paginare.page.unwrap_or(1);
+        let items_per_page = params.paginare.items_per_page.unwrap_or(10);
+        let offset = if cursor.is_some() {
+            0
+        } else {
+            (page - 1) * items_per_page
+        };
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("");
+        push_hits(&mut query_builder, params.q.trim());
+        query_builder
+            .push(" SELECT kind, id, name, location, starts_at, rank, snippet FROM hits WHERE ");
+        push_kind_filter(&mut query_builder, params.kind);
+
+        if let Some(cursor) = &cursor {
+            let (rank_cmp, tie_cmp) = if backward { (">", "<") } else { ("<", ">") };
+            query_builder.push(format!(" AND (rank {rank_cmp} "));
+            query_builder.push_bind(cursor.key.rank);
+            query_builder.push(" OR (rank = ");
+            query_builder.push_bind(cursor.key.rank);
+            query_builder.push(format!(" AND (name, kind, id) {tie_cmp} ("));
+            query_builder.push_bind(cursor.key.name.clone());
+            query_builder.push(", ");
+            query_builder.push_bind(cursor.key.kind);
+            query_builder.push(", ");
+            query_builder.push_bind(cursor.key.id);
+            query_builder.push(")))");
+        }
+
+        query_builder.push(if backward {
+            " ORDER BY rank ASC, name DESC, kind DESC, id DESC"
+        } else {
+            " ORDER BY rank DESC, name ASC, kind ASC, id ASC"
+        });
+        query_builder.push(" LIMIT ");
+        query_builder.push_bind(items_per_page + 1);
+        query_builder.push(" OFFSET ");
+        query_builder.push_bind(offset);
+
+        let hits = query_builder
+            .build_query_as::<SearchHit>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(SearchRepoError::InternalError)?;
+
+        let total = if params.paginare.include_total == Some(true) {
+            let mut count_builder: QueryBuilder<Postgres> = QueryBuilder::new("");
+            push_hits(&mut count_builder, params.q.trim());
+            count_builder.push(" SELECT COUNT(*) FROM hits WHERE ");
+            push_kind_filter(&mut count_builder, params.kind);
+            let total: i64 = count_builder
+                .build_query_scalar()
+                .fetch_one(&self.pool)
+                .await
+                .map_err(SearchRepoError::InternalError)?;
+            Some(total)
+        } else {
+            None
+        };
+
+        Ok(Page::from_rows(
+            hits,
+            items_per_page as usize,
+            backward,
+            cursor.is_some() || offset > 0,
+            |hit| SearchKey {
+                rank: hit.rank,
+                name: hit.name.clone(),
+                kind: hit.kind,
+                id: hit.id,
+            },
+        )
+        .with_total(total))
+    }
+}
+
+/// `WITH q AS (...), hits AS (...)` over every published event and packet
+/// matching `term`, shared by the page and the count queries.
+fn push_hits(query_builder: &mut QueryBuilder<'_, Postgres>, term: &str) {
+    query_builder.push("WITH q AS (SELECT websearch_to_tsquery('search_simple', ");
+    query_builder.push_bind(term.to_string());
+    query_builder.push(") AS tsq, immutable_unaccent(");
+    query_builder.push_bind(term.to_string());
+    query_builder.push(format!(
+        r#") AS term),
+            hits AS (
+                SELECT
+                    'EVENT'::VARCHAR AS kind,
+                    e.id,
+                    e.nume AS name,
+                    e.locatie AS location,
+                    e.starts_at,
+                    ts_rank_cd(e.search_vector, q.tsq)
+                        + word_similarity(q.term, immutable_unaccent(e.nume)) AS rank,
+                    ts_headline(
+                        'search_simple',
+                        concat_ws(' · ', e.nume, e.locatie, e.descriere),
+                        q.tsq,
+                        '{options}'
+                    ) AS snippet
+                FROM EVENIMENTE e, q
+                WHERE e.status = 'PUBLISHED'
+                  AND (e.search_vector @@ q.tsq OR q.term <% immutable_unaccent(e.nume))
+                UNION ALL
+                SELECT
+                    'PACKET'::VARCHAR AS kind,
+                    p.id,
+                    p.nume AS name,
+                    p.locatie AS location,
+                    NULL::TIMESTAMPTZ AS starts_at,
+                    ts_rank_cd(p.search_vector, q.tsq)
+                        + word_similarity(q.term, immutable_unaccent(p.nume)) AS rank,
+                    ts_headline(
+                        'search_simple',
+                        concat_ws(' · ', p.nume, p.locatie, p.descriere),
+                        q.tsq,
+                        '{options}'
+                    ) AS snippet
+                FROM PACHETE p, q
+                WHERE p.status = 'PUBLISHED'
+                  AND (p.search_vector @@ q.tsq OR q.term <% immutable_unaccent(p.nume))
+            )
+            "#,
+        options = HEADLINE_OPTIONS
+    ));
+}
+
+fn push_kind_filter(query_builder: &mut QueryBuilder<'_, Postgres>, kind: Option<SearchHitKind>) {
+    match kind {
+        Some(kind) => {
+            query_builder.push("kind = ");
+            query_builder.push_bind(kind);
+        }
+        None => {
+            query_builder.push("TRUE");
+        }
+    }
+}
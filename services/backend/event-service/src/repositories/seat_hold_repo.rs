@@ -0,0 +1,334 @@
+use crate::models::seat_hold::{HoldClaim, SeatHold};
+use crate::repositories::ticket_repo::TicketRepo;
+use crate::utils::error::{SeatHoldRepoError, TicketRepoError};
+use anyhow::Result;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub(crate) const SEAT_HOLD_COLUMNS: &str =
+    "id, event_id, packet_id, holder_id, quantity, created_at, expires_at";
+
+pub struct SeatHoldRepo {
+    pool: PgPool,
+}
+
+impl SeatHoldRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_hold_for_event(
+        &self,
+        event_id: i32,
+        holder_id: i32,
+        quantity: i32,
+        ttl_seconds: i64,
+    ) -> Result<SeatHold, TicketRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(TicketRepoError::InternalError)?;
+
+        let hold =
+            Self::hold_event_seats(&mut tx, event_id, holder_id, quantity, ttl_seconds).await?;
+
+        tx.commit().await.map_err(TicketRepoError::InternalError)?;
+
+        Ok(hold)
+    }
+
+    pub async fn create_hold_for_packet(
+        &self,
+        packet_id: i32,
+        holder_id: i32,
+        quantity: i32,
+        ttl_seconds: i64,
+    ) -> Result<SeatHold, TicketRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(TicketRepoError::InternalError)?;
+
+        let hold =
+            Self::hold_packet_seats(&mut tx, packet_id, holder_id, quantity, ttl_seconds).await?;
+
+        tx.commit().await.map_err(TicketRepoError::InternalError)?;
+
+        Ok(hold)
+    }
+
+    pub async fn get_hold(&self, id: &str) -> Result<SeatHold, SeatHoldRepoError> {
+        sqlx::query_as::<_, SeatHold>(&format!(
+            "SELECT {} FROM SEAT_HOLDS WHERE id = $1 AND expires_at > NOW()",
+            SEAT_HOLD_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(SeatHoldRepoError::InternalError)?
+        .ok_or(SeatHoldRepoError::NotFound)
+    }
+
+    pub async fn release_hold(&self, id: &str) -> Result<SeatHold, SeatHoldRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(SeatHoldRepoError::InternalError)?;
+
+        let hold = sqlx::query_as::<_, SeatHold>(&format!(
+            "DELETE FROM SEAT_HOLDS WHERE id = $1 RETURNING {}",
+            SEAT_HOLD_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(SeatHoldRepoError::InternalError)?
+        .ok_or(SeatHoldRepoError::NotFound)?;
+
+        Self::restore_seats(&mut tx, &hold)
+            .await
+            .map_err(SeatHoldRepoError::InternalError)?;
+        Self::expire_waitlist_offers(&mut tx, std::slice::from_ref(&hold.id))
+            .await
+            .map_err(SeatHoldRepoError::InternalError)?;
+
+        tx.commit()
+            .await
+            .map_err(SeatHoldRepoError::InternalError)?;
+
+        Ok(hold)
+    }
+
+    pub async fn release_expired_holds(&self) -> Result<Vec<SeatHold>, SeatHoldRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(SeatHoldRepoError::InternalError)?;
+
+        let expired = sqlx::query_as::<_, SeatHold>(&format!(
+            "DELETE FROM SEAT_HOLDS WHERE expires_at <= NOW() RETURNING {}",
+            SEAT_HOLD_COLUMNS
+        ))
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(SeatHoldRepoError::InternalError)?;
+
+        for hold in &expired {
+            Self::restore_seats(&mut tx, hold)
+                .await
+                .map_err(SeatHoldRepoError::InternalError)?;
+        }
+
+        let hold_ids: Vec<String> = expired.iter().map(|hold| hold.id.clone()).collect();
+        Self::expire_waitlist_offers(&mut tx, &hold_ids)
+            .await
+            .map_err(SeatHoldRepoError::InternalError)?;
+
+        tx.commit()
+            .await
+            .map_err(SeatHoldRepoError::InternalError)?;
+
+        Ok(expired)
+    }
+
+    pub(crate) async fn hold_event_seats(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: i32,
+        holder_id: i32,
+        quantity: i32,
+        ttl_seconds: i64,
+    ) -> Result<SeatHold, TicketRepoError> {
+        TicketRepo::reserve_event_seats(tx, event_id, quantity).await?;
+        Self::refresh_packet_seats(tx, &[event_id])
+            .await
+            .map_err(TicketRepoError::InternalError)?;
+
+        Self::insert_hold(tx, Some(event_id), None, holder_id, quantity, ttl_seconds)
+            .await
+            .map_err(TicketRepoError::InternalError)
+    }
+
+    pub(crate) async fn hold_packet_seats(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        packet_id: i32,
+        holder_id: i32,
+        quantity: i32,
+        ttl_seconds: i64,
+    ) -> Result<SeatHold, TicketRepoError> {
+        TicketRepo::ensure_packet_on_sale(tx, packet_id).await?;
+
+        let event_ids: Vec<i32> =
+            sqlx::query_scalar("SELECT evenimentid FROM JOIN_PE WHERE pachetid = $1")
+                .bind(packet_id)
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(TicketRepoError::InternalError)?;
+
+        if event_ids.is_empty() {
+            return Err(TicketRepoError::InvalidReference);
+        }
+
+        for event_id in &event_ids {
+            TicketRepo::reserve_event_seats(tx, *event_id, quantity).await?;
+        }
+        Self::refresh_packet_seats(tx, &event_ids)
+            .await
+            .map_err(TicketRepoError::InternalError)?;
+
+        Self::insert_hold(tx, None, Some(packet_id), holder_id, quantity, ttl_seconds)
+            .await
+            .map_err(TicketRepoError::InternalError)
+    }
+
+    /// Takes `seats` seats out of a live hold of the buyer for the given event
+    /// or packet, deleting the hold once it is used up. The seats were already reserved when the hold
+    /// was created, so the only other change is accepting a waitlist offer that
+    /// was made through this hold.
+    pub(crate) async fn consume_hold(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        hold: &HoldClaim,
+        event_id: Option<i32>,
+        packet_id: Option<i32>,
+        seats: i32,
+    ) -> Result<(), TicketRepoError> {
+        let hold_id = hold.hold_id.as_str();
+        let quantity: Option<i32> = sqlx::query_scalar(
+            r#"
+            SELECT quantity FROM SEAT_HOLDS
+            WHERE id = $1
+              AND event_id IS NOT DISTINCT FROM $2
+              AND packet_id IS NOT DISTINCT FROM $3
+              AND holder_id = $4
+              AND expires_at > NOW()
+            FOR UPDATE
+            "#,
+        )
+        .bind(hold_id)
+        .bind(event_id)
+        .bind(packet_id)
+        .bind(hold.holder_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(TicketRepoError::InternalError)?;
+
+        match quantity {
+            None => Err(TicketRepoError::InvalidHold),
+            Some(quantity) if quantity < seats => Err(TicketRepoError::InvalidHold),
+            Some(quantity) if quantity == seats => {
+                sqlx::query("DELETE FROM SEAT_HOLDS WHERE id = $1")
+                    .bind(hold_id)
+                    .execute(&mut **tx)
+                    .await
+                    .map_err(TicketRepoError::InternalError)?;
+                sqlx::query(
+                    "UPDATE WAITLIST SET status = 'ACCEPTED' WHERE hold_id = $1 AND status = 'OFFERED'",
+                )
+                .bind(hold_id)
+                .execute(&mut **tx)
+                .await
+                .map_err(TicketRepoError::InternalError)?;
+                Ok(())
+            }
+            Some(_) => {
+                sqlx::query("UPDATE SEAT_HOLDS SET quantity = quantity - $2 WHERE id = $1")
+                    .bind(hold_id)
+                    .bind(seats)
+                    .execute(&mut **tx)
+                    .await
+                    .map_err(TicketRepoError::InternalError)?;
+                Ok(())
+            }
+        }
+    }
+
+    async fn insert_hold(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Option<i32>,
+        packet_id: Option<i32>,
+        holder_id: i32,
+        quantity: i32,
+        ttl_seconds: i64,
+    ) -> Result<SeatHold, sqlx::Error> {
+        sqlx::query_as::<_, SeatHold>(&format!(
+            r#"
+            INSERT INTO SEAT_HOLDS (id, event_id, packet_id, holder_id, quantity, expires_at)
+            VALUES ($1, $2, $3, $4, $5, NOW() + make_interval(secs => $6))
+            RETURNING {}
+            "#,
+            SEAT_HOLD_COLUMNS
+        ))
+        .bind(Uuid::now_v7().to_string())
+        .bind(event_id)
+        .bind(packet_id)
+        .bind(holder_id)
+        .bind(quantity)
+        .bind(ttl_seconds as f64)
+        .fetch_one(&mut **tx)
+        .await
+    }
+
+    async fn restore_seats(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        hold: &SeatHold,
+    ) -> Result<(), sqlx::Error> {
+        let event_ids: Vec<i32> = match (hold.event_id, hold.packet_id) {
+            (Some(event_id), _) => vec![event_id],
+            (None, Some(packet_id)) => {
+                sqlx::query_scalar("SELECT evenimentid FROM JOIN_PE WHERE pachetid = $1")
+                    .bind(packet_id)
+                    .fetch_all(&mut **tx)
+                    .await?
+            }
+            (None, None) => return Ok(()),
+        };
+
+        sqlx::query("UPDATE EVENIMENTE SET numarlocuri = numarlocuri + $1 WHERE id = ANY($2)")
+            .bind(hold.quantity)
+            .bind(&event_ids)
+            .execute(&mut **tx)
+            .await?;
+
+        Self::refresh_packet_seats(tx, &event_ids).await
+    }
+
+    async fn expire_waitlist_offers(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        hold_ids: &[String],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE WAITLIST SET status = 'EXPIRED' WHERE hold_id = ANY($1) AND status = 'OFFERED'",
+        )
+        .bind(hold_ids)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn refresh_packet_seats(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_ids: &[i32],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE PACHETE p
+            SET numarlocuri = (
+                SELECT MIN(e.numarlocuri)
+                FROM EVENIMENTE e
+                JOIN JOIN_PE j ON e.id = j.evenimentid
+                WHERE j.pachetid = p.id
+            )
+            WHERE p.id IN (SELECT pachetid FROM JOIN_PE WHERE evenimentid = ANY($1))
+            "#,
+        )
+        .bind(event_ids)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
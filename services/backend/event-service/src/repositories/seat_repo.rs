@@ -0,0 +1,380 @@
+use crate::models::seat::{
+    EventSeat, EventSeatQuery, RoomSeat, SeatChoice, SeatMap, SectionAvailability,
+};
+use crate::utils::error::{SeatRepoError, TicketRepoError, map_sqlx_seat_error};
+use anyhow::Result;
+use sqlx::PgPool;
+
+const ROOM_SEAT_COLUMNS: &str = "id, room_id, section, row_label, seat_number, accessible";
+
+pub struct SeatRepo {
+    pool: PgPool,
+}
+
+impl SeatRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get_seat_map(&self, room_id: i32) -> Result<Vec<RoomSeat>, SeatRepoError> {
+        sqlx::query_as::<_, RoomSeat>(&format!(
+            "SELECT {} FROM ROOM_SEATS WHERE room_id = $1 ORDER BY id",
+            ROOM_SEAT_COLUMNS
+        ))
+        .bind(room_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_seat_error)
+    }
+
+    /// Swaps the seat map of a room and resizes the room to match. Fails while
+    /// any event still has an inventory built from the old seats.
+    pub async fn replace_seat_map(
+        &self,
+        room_id: i32,
+        map: SeatMap,
+    ) -> Result<Vec<RoomSeat>, SeatRepoError> {
+        let seat_count = map.seat_count();
+        let mut sections = Vec::with_capacity(seat_count);
+        let mut rows = Vec::with_capacity(seat_count);
+        let mut numbers = Vec::with_capacity(seat_count);
+        let mut accessible = Vec::with_capacity(seat_count);
+
+        for section in &map.sections {
+            for row in &section.rows {
+                for number in 1..=row.seats {
+                    sections.push(section.name.clone());
+                    rows.push(row.label.clone());
+                    numbers.push(number);
+                    accessible.push(row.accessible_seats.contains(&number));
+                }
+            }
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(SeatRepoError::InternalError)?;
+
+        let resized = sqlx::query("UPDATE VENUE_ROOMS SET capacity = $2 WHERE id = $1")
+            .bind(room_id)
+            .bind(seat_count as i32)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx_seat_error)?;
+
+        if resized.rows_affected() == 0 {
+            return Err(SeatRepoError::NotFound);
+        }
+
+        sqlx::query("DELETE FROM ROOM_SEATS WHERE room_id = $1")
+            .bind(room_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx_seat_error)?;
+
+        let seats = sqlx::query_as::<_, RoomSeat>(&format!(
+            r#"
+            INSERT INTO ROOM_SEATS (room_id, section, row_label, seat_number, accessible)
+            SELECT $1, * FROM UNNEST($2::TEXT[], $3::TEXT[], $4::INT[], $5::BOOL[])
+            RETURNING {}
+            "#,
+            ROOM_SEAT_COLUMNS
+        ))
+        .bind(room_id)
+        .bind(&sections)
+        .bind(&rows)
+        .bind(&numbers)
+        .bind(&accessible)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(map_sqlx_seat_error)?;
+
+        tx.commit().await.map_err(SeatRepoError::InternalError)?;
+
+        Ok(seats)
+    }
+
+    pub async fn has_inventory(&self, event_id: i32) -> Result<bool, SeatRepoError> {
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM EVENT_SEATS WHERE event_id = $1)")
+            .bind(event_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(SeatRepoError::InternalError)
+    }
+
+    /// Copies the seat map of the event's room into a per-event inventory and
+    /// caps the event capacity at the number of seats.
+    pub async fn open_inventory(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<SectionAvailability>, SeatRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(SeatRepoError::InternalError)?;
+
+        let room_id: Option<i32> =
+            sqlx::query_scalar("SELECT room_id FROM EVENIMENTE WHERE id = $1 FOR UPDATE")
+                .bind(event_id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(map_sqlx_seat_error)?;
+
+        let room_id = room_id.ok_or(SeatRepoError::NoSeatMap)?;
+
+        let (opened, sold): (bool, bool) = sqlx::query_as(
+            r#"
+            SELECT
+                EXISTS (SELECT 1 FROM EVENT_SEATS WHERE event_id = $1),
+                EXISTS (SELECT 1 FROM BILETE WHERE evenimentid = $1)
+            "#,
+        )
+        .bind(event_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(SeatRepoError::InternalError)?;
+
+        if opened {
+            return Err(SeatRepoError::InventoryOpen);
+        }
+        if sold {
+            return Err(SeatRepoError::TicketsSold);
+        }
+
+        let seats = sqlx::query(
+            "INSERT INTO EVENT_SEATS (event_id, seat_id) SELECT $1, id FROM ROOM_SEATS WHERE room_id = $2",
+        )
+        .bind(event_id)
+        .bind(room_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_sqlx_seat_error)?
+        .rows_affected();
+
+        if seats == 0 {
+            return Err(SeatRepoError::NoSeatMap);
+        }
+
+        sqlx::query(
+            "UPDATE EVENIMENTE SET numarlocuri = LEAST(COALESCE(numarlocuri, $2), $2) WHERE id = $1",
+        )
+        .bind(event_id)
+        .bind(seats as i32)
+        .execute(&mut *tx)
+        .await
+        .map_err(SeatRepoError::InternalError)?;
+
+        sqlx::query(
+            r#"
+            UPDATE PACHETE p
+            SET numarlocuri = (
+                SELECT MIN(e.numarlocuri)
+                FROM EVENIMENTE e
+                JOIN JOIN_PE j ON e.id = j.evenimentid
+                WHERE j.pachetid = p.id
+            )
+            WHERE p.id IN (SELECT pachetid FROM JOIN_PE WHERE evenimentid = $1)
+            "#,
+        )
+        .bind(event_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(SeatRepoError::InternalError)?;
+
+        tx.commit().await.map_err(SeatRepoError::InternalError)?;
+
+        self.section_availability(event_id).await
+    }
+
+    /// Drops the seat inventory so the event sells general admission again.
+    pub async fn close_inventory(&self, event_id: i32) -> Result<(), SeatRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(SeatRepoError::InternalError)?;
+
+        sqlx::query("SELECT id FROM EVENIMENTE WHERE id = $1 FOR UPDATE")
+            .bind(event_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(map_sqlx_seat_error)?;
+
+        let sold: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM EVENT_SEATS WHERE event_id = $1 AND ticket_cod IS NOT NULL)",
+        )
+        .bind(event_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(SeatRepoError::InternalError)?;
+
+        if sold {
+            return Err(SeatRepoError::TicketsSold);
+        }
+
+        let result = sqlx::query("DELETE FROM EVENT_SEATS WHERE event_id = $1")
+            .bind(event_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(SeatRepoError::InternalError)?;
+
+        if result.rows_affected() == 0 {
+            return Err(SeatRepoError::NotFound);
+        }
+
+        tx.commit().await.map_err(SeatRepoError::InternalError)?;
+
+        Ok(())
+    }
+
+    pub async fn list_event_seats(
+        &self,
+        event_id: i32,
+        params: EventSeatQuery,
+    ) -> Result<Vec<EventSeat>, SeatRepoError> {
+        sqlx::query_as::<_, EventSeat>(
+            r#"
+            SELECT
+                rs.id AS seat_id,
+                rs.section,
+                rs.row_label,
+                rs.seat_number,
+                rs.accessible,
+                es.ticket_cod IS NULL AS available
+            FROM EVENT_SEATS es
+            JOIN ROOM_SEATS rs ON rs.id = es.seat_id
+            WHERE es.event_id = $1
+              AND ($2::TEXT IS NULL OR rs.section = $2)
+              AND (NOT $3 OR es.ticket_cod IS NULL)
+            ORDER BY rs.id
+            "#,
+        )
+        .bind(event_id)
+        .bind(params.section)
+        .bind(params.available_only.unwrap_or(false))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_seat_error)
+    }
+
+    pub async fn section_availability(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<SectionAvailability>, SeatRepoError> {
+        sqlx::query_as::<_, SectionAvailability>(
+            r#"
+            SELECT
+                rs.section,
+                COUNT(*) AS total,
+                COUNT(*) FILTER (WHERE es.ticket_cod IS NULL) AS available,
+                COUNT(*) FILTER (WHERE es.ticket_cod IS NULL AND rs.accessible) AS accessible_available
+            FROM EVENT_SEATS es
+            JOIN ROOM_SEATS rs ON rs.id = es.seat_id
+            WHERE es.event_id = $1
+            GROUP BY rs.section
+            ORDER BY MIN(rs.id)
+            "#,
+        )
+        .bind(event_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_seat_error)
+    }
+
+    /// Locks the requested seat, or the first free one, for the ticket being
+    /// sold. Events without a seat inventory sell general admission and get
+    /// `None`. Accessible seats are only handed out when asked for by ID or
+    /// when nothing else is left.
+    pub(crate) async fn pick_seat(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: i32,
+        choice: &SeatChoice,
+    ) -> Result<Option<RoomSeat>, TicketRepoError> {
+        let seated: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM EVENT_SEATS WHERE event_id = $1)")
+                .bind(event_id)
+                .fetch_one(&mut **tx)
+                .await
+                .map_err(TicketRepoError::InternalError)?;
+
+        if !seated {
+            if choice.seat_id.is_some() || choice.section.is_some() {
+                return Err(TicketRepoError::InvalidSeat);
+            }
+            return Ok(None);
+        }
+
+        let seat: Option<RoomSeat> = sqlx::query_as::<_, RoomSeat>(
+            r#"
+            SELECT rs.id, rs.room_id, rs.section, rs.row_label, rs.seat_number, rs.accessible
+            FROM EVENT_SEATS es
+            JOIN ROOM_SEATS rs ON rs.id = es.seat_id
+            WHERE es.event_id = $1
+              AND es.ticket_cod IS NULL
+              AND ($2::INT IS NULL OR es.seat_id = $2)
+              AND ($3::TEXT IS NULL OR rs.section = $3)
+            ORDER BY rs.accessible, rs.id
+            LIMIT 1
+            FOR UPDATE OF es SKIP LOCKED
+            "#,
+        )
+        .bind(event_id)
+        .bind(choice.seat_id)
+        .bind(choice.section.as_deref())
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(TicketRepoError::InternalError)?;
+
+        if seat.is_some() {
+            return Ok(seat);
+        }
+
+        if choice.seat_id.is_none() && choice.section.is_none() {
+            return Err(TicketRepoError::NoSeatsAvailable);
+        }
+
+        let exists: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM EVENT_SEATS es
+                JOIN ROOM_SEATS rs ON rs.id = es.seat_id
+                WHERE es.event_id = $1
+                  AND ($2::INT IS NULL OR es.seat_id = $2)
+                  AND ($3::TEXT IS NULL OR rs.section = $3)
+            )
+            "#,
+        )
+        .bind(event_id)
+        .bind(choice.seat_id)
+        .bind(choice.section.as_deref())
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(TicketRepoError::InternalError)?;
+
+        match (exists, choice.seat_id) {
+            (false, _) => Err(TicketRepoError::InvalidSeat),
+            (true, Some(_)) => Err(TicketRepoError::SeatTaken),
+            (true, None) => Err(TicketRepoError::NoSeatsAvailable),
+        }
+    }
+
+    pub(crate) async fn assign_seat(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: i32,
+        seat_id: i32,
+        cod: &str,
+    ) -> Result<(), TicketRepoError> {
+        sqlx::query("UPDATE EVENT_SEATS SET ticket_cod = $3 WHERE event_id = $1 AND seat_id = $2")
+            .bind(event_id)
+            .bind(seat_id)
+            .bind(cod)
+            .execute(&mut **tx)
+            .await
+            .map_err(TicketRepoError::InternalError)?;
+        Ok(())
+    }
+}
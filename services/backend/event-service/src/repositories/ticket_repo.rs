@@ -1,9 +1,23 @@
+use crate::models::event::EventStatus;
+use crate::models::event_packets::PaginationParams;
+use crate::models::export::Attendee;
+use crate::models::promo_code::{PromoApplication, PromoCode};
+use crate::models::seat::SeatChoice;
+use crate::models::seat_hold::HoldClaim;
 use crate::models::ticket::{Ticket, UpdateTicket};
+use crate::repositories::price_tier_repo::PriceTierRepo;
+use crate::repositories::promo_code_repo::PromoCodeRepo;
+use crate::repositories::seat_hold_repo::SeatHoldRepo;
+use crate::repositories::seat_repo::SeatRepo;
 use crate::utils::error::{TicketRepoError, map_sqlx_ticket_error};
 use anyhow::Result;
-use sqlx::PgPool;
+use common::pagination::{Cursor, Page};
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
+pub(crate) const TICKET_COLUMNS: &str = "cod, pachetid, evenimentid, tier_id, price_minor, currency, \
+     promo_code_id, discount_minor, checked_in_at, checked_in_gate, seat_id, seat_label";
+
 pub struct TicketRepo {
     pool: PgPool,
 }
@@ -17,13 +31,10 @@ impl TicketRepo {
         &self,
         event_id: i32,
     ) -> Result<Vec<Ticket>, TicketRepoError> {
-        let result = sqlx::query_as::<_, Ticket>(
-            r#"
-            SELECT cod, pachetid, evenimentid
-            FROM BILETE
-            WHERE evenimentid = $1
-            "#,
-        )
+        let result = sqlx::query_as::<_, Ticket>(&format!(
+            "SELECT {} FROM BILETE WHERE evenimentid = $1",
+            TICKET_COLUMNS
+        ))
         .bind(event_id)
         .fetch_all(&self.pool)
         .await;
@@ -54,13 +65,10 @@ impl TicketRepo {
         event_id: i32,
         cod: &str,
     ) -> Result<Ticket, TicketRepoError> {
-        let result = sqlx::query_as::<_, Ticket>(
-            r#"
-            SELECT cod, pachetid, evenimentid
-            FROM BILETE
-            WHERE evenimentid = $1 AND cod = $2
-            "#,
-        )
+        let result = sqlx::query_as::<_, Ticket>(&format!(
+            "SELECT {} FROM BILETE WHERE evenimentid = $1 AND cod = $2",
+            TICKET_COLUMNS
+        ))
         .bind(event_id)
         .bind(cod)
         .fetch_one(&self.pool)
@@ -69,136 +77,127 @@ impl TicketRepo {
         result.map_err(map_sqlx_ticket_error)
     }
 
-    pub async fn create_ticket_for_event(&self, event_id: i32) -> Result<Ticket, TicketRepoError> {
-        let new_code = Uuid::now_v7().to_string();
-
-        let mut tx = self
-            .pool
-            .begin()
-            .await
-            .map_err(TicketRepoError::InternalError)?;
+    /// Sells one ticket for an event under `cod`, or a fresh code when none is
+    /// given.
+    pub async fn create_ticket_for_event(
+        &self,
+        event_id: i32,
+        cod: Option<String>,
+        tier_id: Option<i32>,
+        hold: Option<&HoldClaim>,
+        promo: Option<&PromoApplication>,
+        seat: &SeatChoice,
+    ) -> Result<Ticket, TicketRepoError> {
+        let cod = cod.unwrap_or_else(|| Uuid::now_v7().to_string());
+        let mut tickets = self
+            .create_tickets_for_event(event_id, vec![cod], tier_id, hold, promo, seat)
+            .await?;
 
-        let seats_result: Option<i32> = sqlx::query_scalar(
-            "UPDATE EVENIMENTE
-             SET numarlocuri = numarlocuri - 1
-             WHERE id = $1 AND numarlocuri > 0
-             RETURNING numarlocuri",
-        )
-        .bind(event_id)
-        .fetch_optional(&mut *tx)
-        .await
-        .map_err(TicketRepoError::InternalError)?;
+        Ok(tickets.remove(0))
+    }
 
-        if seats_result.is_none() {
-            let exists: bool =
-                sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM EVENIMENTE WHERE id = $1)")
-                    .bind(event_id)
-                    .fetch_one(&mut *tx)
-                    .await
-                    .map_err(TicketRepoError::InternalError)?;
+    pub async fn get_ticket(&self, cod: &str) -> Result<Ticket, TicketRepoError> {
+        let result = sqlx::query_as::<_, Ticket>(&format!(
+            "SELECT {} FROM BILETE WHERE cod = $1",
+            TICKET_COLUMNS
+        ))
+        .bind(cod)
+        .fetch_one(&self.pool)
+        .await;
+        result.map_err(map_sqlx_ticket_error)
+    }
 
-            return Err(if exists {
-                TicketRepoError::NoSeatsAvailable
+    /// Tickets in code order, resuming after (or before) the cursor's code.
+    pub async fn list_tickets(
+        &self,
+        params: PaginationParams,
+    ) -> Result<Page<Ticket>, TicketRepoError> {
+        let cursor = match params.cursor.as_deref() {
+            Some(cursor) => {
+                Some(Cursor::<String>::decode(cursor).ok_or(TicketRepoError::InvalidCursor)?)
+            }
+            None => None,
+        };
+        let backward = cursor.as_ref().is_some_and(|cursor| cursor.before);
+
+        let mut query_builder: QueryBuilder<Postgres> =
+            QueryBuilder::new(format!("SELECT {} FROM BILETE", TICKET_COLUMNS));
+        if let Some(cursor) = &cursor {
+            query_builder.push(if backward {
+                " WHERE cod < "
             } else {
-                TicketRepoError::InvalidReference
+                " WHERE cod > "
             });
+            query_builder.push_bind(cursor.key.clone());
         }
+        query_builder.push(if backward {
+            " ORDER BY cod DESC"
+        } else {
+            " ORDER BY cod ASC"
+        });
 
-        let ticket = sqlx::query_as::<_, Ticket>(
-            r#"
-            INSERT INTO BILETE (cod, pachetid, evenimentid)
-            VALUES ($1, NULL, $2)
-            RETURNING cod, pachetid, evenimentid
-            "#,
-        )
-        .bind(new_code)
-        .bind(event_id)
-        .fetch_one(&mut *tx)
-        .await
-        .map_err(map_sqlx_ticket_error)?;
+        let page = params.page.unwrap_or(1);
+        let items_per_page = params.items_per_page.unwrap_or(10);
+        let offset = if cursor.is_some() {
+            0
+        } else {
+            (page - 1) * items_per_page
+        };
 
-        let package_ids: Vec<(i32,)> =
-            sqlx::query_as("SELECT DISTINCT pachetid FROM JOIN_PE WHERE evenimentid = $1")
-                .bind(event_id)
-                .fetch_all(&mut *tx)
-                .await
-                .map_err(TicketRepoError::InternalError)?;
+        query_builder.push(" LIMIT ");
+        query_builder.push_bind(items_per_page + 1);
+        query_builder.push(" OFFSET ");
+        query_builder.push_bind(offset);
 
-        for (packet_id,) in package_ids {
-            let min_seats: Option<i32> = sqlx::query_scalar(
-                r#"
-                SELECT MIN(e.numarlocuri)
-                FROM EVENIMENTE e
-                JOIN JOIN_PE j ON e.id = j.evenimentid
-                WHERE j.pachetid = $1
-                "#,
-            )
-            .bind(packet_id)
-            .fetch_optional(&mut *tx)
+        let tickets = query_builder
+            .build_query_as::<Ticket>()
+            .fetch_all(&self.pool)
             .await
-            .map_err(TicketRepoError::InternalError)?
-            .flatten();
+            .map_err(map_sqlx_ticket_error)?;
 
-            sqlx::query("UPDATE PACHETE SET numarlocuri = $1 WHERE id = $2")
-                .bind(min_seats)
-                .bind(packet_id)
-                .execute(&mut *tx)
+        let total = if params.include_total == Some(true) {
+            let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM BILETE")
+                .fetch_one(&self.pool)
                 .await
                 .map_err(TicketRepoError::InternalError)?;
-        }
-
-        tx.commit().await.map_err(TicketRepoError::InternalError)?;
-
-        Ok(ticket)
-    }
-
-    pub async fn get_ticket(&self, cod: &str) -> Result<Ticket, TicketRepoError> {
-        let result = sqlx::query_as::<_, Ticket>(
-            r#"
-            SELECT cod, pachetid, evenimentid
-            FROM BILETE
-            WHERE cod = $1
-            "#,
+            Some(total)
+        } else {
+            None
+        };
+
+        Ok(Page::from_rows(
+            tickets,
+            items_per_page as usize,
+            backward,
+            cursor.is_some() || offset > 0,
+            |ticket| ticket.cod.clone(),
         )
-        .bind(cod)
-        .fetch_one(&self.pool)
-        .await;
-        result.map_err(map_sqlx_ticket_error)
+        .with_total(total))
     }
 
-    pub async fn list_tickets(&self) -> Result<Vec<Ticket>, TicketRepoError> {
-        let result = sqlx::query_as::<_, Ticket>(
-            r#"
-            SELECT * FROM BILETE
-            "#,
-        )
-        .fetch_all(&self.pool)
-        .await;
-
-        result.map_err(map_sqlx_ticket_error)
-    }
+    /// Re-sends an existing ticket. The tier, price, promo and seat were
+    /// claimed for its event or packet at purchase, so a payload that would
+    /// move it elsewhere is refused instead of leaving them behind.
     pub async fn update_ticket(
         &self,
         cod: &str,
         payload: UpdateTicket,
     ) -> Result<Ticket, TicketRepoError> {
-        let result = sqlx::query_as::<_, Ticket>(
-            r#"
-            UPDATE BILETE
-            SET
-                pachetid = $1,
-                evenimentid = $2
-            WHERE COD = $3
-            RETURNING COD, pachetid, evenimentid
-            "#,
-        )
-        .bind(payload.id_pachet)
-        .bind(payload.id_event)
-        .bind(cod)
-        .fetch_one(&self.pool)
-        .await;
+        let ticket = self.get_ticket(cod).await?;
+
+        let moved = payload.id_event != ticket.id_event
+            || payload.id_pachet != ticket.id_pachet
+            || payload
+                .tier_id
+                .is_some_and(|tier_id| Some(tier_id) != ticket.tier_id)
+            || payload
+                .seat_id
+                .is_some_and(|seat_id| Some(seat_id) != ticket.seat_id);
+        if moved {
+            return Err(TicketRepoError::TargetChanged);
+        }
 
-        result.map_err(map_sqlx_ticket_error)
+        Ok(ticket)
     }
 
     pub async fn delete_ticket(&self, cod: &str) -> Result<(), TicketRepoError> {
@@ -208,131 +207,110 @@ impl TicketRepo {
             .await
             .map_err(TicketRepoError::InternalError)?;
 
-        let ticket: Option<Ticket> = sqlx::query_as::<_, Ticket>(
-            "SELECT cod, pachetid, evenimentid FROM BILETE WHERE cod = $1",
-        )
+        Self::remove_ticket(&mut tx, cod).await?;
+
+        tx.commit().await.map_err(TicketRepoError::InternalError)?;
+
+        Ok(())
+    }
+
+    /// Deletes a ticket inside `tx` and gives back what it took: its seat on
+    /// the event (or every event of its packet), its tier slot and promo use.
+    /// Checked-in tickets are kept as the record of attendance.
+    pub(crate) async fn remove_ticket(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        cod: &str,
+    ) -> Result<Ticket, TicketRepoError> {
+        let ticket: Option<Ticket> = sqlx::query_as::<_, Ticket>(&format!(
+            "SELECT {} FROM BILETE WHERE cod = $1 FOR UPDATE",
+            TICKET_COLUMNS
+        ))
         .bind(cod)
-        .fetch_optional(&mut *tx)
+        .fetch_optional(&mut **tx)
         .await
         .map_err(TicketRepoError::InternalError)?;
 
         let ticket = ticket.ok_or(TicketRepoError::NotFound)?;
+        if ticket.checked_in_at.is_some() {
+            return Err(TicketRepoError::CheckedIn);
+        }
 
         sqlx::query("DELETE FROM BILETE WHERE cod = $1")
             .bind(cod)
-            .execute(&mut *tx)
+            .execute(&mut **tx)
             .await
             .map_err(TicketRepoError::InternalError)?;
 
-        if let Some(event_id) = ticket.id_event {
-            sqlx::query("UPDATE EVENIMENTE SET numarlocuri = numarlocuri + 1 WHERE id = $1")
-                .bind(event_id)
-                .execute(&mut *tx)
-                .await
-                .map_err(TicketRepoError::InternalError)?;
+        if let Some(tier_id) = ticket.tier_id {
+            PriceTierRepo::release_tier(tx, tier_id).await?;
+        }
 
-            let package_ids: Vec<(i32,)> =
-                sqlx::query_as("SELECT DISTINCT pachetid FROM JOIN_PE WHERE evenimentid = $1")
-                    .bind(event_id)
-                    .fetch_all(&mut *tx)
-                    .await
-                    .map_err(TicketRepoError::InternalError)?;
-
-            for (packet_id,) in package_ids {
-                let min_seats: Option<i32> = sqlx::query_scalar(
-                    r#"
-                    SELECT MIN(e.numarlocuri)
-                    FROM EVENIMENTE e
-                    JOIN JOIN_PE j ON e.id = j.evenimentid
-                    WHERE j.pachetid = $1
-                    "#,
-                )
-                .bind(packet_id)
-                .fetch_optional(&mut *tx)
-                .await
-                .map_err(TicketRepoError::InternalError)?
-                .flatten();
+        if let Some(promo_code_id) = ticket.promo_code_id {
+            PromoCodeRepo::release_use(tx, promo_code_id).await?;
+        }
 
-                sqlx::query("UPDATE PACHETE SET numarlocuri = $1 WHERE id = $2")
-                    .bind(min_seats)
+        let event_ids: Vec<i32> = match (ticket.id_event, ticket.id_pachet) {
+            (Some(event_id), _) => vec![event_id],
+            (None, Some(packet_id)) => {
+                sqlx::query_scalar("SELECT evenimentid FROM JOIN_PE WHERE pachetid = $1")
                     .bind(packet_id)
-                    .execute(&mut *tx)
+                    .fetch_all(&mut **tx)
                     .await
-                    .map_err(TicketRepoError::InternalError)?;
+                    .map_err(TicketRepoError::InternalError)?
             }
-        } else if let Some(packet_id) = ticket.id_pachet {
-            let event_ids: Vec<(i32,)> =
-                sqlx::query_as("SELECT evenimentid FROM JOIN_PE WHERE pachetid = $1")
-                    .bind(packet_id)
-                    .fetch_all(&mut *tx)
-                    .await
-                    .map_err(TicketRepoError::InternalError)?;
+            (None, None) => Vec::new(),
+        };
 
-            for (event_id,) in event_ids {
-                sqlx::query("UPDATE EVENIMENTE SET numarlocuri = numarlocuri + 1 WHERE id = $1")
-                    .bind(event_id)
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(TicketRepoError::InternalError)?;
-            }
-
-            let min_seats: Option<i32> = sqlx::query_scalar(
-                r#"
-                SELECT MIN(e.numarlocuri)
-                FROM EVENIMENTE e
-                JOIN JOIN_PE j ON e.id = j.evenimentid
-                WHERE j.pachetid = $1
-                "#,
-            )
-            .bind(packet_id)
-            .fetch_optional(&mut *tx)
+        sqlx::query("UPDATE EVENIMENTE SET numarlocuri = numarlocuri + 1 WHERE id = ANY($1)")
+            .bind(&event_ids)
+            .execute(&mut **tx)
             .await
-            .map_err(TicketRepoError::InternalError)?
-            .flatten();
-
-            sqlx::query("UPDATE PACHETE SET numarlocuri = $1 WHERE id = $2")
-                .bind(min_seats)
-                .bind(packet_id)
-                .execute(&mut *tx)
-                .await
-                .map_err(TicketRepoError::InternalError)?;
-        }
-
-        tx.commit().await.map_err(TicketRepoError::InternalError)?;
+            .map_err(TicketRepoError::InternalError)?;
+        Self::sync_packet_capacity(tx, &event_ids).await?;
 
-        Ok(())
+        Ok(ticket)
     }
 
+    /// Deletes a ticket sold for this event, giving back what it took the way
+    /// `delete_ticket` does, and returns the removed ticket.
     pub async fn delete_ticket_for_event(
         &self,
         event_id: i32,
-        cod: String,
-    ) -> Result<(), TicketRepoError> {
-        let result = sqlx::query("DELETE FROM BILETE WHERE evenimentid = $1 AND cod = $2")
-            .bind(event_id)
-            .bind(cod)
-            .execute(&self.pool)
+        cod: &str,
+    ) -> Result<Ticket, TicketRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
             .await
             .map_err(TicketRepoError::InternalError)?;
 
-        if result.rows_affected() == 0 {
-            Err(TicketRepoError::NotFound)
-        } else {
-            Ok(())
+        let sold_here: Option<i32> = sqlx::query_scalar(
+            "SELECT 1 FROM BILETE WHERE cod = $1 AND evenimentid = $2 FOR UPDATE",
+        )
+        .bind(cod)
+        .bind(event_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(TicketRepoError::InternalError)?;
+        if sold_here.is_none() {
+            return Err(TicketRepoError::NotFound);
         }
+
+        let ticket = Self::remove_ticket(&mut tx, cod).await?;
+
+        tx.commit().await.map_err(TicketRepoError::InternalError)?;
+
+        Ok(ticket)
     }
 
     pub async fn list_tickets_for_packet(
         &self,
         packet_id: i32,
     ) -> Result<Vec<Ticket>, TicketRepoError> {
-        let result = sqlx::query_as::<_, Ticket>(
-            r#"
-            SELECT cod, pachetid, evenimentid
-            FROM BILETE
-            WHERE pachetid = $1
-            "#,
-        )
+        let result = sqlx::query_as::<_, Ticket>(&format!(
+            "SELECT {} FROM BILETE WHERE pachetid = $1",
+            TICKET_COLUMNS
+        ))
         .bind(packet_id)
         .fetch_all(&self.pool)
         .await;
@@ -340,19 +318,72 @@ impl TicketRepo {
         result.map_err(map_sqlx_ticket_error)
     }
 
-    pub async fn get_ticket_for_packet(
+    /// Holders of valid tickets to an event, bought for it directly or through
+    /// one of its packets; tickets with an approved refund are left out.
+    pub async fn list_event_attendees(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<Attendee>, TicketRepoError> {
+        sqlx::query_as::<_, Attendee>(
+            r#"
+            SELECT b.cod, b.created_at AS purchased_at, t.name AS tier_name,
+                   b.price_minor, b.currency, b.seat_label,
+                   c.checked_in_at, c.gate AS checked_in_gate
+            FROM BILETE b
+            LEFT JOIN PRICE_TIERS t ON t.id = b.tier_id
+            LEFT JOIN CHECK_INS c ON c.ticket_cod = b.cod AND c.event_id = $1
+            WHERE (
+                b.evenimentid = $1
+                OR b.pachetid IN (SELECT pachetid FROM JOIN_PE WHERE evenimentid = $1)
+            )
+            AND NOT EXISTS (
+                SELECT 1 FROM REFUND_REQUESTS r
+                WHERE r.ticket_cod = b.cod AND r.status = 'APPROVED'
+            )
+            ORDER BY b.created_at, b.cod
+            "#,
+        )
+        .bind(event_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(TicketRepoError::InternalError)
+    }
+
+    pub async fn list_packet_attendees(
         &self,
         packet_id: i32,
-        cod: &str,
-    ) -> Result<Ticket, TicketRepoError> {
-        let result = sqlx::query_as::<_, Ticket>(
+    ) -> Result<Vec<Attendee>, TicketRepoError> {
+        sqlx::query_as::<_, Attendee>(
             r#"
-            SELECT cod, pachetid, evenimentid
-            FROM BILETE
-            WHERE pachetid = $1 AND cod = $2
+            SELECT b.cod, b.created_at AS purchased_at, t.name AS tier_name,
+                   b.price_minor, b.currency, b.seat_label,
+                   b.checked_in_at, b.checked_in_gate
+            FROM BILETE b
+            LEFT JOIN PRICE_TIERS t ON t.id = b.tier_id
+            WHERE b.pachetid = $1
+            AND NOT EXISTS (
+                SELECT 1 FROM REFUND_REQUESTS r
+                WHERE r.ticket_cod = b.cod AND r.status = 'APPROVED'
+            )
+            ORDER BY b.created_at, b.cod
             "#,
         )
         .bind(packet_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(TicketRepoError::InternalError)
+    }
+
+    pub async fn get_ticket_for_packet(
+        &self,
+        packet_id: i32,
+        cod: &str,
+    ) -> Result<Ticket, TicketRepoError> {
+        let result = sqlx::query_as::<_, Ticket>(&format!(
+            "SELECT {} FROM BILETE WHERE pachetid = $1 AND cod = $2",
+            TICKET_COLUMNS
+        ))
+        .bind(packet_id)
         .bind(cod)
         .fetch_one(&self.pool)
         .await;
@@ -360,306 +391,346 @@ impl TicketRepo {
         result.map_err(map_sqlx_ticket_error)
     }
 
+    /// Sells one packet ticket under `cod`, or a fresh code when none is given.
     pub async fn create_ticket_for_packet(
         &self,
         packet_id: i32,
+        cod: Option<String>,
+        tier_id: Option<i32>,
+        hold: Option<&HoldClaim>,
+        promo: Option<&PromoApplication>,
     ) -> Result<Ticket, TicketRepoError> {
-        let new_code = Uuid::now_v7().to_string();
+        let cod = cod.unwrap_or_else(|| Uuid::now_v7().to_string());
+        let mut tickets = self
+            .create_tickets_for_packet(packet_id, vec![cod], tier_id, hold, promo)
+            .await?;
 
+        Ok(tickets.remove(0))
+    }
+
+    /// Deletes a ticket sold for this packet, giving back its seat on every
+    /// event of the packet, and returns the removed ticket.
+    pub async fn delete_ticket_for_packet(
+        &self,
+        packet_id: i32,
+        cod: &str,
+    ) -> Result<Ticket, TicketRepoError> {
         let mut tx = self
             .pool
             .begin()
             .await
             .map_err(TicketRepoError::InternalError)?;
 
-        let events: Vec<(i32,)> = sqlx::query_as(
-            r#"
-            SELECT e.id
-            FROM EVENIMENTE e
-            JOIN JOIN_PE j ON e.id = j.evenimentid
-            WHERE j.pachetid = $1
-            "#,
-        )
-        .bind(packet_id)
-        .fetch_all(&mut *tx)
-        .await
-        .map_err(TicketRepoError::InternalError)?;
-
-        if events.is_empty() {
-            return Err(TicketRepoError::InvalidReference);
+        let sold_here: Option<i32> =
+            sqlx::query_scalar("SELECT 1 FROM BILETE WHERE cod = $1 AND pachetid = $2 FOR UPDATE")
+                .bind(cod)
+                .bind(packet_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(TicketRepoError::InternalError)?;
+        if sold_here.is_none() {
+            return Err(TicketRepoError::NotFound);
         }
 
-        for (event_id,) in &events {
-            let result: Option<i32> = sqlx::query_scalar(
-                "UPDATE EVENIMENTE
-                 SET numarlocuri = numarlocuri - 1
-                 WHERE id = $1 AND numarlocuri > 0
-                 RETURNING numarlocuri",
-            )
-            .bind(event_id)
-            .fetch_optional(&mut *tx)
+        let ticket = Self::remove_ticket(&mut tx, cod).await?;
+
+        tx.commit().await.map_err(TicketRepoError::InternalError)?;
+
+        Ok(ticket)
+    }
+
+    /// Sells one ticket per code for an event in one transaction: either every
+    /// ticket is created and its seat taken, or none are.
+    pub async fn create_tickets_for_event(
+        &self,
+        event_id: i32,
+        codes: Vec<String>,
+        tier_id: Option<i32>,
+        hold: Option<&HoldClaim>,
+        promo: Option<&PromoApplication>,
+        seat: &SeatChoice,
+    ) -> Result<Vec<Ticket>, TicketRepoError> {
+        let quantity = codes.len() as i32;
+
+        let mut tx = self
+            .pool
+            .begin()
             .await
             .map_err(TicketRepoError::InternalError)?;
 
-            if result.is_none() {
-                return Err(TicketRepoError::NoSeatsAvailable);
+        match hold {
+            Some(hold) => {
+                Self::ensure_event_on_sale(&mut tx, event_id).await?;
+                SeatHoldRepo::consume_hold(&mut tx, hold, Some(event_id), None, quantity).await?;
             }
+            None => Self::reserve_event_seats(&mut tx, event_id, quantity).await?,
         }
 
-        let all_affected_packages: Vec<(i32,)> = sqlx::query_as(
-            r#"
-            SELECT DISTINCT j2.pachetid
-            FROM JOIN_PE j1
-            JOIN JOIN_PE j2 ON j1.evenimentid = j2.evenimentid
-            WHERE j1.pachetid = $1
-            "#,
-        )
-        .bind(packet_id)
-        .fetch_all(&mut *tx)
-        .await
-        .map_err(TicketRepoError::InternalError)?;
+        let mut tickets = Vec::with_capacity(quantity as usize);
+        let promo_code = Self::redeem_promo(&mut tx, promo, Some(event_id), None, quantity).await?;
+        for cod in codes {
+            let mut sold_at =
+                PriceTierRepo::claim_tier(&mut tx, Some(event_id), None, tier_id).await?;
+            if let Some(code) = &promo_code {
+                PromoCodeRepo::apply(code, &mut sold_at)?;
+            }
+            let room_seat = SeatRepo::pick_seat(&mut tx, event_id, seat).await?;
 
-        for (affected_packet_id,) in all_affected_packages {
-            let min_seats: Option<i32> = sqlx::query_scalar(
+            let ticket = sqlx::query_as::<_, Ticket>(&format!(
                 r#"
-                SELECT MIN(e.numarlocuri)
-                FROM EVENIMENTE e
-                JOIN JOIN_PE j ON e.id = j.evenimentid
-                WHERE j.pachetid = $1
+                INSERT INTO BILETE
+                    (cod, pachetid, evenimentid, tier_id, price_minor, currency, promo_code_id, discount_minor,
+                     seat_id, seat_label)
+                VALUES ($1, NULL, $2, $3, $4, $5, $6, $7, $8, $9)
+                RETURNING {}
                 "#,
-            )
-            .bind(affected_packet_id)
-            .fetch_optional(&mut *tx)
+                TICKET_COLUMNS
+            ))
+            .bind(&cod)
+            .bind(event_id)
+            .bind(sold_at.tier_id)
+            .bind(sold_at.price_minor)
+            .bind(&sold_at.currency)
+            .bind(sold_at.promo_code_id)
+            .bind(sold_at.discount_minor)
+            .bind(room_seat.as_ref().map(|s| s.id))
+            .bind(room_seat.as_ref().map(|s| s.label()))
+            .fetch_one(&mut *tx)
             .await
-            .map_err(TicketRepoError::InternalError)?
-            .flatten();
+            .map_err(map_sqlx_ticket_error)?;
 
-            sqlx::query("UPDATE PACHETE SET numarlocuri = $1 WHERE id = $2")
-                .bind(min_seats)
-                .bind(affected_packet_id)
-                .execute(&mut *tx)
-                .await
-                .map_err(TicketRepoError::InternalError)?;
+            if let Some(room_seat) = &room_seat {
+                SeatRepo::assign_seat(&mut tx, event_id, room_seat.id, &ticket.cod).await?;
+            }
+
+            if let Some(promo) = promo {
+                PromoCodeRepo::record_redemption(&mut tx, &ticket, promo.buyer_id).await?;
+            }
+
+            tickets.push(ticket);
         }
 
-        let ticket = sqlx::query_as::<_, Ticket>(
-            r#"
-            INSERT INTO BILETE (cod, pachetid, evenimentid)
-            VALUES ($1, $2, NULL)
-            RETURNING cod, pachetid, evenimentid
-            "#,
-        )
-        .bind(new_code)
-        .bind(packet_id)
-        .fetch_one(&mut *tx)
-        .await
-        .map_err(map_sqlx_ticket_error)?;
+        Self::sync_packet_capacity(&mut tx, &[event_id]).await?;
 
         tx.commit().await.map_err(TicketRepoError::InternalError)?;
 
-        Ok(ticket)
+        Ok(tickets)
     }
 
-    pub async fn delete_ticket_for_packet(
+    /// Sells one packet ticket per code in one transaction, taking that many
+    /// seats on every event of the packet.
+    pub async fn create_tickets_for_packet(
         &self,
         packet_id: i32,
-        cod: &str,
-    ) -> Result<(), TicketRepoError> {
-        let result = sqlx::query("DELETE FROM BILETE WHERE pachetid = $1 AND cod = $2")
-            .bind(packet_id)
-            .bind(cod)
-            .execute(&self.pool)
-            .await
-            .map_err(TicketRepoError::InternalError)?;
-
-        if result.rows_affected() == 0 {
-            Err(TicketRepoError::NotFound)
-        } else {
-            Ok(())
-        }
-    }
+        codes: Vec<String>,
+        tier_id: Option<i32>,
+        hold: Option<&HoldClaim>,
+        promo: Option<&PromoApplication>,
+    ) -> Result<Vec<Ticket>, TicketRepoError> {
+        let quantity = codes.len() as i32;
 
-    pub async fn create_ticket_with_code_for_event(
-        &self,
-        cod: String,
-        event_id: i32,
-    ) -> Result<Ticket, TicketRepoError> {
         let mut tx = self
             .pool
             .begin()
             .await
             .map_err(TicketRepoError::InternalError)?;
 
-        let seats_result: Option<i32> = sqlx::query_scalar(
-            "UPDATE EVENIMENTE
-             SET numarlocuri = numarlocuri - 1
-             WHERE id = $1 AND numarlocuri > 0
-             RETURNING numarlocuri",
-        )
-        .bind(event_id)
-        .fetch_optional(&mut *tx)
-        .await
-        .map_err(TicketRepoError::InternalError)?;
+        Self::ensure_packet_on_sale(&mut tx, packet_id).await?;
 
-        if seats_result.is_none() {
-            let exists: bool =
-                sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM EVENIMENTE WHERE id = $1)")
-                    .bind(event_id)
-                    .fetch_one(&mut *tx)
-                    .await
-                    .map_err(TicketRepoError::InternalError)?;
+        let events: Vec<i32> =
+            sqlx::query_scalar("SELECT evenimentid FROM JOIN_PE WHERE pachetid = $1")
+                .bind(packet_id)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(TicketRepoError::InternalError)?;
 
-            return Err(if exists {
-                TicketRepoError::NoSeatsAvailable
-            } else {
-                TicketRepoError::InvalidReference
-            });
+        if events.is_empty() {
+            return Err(TicketRepoError::InvalidReference);
         }
 
-        let ticket = sqlx::query_as::<_, Ticket>(
-            r#"
-            INSERT INTO BILETE (cod, pachetid, evenimentid)
-            VALUES ($1, NULL, $2)
-            RETURNING cod, pachetid, evenimentid
-            "#,
-        )
-        .bind(&cod)
-        .bind(event_id)
-        .fetch_one(&mut *tx)
-        .await
-        .map_err(map_sqlx_ticket_error)?;
+        if let Some(hold) = hold {
+            SeatHoldRepo::consume_hold(&mut tx, hold, None, Some(packet_id), quantity).await?;
+        } else {
+            for event_id in &events {
+                Self::reserve_event_seats(&mut tx, *event_id, quantity).await?;
+            }
+        }
 
-        let package_ids: Vec<(i32,)> =
-            sqlx::query_as("SELECT DISTINCT pachetid FROM JOIN_PE WHERE evenimentid = $1")
-                .bind(event_id)
-                .fetch_all(&mut *tx)
-                .await
-                .map_err(TicketRepoError::InternalError)?;
+        Self::sync_packet_capacity(&mut tx, &events).await?;
+
+        let mut tickets = Vec::with_capacity(quantity as usize);
+        let promo_code =
+            Self::redeem_promo(&mut tx, promo, None, Some(packet_id), quantity).await?;
+        for cod in codes {
+            let mut sold_at =
+                PriceTierRepo::claim_tier(&mut tx, None, Some(packet_id), tier_id).await?;
+            if let Some(code) = &promo_code {
+                PromoCodeRepo::apply(code, &mut sold_at)?;
+            }
 
-        for (packet_id,) in package_ids {
-            let min_seats: Option<i32> = sqlx::query_scalar(
+            let ticket = sqlx::query_as::<_, Ticket>(&format!(
                 r#"
-                SELECT MIN(e.numarlocuri)
-                FROM EVENIMENTE e
-                JOIN JOIN_PE j ON e.id = j.evenimentid
-                WHERE j.pachetid = $1
+                INSERT INTO BILETE
+                    (cod, pachetid, evenimentid, tier_id, price_minor, currency, promo_code_id, discount_minor)
+                VALUES ($1, $2, NULL, $3, $4, $5, $6, $7)
+                RETURNING {}
                 "#,
-            )
+                TICKET_COLUMNS
+            ))
+            .bind(&cod)
             .bind(packet_id)
-            .fetch_optional(&mut *tx)
+            .bind(sold_at.tier_id)
+            .bind(sold_at.price_minor)
+            .bind(&sold_at.currency)
+            .bind(sold_at.promo_code_id)
+            .bind(sold_at.discount_minor)
+            .fetch_one(&mut *tx)
             .await
-            .map_err(TicketRepoError::InternalError)?
-            .flatten();
+            .map_err(map_sqlx_ticket_error)?;
 
-            sqlx::query("UPDATE PACHETE SET numarlocuri = $1 WHERE id = $2")
-                .bind(min_seats)
-                .bind(packet_id)
-                .execute(&mut *tx)
-                .await
-                .map_err(TicketRepoError::InternalError)?;
+            if let Some(promo) = promo {
+                PromoCodeRepo::record_redemption(&mut tx, &ticket, promo.buyer_id).await?;
+            }
+
+            tickets.push(ticket);
         }
 
         tx.commit().await.map_err(TicketRepoError::InternalError)?;
 
-        Ok(ticket)
+        Ok(tickets)
     }
 
-    pub async fn create_ticket_with_code_for_packet(
-        &self,
-        cod: String,
-        packet_id: i32,
-    ) -> Result<Ticket, TicketRepoError> {
-        let mut tx = self
-            .pool
-            .begin()
-            .await
-            .map_err(TicketRepoError::InternalError)?;
+    async fn redeem_promo(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        promo: Option<&PromoApplication>,
+        event_id: Option<i32>,
+        packet_id: Option<i32>,
+        quantity: i32,
+    ) -> Result<Option<PromoCode>, TicketRepoError> {
+        match promo {
+            Some(promo) => PromoCodeRepo::redeem(tx, promo, event_id, packet_id, quantity)
+                .await
+                .map(Some),
+            None => Ok(None),
+        }
+    }
 
-        let events: Vec<(i32,)> = sqlx::query_as(
-            r#"
-            SELECT e.id
-            FROM EVENIMENTE e
-            JOIN JOIN_PE j ON e.id = j.evenimentid
-            WHERE j.pachetid = $1
-            "#,
+    pub(crate) async fn reserve_event_seats(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: i32,
+        seats: i32,
+    ) -> Result<(), TicketRepoError> {
+        let seats_result: Option<i32> = sqlx::query_scalar(
+            "UPDATE EVENIMENTE
+             SET numarlocuri = numarlocuri - $2
+             WHERE id = $1 AND numarlocuri >= $2
+               AND status = 'PUBLISHED'
+               AND (ends_at IS NULL OR ends_at > NOW())
+             RETURNING numarlocuri",
         )
-        .bind(packet_id)
-        .fetch_all(&mut *tx)
+        .bind(event_id)
+        .bind(seats)
+        .fetch_optional(&mut **tx)
         .await
         .map_err(TicketRepoError::InternalError)?;
 
-        if events.is_empty() {
-            return Err(TicketRepoError::InvalidReference);
+        if seats_result.is_some() {
+            return Ok(());
         }
 
-        for (event_id,) in &events {
-            let result: Option<i32> = sqlx::query_scalar(
-                "UPDATE EVENIMENTE
-                 SET numarlocuri = numarlocuri - 1
-                 WHERE id = $1 AND numarlocuri > 0
-                 RETURNING numarlocuri",
-            )
-            .bind(event_id)
-            .fetch_optional(&mut *tx)
-            .await
-            .map_err(TicketRepoError::InternalError)?;
-
-            if result.is_none() {
-                return Err(TicketRepoError::NoSeatsAvailable);
-            }
-        }
+        Self::ensure_event_on_sale(tx, event_id).await?;
+        Err(TicketRepoError::NoSeatsAvailable)
+    }
 
-        let all_affected_packages: Vec<(i32,)> = sqlx::query_as(
+    /// Codes of the tickets a cancellation refunds: those not checked in and
+    /// without a refund request already pending.
+    pub(crate) async fn refundable_codes(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Option<i32>,
+        packet_id: Option<i32>,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
             r#"
-            SELECT DISTINCT j2.pachetid
-            FROM JOIN_PE j1
-            JOIN JOIN_PE j2 ON j1.evenimentid = j2.evenimentid
-            WHERE j1.pachetid = $1
+            SELECT b.cod
+            FROM BILETE b
+            WHERE (b.evenimentid = $1 OR b.pachetid = $2)
+              AND b.checked_in_at IS NULL
+              AND NOT EXISTS (
+                  SELECT 1 FROM REFUND_REQUESTS r
+                  WHERE r.ticket_cod = b.cod AND r.status = 'PENDING'
+              )
+            ORDER BY b.cod
             "#,
         )
+        .bind(event_id)
         .bind(packet_id)
-        .fetch_all(&mut *tx)
+        .fetch_all(&mut **tx)
         .await
-        .map_err(TicketRepoError::InternalError)?;
+    }
 
-        for (affected_packet_id,) in all_affected_packages {
-            let min_seats: Option<i32> = sqlx::query_scalar(
-                r#"
+    /// Caps every packet holding one of these events at the seats left on its
+    /// smallest event, after seats on them were taken or given back.
+    pub(crate) async fn sync_packet_capacity(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_ids: &[i32],
+    ) -> Result<(), TicketRepoError> {
+        sqlx::query(
+            r#"
+            UPDATE PACHETE p
+            SET numarlocuri = (
                 SELECT MIN(e.numarlocuri)
                 FROM EVENIMENTE e
                 JOIN JOIN_PE j ON e.id = j.evenimentid
-                WHERE j.pachetid = $1
-                "#,
+                WHERE j.pachetid = p.id
             )
-            .bind(affected_packet_id)
-            .fetch_optional(&mut *tx)
-            .await
-            .map_err(TicketRepoError::InternalError)?
-            .flatten();
+            WHERE p.id IN (SELECT pachetid FROM JOIN_PE WHERE evenimentid = ANY($1))
+            "#,
+        )
+        .bind(event_ids)
+        .execute(&mut **tx)
+        .await
+        .map_err(TicketRepoError::InternalError)?;
 
-            sqlx::query("UPDATE PACHETE SET numarlocuri = $1 WHERE id = $2")
-                .bind(min_seats)
-                .bind(affected_packet_id)
-                .execute(&mut *tx)
-                .await
-                .map_err(TicketRepoError::InternalError)?;
-        }
+        Ok(())
+    }
 
-        let ticket = sqlx::query_as::<_, Ticket>(
-            r#"
-            INSERT INTO BILETE (cod, pachetid, evenimentid)
-            VALUES ($1, $2, NULL)
-            RETURNING cod, pachetid, evenimentid
-            "#,
+    async fn ensure_event_on_sale(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: i32,
+    ) -> Result<(), TicketRepoError> {
+        let state: Option<(EventStatus, bool)> = sqlx::query_as(
+            "SELECT status, COALESCE(ends_at <= NOW(), FALSE) FROM EVENIMENTE WHERE id = $1",
         )
-        .bind(&cod)
-        .bind(packet_id)
-        .fetch_one(&mut *tx)
+        .bind(event_id)
+        .fetch_optional(&mut **tx)
         .await
-        .map_err(map_sqlx_ticket_error)?;
+        .map_err(TicketRepoError::InternalError)?;
 
-        tx.commit().await.map_err(TicketRepoError::InternalError)?;
+        match state {
+            None => Err(TicketRepoError::InvalidReference),
+            Some((status, _)) if status != EventStatus::Published => {
+                Err(TicketRepoError::NotOnSale)
+            }
+            Some((_, true)) => Err(TicketRepoError::EventEnded),
+            Some((_, false)) => Ok(()),
+        }
+    }
 
-        Ok(ticket)
+    pub(crate) async fn ensure_packet_on_sale(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        packet_id: i32,
+    ) -> Result<(), TicketRepoError> {
+        let status: Option<EventStatus> =
+            sqlx::query_scalar("SELECT status FROM PACHETE WHERE id = $1 FOR SHARE")
+                .bind(packet_id)
+                .fetch_optional(&mut **tx)
+                .await
+                .map_err(TicketRepoError::InternalError)?;
+
+        match status {
+            None => Err(TicketRepoError::InvalidReference),
+            Some(EventStatus::Published) => Ok(()),
+            Some(_) => Err(TicketRepoError::NotOnSale),
+        }
     }
 }
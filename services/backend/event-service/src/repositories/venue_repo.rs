@@ -0,0 +1,253 @@
+use crate::models::venue::{
+    CreateVenue, CreateVenueRoom, UpdateVenue, UpdateVenueRoom, Venue, VenueQuery, VenueRoom,
+};
+use crate::utils::error::{VenueRepoError, map_sqlx_venue_error};
+use anyhow::Result;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+pub(crate) const VENUE_COLUMNS: &str =
+    "id, id_owner, name, address, city, country, latitude, longitude, created_at";
+
+const ROOM_COLUMNS: &str = "id, venue_id, name, capacity";
+
+pub struct VenueRepo {
+    pool: PgPool,
+}
+
+impl VenueRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_venues(&self, params: VenueQuery) -> Result<Vec<Venue>, VenueRepoError> {
+        let mut query: QueryBuilder<Postgres> =
+            QueryBuilder::new(format!("SELECT {} FROM VENUES WHERE TRUE", VENUE_COLUMNS));
+
+        if let Some(city) = params.city {
+            query.push(" AND city ILIKE ");
+            query.push_bind(city);
+        }
+        if let Some(country) = params.country {
+            query.push(" AND country = ");
+            query.push_bind(country);
+        }
+        query.push(" ORDER BY name ASC");
+
+        let mut venues = query
+            .build_query_as::<Venue>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx_venue_error)?;
+
+        let venue_ids: Vec<i32> = venues.iter().map(|v| v.id).collect();
+        let rooms = sqlx::query_as::<_, VenueRoom>(&format!(
+            "SELECT {} FROM VENUE_ROOMS WHERE venue_id = ANY($1) ORDER BY name ASC",
+            ROOM_COLUMNS
+        ))
+        .bind(&venue_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_venue_error)?;
+
+        for room in rooms {
+            if let Some(venue) = venues.iter_mut().find(|v| v.id == room.venue_id) {
+                venue.rooms.push(room);
+            }
+        }
+
+        Ok(venues)
+    }
+
+    pub async fn get_venue(&self, venue_id: i32) -> Result<Venue, VenueRepoError> {
+        let mut venue = sqlx::query_as::<_, Venue>(&format!(
+            "SELECT {} FROM VENUES WHERE id = $1",
+            VENUE_COLUMNS
+        ))
+        .bind(venue_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_venue_error)?;
+
+        venue.rooms = self.list_rooms(venue_id).await?;
+        Ok(venue)
+    }
+
+    pub async fn create_venue(
+        &self,
+        id_owner: i32,
+        payload: CreateVenue,
+    ) -> Result<Venue, VenueRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(VenueRepoError::InternalError)?;
+
+        let mut venue = sqlx::query_as::<_, Venue>(&format!(
+            r#"
+            INSERT INTO VENUES (id_owner, name, address, city, country, latitude, longitude)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING {}
+            "#,
+            VENUE_COLUMNS
+        ))
+        .bind(id_owner)
+        .bind(&payload.name)
+        .bind(&payload.address)
+        .bind(&payload.city)
+        .bind(&payload.country)
+        .bind(payload.latitude)
+        .bind(payload.longitude)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_sqlx_venue_error)?;
+
+        for room in payload.rooms {
+            let room = sqlx::query_as::<_, VenueRoom>(&format!(
+                "INSERT INTO VENUE_ROOMS (venue_id, name, capacity) VALUES ($1, $2, $3) RETURNING {}",
+                ROOM_COLUMNS
+            ))
+            .bind(venue.id)
+            .bind(&room.name)
+            .bind(room.capacity)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(map_sqlx_venue_error)?;
+
+            venue.rooms.push(room);
+        }
+
+        tx.commit().await.map_err(VenueRepoError::InternalError)?;
+
+        Ok(venue)
+    }
+
+    pub async fn update_venue(
+        &self,
+        venue_id: i32,
+        payload: UpdateVenue,
+    ) -> Result<Venue, VenueRepoError> {
+        let mut venue = sqlx::query_as::<_, Venue>(&format!(
+            r#"
+            UPDATE VENUES
+            SET
+                name = COALESCE($1, name),
+                address = COALESCE($2, address),
+                city = COALESCE($3, city),
+                country = COALESCE($4, country),
+                latitude = COALESCE($5, latitude),
+                longitude = COALESCE($6, longitude)
+            WHERE id = $7
+            RETURNING {}
+            "#,
+            VENUE_COLUMNS
+        ))
+        .bind(payload.name.as_deref())
+        .bind(payload.address.as_deref())
+        .bind(payload.city.as_deref())
+        .bind(payload.country.as_deref())
+        .bind(payload.latitude)
+        .bind(payload.longitude)
+        .bind(venue_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_venue_error)?;
+
+        venue.rooms = self.list_rooms(venue_id).await?;
+        Ok(venue)
+    }
+
+    pub async fn delete_venue(&self, venue_id: i32) -> Result<(), VenueRepoError> {
+        let result = sqlx::query("DELETE FROM VENUES WHERE id = $1")
+            .bind(venue_id)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_venue_error)?;
+
+        if result.rows_affected() == 0 {
+            Err(VenueRepoError::NotFound)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub async fn list_rooms(&self, venue_id: i32) -> Result<Vec<VenueRoom>, VenueRepoError> {
+        sqlx::query_as::<_, VenueRoom>(&format!(
+            "SELECT {} FROM VENUE_ROOMS WHERE venue_id = $1 ORDER BY name ASC",
+            ROOM_COLUMNS
+        ))
+        .bind(venue_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_venue_error)
+    }
+
+    pub async fn get_room(&self, room_id: i32) -> Result<VenueRoom, VenueRepoError> {
+        sqlx::query_as::<_, VenueRoom>(&format!(
+            "SELECT {} FROM VENUE_ROOMS WHERE id = $1",
+            ROOM_COLUMNS
+        ))
+        .bind(room_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_venue_error)
+    }
+
+    pub async fn create_room(
+        &self,
+        venue_id: i32,
+        payload: CreateVenueRoom,
+    ) -> Result<VenueRoom, VenueRepoError> {
+        sqlx::query_as::<_, VenueRoom>(&format!(
+            "INSERT INTO VENUE_ROOMS (venue_id, name, capacity) VALUES ($1, $2, $3) RETURNING {}",
+            ROOM_COLUMNS
+        ))
+        .bind(venue_id)
+        .bind(&payload.name)
+        .bind(payload.capacity)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_venue_error)
+    }
+
+    pub async fn update_room(
+        &self,
+        venue_id: i32,
+        room_id: i32,
+        payload: UpdateVenueRoom,
+    ) -> Result<VenueRoom, VenueRepoError> {
+        sqlx::query_as::<_, VenueRoom>(&format!(
+            r#"
+            UPDATE VENUE_ROOMS
+            SET
+                name = COALESCE($1, name),
+                capacity = COALESCE($2, capacity)
+            WHERE id = $3 AND venue_id = $4
+            RETURNING {}
+            "#,
+            ROOM_COLUMNS
+        ))
+        .bind(payload.name.as_deref())
+        .bind(payload.capacity)
+        .bind(room_id)
+        .bind(venue_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_venue_error)
+    }
+
+    pub async fn delete_room(&self, venue_id: i32, room_id: i32) -> Result<(), VenueRepoError> {
+        let result = sqlx::query("DELETE FROM VENUE_ROOMS WHERE id = $1 AND venue_id = $2")
+            .bind(room_id)
+            .bind(venue_id)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_venue_error)?;
+
+        if result.rows_affected() == 0 {
+            Err(VenueRepoError::NotFound)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use crate::models::waitlist::{
+    JoinWaitlist, WAITLIST_OFFER_TTL_SECONDS, WaitlistEntry, WaitlistOffer,
+};
+use crate::repositories::seat_hold_repo::SeatHoldRepo;
+use crate::utils::error::{TicketRepoError, WaitlistRepoError, map_sqlx_waitlist_error};
+use anyhow::Result;
+use sqlx::PgPool;
+
+pub(crate) const WAITLIST_COLUMNS: &str =
+    "id, event_id, packet_id, user_id, email, status, hold_id, offer_expires_at, created_at";
+
+pub struct WaitlistRepo {
+    pool: PgPool,
+}
+
+impl WaitlistRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn join_event_waitlist(
+        &self,
+        event_id: i32,
+        payload: JoinWaitlist,
+    ) -> Result<WaitlistEntry, WaitlistRepoError> {
+        self.join(Some(event_id), None, payload).await
+    }
+
+    pub async fn join_packet_waitlist(
+        &self,
+        packet_id: i32,
+        payload: JoinWaitlist,
+    ) -> Result<WaitlistEntry, WaitlistRepoError> {
+        self.join(None, Some(packet_id), payload).await
+    }
+
+    async fn join(
+        &self,
+        event_id: Option<i32>,
+        packet_id: Option<i32>,
+        payload: JoinWaitlist,
+    ) -> Result<WaitlistEntry, WaitlistRepoError> {
+        sqlx::query_as::<_, WaitlistEntry>(&format!(
+            r#"
+            INSERT INTO WAITLIST (event_id, packet_id, user_id, email)
+            VALUES ($1, $2, $3, $4)
+            RETURNING {}
+            "#,
+            WAITLIST_COLUMNS
+        ))
+        .bind(event_id)
+        .bind(packet_id)
+        .bind(payload.user_id)
+        .bind(payload.email)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_waitlist_error)
+    }
+
+    pub async fn get_entry(&self, id: i32) -> Result<WaitlistEntry, WaitlistRepoError> {
+        sqlx::query_as::<_, WaitlistEntry>(&format!(
+            "SELECT {} FROM WAITLIST WHERE id = $1",
+            WAITLIST_COLUMNS
+        ))
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_waitlist_error)
+    }
+
+    pub async fn leave(&self, id: i32) -> Result<WaitlistEntry, WaitlistRepoError> {
+        sqlx::query_as::<_, WaitlistEntry>(&format!(
+            "DELETE FROM WAITLIST WHERE id = $1 RETURNING {}",
+            WAITLIST_COLUMNS
+        ))
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_waitlist_error)
+    }
+
+    /// Lists the events and packets with people still waiting whose seat counts
+    /// depend on the given event or packet, i.e. the ones worth promoting after
+    /// a seat there was given back.
+    pub async fn waiting_targets(
+        &self,
+        event_id: Option<i32>,
+        packet_id: Option<i32>,
+    ) -> Result<Vec<(Option<i32>, Option<i32>)>, WaitlistRepoError> {
+        sqlx::query_as(
+            r#"
+            WITH freed AS (
+                SELECT $1::INTEGER AS evenimentid WHERE $1 IS NOT NULL
+                UNION
+                SELECT evenimentid FROM JOIN_PE WHERE pachetid = $2
+            )
+            SELECT DISTINCT w.event_id, w.packet_id
+            FROM WAITLIST w
+            WHERE w.status = 'WAITING'
+              AND (
+                w.event_id IN (SELECT evenimentid FROM freed)
+                OR w.packet_id IN (
+                    SELECT j.pachetid FROM JOIN_PE j JOIN freed f ON f.evenimentid = j.evenimentid
+                )
+              )
+            "#,
+        )
+        .bind(event_id)
+        .bind(packet_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(WaitlistRepoError::InternalError)
+    }
+
+    /// Hands the longest-waiting entry of an event or packet a one-seat hold.
+    /// Returns `None` when nobody is waiting or there is no seat to offer.
+    pub async fn offer_next(
+        &self,
+        event_id: Option<i32>,
+        packet_id: Option<i32>,
+    ) -> Result<Option<WaitlistOffer>, WaitlistRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(WaitlistRepoError::InternalError)?;
+
+        let entry = sqlx::query_as::<_, WaitlistEntry>(&format!(
+            r#"
+            SELECT {} FROM WAITLIST
+            WHERE event_id IS NOT DISTINCT FROM $1
+              AND packet_id IS NOT DISTINCT FROM $2
+              AND status = 'WAITING'
+            ORDER BY created_at, id
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+            "#,
+            WAITLIST_COLUMNS
+        ))
+        .bind(event_id)
+        .bind(packet_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(WaitlistRepoError::InternalError)?;
+
+        let Some(entry) = entry else {
+            return Ok(None);
+        };
+
+        let hold = match (event_id, packet_id) {
+            (Some(event_id), _) => {
+                SeatHoldRepo::hold_event_seats(
+                    &mut tx,
+                    event_id,
+                    entry.user_id,
+                    1,
+                    WAITLIST_OFFER_TTL_SECONDS,
+                )
+                .await
+            }
+            (None, Some(packet_id)) => {
+                SeatHoldRepo::hold_packet_seats(
+                    &mut tx,
+                    packet_id,
+                    entry.user_id,
+                    1,
+                    WAITLIST_OFFER_TTL_SECONDS,
+                )
+                .await
+            }
+            (None, None) => return Ok(None),
+        };
+
+        let hold = match hold {
+            Ok(hold) => hold,
+            Err(TicketRepoError::InternalError(e)) => {
+                return Err(WaitlistRepoError::InternalError(e));
+            }
+            Err(_) => return Ok(None),
+        };
+
+        let entry = sqlx::query_as::<_, WaitlistEntry>(&format!(
+            r#"
+            UPDATE WAITLIST
+            SET status = 'OFFERED', hold_id = $2, offer_expires_at = $3
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            WAITLIST_COLUMNS
+        ))
+        .bind(entry.id)
+        .bind(&hold.id)
+        .bind(hold.expires_at)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(WaitlistRepoError::InternalError)?;
+
+        let name: String = match (event_id, packet_id) {
+            (Some(event_id), _) => sqlx::query_scalar("SELECT nume FROM EVENIMENTE WHERE id = $1")
+                .bind(event_id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(WaitlistRepoError::InternalError)?,
+            _ => sqlx::query_scalar("SELECT nume FROM PACHETE WHERE id = $1")
+                .bind(packet_id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(WaitlistRepoError::InternalError)?,
+        };
+
+        tx.commit()
+            .await
+            .map_err(WaitlistRepoError::InternalError)?;
+
+        Ok(Some(WaitlistOffer { entry, name }))
+    }
+}
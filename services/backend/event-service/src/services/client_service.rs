@@ -0,0 +1,56 @@
+use crate::models::export::TicketBuyer;
+use crate::utils::error::ExportError;
+use reqwest::Client;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct TicketBuyersRequest<'a> {
+    codes: &'a [String],
+}
+
+/// HTTP client for the buyer lookups of *client-service*, which only answers
+/// them for the service account.
+pub struct ClientServiceClient {
+    base_url: String,
+    service_token: String,
+    http_client: Client,
+}
+
+impl ClientServiceClient {
+    pub fn new(base_url: String, service_token: String) -> Self {
+        Self {
+            base_url,
+            service_token,
+            http_client: Client::new(),
+        }
+    }
+
+    /// Looks up the buyers of up to 1000 tickets at once. Callers check that
+    /// the tickets belong to an event or packet the user may export.
+    pub async fn ticket_buyers(&self, codes: &[String]) -> Result<Vec<TicketBuyer>, ExportError> {
+        let response = self
+            .http_client
+            .post(format!(
+                "{}/api/client-manager/clients/data/batch",
+                self.base_url
+            ))
+            .bearer_auth(&self.service_token)
+            .json(&TicketBuyersRequest { codes })
+            .send()
+            .await
+            .map_err(|e| {
+                ExportError::ClientService(format!("Failed to connect to client-service: {}", e))
+            })?;
+
+        if !response.status().is_success() {
+            return Err(ExportError::ClientService(format!(
+                "client-service answered {}",
+                response.status()
+            )));
+        }
+
+        response.json().await.map_err(|e| {
+            ExportError::ClientService(format!("Invalid response from client-service: {}", e))
+        })
+    }
+}
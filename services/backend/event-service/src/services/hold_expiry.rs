@@ -0,0 +1,56 @@
+use crate::repositories::seat_hold_repo::SeatHoldRepo;
+use crate::repositories::waitlist_repo::WaitlistRepo;
+use crate::services::waitlist::promote_waitlist;
+use common::rabbitmq::RabbitMQ;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+
+const SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+
+pub struct HoldExpiryWorker {
+    rabbitmq: Arc<RabbitMQ>,
+    seat_hold_repo: Arc<SeatHoldRepo>,
+    waitlist_repo: Arc<WaitlistRepo>,
+}
+
+impl HoldExpiryWorker {
+    pub fn new(
+        rabbitmq: Arc<RabbitMQ>,
+        seat_hold_repo: Arc<SeatHoldRepo>,
+        waitlist_repo: Arc<WaitlistRepo>,
+    ) -> Self {
+        Self {
+            rabbitmq,
+            seat_hold_repo,
+            waitlist_repo,
+        }
+    }
+
+    pub async fn start(&self) {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+
+        info!("Started releasing expired seat holds");
+
+        loop {
+            interval.tick().await;
+
+            match self.seat_hold_repo.release_expired_holds().await {
+                Ok(released) if released.is_empty() => {}
+                Ok(released) => {
+                    info!("Released {} expired seat hold(s)", released.len());
+                    for hold in released {
+                        promote_waitlist(
+                            &self.rabbitmq,
+                            &self.waitlist_repo,
+                            hold.event_id,
+                            hold.packet_id,
+                        )
+                        .await;
+                    }
+                }
+                Err(e) => error!("Failed to release expired seat holds: {:?}", e),
+            }
+        }
+    }
+}
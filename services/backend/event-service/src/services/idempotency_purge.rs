@@ -0,0 +1,32 @@
+use crate::repositories::idempotency_repo::IdempotencyRepo;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub struct IdempotencyPurgeWorker {
+    idempotency_repo: Arc<IdempotencyRepo>,
+}
+
+impl IdempotencyPurgeWorker {
+    pub fn new(idempotency_repo: Arc<IdempotencyRepo>) -> Self {
+        Self { idempotency_repo }
+    }
+
+    pub async fn start(&self) {
+        let mut interval = tokio::time::interval(PURGE_INTERVAL);
+
+        info!("Started purging expired idempotency keys");
+
+        loop {
+            interval.tick().await;
+
+            match self.idempotency_repo.purge_expired().await {
+                Ok(0) => {}
+                Ok(purged) => info!("Purged {} expired idempotency key(s)", purged),
+                Err(e) => error!("Failed to purge expired idempotency keys: {:?}", e),
+            }
+        }
+    }
+}
@@ -1 +1,6 @@
+pub mod client_service;
+pub mod hold_expiry;
+pub mod idempotency_purge;
+pub mod outbox_relay;
 pub mod refund_consumer;
+pub mod waitlist;
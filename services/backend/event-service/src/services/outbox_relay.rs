@@ -0,0 +1,47 @@
+use crate::repositories::outbox_repo::OutboxRepo;
+use common::rabbitmq::RabbitMQ;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+
+const RELAY_INTERVAL: Duration = Duration::from_secs(2);
+const RELAY_BATCH_SIZE: i64 = 100;
+
+pub struct OutboxRelay {
+    rabbitmq: Arc<RabbitMQ>,
+    outbox_repo: Arc<OutboxRepo>,
+}
+
+impl OutboxRelay {
+    pub fn new(rabbitmq: Arc<RabbitMQ>, outbox_repo: Arc<OutboxRepo>) -> Self {
+        Self {
+            rabbitmq,
+            outbox_repo,
+        }
+    }
+
+    pub async fn start(&self) {
+        let mut interval = tokio::time::interval(RELAY_INTERVAL);
+
+        info!("Started relaying outbox messages");
+
+        loop {
+            interval.tick().await;
+
+            loop {
+                match self
+                    .outbox_repo
+                    .relay_pending(&self.rabbitmq, RELAY_BATCH_SIZE)
+                    .await
+                {
+                    Ok(sent) if sent as i64 == RELAY_BATCH_SIZE => continue,
+                    Ok(_) => break,
+                    Err(e) => {
+                        error!("Failed to relay outbox messages: {:?}", e);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
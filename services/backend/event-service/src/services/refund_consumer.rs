@@ -1,4 +1,6 @@
 use crate::repositories::refund_repo::RefundRepo;
+use crate::repositories::ticket_repo::TicketRepo;
+use crate::utils::error::TicketRepoError;
 use anyhow::{Context, Result};
 use common::rabbitmq::RabbitMQ;
 use common::rabbitmq::messages::{
@@ -15,13 +17,19 @@ use tracing::{error, info, warn};
 pub struct RefundRequestConsumer {
     rabbitmq: Arc<RabbitMQ>,
     refund_repo: Arc<RefundRepo>,
+    ticket_repo: Arc<TicketRepo>,
 }
 
 impl RefundRequestConsumer {
-    pub fn new(rabbitmq: Arc<RabbitMQ>, refund_repo: Arc<RefundRepo>) -> Self {
+    pub fn new(
+        rabbitmq: Arc<RabbitMQ>,
+        refund_repo: Arc<RefundRepo>,
+        ticket_repo: Arc<TicketRepo>,
+    ) -> Self {
         Self {
             rabbitmq,
             refund_repo,
+            ticket_repo,
         }
     }
 
@@ -109,6 +117,10 @@ impl RefundRequestConsumer {
         refund_repo: &RefundRepo,
         message: &RefundRequested,
     ) -> Result<()> {
+        if message.auto_approve {
+            return self.process_automatic_refund(refund_repo, message).await;
+        }
+
         let created_refund = refund_repo
             .create_refund_request(
                 &message.ticket_cod,
@@ -122,6 +134,14 @@ impl RefundRequestConsumer {
             .await
             .map_err(|e| anyhow::anyhow!("Failed to create refund request: {:?}", e))?;
 
+        if created_refund.status == "REJECTED" {
+            info!(
+                "Rejected refund request {} because ticket {} was already checked in",
+                created_refund.id, created_refund.ticket_cod
+            );
+            return Ok(());
+        }
+
         let ws_message_owner = WebSocketMessage::NewRefundRequest(NewRefundRequest {
             request_id: created_refund.id,
             ticket_cod: created_refund.ticket_cod.clone(),
@@ -175,4 +195,34 @@ impl RefundRequestConsumer {
 
         Ok(())
     }
+
+    async fn process_automatic_refund(
+        &self,
+        refund_repo: &RefundRepo,
+        message: &RefundRequested,
+    ) -> Result<()> {
+        match self.ticket_repo.get_ticket(&message.ticket_cod).await {
+            Ok(_) => {}
+            Err(TicketRepoError::NotFound) => {
+                info!(
+                    "Ticket {} is already gone, skipping automatic refund",
+                    message.ticket_cod
+                );
+                return Ok(());
+            }
+            Err(e) => return Err(anyhow::anyhow!("Failed to look up ticket: {:?}", e)),
+        }
+
+        let refund = refund_repo
+            .auto_approve_refund(message)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to approve refund request: {:?}", e))?;
+
+        info!(
+            "Resolved automatic refund request {} for ticket {} as {}",
+            refund.id, refund.ticket_cod, refund.status
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,88 @@
+use crate::models::waitlist::WaitlistOffer;
+use crate::repositories::waitlist_repo::WaitlistRepo;
+use common::rabbitmq::RabbitMQ;
+use common::rabbitmq::messages::{ROUTING_KEY_WAITLIST_OFFER, WaitlistOfferMade};
+use common::websocket::messages::{
+    ROUTING_KEY_WS_BROADCAST, WaitlistOffer as WsWaitlistOffer, WebSocketMessage,
+};
+use tracing::{error, info};
+
+/// Offers seats to waitlisted users after a seat on the given event or packet
+/// was given back. Every event and packet sharing that seat is considered, and
+/// each keeps offering until its line is empty or it runs out of seats.
+pub async fn promote_waitlist(
+    rabbitmq: &RabbitMQ,
+    waitlist_repo: &WaitlistRepo,
+    event_id: Option<i32>,
+    packet_id: Option<i32>,
+) {
+    let targets = match waitlist_repo.waiting_targets(event_id, packet_id).await {
+        Ok(targets) => targets,
+        Err(e) => {
+            error!("Failed to look up waitlists to promote: {:?}", e);
+            return;
+        }
+    };
+
+    for (event_id, packet_id) in targets {
+        loop {
+            match waitlist_repo.offer_next(event_id, packet_id).await {
+                Ok(Some(offer)) => announce_offer(rabbitmq, &offer).await,
+                Ok(None) => break,
+                Err(e) => {
+                    error!("Failed to offer a waitlisted seat: {:?}", e);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+async fn announce_offer(rabbitmq: &RabbitMQ, offer: &WaitlistOffer) {
+    let entry = &offer.entry;
+    let hold_id = entry.hold_id.clone().unwrap_or_default();
+    let expires_at = entry
+        .offer_expires_at
+        .map(|at| at.to_rfc3339())
+        .unwrap_or_default();
+
+    let message = WaitlistOfferMade {
+        entry_id: entry.id,
+        user_id: entry.user_id,
+        email: entry.email.clone(),
+        event_id: entry.event_id,
+        packet_id: entry.packet_id,
+        name: offer.name.clone(),
+        hold_id: hold_id.clone(),
+        expires_at: expires_at.clone(),
+    };
+
+    if let Ok(json) = serde_json::to_vec(&message) {
+        if let Err(e) = rabbitmq.publish(ROUTING_KEY_WAITLIST_OFFER, &json).await {
+            error!("Failed to publish waitlist.offer message: {:?}", e);
+        } else {
+            info!("Published waitlist.offer for entry {}", entry.id);
+        }
+    }
+
+    let ws_message = WebSocketMessage::WaitlistOffer(WsWaitlistOffer {
+        entry_id: entry.id,
+        event_id: entry.event_id,
+        packet_id: entry.packet_id,
+        name: offer.name.clone(),
+        hold_id,
+        expires_at,
+        user_id: entry.user_id,
+    });
+
+    if let Ok(json) = serde_json::to_vec(&ws_message) {
+        if let Err(e) = rabbitmq.publish(ROUTING_KEY_WS_BROADCAST, &json).await {
+            error!("Failed to publish WebSocket message: {:?}", e);
+        } else {
+            info!(
+                "Published WebSocket notification for waitlist entry {}",
+                entry.id
+            );
+        }
+    }
+}
@@ -1,5 +1,23 @@
-use crate::handlers::{event::*, event_packets::*, join_pe::*, ticket::*};
-use crate::models::{event::Event, event_packets::EventPackets, ticket::Ticket};
+use crate::handlers::{
+    analytics::*, calendar::*, check_in::*, event::*, event_packets::*, export::*, import::*,
+    join_pe::*, price_tier::*, promo_code::*, search::*, seat::*, seat_hold::*, ticket::*,
+    venue::*, waitlist::*,
+};
+use crate::models::{
+    analytics::{Granularity, SalesBucket, SalesSummary, SellThrough, TierRevenue},
+    check_in::{AddScanner, Attendance, CheckIn, CheckInRequest, EventScanner, GateAttendance},
+    event::{Event, EventCategory, EventStatus, FacetCount, Facets},
+    event_packets::EventPackets,
+    import::{ImportPacket, ImportReport, ImportRequest, ImportRowKind, ImportRowResult},
+    price_tier::{CreatePriceTier, PriceTier, UpdatePriceTier},
+    promo_code::{CreatePromoCode, DiscountType, PromoCode, PromoCodeStats, UpdatePromoCode},
+    search::{SearchHit, SearchHitKind},
+    seat::{EventSeat, RoomSeat, SeatMap, SeatMapRow, SeatMapSection, SectionAvailability},
+    seat_hold::{CreateSeatHold, SeatHold},
+    ticket::{BatchPurchaseOptions, Ticket, TicketCalendarRequest},
+    venue::{CreateVenue, CreateVenueRoom, UpdateVenue, UpdateVenueRoom, Venue, VenueRoom},
+    waitlist::{JoinWaitlist, WaitlistEntry, WaitlistStatus},
+};
 use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::{Modify, OpenApi};
 
@@ -13,6 +31,11 @@ use utoipa::{Modify, OpenApi};
         patch_event,
         delete_event,
         list_events,
+        list_owned_events,
+        list_event_facets,
+        publish_event,
+        close_event_sales,
+        cancel_event,
 
 
         create_event_packet,
@@ -21,6 +44,11 @@ use utoipa::{Modify, OpenApi};
         patch_event_packet,
         delete_event_packet,
         list_event_packets,
+        list_owned_event_packets,
+        list_event_packet_facets,
+        publish_event_packet,
+        close_event_packet_sales,
+        cancel_event_packet,
 
 
         get_ticket,
@@ -28,10 +56,12 @@ use utoipa::{Modify, OpenApi};
         delete_ticket,
         list_tickets,
         create_ticket_for_event,
+        create_tickets_for_event,
         get_ticket_for_event,
         delete_ticket_for_event,
         list_tickets_for_packet,
         create_ticket_for_packet,
+        create_tickets_for_packet,
         get_ticket_for_packet,
         delete_ticket_for_packet,
 
@@ -39,15 +69,97 @@ use utoipa::{Modify, OpenApi};
         add_event_to_packet,
         remove_event_from_packet,
         list_events_for_packet,
-        list_packets_for_event
+        list_packets_for_event,
+
+
+        list_price_tiers_for_event,
+        list_price_tiers_for_packet,
+        get_price_tier,
+        create_price_tier_for_event,
+        create_price_tier_for_packet,
+        update_price_tier,
+        delete_price_tier,
+
+
+        create_hold_for_event,
+        create_hold_for_packet,
+        get_hold,
+        release_hold,
+
+
+        join_event_waitlist,
+        join_packet_waitlist,
+        get_waitlist_entry,
+        leave_waitlist,
+
+
+        check_in_ticket,
+        get_attendance,
+        list_scanners,
+        add_scanner,
+        remove_scanner,
+
+        list_promo_codes_for_event,
+        list_promo_codes_for_packet,
+        create_promo_code_for_event,
+        create_promo_code_for_packet,
+        get_promo_code,
+        update_promo_code,
+        delete_promo_code,
+        get_promo_code_stats,
+
+        list_venues,
+        get_venue,
+        create_venue,
+        update_venue,
+        delete_venue,
+        create_room,
+        update_room,
+        delete_room,
+
+        get_seat_map,
+        replace_seat_map,
+        open_seat_inventory,
+        close_seat_inventory,
+        list_event_seats,
+        list_event_sections,
+
+        search,
+
+        get_event_calendar,
+        get_event_packet_calendar,
+        get_tickets_calendar,
+
+        get_summary,
+        get_sales,
+        get_event_sell_through,
+        get_packet_sell_through,
+        get_tier_revenue,
+
+        export_event_attendees,
+        export_event_packet_attendees,
+
+        import_catalog
     ),
-    components(schemas(Event, EventPackets, Ticket)),
+    components(schemas(Event, EventStatus, EventCategory, Facets, FacetCount, EventPackets, Ticket, BatchPurchaseOptions, PriceTier, CreatePriceTier, UpdatePriceTier, SeatHold, CreateSeatHold, WaitlistEntry, WaitlistStatus, JoinWaitlist, CheckIn, CheckInRequest, Attendance, GateAttendance, EventScanner, AddScanner, PromoCode, DiscountType, CreatePromoCode, UpdatePromoCode, PromoCodeStats, Venue, VenueRoom, CreateVenue, UpdateVenue, CreateVenueRoom, UpdateVenueRoom, RoomSeat, SeatMap, SeatMapSection, SeatMapRow, EventSeat, SectionAvailability, SearchHit, SearchHitKind, TicketCalendarRequest, Granularity, SalesSummary, SalesBucket, SellThrough, TierRevenue, ImportRequest, ImportPacket, ImportReport, ImportRowResult, ImportRowKind)),
     modifiers(&SecurityAddon),
     tags(
         (name = "events", description = "Event management endpoints"),
         (name = "event_packets", description = "Event packet management endpoints"),
         (name = "tickets", description = "Ticket management endpoints"),
-        (name = "joins", description = "Link events with packets")
+        (name = "joins", description = "Link events with packets"),
+        (name = "price_tiers", description = "Ticket price tiers for events and packets"),
+        (name = "holds", description = "Time-limited seat reservations before purchase"),
+        (name = "waitlist", description = "Queue for sold-out events and packets"),
+        (name = "Check-in", description = "Ticket scanning and attendance on event day"),
+        (name = "Promo codes", description = "Owner-managed discount codes and their redemptions"),
+        (name = "Venues", description = "Venues and rooms that events are held in"),
+        (name = "Seating", description = "Room seat maps and per-event seat inventory"),
+        (name = "Search", description = "Ranked full-text search over events and packets"),
+        (name = "Calendar", description = "iCalendar exports of events, packets and tickets"),
+        (name = "Analytics", description = "Sales, sell-through, refund and check-in figures for owners"),
+        (name = "Export", description = "Attendee lists for organizers as CSV or XLSX"),
+        (name = "Import", description = "Bulk creation of events and packets from CSV or JSON")
     )
 )]
 pub struct ApiDoc;
@@ -17,6 +17,18 @@ pub enum ApiError {
     Ticket(TicketRepoError),
     Join(JoinPeRepoError),
     Refund(RefundRepoError),
+    PriceTier(PriceTierRepoError),
+    SeatHold(SeatHoldRepoError),
+    Waitlist(WaitlistRepoError),
+    Idempotency(IdempotencyRepoError),
+    CheckIn(CheckInRepoError),
+    PromoCode(PromoCodeRepoError),
+    Venue(VenueRepoError),
+    Seat(SeatRepoError),
+    Search(SearchRepoError),
+    Analytics(AnalyticsRepoError),
+    Export(ExportError),
+    Import(ImportRepoError),
     Unauthorized(String),
     Forbidden(String),
     Conflict(String),
@@ -28,6 +40,7 @@ pub enum EventRepoError {
     InvalidReference,
     DuplicateEntry,
     ConstraintViolation(String),
+    InvalidCursor,
     InternalError(Error),
 }
 
@@ -36,6 +49,8 @@ pub enum EventPacketRepoError {
     NotFound,
     DuplicateName,
     InvalidEventId,
+    ConstraintViolation(String),
+    InvalidCursor,
     InternalError(Error),
 }
 
@@ -46,6 +61,20 @@ pub enum TicketRepoError {
     InvalidReference,
     ConstraintViolation,
     NoSeatsAvailable,
+    EventEnded,
+    NotOnSale,
+    InvalidTier,
+    TierUnavailable,
+    InvalidHold,
+    InvalidPromoCode,
+    PromoCodeExhausted,
+    PromoCodeUserLimit,
+    PromoCodeNotApplicable,
+    InvalidSeat,
+    SeatTaken,
+    TargetChanged,
+    CheckedIn,
+    InvalidCursor,
     InternalError(Error),
 }
 
@@ -59,6 +88,95 @@ pub enum JoinPeRepoError {
     InternalError(Error),
 }
 
+#[derive(Debug)]
+pub enum PriceTierRepoError {
+    NotFound,
+    DuplicateName,
+    InvalidReference,
+    ConstraintViolation,
+    InternalError(Error),
+}
+
+#[derive(Debug)]
+pub enum VenueRepoError {
+    NotFound,
+    DuplicateName,
+    InUse,
+    ConstraintViolation,
+    InternalError(Error),
+}
+
+#[derive(Debug)]
+pub enum SeatRepoError {
+    NotFound,
+    NoSeatMap,
+    InUse,
+    InventoryOpen,
+    TicketsSold,
+    InternalError(Error),
+}
+
+#[derive(Debug)]
+pub enum SearchRepoError {
+    InvalidCursor,
+    InternalError(Error),
+}
+
+#[derive(Debug)]
+pub enum AnalyticsRepoError {
+    InternalError(Error),
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    ClientService(String),
+    Render(String),
+}
+
+#[derive(Debug)]
+pub enum ImportRepoError {
+    InternalError(Error),
+}
+
+#[derive(Debug)]
+pub enum PromoCodeRepoError {
+    NotFound,
+    DuplicateCode,
+    InvalidReference,
+    ConstraintViolation,
+    InternalError(Error),
+}
+
+#[derive(Debug)]
+pub enum SeatHoldRepoError {
+    NotFound,
+    InternalError(Error),
+}
+
+#[derive(Debug)]
+pub enum WaitlistRepoError {
+    NotFound,
+    AlreadyWaiting,
+    InvalidReference,
+    InternalError(Error),
+}
+
+#[derive(Debug)]
+pub enum IdempotencyRepoError {
+    InternalError(Error),
+}
+
+#[derive(Debug)]
+pub enum CheckInRepoError {
+    TicketNotFound,
+    WrongEvent,
+    Refunded,
+    AlreadyCheckedIn(String),
+    DuplicateScanner,
+    ScannerNotFound,
+    InternalError(Error),
+}
+
 #[derive(Debug)]
 pub enum RefundRepoError {
     NotFound,
@@ -107,6 +225,78 @@ impl From<JoinPeRepoError> for ApiError {
     }
 }
 
+impl From<VenueRepoError> for ApiError {
+    fn from(error: VenueRepoError) -> Self {
+        ApiError::Venue(error)
+    }
+}
+
+impl From<SeatRepoError> for ApiError {
+    fn from(error: SeatRepoError) -> Self {
+        ApiError::Seat(error)
+    }
+}
+
+impl From<SearchRepoError> for ApiError {
+    fn from(error: SearchRepoError) -> Self {
+        ApiError::Search(error)
+    }
+}
+
+impl From<AnalyticsRepoError> for ApiError {
+    fn from(error: AnalyticsRepoError) -> Self {
+        ApiError::Analytics(error)
+    }
+}
+
+impl From<ExportError> for ApiError {
+    fn from(error: ExportError) -> Self {
+        ApiError::Export(error)
+    }
+}
+
+impl From<ImportRepoError> for ApiError {
+    fn from(error: ImportRepoError) -> Self {
+        ApiError::Import(error)
+    }
+}
+
+impl From<PromoCodeRepoError> for ApiError {
+    fn from(error: PromoCodeRepoError) -> Self {
+        ApiError::PromoCode(error)
+    }
+}
+
+impl From<PriceTierRepoError> for ApiError {
+    fn from(error: PriceTierRepoError) -> Self {
+        ApiError::PriceTier(error)
+    }
+}
+
+impl From<SeatHoldRepoError> for ApiError {
+    fn from(error: SeatHoldRepoError) -> Self {
+        ApiError::SeatHold(error)
+    }
+}
+
+impl From<WaitlistRepoError> for ApiError {
+    fn from(error: WaitlistRepoError) -> Self {
+        ApiError::Waitlist(error)
+    }
+}
+
+impl From<IdempotencyRepoError> for ApiError {
+    fn from(error: IdempotencyRepoError) -> Self {
+        ApiError::Idempotency(error)
+    }
+}
+
+impl From<CheckInRepoError> for ApiError {
+    fn from(error: CheckInRepoError) -> Self {
+        ApiError::CheckIn(error)
+    }
+}
+
 impl From<RefundRepoError> for ApiError {
     fn from(error: RefundRepoError) -> Self {
         ApiError::Refund(error)
@@ -148,11 +338,21 @@ impl IntoResponse for ApiError {
                         details: vec!["The requested event was not found.".to_string()],
                     },
                 ),
+                EventRepoError::InvalidCursor => (
+                    StatusCode::BAD_REQUEST,
+                    ApiErrorResponse {
+                        error: "Invalid Cursor".to_string(),
+                        details: vec![
+                            "The cursor is malformed or belongs to another listing; follow the event links again."
+                                .to_string(),
+                        ],
+                    },
+                ),
                 EventRepoError::InvalidReference => (
                     StatusCode::BAD_REQUEST,
                     ApiErrorResponse {
                         error: "Invalid Reference".to_string(),
-                        details: vec!["A provided reference, such as an owner ID, is invalid."
+                        details: vec!["A provided reference, such as an owner or room ID, is invalid."
                             .to_string()],
                     },
                 ),
@@ -187,6 +387,16 @@ impl IntoResponse for ApiError {
                         details: vec!["The requested event packet was not found.".to_string()],
                     },
                 ),
+                EventPacketRepoError::InvalidCursor => (
+                    StatusCode::BAD_REQUEST,
+                    ApiErrorResponse {
+                        error: "Invalid Cursor".to_string(),
+                        details: vec![
+                            "The cursor is malformed or belongs to another listing; follow the event packet links again."
+                                .to_string(),
+                        ],
+                    },
+                ),
                 EventPacketRepoError::DuplicateName => (
                     StatusCode::CONFLICT,
                     ApiErrorResponse {
@@ -202,6 +412,13 @@ impl IntoResponse for ApiError {
                         details: vec!["A provided event ID is invalid.".to_string()],
                     },
                 ),
+                EventPacketRepoError::ConstraintViolation(msg) => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Constraint Violation".to_string(),
+                        details: vec![msg],
+                    },
+                ),
                 EventPacketRepoError::InternalError(_) => (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     ApiErrorResponse {
@@ -219,6 +436,16 @@ impl IntoResponse for ApiError {
                         details: vec!["The requested ticket was not found.".to_string()],
                     },
                 ),
+                TicketRepoError::InvalidCursor => (
+                    StatusCode::BAD_REQUEST,
+                    ApiErrorResponse {
+                        error: "Invalid Cursor".to_string(),
+                        details: vec![
+                            "The cursor is malformed or belongs to another listing; follow the ticket links again."
+                                .to_string(),
+                        ],
+                    },
+                ),
                 TicketRepoError::DuplicateEntry => (
                     StatusCode::CONFLICT,
                     ApiErrorResponse {
@@ -250,6 +477,130 @@ impl IntoResponse for ApiError {
                         details: vec!["No seats available for this event or packet.".to_string()],
                     },
                 ),
+                TicketRepoError::EventEnded => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Event Ended".to_string(),
+                        details: vec![
+                            "Tickets can no longer be sold because the event has already ended."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                TicketRepoError::NotOnSale => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Not On Sale".to_string(),
+                        details: vec![
+                            "Tickets are only sold for published events and packets.".to_string(),
+                        ],
+                    },
+                ),
+                TicketRepoError::InvalidTier => (
+                    StatusCode::BAD_REQUEST,
+                    ApiErrorResponse {
+                        error: "Invalid Reference".to_string(),
+                        details: vec![
+                            "The price tier does not belong to this event or packet.".to_string(),
+                        ],
+                    },
+                ),
+                TicketRepoError::TierUnavailable => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Tier Unavailable".to_string(),
+                        details: vec![
+                            "The price tier is sold out or outside its sale window.".to_string(),
+                        ],
+                    },
+                ),
+                TicketRepoError::InvalidHold => (
+                    StatusCode::BAD_REQUEST,
+                    ApiErrorResponse {
+                        error: "Invalid Hold".to_string(),
+                        details: vec![
+                            "The seat hold does not exist, has expired, or is for another event or packet."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                TicketRepoError::InvalidPromoCode => (
+                    StatusCode::BAD_REQUEST,
+                    ApiErrorResponse {
+                        error: "Invalid Promo Code".to_string(),
+                        details: vec![
+                            "The promo code does not exist for this event or packet, is disabled or has expired."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                TicketRepoError::PromoCodeExhausted => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Promo Code Exhausted".to_string(),
+                        details: vec!["The promo code has reached its usage limit.".to_string()],
+                    },
+                ),
+                TicketRepoError::PromoCodeUserLimit => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Promo Code Limit Reached".to_string(),
+                        details: vec![
+                            "The buyer has already used this promo code as often as allowed."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                TicketRepoError::PromoCodeNotApplicable => (
+                    StatusCode::BAD_REQUEST,
+                    ApiErrorResponse {
+                        error: "Promo Code Not Applicable".to_string(),
+                        details: vec![
+                            "The promo code cannot be applied to a ticket at this price or currency."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                TicketRepoError::InvalidSeat => (
+                    StatusCode::BAD_REQUEST,
+                    ApiErrorResponse {
+                        error: "Invalid Seat".to_string(),
+                        details: vec![
+                            "The seat or section is not part of this event's seat inventory."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                TicketRepoError::SeatTaken => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Seat Taken".to_string(),
+                        details: vec![
+                            "The seat has already been sold or is being bought right now."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                TicketRepoError::TargetChanged => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Conflict".to_string(),
+                        details: vec![
+                            "A sold ticket cannot be moved to another event, packet, tier or seat; delete it and buy a new one."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                TicketRepoError::CheckedIn => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Conflict".to_string(),
+                        details: vec![
+                            "The ticket has already been checked in and cannot be deleted."
+                                .to_string(),
+                        ],
+                    },
+                ),
                 TicketRepoError::InternalError(_) => (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     ApiErrorResponse {
@@ -328,15 +679,42 @@ impl IntoResponse for ApiError {
                 },
             ),
 
-            ApiError::Refund(e) => match e {
-                RefundRepoError::NotFound => (
+            ApiError::PriceTier(e) => match e {
+                PriceTierRepoError::NotFound => (
                     StatusCode::NOT_FOUND,
                     ApiErrorResponse {
                         error: "Resource Not Found".to_string(),
-                        details: vec!["The requested refund request was not found.".to_string()],
+                        details: vec!["The requested price tier was not found.".to_string()],
                     },
                 ),
-                RefundRepoError::InternalError(_) => (
+                PriceTierRepoError::DuplicateName => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Duplicate Entry".to_string(),
+                        details: vec![
+                            "A price tier with this name already exists for this event or packet."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                PriceTierRepoError::InvalidReference => (
+                    StatusCode::BAD_REQUEST,
+                    ApiErrorResponse {
+                        error: "Invalid Reference".to_string(),
+                        details: vec!["Invalid packet or event ID provided.".to_string()],
+                    },
+                ),
+                PriceTierRepoError::ConstraintViolation => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Constraint Violation".to_string(),
+                        details: vec![
+                            "Capacity cannot drop below tickets already sold, and sales end must follow sales start."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                PriceTierRepoError::InternalError(_) => (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     ApiErrorResponse {
                         error: "Internal Server Error".to_string(),
@@ -344,26 +722,379 @@ impl IntoResponse for ApiError {
                     },
                 ),
             },
-        };
 
-        (status, Json(body)).into_response()
-    }
-}
-pub fn map_sqlx_event_error(err: Error) -> EventRepoError {
-    if let Some(db_err) = err.as_database_error() {
-        if let Some(code) = db_err.code() {
-            match code.as_ref() {
-                "23503" => return EventRepoError::InvalidReference,
-                "23505" => return EventRepoError::DuplicateEntry,
-                _ => {}
-            }
-        }
-    }
-    match err {
-        Error::RowNotFound => EventRepoError::NotFound,
-        e => EventRepoError::InternalError(e),
-    }
-}
+            ApiError::Venue(e) => match e {
+                VenueRepoError::NotFound => (
+                    StatusCode::NOT_FOUND,
+                    ApiErrorResponse {
+                        error: "Resource Not Found".to_string(),
+                        details: vec!["The requested venue or room was not found.".to_string()],
+                    },
+                ),
+                VenueRepoError::DuplicateName => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Duplicate Entry".to_string(),
+                        details: vec![
+                            "A venue with this name, or a room with this name in the venue, already exists."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                VenueRepoError::InUse => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Venue In Use".to_string(),
+                        details: vec![
+                            "Events are still scheduled in this venue or room.".to_string(),
+                        ],
+                    },
+                ),
+                VenueRepoError::ConstraintViolation => (
+                    StatusCode::BAD_REQUEST,
+                    ApiErrorResponse {
+                        error: "Constraint Violation".to_string(),
+                        details: vec![
+                            "Latitude and longitude must be set together and stay within range."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                VenueRepoError::InternalError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ApiErrorResponse {
+                        error: "Internal Server Error".to_string(),
+                        details: vec!["An internal server error occurred.".to_string()],
+                    },
+                ),
+            },
+
+            ApiError::Seat(e) => match e {
+                SeatRepoError::NotFound => (
+                    StatusCode::NOT_FOUND,
+                    ApiErrorResponse {
+                        error: "Resource Not Found".to_string(),
+                        details: vec!["The requested room or event was not found.".to_string()],
+                    },
+                ),
+                SeatRepoError::NoSeatMap => (
+                    StatusCode::BAD_REQUEST,
+                    ApiErrorResponse {
+                        error: "No Seat Map".to_string(),
+                        details: vec![
+                            "The event is not held in a room with a seat map.".to_string(),
+                        ],
+                    },
+                ),
+                SeatRepoError::InUse => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Seat Map In Use".to_string(),
+                        details: vec![
+                            "Events still sell seats from this seat map.".to_string(),
+                        ],
+                    },
+                ),
+                SeatRepoError::InventoryOpen => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Seat Inventory Exists".to_string(),
+                        details: vec![
+                            "The event already has a seat inventory.".to_string(),
+                        ],
+                    },
+                ),
+                SeatRepoError::TicketsSold => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Tickets Already Sold".to_string(),
+                        details: vec![
+                            "Reserved seating can only be set up or removed before any ticket is sold."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                SeatRepoError::InternalError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ApiErrorResponse {
+                        error: "Internal Server Error".to_string(),
+                        details: vec!["An internal server error occurred.".to_string()],
+                    },
+                ),
+            },
+
+            ApiError::Search(e) => match e {
+                SearchRepoError::InvalidCursor => (
+                    StatusCode::BAD_REQUEST,
+                    ApiErrorResponse {
+                        error: "Invalid Cursor".to_string(),
+                        details: vec![
+                            "The cursor is malformed or belongs to another search; start again from the first page."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                SearchRepoError::InternalError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ApiErrorResponse {
+                        error: "Internal Server Error".to_string(),
+                        details: vec!["An internal server error occurred.".to_string()],
+                    },
+                ),
+            },
+
+            ApiError::Analytics(e) => match e {
+                AnalyticsRepoError::InternalError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ApiErrorResponse {
+                        error: "Internal Server Error".to_string(),
+                        details: vec!["An internal server error occurred.".to_string()],
+                    },
+                ),
+            },
+
+            ApiError::Export(e) => match e {
+                ExportError::ClientService(msg) => (
+                    StatusCode::BAD_GATEWAY,
+                    ApiErrorResponse {
+                        error: "Bad Gateway".to_string(),
+                        details: vec![format!("Buyer details could not be loaded: {}", msg)],
+                    },
+                ),
+                ExportError::Render(msg) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ApiErrorResponse {
+                        error: "Internal Server Error".to_string(),
+                        details: vec![format!("The export could not be written: {}", msg)],
+                    },
+                ),
+            },
+
+            ApiError::Import(e) => match e {
+                ImportRepoError::InternalError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ApiErrorResponse {
+                        error: "Internal Server Error".to_string(),
+                        details: vec!["An internal server error occurred.".to_string()],
+                    },
+                ),
+            },
+
+            ApiError::PromoCode(e) => match e {
+                PromoCodeRepoError::NotFound => (
+                    StatusCode::NOT_FOUND,
+                    ApiErrorResponse {
+                        error: "Resource Not Found".to_string(),
+                        details: vec!["The requested promo code was not found.".to_string()],
+                    },
+                ),
+                PromoCodeRepoError::DuplicateCode => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Duplicate Entry".to_string(),
+                        details: vec![
+                            "A promo code with this code already exists for this event or packet."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                PromoCodeRepoError::InvalidReference => (
+                    StatusCode::BAD_REQUEST,
+                    ApiErrorResponse {
+                        error: "Invalid Reference".to_string(),
+                        details: vec!["Invalid packet or event ID provided.".to_string()],
+                    },
+                ),
+                PromoCodeRepoError::ConstraintViolation => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Constraint Violation".to_string(),
+                        details: vec![
+                            "The usage limit cannot drop below the number of times the code was already used."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                PromoCodeRepoError::InternalError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ApiErrorResponse {
+                        error: "Internal Server Error".to_string(),
+                        details: vec!["An internal server error occurred.".to_string()],
+                    },
+                ),
+            },
+
+            ApiError::SeatHold(e) => match e {
+                SeatHoldRepoError::NotFound => (
+                    StatusCode::NOT_FOUND,
+                    ApiErrorResponse {
+                        error: "Resource Not Found".to_string(),
+                        details: vec![
+                            "The requested seat hold was not found or has already expired."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                SeatHoldRepoError::InternalError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ApiErrorResponse {
+                        error: "Internal Server Error".to_string(),
+                        details: vec!["An internal server error occurred.".to_string()],
+                    },
+                ),
+            },
+
+            ApiError::Waitlist(e) => match e {
+                WaitlistRepoError::NotFound => (
+                    StatusCode::NOT_FOUND,
+                    ApiErrorResponse {
+                        error: "Resource Not Found".to_string(),
+                        details: vec!["The requested waitlist entry was not found.".to_string()],
+                    },
+                ),
+                WaitlistRepoError::AlreadyWaiting => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Duplicate Entry".to_string(),
+                        details: vec![
+                            "This user is already on the waitlist for this event or packet."
+                                .to_string(),
+                        ],
+                    },
+                ),
+                WaitlistRepoError::InvalidReference => (
+                    StatusCode::BAD_REQUEST,
+                    ApiErrorResponse {
+                        error: "Invalid Reference".to_string(),
+                        details: vec!["Invalid packet or event ID provided.".to_string()],
+                    },
+                ),
+                WaitlistRepoError::InternalError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ApiErrorResponse {
+                        error: "Internal Server Error".to_string(),
+                        details: vec!["An internal server error occurred.".to_string()],
+                    },
+                ),
+            },
+
+            ApiError::Idempotency(e) => match e {
+                IdempotencyRepoError::InternalError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ApiErrorResponse {
+                        error: "Internal Server Error".to_string(),
+                        details: vec!["An internal server error occurred.".to_string()],
+                    },
+                ),
+            },
+
+            ApiError::CheckIn(e) => match e {
+                CheckInRepoError::TicketNotFound => (
+                    StatusCode::NOT_FOUND,
+                    ApiErrorResponse {
+                        error: "Resource Not Found".to_string(),
+                        details: vec!["The scanned ticket does not exist.".to_string()],
+                    },
+                ),
+                CheckInRepoError::WrongEvent => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Wrong Event".to_string(),
+                        details: vec![
+                            "The ticket is not valid for this event.".to_string(),
+                        ],
+                    },
+                ),
+                CheckInRepoError::Refunded => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Ticket Refunded".to_string(),
+                        details: vec!["The ticket was refunded and is no longer valid.".to_string()],
+                    },
+                ),
+                CheckInRepoError::AlreadyCheckedIn(msg) => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Already Checked In".to_string(),
+                        details: vec![msg],
+                    },
+                ),
+                CheckInRepoError::DuplicateScanner => (
+                    StatusCode::CONFLICT,
+                    ApiErrorResponse {
+                        error: "Duplicate Entry".to_string(),
+                        details: vec![
+                            "This user is already a scanner for this event.".to_string(),
+                        ],
+                    },
+                ),
+                CheckInRepoError::ScannerNotFound => (
+                    StatusCode::NOT_FOUND,
+                    ApiErrorResponse {
+                        error: "Resource Not Found".to_string(),
+                        details: vec!["The user is not a scanner for this event.".to_string()],
+                    },
+                ),
+                CheckInRepoError::InternalError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ApiErrorResponse {
+                        error: "Internal Server Error".to_string(),
+                        details: vec!["An internal server error occurred.".to_string()],
+                    },
+                ),
+            },
+
+            ApiError::Refund(e) => match e {
+                RefundRepoError::NotFound => (
+                    StatusCode::NOT_FOUND,
+                    ApiErrorResponse {
+                        error: "Resource Not Found".to_string(),
+                        details: vec!["The requested refund request was not found.".to_string()],
+                    },
+                ),
+                RefundRepoError::InternalError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ApiErrorResponse {
+                        error: "Internal Server Error".to_string(),
+                        details: vec!["An internal server error occurred.".to_string()],
+                    },
+                ),
+            },
+        };
+
+        (status, Json(body)).into_response()
+    }
+}
+pub fn map_sqlx_event_error(err: Error) -> EventRepoError {
+    if let Some(db_err) = err.as_database_error() {
+        if let Some(code) = db_err.code() {
+            match code.as_ref() {
+                "23503" => return EventRepoError::InvalidReference,
+                "23505" => return EventRepoError::DuplicateEntry,
+                "23514" if db_err.constraint() == Some("chk_eveniment_room_schedule") => {
+                    return EventRepoError::ConstraintViolation(
+                        "An event held in a room needs a start and end time.".to_string(),
+                    );
+                }
+                "23514" => {
+                    return EventRepoError::ConstraintViolation(
+                        "Event end time must be after its start time.".to_string(),
+                    );
+                }
+                "23P01" => {
+                    return EventRepoError::ConstraintViolation(
+                        "The room is already booked for an overlapping time.".to_string(),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+    match err {
+        Error::RowNotFound => EventRepoError::NotFound,
+        e => EventRepoError::InternalError(e),
+    }
+}
 
 pub fn map_sqlx_packet_error(err: Error) -> EventPacketRepoError {
     if let Some(db_err) = err.as_database_error() {
@@ -386,6 +1117,9 @@ pub fn map_sqlx_ticket_error(err: Error) -> TicketRepoError {
         if let Some(code) = db_err.code() {
             match code.as_ref() {
                 "23503" => return TicketRepoError::InvalidReference,
+                "23505" if db_err.constraint() == Some("uq_bilete_event_seat") => {
+                    return TicketRepoError::SeatTaken;
+                }
                 "23505" => return TicketRepoError::DuplicateEntry,
                 "23514" => return TicketRepoError::ConstraintViolation,
                 _ => {}
@@ -414,6 +1148,86 @@ pub fn map_sqlx_join_pe_error(err: Error) -> JoinPeRepoError {
     }
 }
 
+pub fn map_sqlx_price_tier_error(err: Error) -> PriceTierRepoError {
+    if let Some(db_err) = err.as_database_error()
+        && let Some(code) = db_err.code()
+    {
+        match code.as_ref() {
+            "23503" => return PriceTierRepoError::InvalidReference,
+            "23505" => return PriceTierRepoError::DuplicateName,
+            "23514" => return PriceTierRepoError::ConstraintViolation,
+            _ => {}
+        }
+    }
+    match err {
+        Error::RowNotFound => PriceTierRepoError::NotFound,
+        e => PriceTierRepoError::InternalError(e),
+    }
+}
+
+pub fn map_sqlx_venue_error(err: Error) -> VenueRepoError {
+    if let Some(db_err) = err.as_database_error()
+        && let Some(code) = db_err.code()
+    {
+        match code.as_ref() {
+            "23503" => return VenueRepoError::InUse,
+            "23505" => return VenueRepoError::DuplicateName,
+            "23514" => return VenueRepoError::ConstraintViolation,
+            _ => {}
+        }
+    }
+    match err {
+        Error::RowNotFound => VenueRepoError::NotFound,
+        e => VenueRepoError::InternalError(e),
+    }
+}
+
+pub fn map_sqlx_seat_error(err: Error) -> SeatRepoError {
+    if let Some(db_err) = err.as_database_error()
+        && let Some(code) = db_err.code()
+        && code.as_ref() == "23503"
+    {
+        return SeatRepoError::InUse;
+    }
+    match err {
+        Error::RowNotFound => SeatRepoError::NotFound,
+        e => SeatRepoError::InternalError(e),
+    }
+}
+
+pub fn map_sqlx_promo_code_error(err: Error) -> PromoCodeRepoError {
+    if let Some(db_err) = err.as_database_error()
+        && let Some(code) = db_err.code()
+    {
+        match code.as_ref() {
+            "23503" => return PromoCodeRepoError::InvalidReference,
+            "23505" => return PromoCodeRepoError::DuplicateCode,
+            "23514" => return PromoCodeRepoError::ConstraintViolation,
+            _ => {}
+        }
+    }
+    match err {
+        Error::RowNotFound => PromoCodeRepoError::NotFound,
+        e => PromoCodeRepoError::InternalError(e),
+    }
+}
+
+pub fn map_sqlx_waitlist_error(err: Error) -> WaitlistRepoError {
+    if let Some(db_err) = err.as_database_error()
+        && let Some(code) = db_err.code()
+    {
+        match code.as_ref() {
+            "23503" => return WaitlistRepoError::InvalidReference,
+            "23505" => return WaitlistRepoError::AlreadyWaiting,
+            _ => {}
+        }
+    }
+    match err {
+        Error::RowNotFound => WaitlistRepoError::NotFound,
+        e => WaitlistRepoError::InternalError(e),
+    }
+}
+
 pub fn map_authorization_error(error: common::authorization::AuthorizationError) -> ApiError {
     match error {
         common::authorization::AuthorizationError::Forbidden(msg) => ApiError::Forbidden(msg),
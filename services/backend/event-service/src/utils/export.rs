@@ -0,0 +1,143 @@
+use crate::models::export::{Attendee, TicketBuyer};
+use crate::utils::error::ExportError;
+use chrono::SecondsFormat;
+use rust_xlsxwriter::{Format, Workbook};
+use std::collections::HashMap;
+
+pub const CSV_CONTENT_TYPE: &str = "text/csv; charset=utf-8";
+pub const XLSX_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+const COLUMNS: [&str; 12] = [
+    "ticket_code",
+    "purchased_at",
+    "tier",
+    "price_minor",
+    "currency",
+    "seat",
+    "checked_in",
+    "checked_in_at",
+    "gate",
+    "first_name",
+    "last_name",
+    "email",
+];
+const PRICE_COLUMN: u16 = 3;
+
+pub type Buyers = HashMap<String, TicketBuyer>;
+
+pub fn index_buyers(buyers: Vec<TicketBuyer>) -> Buyers {
+    buyers
+        .into_iter()
+        .map(|buyer| (buyer.ticket_code.clone(), buyer))
+        .collect()
+}
+
+pub fn csv_header() -> String {
+    csv_line(COLUMNS.iter().map(|column| column.to_string()))
+}
+
+pub fn csv_rows(attendees: &[Attendee], buyers: &Buyers) -> String {
+    attendees
+        .iter()
+        .map(|attendee| csv_line(cells(attendee, buyers.get(&attendee.cod))))
+        .collect()
+}
+
+pub fn render_xlsx(attendees: &[Attendee], buyers: &Buyers) -> Result<Vec<u8>, ExportError> {
+    let render_error = |e: rust_xlsxwriter::XlsxError| ExportError::Render(e.to_string());
+
+    let mut workbook = Workbook::new();
+    let header_format = Format::new().set_bold();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Attendees").map_err(render_error)?;
+
+    for (col, column) in COLUMNS.iter().enumerate() {
+        worksheet
+            .write_string_with_format(0, col as u16, *column, &header_format)
+            .map_err(render_error)?;
+    }
+
+    for (index, attendee) in attendees.iter().enumerate() {
+        let row = index as u32 + 1;
+        for (col, cell) in cells(attendee, buyers.get(&attendee.cod))
+            .into_iter()
+            .enumerate()
+        {
+            let col = col as u16;
+            match attendee.price_minor {
+                Some(price) if col == PRICE_COLUMN => {
+                    worksheet.write_number(row, col, price as f64)
+                }
+                _ => worksheet.write_string(row, col, cell),
+            }
+            .map_err(render_error)?;
+        }
+    }
+
+    worksheet.set_freeze_panes(1, 0).map_err(render_error)?;
+    worksheet.autofit();
+
+    workbook.save_to_buffer().map_err(render_error)
+}
+
+/// One export row. Buyer details are only filled in for clients who made
+/// their info public; everyone else is listed by ticket alone.
+fn cells(attendee: &Attendee, buyer: Option<&TicketBuyer>) -> [String; 12] {
+    let buyer = buyer.filter(|buyer| buyer.public_info);
+
+    [
+        attendee.cod.clone(),
+        attendee
+            .purchased_at
+            .to_rfc3339_opts(SecondsFormat::Secs, true),
+        attendee.tier_name.clone().unwrap_or_default(),
+        attendee
+            .price_minor
+            .map(|price| price.to_string())
+            .unwrap_or_default(),
+        attendee.currency.clone().unwrap_or_default(),
+        attendee.seat_label.clone().unwrap_or_default(),
+        attendee.checked_in_at.is_some().to_string(),
+        attendee
+            .checked_in_at
+            .map(|instant| instant.to_rfc3339_opts(SecondsFormat::Secs, true))
+            .unwrap_or_default(),
+        attendee.checked_in_gate.clone().unwrap_or_default(),
+        buyer
+            .and_then(|buyer| buyer.prenume.clone())
+            .unwrap_or_default(),
+        buyer
+            .and_then(|buyer| buyer.nume.clone())
+            .unwrap_or_default(),
+        buyer
+            .and_then(|buyer| buyer.email.clone())
+            .unwrap_or_default(),
+    ]
+}
+
+fn csv_line(cells: impl IntoIterator<Item = String>) -> String {
+    let mut line = cells
+        .into_iter()
+        .map(|cell| escape_csv(&cell))
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+/// Quotes a field per RFC 4180. Values a spreadsheet would read as a formula
+/// get a leading apostrophe, since names and gates are free text.
+fn escape_csv(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
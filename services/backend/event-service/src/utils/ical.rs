@@ -0,0 +1,108 @@
+use crate::models::event::{Event, EventStatus};
+use chrono::{DateTime, Utc};
+
+pub const CALENDAR_CONTENT_TYPE: &str = "text/calendar; charset=utf-8";
+
+const PRODUCT_ID: &str = "-//SweatFindr//Event Manager//EN";
+const UID_DOMAIN: &str = "sweatfindr";
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Renders an RFC 5545 calendar with one `VEVENT` per event. Times are written
+/// in UTC, so no `VTIMEZONE` blocks are needed; events without a start time
+/// cannot be placed in a calendar and are left out.
+pub fn render_calendar(name: &str, events: &[Event], base_url: &str) -> String {
+    let stamp = format_instant(Utc::now());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODUCT_ID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+    ];
+
+    for event in events {
+        let Some(starts_at) = event.starts_at else {
+            continue;
+        };
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:event-{}@{}", event.id, UID_DOMAIN));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART:{}", format_instant(starts_at)));
+        if let Some(ends_at) = event.ends_at {
+            lines.push(format!("DTEND:{}", format_instant(ends_at)));
+        }
+        lines.push(format!("SUMMARY:{}", escape_text(&event.nume)));
+        if let Some(location) = &event.locatie {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        if let Some(description) = &event.descriere {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        if let (Some(latitude), Some(longitude)) = (event.latitude, event.longitude) {
+            lines.push(format!("GEO:{:.6};{:.6}", latitude, longitude));
+        }
+        if let Some(category) = event.category {
+            lines.push(format!("CATEGORIES:{}", category));
+        }
+        lines.push(format!("URL:{}/events/{}", base_url, event.id));
+        lines.push(format!(
+            "STATUS:{}",
+            if event.status == EventStatus::Cancelled {
+                "CANCELLED"
+            } else {
+                "CONFIRMED"
+            }
+        ));
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("")
+}
+
+fn format_instant(instant: DateTime<Utc>) -> String {
+    instant.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapes a TEXT value: backslashes, separators and line breaks.
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Splits a content line into CRLF-terminated chunks of at most 75 octets,
+/// continuation lines starting with a space, without cutting a UTF-8 character.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + line.len() / MAX_LINE_OCTETS * 3 + 2);
+    let mut octets = 0;
+
+    for c in line.chars() {
+        let width = c.len_utf8();
+        if octets + width > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(c);
+        octets += width;
+    }
+
+    folded.push_str("\r\n");
+    folded
+}
@@ -0,0 +1,101 @@
+use crate::models::idempotency::IdempotencyClaim;
+use crate::repositories::idempotency_repo::IdempotencyRepo;
+use crate::utils::error::ApiError;
+use axum::Json;
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+use std::future::Future;
+use tracing::{error, info};
+
+/// Runs `work` at most once per idempotency key. Retries with the same key
+/// and request get the stored response of the first successful run; failed
+/// runs are not stored, so the client can retry them. Reusing a key for a
+/// request with another `fingerprint` is refused.
+pub async fn run_idempotent<T, F>(
+    repo: &IdempotencyRepo,
+    scope: &str,
+    key: Option<&str>,
+    fingerprint: &str,
+    work: F,
+) -> Result<Response, ApiError>
+where
+    T: Serialize,
+    F: Future<Output = Result<(StatusCode, Option<Json<T>>), ApiError>>,
+{
+    let Some(key) = key else {
+        return work.await.map(|(status, body)| respond(status, body));
+    };
+
+    match repo.claim(scope, key, fingerprint).await? {
+        IdempotencyClaim::Claimed => {}
+        IdempotencyClaim::Completed { status, body } => {
+            info!("Replaying stored response for idempotency key {}", key);
+            let status = StatusCode::from_u16(status).unwrap_or(StatusCode::OK);
+            return Ok(match body {
+                Some(body) => {
+                    (status, [(header::CONTENT_TYPE, "application/json")], body).into_response()
+                }
+                None => status.into_response(),
+            });
+        }
+        IdempotencyClaim::InProgress => {
+            return Err(ApiError::Conflict(
+                "A request with this Idempotency-Key is still being processed".to_string(),
+            ));
+        }
+        IdempotencyClaim::Mismatch => {
+            return Err(ApiError::Conflict(
+                "This Idempotency-Key was already used for a different request".to_string(),
+            ));
+        }
+    }
+
+    match work.await {
+        Ok((status, body)) => {
+            let stored = match &body {
+                Some(Json(body)) => serde_json::to_string(body).map(Some),
+                None => Ok(None),
+            };
+
+            let recorded = match stored {
+                Ok(stored) => {
+                    repo.complete(scope, key, status.as_u16(), stored.as_deref())
+                        .await
+                }
+                Err(e) => {
+                    error!(
+                        "Failed to serialize response for idempotency key {}: {:?}",
+                        key, e
+                    );
+                    repo.release(scope, key).await
+                }
+            };
+
+            if let Err(e) = recorded {
+                error!(
+                    "Failed to store response for idempotency key {}: {:?}",
+                    key, e
+                );
+            }
+
+            Ok(respond(status, body))
+        }
+        Err(e) => {
+            if let Err(release_error) = repo.release(scope, key).await {
+                error!(
+                    "Failed to release idempotency key {}: {:?}",
+                    key, release_error
+                );
+            }
+            Err(e)
+        }
+    }
+}
+
+fn respond<T: Serialize>(status: StatusCode, body: Option<Json<T>>) -> Response {
+    match body {
+        Some(body) => (status, body).into_response(),
+        None => status.into_response(),
+    }
+}
@@ -0,0 +1,113 @@
+use crate::models::import::{ImportRequest, ImportRow, ImportRowKind};
+use serde_json::{Map, Value};
+
+const KIND_COLUMN: &str = "kind";
+const INTEGER_COLUMNS: [&str; 2] = ["numarlocuri", "room_id"];
+const FLOAT_COLUMNS: [&str; 2] = ["latitude", "longitude"];
+const LIST_COLUMNS: [&str; 2] = ["tags", "events"];
+const LIST_SEPARATOR: char = ';';
+
+pub fn json_rows(request: ImportRequest) -> Vec<ImportRow> {
+    let events = request
+        .events
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| ImportRow {
+            kind: ImportRowKind::Event,
+            row: index + 1,
+            value,
+        });
+    let packets = request
+        .packets
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| ImportRow {
+            kind: ImportRowKind::Packet,
+            row: index + 1,
+            value,
+        });
+
+    events.chain(packets).collect()
+}
+
+/// Takes the `events` list off a packet row, leaving the packet's own fields.
+pub fn take_packet_events(value: &mut Value) -> Result<Vec<String>, serde_json::Error> {
+    match value.as_object_mut().and_then(|row| row.remove("events")) {
+        Some(events) => serde_json::from_value(events),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Reads a CSV upload with a header row. The `kind` column tells events from
+/// packets; the other columns carry the fields of `CreateEvent` and
+/// `CreateEventPacket` under the same names, with `tags` and a packet's
+/// `events` separated by `;`. Empty cells are left out.
+pub fn csv_rows(body: &[u8]) -> Result<Vec<ImportRow>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(body);
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Invalid CSV header: {}", e))?
+        .clone();
+
+    if !headers.iter().any(|column| column == KIND_COLUMN) {
+        return Err("The CSV needs a `kind` column with `event` or `packet` on every row".into());
+    }
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| format!("Invalid CSV: {}", e))?;
+        let row = record
+            .position()
+            .map_or(rows.len() + 2, |position| position.line() as usize);
+
+        let mut kind = None;
+        let mut value = Map::new();
+        for (column, cell) in headers.iter().zip(record.iter()) {
+            if column == KIND_COLUMN {
+                kind = Some(cell.to_ascii_lowercase());
+            } else if !cell.is_empty() {
+                value.insert(column.to_string(), csv_value(column, cell));
+            }
+        }
+
+        let kind = match kind.as_deref() {
+            Some("event") => ImportRowKind::Event,
+            Some("packet") => ImportRowKind::Packet,
+            _ => {
+                return Err(format!("Line {}: `kind` must be `event` or `packet`", row));
+            }
+        };
+
+        rows.push(ImportRow {
+            kind,
+            row,
+            value: Value::Object(value),
+        });
+    }
+
+    Ok(rows)
+}
+
+/// Types a cell for its column. Numbers that do not parse stay strings, so
+/// the row fails deserialization with a message naming the field.
+fn csv_value(column: &str, cell: &str) -> Value {
+    if LIST_COLUMNS.contains(&column) {
+        Value::Array(
+            cell.split(LIST_SEPARATOR)
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(Value::from)
+                .collect(),
+        )
+    } else if INTEGER_COLUMNS.contains(&column) {
+        cell.parse::<i64>()
+            .map_or_else(|_| Value::from(cell), Value::from)
+    } else if FLOAT_COLUMNS.contains(&column) {
+        cell.parse::<f64>()
+            .map_or_else(|_| Value::from(cell), Value::from)
+    } else {
+        Value::from(cell)
+    }
+}
@@ -1,9 +1,13 @@
 use crate::models::event::{Event, EventQuery};
-use crate::models::event_packets::{EventPacketQuery, EventPackets};
+use crate::models::event_packets::{EventPacketQuery, EventPackets, PaginationParams};
+use crate::models::price_tier::PriceTier;
 use crate::models::ticket::Ticket;
 
 pub use common::links::{Link, Links, Response, ResponseBuilder};
-use hateoas_macros::{hateoas_simple, hateoas_nested, hateoas_collection, hateoas_filtered};
+use common::pagination::Page;
+use hateoas_macros::{
+    hateoas_collection, hateoas_filtered, hateoas_lookup, hateoas_nested, hateoas_simple,
+};
 
 #[hateoas_simple(
     resource = "tickets",
@@ -13,6 +17,23 @@ use hateoas_macros::{hateoas_simple, hateoas_nested, hateoas_collection, hateoas
 )]
 pub fn build_simple_ticket(ticket: Ticket, base_url: &str) -> Response<Ticket> {}
 
+#[hateoas_filtered(
+    resource = "tickets",
+    self_methods = "GET",
+    parent_methods = "[GET, POST]",
+    query_fields(
+        ("page", "page"),
+        ("items_per_page", "items_per_page"),
+        ("cursor", "cursor"),
+        ("include_total", "include_total")
+    )
+)]
+pub fn build_filtered_tickets(
+    tickets: Page<Ticket>,
+    params: &PaginationParams,
+    base_url: &str,
+) -> Vec<Response<Ticket>> {}
+
 #[hateoas_simple(
     resource = "events",
     id_field = "id",
@@ -33,12 +54,21 @@ pub fn build_simple_event(event: Event, base_url: &str) -> Response<Event> {}
         ("locatie", "location"),
         ("nume", "name"),
         ("owner_id", "owner_id"),
+        ("from", "from"),
+        ("to", "to"),
+        ("upcoming_only", "upcoming_only"),
+        ("category", "category"),
+        ("tags", "tags"),
+        ("near", "near"),
+        ("radius_km", "radius_km"),
         ("paginare.page", "page"),
-        ("paginare.items_per_page", "items_per_page")
+        ("paginare.items_per_page", "items_per_page"),
+        ("paginare.cursor", "cursor"),
+        ("paginare.include_total", "include_total")
     )
 )]
 pub fn build_filtered_event(
-    events: Vec<Event>,
+    events: Page<Event>,
     params: &EventQuery,
     base_url: &str,
 ) -> Vec<Response<Event>> {}
@@ -108,12 +138,25 @@ pub fn build_ticket_over_packet(
     query_fields(
         ("paginare.page", "page"),
         ("paginare.items_per_page", "items_per_page"),
+        ("paginare.cursor", "cursor"),
+        ("paginare.include_total", "include_total"),
         ("bilete", "available_tickets"),
-        ("descriere", "type")
+        ("descriere", "type"),
+        ("category", "category"),
+        ("tags", "tags"),
+        ("near", "near"),
+        ("radius_km", "radius_km")
     )
 )]
 pub fn build_filtered_event_packets(
-    packets: Vec<EventPackets>,
+    packets: Page<EventPackets>,
     params: &EventPacketQuery,
     base_url: &str,
 ) -> Vec<Response<EventPackets>> {}
+
+#[hateoas_lookup(
+    resource = "price-tiers",
+    lookup_param = "tier_id",
+    self_methods = "[GET, PATCH, DELETE]"
+)]
+pub fn build_price_tier(tier: PriceTier, tier_id: i32, base_url: &str) -> Response<PriceTier> {}
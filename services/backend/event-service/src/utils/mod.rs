@@ -1,3 +1,7 @@
 pub mod doc;
 pub mod error;
+pub mod export;
+pub mod ical;
+pub mod idempotency;
+pub mod import;
 pub mod links;
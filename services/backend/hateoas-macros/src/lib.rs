@@ -366,6 +366,9 @@ pub fn hateoas_collection(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 /// Macro for building filtered HATEOAS responses with query parameters
 ///
+/// The data parameter is a `common::pagination::Page`; every item gets `first`
+/// and, when the page has them, `prev`/`next` links built from its cursors.
+///
 /// Usage:
 /// ```
 /// #[hateoas_filtered(
@@ -377,7 +380,7 @@ pub fn hateoas_collection(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///         ("nume", "name")
 ///     )
 /// )]
-/// pub fn build_filtered_event(events: Vec<Event>, params: &EventQuery, base_url: &str) -> Vec<Response<Event>> {}
+/// pub fn build_filtered_event(events: Page<Event>, params: &EventQuery, base_url: &str) -> Vec<Response<Event>> {}
 /// ```
 #[proc_macro_attribute]
 pub fn hateoas_filtered(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -438,107 +441,55 @@ pub fn hateoas_filtered(attr: TokenStream, item: TokenStream) -> TokenStream {
         })
         .collect();
 
-    let query_checks_clone = query_checks.clone();
-
-    let has_pagination = args
-        .query_fields
-        .iter()
-        .any(|(field, _)| field == "paginare.page");
-
-    let expanded = if has_pagination {
-        quote! {
-            #fn_vis #fn_sig {
-                let mut responses = Vec::new();
-
-                let has_page_field = #query_param.paginare.page.is_some();
-                let current_page = #query_param.paginare.page.unwrap_or(1);
-                let items_per_page = #query_param.paginare.items_per_page.unwrap_or(10);
-                let item_count = #data_param.len();
-
-                // Repository fetches items_per_page + 1 to check if there's a next page
-                // If we got more than items_per_page, there's a next page
-                let has_next_page = item_count > items_per_page as usize;
-
-                // Only iterate over items_per_page items (skip the extra one used for next page detection)
-                for item in #data_param.into_iter().take(items_per_page as usize) {
-                    let mut self_href = format!("{}/{}", base_url, #resource);
-                    let mut query_parts = vec![];
-
-                    #(#query_checks)*
-
-                    if !query_parts.is_empty() {
-                        self_href = format!("{}?{}", self_href, query_parts.join("&"));
-                    }
+    let expanded = quote! {
+        #fn_vis #fn_sig {
+            let mut responses = Vec::new();
 
-                    let mut response_builder = ResponseBuilder::new(item, self_href)
-                        .self_types(&[#self_methods])
-                        .parent_with_types(format!("{}/{}", base_url, #resource), &[#parent_methods]);
-
-                    if has_page_field {
-                        let mut non_page_params = vec![];
-                        #(#query_checks_clone)*
-                        non_page_params.retain(|p: &String| !p.starts_with("page=") && !p.starts_with("items_per_page="));
-
-                        let base_with_params = if non_page_params.is_empty() {
-                            format!("{}/{}", base_url, #resource)
-                        } else {
-                            format!("{}/{}?{}", base_url, #resource, non_page_params.join("&"))
-                        };
-
-                        if current_page > 1 {
-                            let prev_page = current_page - 1;
-                            let prev_url = if non_page_params.is_empty() {
-                                format!("{}?page={}&items_per_page={}", base_with_params, prev_page, items_per_page)
-                            } else {
-                                format!("{}&page={}&items_per_page={}", base_with_params, prev_page, items_per_page)
-                            };
-                            response_builder = response_builder.link_with_types("prev", prev_url, &["GET"]);
-                        }
+            let mut query_parts: Vec<String> = vec![];
+            #(#query_checks)*
 
-                        // Only add next link if there's actually a next page
-                        if has_next_page {
-                            let next_page = current_page + 1;
-                            let next_url = if non_page_params.is_empty() {
-                                format!("{}?page={}&items_per_page={}", base_with_params, next_page, items_per_page)
-                            } else {
-                                format!("{}&page={}&items_per_page={}", base_with_params, next_page, items_per_page)
-                            };
-                            response_builder = response_builder.link_with_types("next", next_url, &["GET"]);
-                        }
-                    }
+            let collection_href = format!("{}/{}", base_url, #resource);
+            let self_href = if query_parts.is_empty() {
+                collection_href.clone()
+            } else {
+                format!("{}?{}", collection_href, query_parts.join("&"))
+            };
 
-                    responses.push(response_builder.build());
+            // Paging links keep the filters and page size but not the current position
+            query_parts.retain(|p: &String| !p.starts_with("page=") && !p.starts_with("cursor="));
+            let first_href = if query_parts.is_empty() {
+                collection_href.clone()
+            } else {
+                format!("{}?{}", collection_href, query_parts.join("&"))
+            };
+            let separator = if query_parts.is_empty() { "?" } else { "&" };
+            let next_href = #data_param
+                .next_cursor
+                .as_ref()
+                .map(|cursor| format!("{}{}cursor={}", first_href, separator, cursor));
+            let prev_href = #data_param
+                .prev_cursor
+                .as_ref()
+                .map(|cursor| format!("{}{}cursor={}", first_href, separator, cursor));
+
+            for item in #data_param.items {
+                let mut response_builder = ResponseBuilder::new(item, self_href.clone())
+                    .self_types(&[#self_methods])
+                    .parent_with_types(collection_href.clone(), &[#parent_methods])
+                    .link_with_types("first", first_href.clone(), &["GET"]);
+
+                if let Some(prev_href) = &prev_href {
+                    response_builder = response_builder.link_with_types("prev", prev_href.clone(), &["GET"]);
                 }
 
-                responses
-            }
-        }
-    } else {
-        // without pagination
-        quote! {
-            #fn_vis #fn_sig {
-                let mut responses = Vec::new();
-
-                for item in #data_param {
-                    let mut self_href = format!("{}/{}", base_url, #resource);
-                    let mut query_parts = vec![];
-
-                    #(#query_checks)*
-
-                    if !query_parts.is_empty() {
-                        self_href = format!("{}?{}", self_href, query_parts.join("&"));
-                    }
-
-                    let response = ResponseBuilder::new(item, self_href)
-                        .self_types(&[#self_methods])
-                        .parent_with_types(format!("{}/{}", base_url, #resource), &[#parent_methods])
-                        .build();
-
-                    responses.push(response);
+                if let Some(next_href) = &next_href {
+                    response_builder = response_builder.link_with_types("next", next_href.clone(), &["GET"]);
                 }
 
-                responses
+                responses.push(response_builder.build());
             }
+
+            responses
         }
     };
 
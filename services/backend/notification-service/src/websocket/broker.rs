@@ -92,6 +92,9 @@ impl WebSocketBroker {
                     .broadcast_to_user(data.event_owner_id, &json)
                     .await;
             }
+            WebSocketMessage::WaitlistOffer(ref data) => {
+                self.ws_manager.broadcast_to_user(data.user_id, &json).await;
+            }
         }
     }
 }